@@ -5,11 +5,14 @@ use clap::builder::Styles;
 #[cfg(not(feature = "no_colors"))]
 use clap::builder::styling::AnsiColor;
 use czkawka_core::CZKAWKA_VERSION;
+use czkawka_core::common::keep_rules::KeepRules;
 use czkawka_core::common::model::{CheckingMethod, HashType};
 use czkawka_core::common::tool_data::DeleteMethod;
 use czkawka_core::re_exported::{Cropdetect, FilterType, HashAlg};
 use czkawka_core::tools::broken_files::CheckedTypes;
+use czkawka_core::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, MAX_PARTIAL_HASH_SEGMENTS};
 use czkawka_core::tools::same_music::MusicSimilarity;
+use czkawka_core::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, MAX_SERIES_INTERVAL};
 use czkawka_core::tools::similar_videos::{ALLOWED_SKIP_FORWARD_AMOUNT, ALLOWED_VID_HASH_DURATION, DEFAULT_SKIP_FORWARD_AMOUNT, crop_detect_from_str_opt};
 use czkawka_core::tools::video_optimizer::VideoCodec;
 
@@ -113,6 +116,54 @@ pub enum Commands {
         after_help = "EXAMPLE:\n    czkawka exif-remover -d /home/rafal -f results.txt"
     )]
     ExifRemover(ExifRemoverArgs),
+    #[clap(
+        name = "audit",
+        about = "Runs multiple tools, reading folders only once",
+        after_help = "EXAMPLE:\n    czkawka audit -d /home/rafal -e /home/rafal/Pulpit -t dup,empty-files,empty-folders,temp,broken -f results.txt"
+    )]
+    Audit(AuditArgs),
+    #[clap(
+        name = "watch",
+        about = "Keeps duplicates and empty files results up to date, while files in folders are changed",
+        after_help = "EXAMPLE:\n    czkawka watch -d /srv/drop -t dup,empty-files --hardlink-new-duplicates"
+    )]
+    Watch(WatchArgs),
+    #[clap(
+        name = "journal",
+        about = "Lists and reverts previous file operations",
+        after_help = "EXAMPLE:\n    czkawka journal\n    czkawka journal -r 1735689600_0a1b2c3d"
+    )]
+    Journal(JournalArgs),
+    #[clap(
+        name = "quarantine",
+        about = "Lists, restores and purges items moved to quarantine folder",
+        after_help = "EXAMPLE:\n    czkawka quarantine -d /srv/quarantine\n    czkawka quarantine -d /srv/quarantine -r 1735689600_0a1b2c3d\n    czkawka quarantine -d /srv/quarantine -p 30"
+    )]
+    Quarantine(QuarantineArgs),
+}
+
+impl Commands {
+    pub(crate) fn get_common_cli_items(&self) -> Option<&CommonCliItems> {
+        match self {
+            Self::Duplicates(args) => Some(&args.common_cli_items),
+            Self::EmptyFolders(args) => Some(&args.common_cli_items),
+            Self::BiggestFiles(args) => Some(&args.common_cli_items),
+            Self::EmptyFiles(args) => Some(&args.common_cli_items),
+            Self::Temporary(args) => Some(&args.common_cli_items),
+            Self::SimilarImages(args) => Some(&args.common_cli_items),
+            Self::SameMusic(args) => Some(&args.common_cli_items),
+            Self::InvalidSymlinks(args) => Some(&args.common_cli_items),
+            Self::BrokenFiles(args) => Some(&args.common_cli_items),
+            Self::SimilarVideos(args) => Some(&args.common_cli_items),
+            Self::BadExtensions(args) => Some(&args.common_cli_items),
+            Self::BadNames(args) => Some(&args.common_cli_items),
+            Self::VideoOptimizer(args) => Some(&args.common_cli_items),
+            Self::ExifRemover(args) => Some(&args.common_cli_items),
+            Self::Audit(args) => Some(&args.common_cli_items),
+            Self::Watch(args) => Some(&args.common_cli_items),
+            Self::Journal(_) | Self::Quarantine(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, clap::Args)]
@@ -137,6 +188,53 @@ pub struct DuplicatesArgs {
         long_help = "Use prehash cache to speed up the scanning process by avoiding rehashing files that have already been hashed"
     )]
     pub use_prehash_cache: bool,
+    #[clap(
+        long,
+        help = "Use scan snapshot to speed up rescans",
+        long_help = "Saves state of scan (folders modification dates, size groups and hash groups) and uses it in next scan with the same settings. Folders with unchanged modification date are not read again and groups without changed files are not hashed again. Files modified in place, without modifying their folder, will not be noticed until their folder changes, but they are skipped when deleting or linking files."
+    )]
+    pub use_scan_snapshot: bool,
+    #[clap(
+        long,
+        help = "Verify hash groups byte by byte",
+        long_help = "Compares content of files in each group found by hash, before showing or deleting them. Groups with files that only have the same hash are split. Slower, but recommended with fast hashes like CRC32, where collisions are possible."
+    )]
+    pub verify_byte_by_byte: bool,
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Compare files against saved reference index",
+        long_help = "Compares found files with files from reference index, created earlier with --create-reference-index, so folders from index don't need to be available. Files already present in index are shown as archived. Works only with HASH search method and always uses BLAKE3 hash."
+    )]
+    pub reference_index: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "FILE",
+        conflicts_with = "reference_index",
+        help = "Create reference index from given directories",
+        long_help = "Instead of searching for duplicates, hashes all files from given directories and saves their paths, sizes and hashes to file, which later may be used with --reference-index."
+    )]
+    pub create_reference_index: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Find duplicated folders",
+        long_help = "Groups folders with same content(names and hashes of all files inside) instead of single files and shows folders which are fully included in other folders. Delete methods remove whole folders, hardlinking and reflinking is done for all files inside. Works only with HASH search method and without reference folders."
+    )]
+    pub duplicate_folders: bool,
+    #[clap(
+        long,
+        default_value_t = DEFAULT_PARTIAL_HASH_SEGMENTS,
+        value_parser = clap::value_parser!(u64).range(0..=MAX_PARTIAL_HASH_SEGMENTS),
+        help = "Number of sampled parts in PARTIAL_HASH mode",
+        long_help = "Number of evenly spaced 1 MiB parts of file, which are hashed in PARTIAL_HASH search method besides first and last part. Bigger value gives more reliable results, but is slower."
+    )]
+    pub partial_hash_segments: u64,
+    #[clap(
+        long,
+        help = "Check also files inside archives",
+        long_help = "Compares also files packed inside zip, tar and tar.gz archives. Such files are only shown in results and are never deleted or linked. Works only with HASH search method."
+    )]
+    pub check_archives: bool,
     #[clap(
         short,
         long,
@@ -169,8 +267,8 @@ pub struct DuplicatesArgs {
         long,
         default_value = "HASH",
         value_parser = parse_checking_method_duplicate,
-        help = "Search method (NAME, SIZE, HASH)",
-        long_help = "Methods to search files.\nNAME - Fast but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nHASH - The slowest method, checking by the hash of the entire file"
+        help = "Search method (NAME, SIZE, PARTIAL_HASH, HASH)",
+        long_help = "Methods to search files.\nNAME - Fast but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nPARTIAL_HASH - Fast for big files, checking by the hash of start, end and few evenly spaced parts of the file, so results are only probable duplicates,\nHASH - The slowest method, checking by the hash of the entire file"
     )]
     pub search_method: CheckingMethod,
     #[clap(flatten)]
@@ -188,6 +286,9 @@ pub struct DuplicatesArgs {
     pub case_sensitive_name_comparison: CaseSensitiveNameComparison,
     #[clap(flatten)]
     pub allow_hard_links: AllowHardLinks,
+    #[cfg(feature = "sftp")]
+    #[clap(flatten)]
+    pub sftp: SftpOptions,
 }
 
 #[derive(Debug, clap::Args)]
@@ -219,6 +320,9 @@ pub struct BiggestFilesArgs {
         long_help = "Switch mode to find smallest files instead of biggest ones"
     )]
     pub smallest_mode: bool,
+    #[cfg(feature = "sftp")]
+    #[clap(flatten)]
+    pub sftp: SftpOptions,
 }
 
 #[derive(Debug, clap::Args)]
@@ -227,6 +331,9 @@ pub struct EmptyFilesArgs {
     pub common_cli_items: CommonCliItems,
     #[clap(flatten)]
     pub delete_method: SDMethod,
+    #[cfg(feature = "sftp")]
+    #[clap(flatten)]
+    pub sftp: SftpOptions,
 }
 
 #[derive(Debug, clap::Args)]
@@ -235,6 +342,9 @@ pub struct TemporaryArgs {
     pub common_cli_items: CommonCliItems,
     #[clap(flatten)]
     pub delete_method: SDMethod,
+    #[cfg(feature = "sftp")]
+    #[clap(flatten)]
+    pub sftp: SftpOptions,
 }
 
 #[derive(Debug, clap::Args)]
@@ -303,6 +413,48 @@ pub struct SimilarImagesArgs {
         long_help = "Size of the perceptual hash. Larger values provide more detailed comparison but require higher max_difference values. 8 is fastest and least detailed, 64 is slowest but most detailed. Recommended: 8 or 16 for typical use."
     )]
     pub hash_size: u8,
+    #[clap(
+        long,
+        help = "Find also rotated and mirrored images",
+        long_help = "Additionally calculates hashes of images rotated by 90, 180 and 270 degrees and mirrored, so images rotated or flipped by editors (without EXIF orientation) are matched with originals. Results show which transform matched. Hashing is about 8 times slower."
+    )]
+    pub check_transforms: bool,
+    #[clap(
+        long,
+        help = "Find also images containing large part of other image",
+        long_help = "Additionally compares keypoints of every pair of images, so cropped images, screenshots with borders or images with added watermarks are matched with originals. Results show which part of smaller image is visible in bigger one. Really slow, so it is skipped with warning when more images than --match-crops-max-images are found."
+    )]
+    pub match_crops: bool,
+    #[clap(
+        long,
+        default_value_t = DEFAULT_MATCH_CROPS_MAX_IMAGES,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        help = "Maximum number of images compared with --match-crops",
+        long_help = "Maximum number of images, for which crops are matched - every pair of images is compared, so time grows quadratically with number of images."
+    )]
+    pub match_crops_max_images: usize,
+    #[clap(
+        long,
+        conflicts_with = "match_crops",
+        help = "Query image(s) to find in scanned directories",
+        long_help = "Instead of grouping all scanned images, finds only images similar to given query images, sorted from the most similar. Query images may be outside scanned directories and are never deleted. Can be used multiple times."
+    )]
+    pub query_image: Vec<PathBuf>,
+    #[clap(
+        long,
+        default_value = "0",
+        value_parser = clap::value_parser!(u32).range(..=i64::from(MAX_SERIES_INTERVAL)),
+        conflicts_with_all = ["match_crops", "query_image"],
+        help = "Find photo series taken within given number of seconds",
+        long_help = "Instead of grouping similar images, groups photos taken one after another (bursts, bracketing), within given number of seconds, based on EXIF capture time or modification date. Max difference is doubled, because camera may move between shots. Photos in groups are sorted from the sharpest, so use \"sharpest\" keep rule to keep the best one. 0 disables this mode, at most 60 seconds."
+    )]
+    pub series_interval: u32,
+    #[clap(
+        long,
+        help = "Calculate sharpness, JPEG quality and upscaling of images",
+        long_help = "Additionally calculates sharpness, JPEG quality and upscaling of images, which are shown in results. Hashing is slower, because every image is checked at full resolution. Enabled automatically by AEHQ delete method and by \"sharpest\" and \"highest-quality\" keep rules."
+    )]
+    pub calculate_quality: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -381,8 +533,28 @@ pub struct SameMusicArgs {
         long_help = "Maximum allowed difference between audio segments (0.0-10.0). Value 0.0 will find only identical segments, while 10.0 will find segments that are barely similar. Lower values mean stricter matching."
     )]
     pub maximum_difference: f64,
+    #[clap(
+        long,
+        value_parser = parse_minimum_match_coverage,
+        default_value = "0.0",
+        help = "Minimum percent of track covered by similar segments",
+        long_help = "Minimum percent(0.0-100.0) of longer track, which must be covered by similar segments, when comparing by fingerprints. Bigger values allow to skip tracks which only contain sample of other track or e.g. radio edits of album versions."
+    )]
+    pub minimum_match_coverage: f32,
 }
 
+fn parse_minimum_match_coverage(src: &str) -> Result<f32, String> {
+    match src.parse::<f32>() {
+        Ok(minimum_match_coverage) => {
+            if !(0.0..=100.0).contains(&minimum_match_coverage) {
+                Err("Minimum match coverage must be between 0.0 and 100.0".to_string())
+            } else {
+                Ok(minimum_match_coverage)
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
 fn parse_maximum_difference(src: &str) -> Result<f64, String> {
     match src.parse::<f64>() {
         Ok(maximum_difference) => {
@@ -518,6 +690,144 @@ pub struct BadExtensionsArgs {
     pub fix_extensions: bool,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct AuditArgs {
+    #[clap(flatten)]
+    pub common_cli_items: CommonCliItems,
+    #[clap(flatten)]
+    pub delete_method: SDMethod,
+    #[clap(
+        short = 't',
+        long,
+        value_delimiter = ',',
+        default_value = "dup,empty-files,empty-folders,temp,broken",
+        value_parser = parse_audit_tool,
+        help = "Tools to run (DUP, EMPTY-FILES, EMPTY-FOLDERS, TEMP, BROKEN)",
+        long_help = "Comma separated list of tools that use files found during single traversal of folders. Results are printed in the same order.\nDUP - duplicates, EMPTY-FILES - empty files, EMPTY-FOLDERS - empty folders, TEMP - temporary files, BROKEN - broken files\nDelete options remove items found by all tools except duplicates, which are only reported."
+    )]
+    pub tools: Vec<AuditToolName>,
+    #[clap(
+        short,
+        long,
+        value_parser = parse_minimal_file_size,
+        default_value = "8192",
+        help = "Minimum size in bytes of checked duplicates",
+        long_help = "Minimum size of files checked by duplicates tool in bytes, other tools are not affected"
+    )]
+    pub minimal_file_size: u64,
+    #[clap(
+        short,
+        long,
+        default_value = "HASH",
+        value_parser = parse_checking_method_duplicate,
+        help = "Search method of duplicates (NAME, SIZE, PARTIAL_HASH, HASH)",
+        long_help = "Methods to search duplicated files.\nNAME - Fast but rarely usable,\nSIZE - Fast but not accurate, checking by the file's size,\nPARTIAL_HASH - Fast for big files, checking by the hash of start, end and few evenly spaced parts of the file, so results are only probable duplicates,\nHASH - The slowest method, checking by the hash of the entire file"
+    )]
+    pub search_method: CheckingMethod,
+    #[clap(
+        short = 'b',
+        long,
+        default_value = "PDF",
+        value_parser = parse_broken_files,
+        help = "Checked types of broken files (PDF, AUDIO, IMAGE, ARCHIVE, VIDEO)",
+        long_help = "Types of files checked by broken files tool - default PDF."
+    )]
+    pub broken_types: Vec<CheckedTypes>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditToolName {
+    Duplicates,
+    EmptyFiles,
+    EmptyFolders,
+    Temporary,
+    BrokenFiles,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct WatchArgs {
+    #[clap(flatten)]
+    pub common_cli_items: CommonCliItems,
+    #[clap(
+        short = 't',
+        long,
+        value_delimiter = ',',
+        default_value = "dup,empty-files",
+        value_parser = parse_watch_tool,
+        help = "Tools which results are kept up to date (DUP, EMPTY-FILES)",
+        long_help = "Comma separated list of tools. After initial scan, their results are printed as NDJSON and later only changes of results are printed.\nDUP - duplicates checked by hash, EMPTY-FILES - empty files"
+    )]
+    pub tools: Vec<WatchToolName>,
+    #[clap(
+        short,
+        long,
+        value_parser = parse_minimal_file_size,
+        default_value = "8192",
+        help = "Minimum size in bytes of checked duplicates",
+        long_help = "Minimum size of files checked by duplicates tool in bytes, empty files tool is not affected"
+    )]
+    pub minimal_file_size: u64,
+    #[clap(
+        long,
+        default_value = "BLAKE3",
+        value_parser = parse_hash_type,
+        help = "Hash type (BLAKE3, CRC32, XXH3)",
+        long_help = "Hash algorithm used to calculate hashes of duplicates. Hashes from cache of duplicates tool are reused, if they were calculated with same algorithm."
+    )]
+    pub hash_type: HashType,
+    #[clap(flatten)]
+    pub allow_hard_links: AllowHardLinks,
+    #[clap(
+        long,
+        help = "Replaces new duplicates with hard links",
+        long_help = "Every file which becomes exact duplicate of already found file, is replaced by hard link to it. Files found in initial scan are not changed."
+    )]
+    pub hardlink_new_duplicates: bool,
+    #[clap(
+        long,
+        default_value = "1000",
+        help = "Time in milliseconds without new changes, after which changed files are checked",
+        long_help = "Changes are collected until no new change arrives for this time, so files which are still being copied are checked only once."
+    )]
+    pub debounce_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchToolName {
+    Duplicates,
+    EmptyFiles,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct JournalArgs {
+    #[clap(
+        short = 'r',
+        long,
+        value_name = "SESSION_ID",
+        help = "Reverts session with given id",
+        long_help = "Reverts all operations from given session, where possible - files are restored from trash, moved or renamed back and links are replaced with copies of content. Permanently deleted files cannot be restored."
+    )]
+    pub revert: Option<String>,
+    #[clap(short = 'a', long, help = "Prints all operations of every session")]
+    pub show_all_entries: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct QuarantineArgs {
+    #[clap(short = 'd', long, value_name = "folder", help = "Quarantine folder")]
+    pub quarantine_folder: PathBuf,
+    #[clap(
+        short = 'r',
+        long,
+        value_name = "SESSION_ID",
+        help = "Restores session with given id, or all sessions with \"all\"",
+        long_help = "Moves items from given quarantine session back to their original location. Items whose original path is already occupied are left in quarantine."
+    )]
+    pub restore: Option<String>,
+    #[clap(short = 'p', long, value_name = "days", help = "Permanently removes sessions older than given number of days")]
+    pub purge_days: Option<u32>,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct BadNamesArgs {
     #[clap(flatten)]
@@ -783,7 +1093,7 @@ pub struct CommonCliItems {
     #[clap(
         short,
         long,
-        required = true,
+        required_unless_present_any = ["import_results", "profile"],
         help = "Directory(ies) to search",
         long_help = "List of directory(ies) to search (absolute paths). These directories will be scanned but not set as reference folders."
     )]
@@ -817,6 +1127,10 @@ pub struct CommonCliItems {
     pub json_compact_file_to_save: JsonCompactFileToSave,
     #[clap(flatten)]
     pub json_pretty_file_to_save: JsonPrettyFileToSave,
+    #[clap(flatten)]
+    pub csv_file_to_save: CsvFileToSave,
+    #[clap(flatten)]
+    pub sqlite_file_to_save: SqliteFileToSave,
     #[clap(
         short = 'R',
         long,
@@ -834,6 +1148,26 @@ pub struct CommonCliItems {
     pub exclude_other_filesystems: bool,
     #[clap(flatten)]
     pub do_not_print: DoNotPrint,
+    #[clap(
+        long,
+        help = "Print progress and results as NDJSON",
+        long_help = "Prints one JSON object per line to stdout instead of text. Progress events are printed while scanning, then every group(or entry) of results, messages and summary. Every line contains \"schema_version\", \"tool\" and \"type\" fields, shared by all tools."
+    )]
+    pub ndjson: bool,
+    #[clap(
+        long,
+        value_name = "json-file-name",
+        help = "Load results from JSON file instead of searching",
+        long_help = "Loads results previously saved with --compact-file-to-save or --pretty-file-to-save by the same tool with the same search method, instead of scanning directories. Files removed or modified since saving are skipped. Delete, move and other actions are then performed on loaded results."
+    )]
+    pub import_results: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "name-or-file",
+        help = "Load settings from profile",
+        long_help = "Loads settings from TOML profile - path to file or name of profile saved in \"profiles\" folder inside config folder. Profile uses the same fields as Krokiet presets, so preset exported from GUI can be used directly. Options set explicitly in command line override values from profile."
+    )]
+    pub profile: Option<String>,
     #[clap(
         short = 'W',
         long,
@@ -868,17 +1202,24 @@ pub struct DoNotPrint {
     pub do_not_print_messages: bool,
 }
 
-#[derive(Debug, clap::Args, Clone, Copy)]
+#[derive(Debug, clap::Args, Clone)]
 pub struct DMethod {
     #[clap(
         short = 'D',
         long,
         default_value = "NONE",
         value_parser = parse_delete_method,
-        help = "Delete method (AEN, AEO, ON, OO, AEB, AES, OB, OS, HARD)",
-        long_help = "Method for selecting which files to delete from duplicate groups:\nAEN - All files Except Newest (keeps newest)\nAEO - All files Except Oldest (keeps oldest)\nON - Only 1 file, the Newest (deletes all but newest)\nOO - Only 1 file, the Oldest (deletes all but oldest)\nAEB - All files Except Biggest (keeps biggest)\nAES - All files Except Smallest (keeps smallest)\nOB - Only 1 file, the Biggest (deletes all but biggest)\nOS - Only 1 file, the Smallest (deletes all but smallest)\nHARD - create hard links to save space\nNONE - do not delete files (default)"
+        help = "Delete method (AEN, AEO, ON, OO, AEB, AES, OB, OS, RULES, AEHQ, HARD, REFLINK)",
+        long_help = "Method for selecting which files to delete from duplicate groups:\nAEN - All files Except Newest (keeps newest)\nAEO - All files Except Oldest (keeps oldest)\nON - Only 1 file, the Newest (deletes all but newest)\nOO - Only 1 file, the Oldest (deletes all but oldest)\nAEB - All files Except Biggest (keeps biggest)\nAES - All files Except Smallest (keeps smallest)\nOB - Only 1 file, the Biggest (deletes all but biggest)\nOS - Only 1 file, the Smallest (deletes all but smallest)\nRULES - All files except the best one, chosen by --keep-rules\nAEHQ - All images Except the one with Highest Quality (resolution, JPEG compression and sharpness, only similar images)\nHARD - create hard links to save space\nREFLINK - share data of files with copy-on-write reflinks, each file keeps its own metadata (Linux only, e.g. Btrfs or XFS)\nNONE - do not delete files (default)"
     )]
     pub delete_method: DeleteMethod,
+    #[clap(
+        long,
+        value_parser = parse_keep_rules,
+        help = "Rules which choose file to keep, e.g. \"in:/archive,not-contains:Copy of,shortest-path\"",
+        long_help = "Comma separated list of rules used by RULES delete method to choose file which will be kept in each group. Next rules are used only when previous ones treat files as equal. With HARD and REFLINK, file chosen by rules is used as original.\nnewest, oldest, biggest, smallest, shortest-path, longest-path - compare basic file properties\nmost-exif - prefer file with more EXIF tags\nhighest-bitrate - prefer file with highest bitrate(music and videos only)\nsharpest - prefer the sharpest photo(similar images only)\nhighest-quality - prefer image with the best resolution, JPEG compression and sharpness, upscaled images are treated like smaller ones(similar images only)\nin:PATH, not-in:PATH - prefer files inside/outside of given folder\ncontains:TEXT, not-contains:TEXT - prefer files which path contains/doesn't contain given text"
+    )]
+    pub keep_rules: Option<KeepRules>,
     #[clap(
         short = 'Q',
         long,
@@ -893,10 +1234,30 @@ pub struct DMethod {
         long_help = "Instead of permanently deleting files, move them to the system trash/recycle bin where they can be recovered."
     )]
     pub move_to_trash: bool,
+    #[clap(flatten)]
+    pub quarantine: QuarantineOptions,
+}
+
+#[derive(Debug, clap::Args, Clone)]
+pub struct QuarantineOptions {
+    #[clap(
+        long,
+        value_name = "folder",
+        help = "Move items to quarantine folder instead of deleting them",
+        long_help = "Instead of deleting files(or moving them to trash), moves them into given folder, keeping their original folder structure. Every run is saved as separate session with manifest, so items can be restored with \"quarantine\" command."
+    )]
+    pub quarantine_folder: Option<PathBuf>,
+    #[clap(
+        long,
+        default_value = "0",
+        value_name = "days",
+        help = "Purge quarantine sessions older than given number of days before deleting (0 - never)"
+    )]
+    pub quarantine_purge_days: u32,
 }
 
 // Simple delete method - delete files or not
-#[derive(Debug, clap::Args, Clone, Copy)]
+#[derive(Debug, clap::Args, Clone)]
 pub struct SDMethod {
     #[clap(short = 'D', long, help = "Delete found items", long_help = "Automatically delete all found items matching the criteria.")]
     pub delete_files: bool,
@@ -914,6 +1275,29 @@ pub struct SDMethod {
         long_help = "Instead of permanently deleting files, move them to the system trash/recycle bin where they can be recovered."
     )]
     pub move_to_trash: bool,
+    #[clap(flatten)]
+    pub quarantine: QuarantineOptions,
+}
+
+#[cfg(feature = "sftp")]
+#[derive(Debug, clap::Args, Clone)]
+pub struct SftpOptions {
+    #[clap(
+        long,
+        value_name = "user@host[:port]",
+        help = "Scan directories on remote server over SFTP",
+        long_help = "Included, excluded and reference directories are searched on given server over SFTP instead of local disk. Password is taken from CZKAWKA_SFTP_PASSWORD environment variable, if neither it nor private key is set, ssh agent is used. Host key of server must be already saved in known hosts file, otherwise connection is refused. Cache is not used. Moving to trash or quarantine and reflinking are not supported."
+    )]
+    pub sftp: Option<String>,
+    #[clap(long, value_name = "file", requires = "sftp", help = "Private key used to authenticate on SFTP server")]
+    pub sftp_key: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "file",
+        requires = "sftp",
+        help = "Known hosts file used to verify SFTP server, by default ~/.ssh/known_hosts"
+    )]
+    pub sftp_known_hosts: Option<PathBuf>,
 }
 
 #[derive(Debug, clap::Args)]
@@ -963,6 +1347,28 @@ pub struct JsonPrettyFileToSave {
     pub pretty_file_to_save: Option<PathBuf>,
 }
 
+#[derive(Debug, clap::Args)]
+pub struct CsvFileToSave {
+    #[clap(
+        long,
+        value_name = "csv-file-name",
+        help = "Save results to CSV file",
+        long_help = "Saves the search results into a CSV file with one row per file. Files from the same group share the same group_id column value."
+    )]
+    pub csv_file_to_save: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SqliteFileToSave {
+    #[clap(
+        long,
+        value_name = "sqlite-file-name",
+        help = "Save results to SQLite database",
+        long_help = "Saves the search results into a new SQLite database with \"scans\", \"groups\" and \"files\" tables. Existing file with the same name is overwritten."
+    )]
+    pub sqlite_file_to_save: Option<PathBuf>,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct AllowHardLinks {
     #[clap(
@@ -1024,6 +1430,25 @@ impl JsonPrettyFileToSave {
     }
 }
 
+impl CsvFileToSave {
+    pub(crate) fn file_name(&self) -> Option<&str> {
+        if let Some(file_name) = &self.csv_file_to_save {
+            return file_name.to_str();
+        }
+
+        None
+    }
+}
+impl SqliteFileToSave {
+    pub(crate) fn file_name(&self) -> Option<&str> {
+        if let Some(file_name) = &self.sqlite_file_to_save {
+            return file_name.to_str();
+        }
+
+        None
+    }
+}
+
 fn parse_scan_duration(s: &str) -> Result<u32, String> {
     match s.parse::<u32>() {
         Ok(scan_duration) => {
@@ -1037,7 +1462,7 @@ fn parse_scan_duration(s: &str) -> Result<u32, String> {
     }
 }
 
-fn parse_crop_detect(src: &str) -> Result<Cropdetect, String> {
+pub(crate) fn parse_crop_detect(src: &str) -> Result<Cropdetect, String> {
     match crop_detect_from_str_opt(src) {
         Some(crop_detect) => Ok(crop_detect),
         None => Err(format!("Crop detect \"{src}\" is not valid")),
@@ -1056,7 +1481,7 @@ fn parse_skip_forward_amount(src: &str) -> Result<u32, String> {
         Err(e) => Err(e.to_string()),
     }
 }
-fn parse_hash_type(src: &str) -> Result<HashType, &'static str> {
+pub(crate) fn parse_hash_type(src: &str) -> Result<HashType, &'static str> {
     match src.to_ascii_lowercase().as_str() {
         "blake3" => Ok(HashType::Blake3),
         "crc32" => Ok(HashType::Crc32),
@@ -1078,13 +1503,33 @@ fn parse_tolerance(src: &str) -> Result<i32, &'static str> {
     }
 }
 
-fn parse_checking_method_duplicate(src: &str) -> Result<CheckingMethod, &'static str> {
+pub(crate) fn parse_checking_method_duplicate(src: &str) -> Result<CheckingMethod, &'static str> {
     match src.to_ascii_lowercase().as_str() {
         "name" => Ok(CheckingMethod::Name),
         "size" => Ok(CheckingMethod::Size),
         "size_name" => Ok(CheckingMethod::SizeName),
+        "partial_hash" => Ok(CheckingMethod::PartialHash),
         "hash" => Ok(CheckingMethod::Hash),
-        _ => Err("Couldn't parse the search method (allowed: NAME, SIZE, HASH)"),
+        _ => Err("Couldn't parse the search method (allowed: NAME, SIZE, SIZE_NAME, PARTIAL_HASH, HASH)"),
+    }
+}
+
+fn parse_audit_tool(src: &str) -> Result<AuditToolName, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "dup" => Ok(AuditToolName::Duplicates),
+        "empty-files" => Ok(AuditToolName::EmptyFiles),
+        "empty-folders" => Ok(AuditToolName::EmptyFolders),
+        "temp" => Ok(AuditToolName::Temporary),
+        "broken" => Ok(AuditToolName::BrokenFiles),
+        _ => Err("Couldn't parse the audit tool (allowed: DUP, EMPTY-FILES, EMPTY-FOLDERS, TEMP, BROKEN)"),
+    }
+}
+
+fn parse_watch_tool(src: &str) -> Result<WatchToolName, &'static str> {
+    match src.to_ascii_lowercase().as_str() {
+        "dup" => Ok(WatchToolName::Duplicates),
+        "empty-files" => Ok(WatchToolName::EmptyFiles),
+        _ => Err("Couldn't parse the watch tool (allowed: DUP, EMPTY-FILES)"),
     }
 }
 
@@ -1099,7 +1544,7 @@ fn parse_broken_files(src: &str) -> Result<CheckedTypes, &'static str> {
     }
 }
 
-fn parse_checking_method_same_music(src: &str) -> Result<CheckingMethod, &'static str> {
+pub(crate) fn parse_checking_method_same_music(src: &str) -> Result<CheckingMethod, &'static str> {
     match src.to_ascii_lowercase().as_str() {
         "tags" => Ok(CheckingMethod::AudioTags),
         "content" => Ok(CheckingMethod::AudioContent),
@@ -1139,14 +1584,25 @@ fn parse_delete_method(src: &str) -> Result<DeleteMethod, &'static str> {
         "aen" => Ok(DeleteMethod::AllExceptNewest),
         "aeo" => Ok(DeleteMethod::AllExceptOldest),
         "hard" => Ok(DeleteMethod::HardLink),
+        "reflink" => Ok(DeleteMethod::Reflink),
         "on" => Ok(DeleteMethod::OneNewest),
         "oo" => Ok(DeleteMethod::OneOldest),
         "aeb" => Ok(DeleteMethod::AllExceptBiggest),
         "aes" => Ok(DeleteMethod::AllExceptSmallest),
         "ob" => Ok(DeleteMethod::OneBiggest),
         "os" => Ok(DeleteMethod::OneSmallest),
-        _ => Err("Couldn't parse the delete method (allowed: AEN, AEO, ON, OO, HARD, AEB, AES, OB, OS)"),
+        "rules" => Ok(DeleteMethod::AllExceptBestByRules),
+        "aehq" => Ok(DeleteMethod::AllExceptHighestQuality),
+        _ => Err("Couldn't parse the delete method (allowed: AEN, AEO, ON, OO, HARD, REFLINK, AEB, AES, OB, OS, RULES, AEHQ)"),
+    }
+}
+
+pub(crate) fn parse_keep_rules(src: &str) -> Result<KeepRules, String> {
+    let keep_rules: KeepRules = src.parse()?;
+    if keep_rules.is_empty() {
+        return Err("At least one keep rule must be set".to_string());
     }
+    Ok(keep_rules)
 }
 
 fn parse_minimal_file_size(src: &str) -> Result<u64, String> {
@@ -1169,7 +1625,7 @@ fn parse_maximal_file_size(src: &str) -> Result<u64, String> {
     }
 }
 
-fn parse_similar_image_filter(src: &str) -> Result<FilterType, String> {
+pub(crate) fn parse_similar_image_filter(src: &str) -> Result<FilterType, String> {
     let filter_type = match src.to_lowercase().as_str() {
         "lanczos3" => FilterType::Lanczos3,
         "nearest" => FilterType::Nearest,
@@ -1181,7 +1637,7 @@ fn parse_similar_image_filter(src: &str) -> Result<FilterType, String> {
     Ok(filter_type)
 }
 
-fn parse_similar_hash_algorithm(src: &str) -> Result<HashAlg, String> {
+pub(crate) fn parse_similar_hash_algorithm(src: &str) -> Result<HashAlg, String> {
     let algorithm = match src.to_lowercase().as_str() {
         "mean" => HashAlg::Mean,
         "gradient" => HashAlg::Gradient,
@@ -1194,7 +1650,7 @@ fn parse_similar_hash_algorithm(src: &str) -> Result<HashAlg, String> {
     Ok(algorithm)
 }
 
-fn parse_image_hash_size(src: &str) -> Result<u8, String> {
+pub(crate) fn parse_image_hash_size(src: &str) -> Result<u8, String> {
     let hash_size = match src.to_lowercase().as_str() {
         "8" => 8,
         "16" => 16,
@@ -1275,4 +1731,5 @@ EXAMPLES:
     {bin} bad-names -d /home/rafal -u -j -w -n -f results.txt
     {bin} video-optimizer -d /home/rafal transcode -c h264 -f results.txt
     {bin} video-optimizer -d /home/rafal crop -m blackbars -f results.txt
-    {bin} exif-remover -d /home/rafal -x IMAGE -f results.txt"#;
+    {bin} exif-remover -d /home/rafal -x IMAGE -f results.txt
+    {bin} audit -d /home/rafal -t dup,empty-folders,temp -f results.txt"#;
@@ -1,24 +1,37 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::thread;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use commands::Commands;
 use crossbeam_channel::{Receiver, Sender, unbounded};
 use czkawka_core::common::config_cache_path::{print_infos_and_warnings, set_config_cache_path};
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::image::register_image_decoding_hooks;
+use czkawka_core::common::import::import_results_instead_of_search;
+use czkawka_core::common::journal::{JournalSession, find_journal_session, list_journal_sessions, revert_journal_session};
+use czkawka_core::common::keep_rules::KeepRule;
 use czkawka_core::common::logger::{filtering_messages, print_version_mode, setup_logger};
+use czkawka_core::common::model::{CheckingMethod, HashType, ToolType};
+use czkawka_core::common::ndjson::NdjsonWriter;
 use czkawka_core::common::progress_data::ProgressData;
+use czkawka_core::common::quarantine::{list_quarantine_sessions, purge_quarantine, restore_quarantine_session};
 use czkawka_core::common::set_number_of_threads;
+#[cfg(feature = "sftp")]
+use czkawka_core::common::sftp_file_system::{SftpConnection, SftpFileSystem};
 use czkawka_core::common::tool_data::{CommonData, DeleteMethod};
-use czkawka_core::common::traits::{AllTraits, FixingItems, PrintResults, Search};
+use czkawka_core::common::traits::{AllTraits, FixingItems, PrintResults, WatchingResults};
+use czkawka_core::common::watch::{FolderWatcher, WatchUpdate};
+use czkawka_core::helpers::messages::{MessageLimit, Messages};
+use czkawka_core::tools::audit::{Audit, AuditTool};
 use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsFixParams, BadExtensionsParameters};
 use czkawka_core::tools::bad_names::{BadNames, BadNamesParameters, NameFixerParams, NameIssues};
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters, SearchMode};
 use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
-use czkawka_core::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters};
+use czkawka_core::tools::duplicate::{DEFAULT_MINIMAL_CACHE_SIZE, DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinder, DuplicateFinderParameters};
 use czkawka_core::tools::empty_files::EmptyFiles;
 use czkawka_core::tools::empty_folder::EmptyFolder;
 use czkawka_core::tools::exif_remover::{ExifRemover, ExifRemoverParameters, ExifTagsFixerParams};
@@ -32,15 +45,23 @@ use czkawka_core::tools::video_optimizer::{
 };
 use log::{debug, error, info};
 
+#[cfg(feature = "sftp")]
+use crate::commands::SftpOptions;
 use crate::commands::{
-    Args, BadExtensionsArgs, BadNamesArgs, BiggestFilesArgs, BrokenFilesArgs, CommonCliItems, DMethod, DuplicatesArgs, EmptyFilesArgs, EmptyFoldersArgs, ExifRemoverArgs,
-    InvalidSymlinksArgs, SDMethod, SameMusicArgs, SimilarImagesArgs, SimilarVideosArgs, TemporaryArgs, VideoOptimizerArgs,
+    Args, AuditArgs, AuditToolName, BadExtensionsArgs, BadNamesArgs, BiggestFilesArgs, BrokenFilesArgs, CommonCliItems, DMethod, DuplicatesArgs, EmptyFilesArgs, EmptyFoldersArgs,
+    ExifRemoverArgs, InvalidSymlinksArgs, JournalArgs, QuarantineArgs, SDMethod, SameMusicArgs, SimilarImagesArgs, SimilarVideosArgs, TemporaryArgs, VideoOptimizerArgs, WatchArgs,
+    WatchToolName,
 };
-use crate::progress::connect_progress;
+use crate::profile::apply_profile;
+use crate::progress::{connect_progress, connect_progress_ndjson};
 
 mod commands;
+mod profile;
 mod progress;
 
+#[cfg(feature = "sftp")]
+const SFTP_PASSWORD_ENV: &str = "CZKAWKA_SFTP_PASSWORD";
+
 #[derive(Debug)]
 pub struct CliOutput {
     pub found_any_files: bool,
@@ -51,16 +72,25 @@ pub struct CliOutput {
 fn main() {
     register_image_decoding_hooks();
     if cfg!(debug_assertions) {
-        use clap::CommandFactory;
         Args::command().debug_assert();
     }
-    let command = Args::parse().command;
+    let matches = Args::command().get_matches();
+    let mut command = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit()).command;
 
     let config_cache_path_set_result = set_config_cache_path("Czkawka", "Czkawka");
     setup_logger(true, "czkawka_cli", filtering_messages);
     print_version_mode("Czkawka cli");
     print_infos_and_warnings(config_cache_path_set_result.infos, config_cache_path_set_result.warnings);
 
+    // Profile may be stored in config folder, so it can be loaded only after setting config path
+    // Terminal logs are disabled by default in CLI, so error needs to be printed directly
+    #[expect(clippy::print_stderr)]
+    if let Err(e) = apply_profile(&mut command, &matches) {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+    let ndjson_output = command.get_common_cli_items().is_some_and(|common_cli_items| common_cli_items.ndjson);
+
     if cfg!(debug_assertions) {
         debug!("Running command - {command:?}");
     }
@@ -86,6 +116,10 @@ fn main() {
             Commands::BadNames(bad_names_args) => bad_names(bad_names_args, &stop_flag, &progress_sender),
             Commands::VideoOptimizer(video_optimizer_args) => video_optimizer(video_optimizer_args, &stop_flag, &progress_sender),
             Commands::ExifRemover(exif_remover_args) => exif_remover(exif_remover_args, &stop_flag, &progress_sender),
+            Commands::Audit(audit_args) => audit(audit_args, &stop_flag, &progress_sender),
+            Commands::Watch(watch_args) => watch(watch_args, &stop_flag, &progress_sender),
+            Commands::Journal(journal_args) => journal(journal_args),
+            Commands::Quarantine(quarantine_args) => quarantine(quarantine_args),
         })
         .expect("Failed to spawn calculation thread");
 
@@ -98,7 +132,11 @@ fn main() {
     })
     .expect("Error setting Ctrl-C handler");
 
-    connect_progress(&progress_receiver);
+    if ndjson_output {
+        connect_progress_ndjson(&progress_receiver);
+    } else {
+        connect_progress(&progress_receiver);
+    }
 
     let cli_output = calculate_thread.join().expect("Failed to join calculation thread");
 
@@ -128,6 +166,15 @@ fn duplicates(duplicates: DuplicatesArgs, stop_flag: &Arc<AtomicBool>, progress_
         case_sensitive_name_comparison,
         minimal_prehash_cache_file_size,
         use_prehash_cache,
+        use_scan_snapshot,
+        verify_byte_by_byte,
+        reference_index,
+        create_reference_index,
+        duplicate_folders,
+        partial_hash_segments,
+        check_archives,
+        #[cfg(feature = "sftp")]
+        sftp,
     } = duplicates;
 
     let params = DuplicateFinderParameters::new(
@@ -137,16 +184,43 @@ fn duplicates(duplicates: DuplicatesArgs, stop_flag: &Arc<AtomicBool>, progress_
         minimal_cached_file_size,
         minimal_prehash_cache_file_size,
         case_sensitive_name_comparison.case_sensitive_name_comparison,
+        use_scan_snapshot,
+        verify_byte_by_byte,
+        reference_index,
+        duplicate_folders,
+        partial_hash_segments,
+        check_archives,
     );
     let mut tool = DuplicateFinder::new(params);
 
+    #[cfg(feature = "sftp")]
+    if let Err(e) = set_sftp_file_system(&mut tool, &sftp) {
+        return error_output(&e);
+    }
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
     tool.set_hide_hard_links(!allow_hard_links.allow_hard_links);
-    set_advanced_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    if let Some(reference_index_file) = create_reference_index {
+        if let Some(reference_index) = tool.create_reference_index(stop_flag, Some(progress_sender)) {
+            match reference_index.save_to_file(&reference_index_file) {
+                Ok(()) => info!(
+                    "Saved reference index with {} files to \"{}\"",
+                    reference_index.entries.len(),
+                    reference_index_file.to_string_lossy()
+                ),
+                Err(e) => error!("{e}"),
+            }
+        }
+        return save_and_write_results_to_writer(&tool, &common_cli_items);
+    }
+
+    if let Err(e) = set_advanced_delete(&mut tool, delete_method) {
+        return error_output(&e);
+    }
+
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -159,7 +233,7 @@ fn empty_folders(empty_folders: EmptyFoldersArgs, stop_flag: &Arc<AtomicBool>, p
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -170,42 +244,66 @@ fn biggest_files(biggest_files: BiggestFilesArgs, stop_flag: &Arc<AtomicBool>, p
         number_of_files,
         delete_method,
         smallest_mode,
+        #[cfg(feature = "sftp")]
+        sftp,
     } = biggest_files;
 
     let big_files_mode = if smallest_mode { SearchMode::SmallestFiles } else { SearchMode::BiggestFiles };
     let params = BigFileParameters::new(number_of_files, big_files_mode);
     let mut tool = BigFile::new(params);
 
+    #[cfg(feature = "sftp")]
+    if let Err(e) = set_sftp_file_system(&mut tool, &sftp) {
+        return error_output(&e);
+    }
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
 
 fn empty_files(empty_files: EmptyFilesArgs, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> CliOutput {
-    let EmptyFilesArgs { common_cli_items, delete_method } = empty_files;
+    let EmptyFilesArgs {
+        common_cli_items,
+        delete_method,
+        #[cfg(feature = "sftp")]
+        sftp,
+    } = empty_files;
 
     let mut tool = EmptyFiles::new();
 
+    #[cfg(feature = "sftp")]
+    if let Err(e) = set_sftp_file_system(&mut tool, &sftp) {
+        return error_output(&e);
+    }
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
 
 fn temporary(temporary: TemporaryArgs, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> CliOutput {
-    let TemporaryArgs { common_cli_items, delete_method } = temporary;
+    let TemporaryArgs {
+        common_cli_items,
+        delete_method,
+        #[cfg(feature = "sftp")]
+        sftp,
+    } = temporary;
 
     let mut tool = Temporary::new();
 
+    #[cfg(feature = "sftp")]
+    if let Err(e) = set_sftp_file_system(&mut tool, &sftp) {
+        return error_output(&e);
+    }
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -223,18 +321,40 @@ fn similar_images(similar_images: SimilarImagesArgs, stop_flag: &Arc<AtomicBool>
         delete_method,
         allow_hard_links,
         ignore_same_size,
+        check_transforms,
+        match_crops,
+        match_crops_max_images,
+        query_image,
+        series_interval,
+        calculate_quality,
     } = similar_images;
 
-    let params = SimilarImagesParameters::new(max_difference, hash_size, hash_alg, image_filter, ignore_same_size.ignore_same_size);
+    let params = SimilarImagesParameters {
+        calculate_quality,
+        ..SimilarImagesParameters::new(
+            max_difference,
+            hash_size,
+            hash_alg,
+            image_filter,
+            ignore_same_size.ignore_same_size,
+            check_transforms,
+            match_crops,
+            match_crops_max_images,
+            series_interval,
+        )
+    };
     let mut tool = SimilarImages::new(params);
 
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
     tool.set_hide_hard_links(!allow_hard_links.allow_hard_links);
-    set_advanced_delete(&mut tool, delete_method);
+    tool.set_query_images(query_image);
+    if let Err(e) = set_advanced_delete(&mut tool, delete_method) {
+        return error_output(&e);
+    }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -252,6 +372,7 @@ fn same_music(same_music: SameMusicArgs, stop_flag: &Arc<AtomicBool>, progress_s
         search_method,
         approximate_comparison,
         compare_fingerprints_only_with_similar_titles,
+        minimum_match_coverage,
     } = same_music;
 
     let params = SameMusicParameters::new(
@@ -261,15 +382,18 @@ fn same_music(same_music: SameMusicArgs, stop_flag: &Arc<AtomicBool>, progress_s
         minimum_segment_duration,
         maximum_difference,
         compare_fingerprints_only_with_similar_titles,
+        minimum_match_coverage,
     );
     let mut tool = SameMusic::new(params);
 
     set_common_settings(&mut tool, &common_cli_items, Some(reference_directories.reference_directories.as_ref()));
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
-    set_advanced_delete(&mut tool, delete_method);
+    if let Err(e) = set_advanced_delete(&mut tool, delete_method) {
+        return error_output(&e);
+    }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -282,7 +406,7 @@ fn invalid_symlinks(invalid_symlinks: InvalidSymlinksArgs, stop_flag: &Arc<Atomi
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -304,7 +428,7 @@ fn broken_files(broken_files: BrokenFilesArgs, stop_flag: &Arc<AtomicBool>, prog
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -341,9 +465,11 @@ fn similar_videos(similar_videos: SimilarVideosArgs, stop_flag: &Arc<AtomicBool>
     tool.set_minimal_file_size(minimal_file_size);
     tool.set_maximal_file_size(maximal_file_size);
     tool.set_hide_hard_links(!allow_hard_links.allow_hard_links);
-    set_advanced_delete(&mut tool, delete_method);
+    if let Err(e) = set_advanced_delete(&mut tool, delete_method) {
+        return error_output(&e);
+    }
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
@@ -356,7 +482,7 @@ fn bad_extensions(bad_extensions: BadExtensionsArgs, stop_flag: &Arc<AtomicBool>
 
     set_common_settings(&mut tool, &common_cli_items, None);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     if fix_extensions {
         let fix_params = BadExtensionsFixParams {};
@@ -401,7 +527,7 @@ fn bad_names(bad_names: BadNamesArgs, stop_flag: &Arc<AtomicBool>, progress_send
     set_common_settings(&mut tool, &common_cli_items, None);
     set_simple_delete(&mut tool, delete_method);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     if fix_names {
         let fix_params = NameFixerParams::default();
@@ -449,7 +575,7 @@ fn video_optimizer(video_optimizer: VideoOptimizerArgs, stop_flag: &Arc<AtomicBo
 
             let mut tool = VideoOptimizer::new(params);
             set_common_settings(&mut tool, &common_cli_items, None);
-            tool.search(stop_flag, Some(progress_sender));
+            search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
             if fix_videos {
                 let fix_params = VideoOptimizerFixParams::VideoTranscode(VideoTranscodeFixParams {
@@ -504,7 +630,7 @@ fn video_optimizer(video_optimizer: VideoOptimizerArgs, stop_flag: &Arc<AtomicBo
 
             let mut tool = VideoOptimizer::new(params);
             set_common_settings(&mut tool, &common_cli_items, None);
-            tool.search(stop_flag, Some(progress_sender));
+            search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
             if fix_videos {
                 let fix_params = VideoOptimizerFixParams::VideoCrop(VideoCropFixParams {
@@ -536,7 +662,7 @@ fn exif_remover(exif_remover: ExifRemoverArgs, stop_flag: &Arc<AtomicBool>, prog
 
     set_common_settings(&mut tool, &common_cli_items, None);
 
-    tool.search(stop_flag, Some(progress_sender));
+    search_or_import(&mut tool, &common_cli_items, stop_flag, progress_sender);
 
     if fix_exif {
         let fix_params = ExifTagsFixerParams { override_file };
@@ -546,21 +672,392 @@ fn exif_remover(exif_remover: ExifRemoverArgs, stop_flag: &Arc<AtomicBool>, prog
     save_and_write_results_to_writer(&tool, &common_cli_items)
 }
 
-fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T, common_cli_items: &CommonCliItems) -> CliOutput {
+fn audit(audit: AuditArgs, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> CliOutput {
+    let AuditArgs {
+        common_cli_items,
+        delete_method,
+        tools,
+        minimal_file_size,
+        search_method,
+        broken_types,
+    } = audit;
+
+    if common_cli_items.import_results.is_some() {
+        return CliOutput {
+            found_any_files: false,
+            ignored_error_code_on_found: common_cli_items.ignore_error_code_on_found,
+            output: "Importing results is not supported by audit, every tool needs to import its results separately".to_string(),
+        };
+    }
+
+    let mut broken_type = CheckedTypes::NONE;
+    for check_type in broken_types {
+        broken_type |= check_type;
+    }
+
+    set_number_of_threads(common_cli_items.thread_number);
+    let mut audit = Audit::new();
+    let mut added_tools = Vec::new();
+    for tool_name in tools {
+        if added_tools.contains(&tool_name) {
+            continue;
+        }
+        added_tools.push(tool_name);
+
+        let audit_tool = match tool_name {
+            AuditToolName::Duplicates => {
+                let params = DuplicateFinderParameters::new(
+                    search_method,
+                    HashType::Blake3,
+                    false,
+                    DEFAULT_MINIMAL_CACHE_SIZE,
+                    DEFAULT_MINIMAL_CACHE_SIZE,
+                    false,
+                    false,
+                    false,
+                    None,
+                    false,
+                    DEFAULT_PARTIAL_HASH_SEGMENTS,
+                    false,
+                );
+                let mut tool = DuplicateFinder::new(params);
+                // Duplicates are only reported, because choosing files to keep needs options not available in audit
+                set_common_tool_settings(&mut tool, &common_cli_items, None);
+                tool.set_minimal_file_size(minimal_file_size);
+                AuditTool::Duplicates(Box::new(tool))
+            }
+            AuditToolName::EmptyFiles => AuditTool::EmptyFiles(prepare_audited_tool(EmptyFiles::new(), &common_cli_items, &delete_method)),
+            AuditToolName::EmptyFolders => AuditTool::EmptyFolders(prepare_audited_tool(EmptyFolder::new(), &common_cli_items, &delete_method)),
+            AuditToolName::Temporary => AuditTool::Temporary(prepare_audited_tool(Temporary::new(), &common_cli_items, &delete_method)),
+            AuditToolName::BrokenFiles => AuditTool::BrokenFiles(prepare_audited_tool(
+                BrokenFiles::new(BrokenFilesParameters::new(broken_type)),
+                &common_cli_items,
+                &delete_method,
+            )),
+        };
+        audit.add_tool(audit_tool);
+    }
+
+    audit.search(stop_flag, Some(progress_sender));
+
+    let mut results = Vec::new();
+    let mut messages = Vec::new();
+    if !common_cli_items.ndjson {
+        let messages_text = audit.get_text_messages().create_messages_text(MessageLimit::NoLimit);
+        if !messages_text.is_empty() {
+            let _ = writeln!(messages, "===== audit =====\n{messages_text}");
+        }
+    }
+    for tool in audit.get_tools() {
+        match tool {
+            AuditTool::Duplicates(tool) => write_audited_tool_results(tool.as_ref(), &common_cli_items, "duplicates", &mut results, &mut messages),
+            AuditTool::EmptyFiles(tool) => write_audited_tool_results(tool, &common_cli_items, "empty_files", &mut results, &mut messages),
+            AuditTool::EmptyFolders(tool) => write_audited_tool_results(tool, &common_cli_items, "empty_folders", &mut results, &mut messages),
+            AuditTool::Temporary(tool) => write_audited_tool_results(tool, &common_cli_items, "temporary", &mut results, &mut messages),
+            AuditTool::BrokenFiles(tool) => write_audited_tool_results(tool, &common_cli_items, "broken_files", &mut results, &mut messages),
+        }
+    }
+
+    // Results of all tools are saved to single text file, but other formats are saved per tool
     if let Some(file_name) = common_cli_items.file_to_save.file_name()
-        && let Err(e) = component.print_results_to_file(file_name)
+        && let Err(e) = std::fs::write(file_name, &results)
     {
         error!("Failed to save results to file {e}");
     }
-    if let Some(file_name) = common_cli_items.json_compact_file_to_save.file_name()
-        && let Err(e) = component.save_results_to_file_as_json(file_name, false)
+
+    if common_cli_items.ndjson {
+        let mut writer = NdjsonWriter::new(std::io::stdout().lock(), ToolType::None);
+        if let Err(e) = writer.write_messages(audit.get_text_messages()) {
+            error!("Failed to print ndjson results to output: {e}");
+        }
+    }
+
+    let mut output = Vec::new();
+    if !common_cli_items.ndjson {
+        if !common_cli_items.do_not_print.do_not_print_results {
+            output.extend(results);
+        }
+        if !common_cli_items.do_not_print.do_not_print_messages {
+            output.extend(messages);
+        }
+    }
+
+    CliOutput {
+        found_any_files: audit.found_any_items(),
+        ignored_error_code_on_found: common_cli_items.ignore_error_code_on_found,
+        output: String::from_utf8_lossy(&output).to_string(),
+    }
+}
+
+fn watch(watch: WatchArgs, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> CliOutput {
+    let WatchArgs {
+        common_cli_items,
+        tools,
+        minimal_file_size,
+        hash_type,
+        allow_hard_links,
+        hardlink_new_duplicates,
+        debounce_ms,
+    } = watch;
+
+    if common_cli_items.import_results.is_some() {
+        return error_output("Importing results is not supported by watch, because current state of files is needed");
+    }
+
+    set_number_of_threads(common_cli_items.thread_number);
+    let mut duplicates = tools.contains(&WatchToolName::Duplicates).then(|| {
+        let params = DuplicateFinderParameters::new(
+            CheckingMethod::Hash,
+            hash_type,
+            false,
+            DEFAULT_MINIMAL_CACHE_SIZE,
+            DEFAULT_MINIMAL_CACHE_SIZE,
+            false,
+            false,
+            false,
+            None,
+            false,
+            DEFAULT_PARTIAL_HASH_SEGMENTS,
+            false,
+        );
+        let mut tool = DuplicateFinder::new(params);
+        set_common_tool_settings(&mut tool, &common_cli_items, None);
+        tool.set_minimal_file_size(minimal_file_size);
+        tool.set_hide_hard_links(!allow_hard_links.allow_hard_links);
+        tool.set_hardlink_new_duplicates(hardlink_new_duplicates);
+        tool
+    });
+    let mut empty_files = tools.contains(&WatchToolName::EmptyFiles).then(|| {
+        let mut tool = EmptyFiles::new();
+        set_common_tool_settings(&mut tool, &common_cli_items, None);
+        tool
+    });
+
+    // Initial results are printed like in ndjson mode of every tool, later only their changes are printed
+    if let Some(tool) = &mut duplicates
+        && let Err(e) = start_watching(tool, stop_flag, progress_sender)
     {
-        error!("Failed to save compact json results to file {e}");
+        return error_output(&e);
     }
-    if let Some(file_name) = common_cli_items.json_pretty_file_to_save.file_name()
-        && let Err(e) = component.save_results_to_file_as_json(file_name, true)
+    if let Some(tool) = &mut empty_files
+        && let Err(e) = start_watching(tool, stop_flag, progress_sender)
     {
-        error!("Failed to save pretty json results to file {e}");
+        return error_output(&e);
+    }
+    // All tools use same folders, so single watcher is enough
+    let folder_watcher = match (&duplicates, &empty_files) {
+        (Some(tool), _) => FolderWatcher::new(tool.get_cd()),
+        (None, Some(tool)) => FolderWatcher::new(tool.get_cd()),
+        (None, None) => Err("No tools to run in watch mode".to_string()),
+    };
+    let folder_watcher = match folder_watcher {
+        Ok(folder_watcher) => folder_watcher,
+        Err(e) => return error_output(&e),
+    };
+
+    let debounce = Duration::from_millis(debounce_ms);
+    while let Some((changed_paths, warnings)) = folder_watcher.wait_for_changes(stop_flag, debounce) {
+        write_watch_update(ToolType::None, WatchUpdate { events: Vec::new(), warnings });
+        if let Some(tool) = &mut duplicates {
+            write_watch_update(ToolType::Duplicate, tool.apply_watch_changes(&changed_paths));
+        }
+        if let Some(tool) = &mut empty_files {
+            write_watch_update(ToolType::EmptyFiles, tool.apply_watch_changes(&changed_paths));
+        }
+    }
+
+    if let Some(tool) = &mut duplicates {
+        tool.finish_watching();
+    }
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: common_cli_items.ignore_error_code_on_found,
+        output: String::new(),
+    }
+}
+
+fn start_watching<T>(tool: &mut T, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> Result<(), String>
+where
+    T: AllTraits + WatchingResults,
+{
+    tool.search(stop_flag, Some(progress_sender));
+    if let Some(critical) = &tool.get_text_messages().critical {
+        return Err(critical.clone());
+    }
+    if let Err(e) = tool.print_results_as_ndjson(std::io::stdout().lock()) {
+        error!("Failed to print ndjson results to output: {e}");
+    }
+    tool.prepare_watching(stop_flag)
+}
+
+fn write_watch_update(tool_type: ToolType, update: WatchUpdate) {
+    let mut writer = NdjsonWriter::new(std::io::stdout().lock(), tool_type);
+    let messages = Messages {
+        warnings: update.warnings,
+        ..Default::default()
+    };
+    if let Err(e) = writer.write_watch_events(&update.events).and_then(|()| writer.write_messages(&messages)) {
+        error!("Failed to print ndjson results to output: {e}");
+    }
+}
+
+fn prepare_audited_tool<T>(mut tool: T, common_cli_items: &CommonCliItems, delete_method: &SDMethod) -> T
+where
+    T: AllTraits,
+{
+    set_common_tool_settings(&mut tool, common_cli_items, None);
+    set_simple_delete(&mut tool, delete_method.clone());
+    tool
+}
+
+fn write_audited_tool_results<T: CommonData + PrintResults>(component: &T, common_cli_items: &CommonCliItems, tool_name: &str, results: &mut Vec<u8>, messages: &mut Vec<u8>) {
+    save_results_to_files(component, common_cli_items, Some(tool_name));
+
+    if common_cli_items.ndjson {
+        if let Err(e) = component.print_results_as_ndjson(std::io::stdout().lock()) {
+            error!("Failed to print ndjson results to output: {e}");
+        }
+        return;
+    }
+
+    let _ = writeln!(results, "===== {tool_name} =====");
+    let _ = component.write_results(results);
+    let _ = writeln!(results);
+
+    let messages_text = component.get_text_messages().create_messages_text(MessageLimit::NoLimit);
+    if !messages_text.is_empty() {
+        let _ = writeln!(messages, "===== {tool_name} =====\n{messages_text}");
+    }
+}
+
+// Every audited tool saves its results to separate file, e.g. results.json -> results_duplicates.json
+fn file_name_with_suffix(file_name: &str, suffix: &str) -> String {
+    let path = Path::new(file_name);
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let new_file_name = match path.extension() {
+        Some(extension) => format!("{stem}_{suffix}.{}", extension.to_string_lossy()),
+        None => format!("{stem}_{suffix}"),
+    };
+    path.with_file_name(new_file_name).to_string_lossy().to_string()
+}
+
+fn journal(journal: JournalArgs) -> CliOutput {
+    let JournalArgs { revert, show_all_entries } = journal;
+
+    let output = if let Some(session_id) = revert {
+        match find_journal_session(&session_id) {
+            Ok(session) => {
+                let result = revert_journal_session(&session);
+                let mut output = format!("Reverted {}/{} operations from session {session_id}", result.reverted, session.entries.len());
+                for error in result.errors {
+                    output.push_str(&format!("\n{error}"));
+                }
+                output
+            }
+            Err(e) => e,
+        }
+    } else {
+        match list_journal_sessions() {
+            Ok(sessions) if sessions.is_empty() => "No journal sessions found".to_string(),
+            Ok(sessions) => sessions
+                .iter()
+                .map(|session| format_journal_session(session, show_all_entries))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Err(e) => e,
+        }
+    };
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output,
+    }
+}
+
+fn format_journal_session(session: &JournalSession, show_all_entries: bool) -> String {
+    let mut output = format!(
+        "Session {} - {} - started {} - {} operations",
+        session.id,
+        session.source,
+        session.started,
+        session.entries.len()
+    );
+    if show_all_entries {
+        for entry in &session.entries {
+            output.push_str(&format!("\n    {:?} \"{}\"", entry.action, entry.original_path.to_string_lossy()));
+            if let Some(target) = &entry.target {
+                output.push_str(&format!(" -> \"{}\"", target.to_string_lossy()));
+            }
+        }
+    }
+    output
+}
+
+fn quarantine(quarantine: QuarantineArgs) -> CliOutput {
+    let QuarantineArgs {
+        quarantine_folder,
+        restore,
+        purge_days,
+    } = quarantine;
+
+    let mut output = Vec::new();
+    if let Some(purge_days) = purge_days {
+        match purge_quarantine(&quarantine_folder, purge_days) {
+            Ok(removed_sessions) => output.push(format!("Purged {removed_sessions} quarantine sessions")),
+            Err(e) => output.push(e),
+        }
+    }
+
+    match list_quarantine_sessions(&quarantine_folder) {
+        Ok(sessions) => {
+            if let Some(session_id) = restore {
+                let sessions_to_restore: Vec<_> = sessions.iter().filter(|session| session_id == "all" || session.id == session_id).collect();
+                if sessions_to_restore.is_empty() {
+                    output.push(format!("Cannot find quarantine session \"{session_id}\""));
+                }
+                for session in sessions_to_restore {
+                    let result = restore_quarantine_session(session);
+                    output.push(format!("Restored {}/{} items from session {}", result.restored, session.entries.len(), session.id));
+                    output.extend(result.errors);
+                }
+            } else if sessions.is_empty() {
+                output.push("No quarantine sessions found".to_string());
+            } else {
+                for session in &sessions {
+                    output.push(format!("Session {} - started {} - {} items", session.id, session.started, session.entries.len()));
+                }
+            }
+        }
+        Err(e) => output.push(e),
+    }
+
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output: output.join("\n"),
+    }
+}
+
+fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T, common_cli_items: &CommonCliItems) -> CliOutput {
+    if let Some(file_name) = common_cli_items.file_to_save.file_name()
+        && let Err(e) = component.print_results_to_file(file_name)
+    {
+        error!("Failed to save results to file {e}");
+    }
+    save_results_to_files(component, common_cli_items, None);
+
+    // Results are streamed directly to stdout, so nothing is collected into output
+    if common_cli_items.ndjson {
+        if let Err(e) = component.print_results_as_ndjson(std::io::stdout().lock()) {
+            error!("Failed to print ndjson results to output: {e}");
+        }
+        return CliOutput {
+            found_any_files: component.found_any_items(),
+            ignored_error_code_on_found: common_cli_items.ignore_error_code_on_found,
+            output: String::new(),
+        };
     }
 
     let mut buf_writer = std::io::BufWriter::new(Vec::new());
@@ -591,6 +1088,33 @@ fn save_and_write_results_to_writer<T: CommonData + PrintResults>(component: &T,
     cli_output
 }
 
+fn save_results_to_files<T: CommonData + PrintResults>(component: &T, common_cli_items: &CommonCliItems, file_name_suffix: Option<&str>) {
+    let file_name = |file_name: &str| match file_name_suffix {
+        Some(suffix) => file_name_with_suffix(file_name, suffix),
+        None => file_name.to_string(),
+    };
+    if let Some(file_name_to_save) = common_cli_items.json_compact_file_to_save.file_name()
+        && let Err(e) = component.save_results_to_file_as_json(&file_name(file_name_to_save), false)
+    {
+        error!("Failed to save compact json results to file {e}");
+    }
+    if let Some(file_name_to_save) = common_cli_items.json_pretty_file_to_save.file_name()
+        && let Err(e) = component.save_results_to_file_as_json(&file_name(file_name_to_save), true)
+    {
+        error!("Failed to save pretty json results to file {e}");
+    }
+    if let Some(file_name_to_save) = common_cli_items.csv_file_to_save.file_name()
+        && let Err(e) = component.save_results_to_file_as_csv(&file_name(file_name_to_save))
+    {
+        error!("Failed to save csv results to file {e}");
+    }
+    if let Some(file_name_to_save) = common_cli_items.sqlite_file_to_save.file_name()
+        && let Err(e) = component.save_results_to_file_as_sqlite(&file_name(file_name_to_save))
+    {
+        error!("Failed to save sqlite results to file {e}");
+    }
+}
+
 fn set_simple_delete<T>(component: &mut T, s_delete: SDMethod)
 where
     T: AllTraits,
@@ -600,15 +1124,67 @@ where
     }
     component.set_dry_run(s_delete.dry_run);
     component.set_move_to_trash(s_delete.move_to_trash);
+    component.set_quarantine(s_delete.quarantine.quarantine_folder, s_delete.quarantine.quarantine_purge_days);
 }
 
-fn set_advanced_delete<T>(component: &mut T, a_delete: DMethod)
+fn set_advanced_delete<T>(component: &mut T, a_delete: DMethod) -> Result<(), String>
 where
     T: AllTraits,
 {
+    let keep_rules = a_delete.keep_rules.unwrap_or_default();
+    // Quality of items is calculated only for images, so in other tools all items would be treated as equal
+    if component.get_tool_type() != ToolType::SimilarImages {
+        if a_delete.delete_method.works_only_with_similar_images() {
+            return Err("Delete method AEHQ can be used only with similar images".to_string());
+        }
+        if keep_rules.get_rules().iter().any(KeepRule::works_only_with_similar_images) {
+            return Err("Keep rules \"sharpest\" and \"highest-quality\" can be used only with similar images".to_string());
+        }
+    }
+
     component.set_delete_method(a_delete.delete_method);
+    component.set_keep_rules(keep_rules);
     component.set_dry_run(a_delete.dry_run);
     component.set_move_to_trash(a_delete.move_to_trash);
+    component.set_quarantine(a_delete.quarantine.quarantine_folder, a_delete.quarantine.quarantine_purge_days);
+    Ok(())
+}
+
+fn search_or_import<T>(component: &mut T, common_cli_items: &CommonCliItems, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>)
+where
+    T: AllTraits,
+{
+    match &common_cli_items.import_results {
+        Some(file_name) => import_results_instead_of_search(component, file_name, stop_flag, Some(progress_sender)),
+        None => component.search(stop_flag, Some(progress_sender)),
+    }
+}
+
+// Must be called before setting paths, because they are checked on remote server
+#[cfg(feature = "sftp")]
+fn set_sftp_file_system<T>(component: &mut T, sftp_options: &SftpOptions) -> Result<(), String>
+where
+    T: AllTraits,
+{
+    let Some(address) = &sftp_options.sftp else {
+        return Ok(());
+    };
+    let mut connection = SftpConnection::from_address(address)?;
+    connection.private_key = sftp_options.sftp_key.clone();
+    connection.known_hosts = sftp_options.sftp_known_hosts.clone();
+    connection.password = std::env::var(SFTP_PASSWORD_ENV).ok();
+    let file_system = SftpFileSystem::connect(&connection)?;
+    component.set_file_system(Arc::new(file_system));
+    Ok(())
+}
+
+fn error_output(error: &str) -> CliOutput {
+    error!("{error}");
+    CliOutput {
+        found_any_files: false,
+        ignored_error_code_on_found: false,
+        output: String::new(),
+    }
 }
 
 fn set_common_settings<T>(component: &mut T, common_cli_items: &CommonCliItems, reference_directories: Option<&Vec<PathBuf>>)
@@ -616,7 +1192,14 @@ where
     T: AllTraits,
 {
     set_number_of_threads(common_cli_items.thread_number);
+    set_common_tool_settings(component, common_cli_items, reference_directories);
+}
 
+// Global settings like number of threads can be set only once, so tools run together use only this part
+fn set_common_tool_settings<T>(component: &mut T, common_cli_items: &CommonCliItems, reference_directories: Option<&Vec<PathBuf>>)
+where
+    T: AllTraits,
+{
     let mut included_directories = common_cli_items.directories.clone();
     if let Some(reference_directories) = reference_directories {
         included_directories.extend_from_slice(reference_directories);
@@ -0,0 +1,348 @@
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use czkawka_core::common::profile::{ScanProfile, load_profile};
+use czkawka_core::tools::broken_files::CheckedTypes;
+use czkawka_core::tools::same_music::MusicSimilarity;
+
+use crate::commands::{
+    Commands, CommonCliItems, DMethod, SDMethod, parse_checking_method_duplicate, parse_checking_method_same_music, parse_crop_detect, parse_hash_type, parse_image_hash_size,
+    parse_keep_rules, parse_similar_hash_algorithm, parse_similar_image_filter,
+};
+
+// Values from profile are used only for arguments not set explicitly in command line
+struct ProfileOverrider<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ProfileOverrider<'_> {
+    fn set<T>(&self, id: &str, target: &mut T, value: Option<T>) {
+        if let Some(value) = value
+            && self.matches.value_source(id) != Some(ValueSource::CommandLine)
+        {
+            *target = value;
+        }
+    }
+
+    fn set_parsed<T, E: ToString>(&self, id: &str, target: &mut T, value: Option<&String>, parse: impl Fn(&str) -> Result<T, E>) -> Result<(), String> {
+        let value = value
+            .map(|value| parse(value).map_err(|e| format!("Invalid value \"{value}\" of \"{id}\" in profile - {}", e.to_string())))
+            .transpose()?;
+        self.set(id, target, value);
+        Ok(())
+    }
+}
+
+fn split_comma_list(value: Option<&String>) -> Option<Vec<String>> {
+    value.map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect())
+}
+
+fn kb_to_bytes(value: Option<i32>) -> Option<u64> {
+    value.map(|value| value.max(0) as u64 * 1024)
+}
+
+pub(crate) fn apply_profile(command: &mut Commands, matches: &ArgMatches) -> Result<(), String> {
+    let Some(profile_name) = command.get_common_cli_items().and_then(|common_cli_items| common_cli_items.profile.clone()) else {
+        return Ok(());
+    };
+    let profile = load_profile(&profile_name)?;
+    let Some((_, sub_matches)) = matches.subcommand() else {
+        return Ok(());
+    };
+    let o = ProfileOverrider { matches: sub_matches };
+
+    match command {
+        Commands::Duplicates(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_delete_method(&o, &mut args.delete_method, &profile)?;
+            o.set(
+                "reference_directories",
+                &mut args.reference_directories.reference_directories,
+                profile.included_paths_referenced.clone(),
+            );
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            o.set("maximal_file_size", &mut args.maximal_file_size, kb_to_bytes(profile.maximum_file_size));
+            o.set("use_prehash_cache", &mut args.use_prehash_cache, profile.duplicate_use_prehash);
+            o.set(
+                "minimal_cached_file_size",
+                &mut args.minimal_cached_file_size,
+                profile.duplicate_minimal_hash_cache_size.map(|size| size.max(0) as u64),
+            );
+            o.set(
+                "minimal_prehash_cache_file_size",
+                &mut args.minimal_prehash_cache_file_size,
+                profile.duplicate_minimal_prehash_cache_size.map(|size| size.max(0) as u64),
+            );
+            // GUI names this method differently
+            let check_method = profile.duplicates_sub_check_method.as_ref().map(|method| method.replace("size_and_name", "size_name"));
+            o.set_parsed("search_method", &mut args.search_method, check_method.as_ref(), parse_checking_method_duplicate)?;
+            o.set_parsed("hash_type", &mut args.hash_type, profile.duplicates_sub_available_hash_type.as_ref(), parse_hash_type)?;
+            o.set(
+                "case_sensitive_name_comparison",
+                &mut args.case_sensitive_name_comparison.case_sensitive_name_comparison,
+                profile.duplicates_sub_name_case_sensitive,
+            );
+            o.set("verify_byte_by_byte", &mut args.verify_byte_by_byte, profile.duplicates_sub_verify_byte_by_byte);
+            o.set("allow_hard_links", &mut args.allow_hard_links.allow_hard_links, profile.hide_hard_links.map(|hide| !hide));
+        }
+        Commands::EmptyFolders(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+        }
+        Commands::BiggestFiles(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+            o.set(
+                "number_of_files",
+                &mut args.number_of_files,
+                profile.biggest_files_sub_number_of_files.map(|number| number.max(1) as usize),
+            );
+            o.set(
+                "smallest_mode",
+                &mut args.smallest_mode,
+                profile.biggest_files_sub_method.as_ref().map(|method| method == "smallest"),
+            );
+        }
+        Commands::EmptyFiles(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+        }
+        Commands::Temporary(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+        }
+        Commands::SimilarImages(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_delete_method(&o, &mut args.delete_method, &profile)?;
+            o.set(
+                "reference_directories",
+                &mut args.reference_directories.reference_directories,
+                profile.included_paths_referenced.clone(),
+            );
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            o.set("maximal_file_size", &mut args.maximal_file_size, kb_to_bytes(profile.maximum_file_size));
+            o.set(
+                "max_difference",
+                &mut args.max_difference,
+                profile.similar_images_sub_similarity.map(|similarity| similarity.max(0) as u32),
+            );
+            o.set("allow_hard_links", &mut args.allow_hard_links.allow_hard_links, profile.hide_hard_links.map(|hide| !hide));
+            o.set("ignore_same_size", &mut args.ignore_same_size.ignore_same_size, profile.similar_images_sub_ignore_same_size);
+            o.set("check_transforms", &mut args.check_transforms, profile.similar_images_sub_check_transforms);
+            o.set("match_crops", &mut args.match_crops, profile.similar_images_sub_match_crops);
+            o.set("series_interval", &mut args.series_interval, profile.similar_images_sub_series_interval);
+            o.set("calculate_quality", &mut args.calculate_quality, profile.similar_images_sub_calculate_quality);
+            o.set(
+                "match_crops_max_images",
+                &mut args.match_crops_max_images,
+                profile.similar_images_sub_match_crops_max_images.filter(|max_images| *max_images > 0),
+            );
+            o.set_parsed("hash_alg", &mut args.hash_alg, profile.similar_images_sub_hash_alg.as_ref(), parse_similar_hash_algorithm)?;
+            o.set_parsed(
+                "image_filter",
+                &mut args.image_filter,
+                profile.similar_images_sub_resize_algorithm.as_ref(),
+                parse_similar_image_filter,
+            )?;
+            o.set_parsed("hash_size", &mut args.hash_size, profile.similar_images_sub_hash_size.as_ref(), parse_image_hash_size)?;
+        }
+        Commands::SameMusic(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_delete_method(&o, &mut args.delete_method, &profile)?;
+            o.set(
+                "reference_directories",
+                &mut args.reference_directories.reference_directories,
+                profile.included_paths_referenced.clone(),
+            );
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            o.set("maximal_file_size", &mut args.maximal_file_size, kb_to_bytes(profile.maximum_file_size));
+            o.set("approximate_comparison", &mut args.approximate_comparison, profile.similar_music_sub_approximate_comparison);
+            o.set(
+                "compare_fingerprints_only_with_similar_titles",
+                &mut args.compare_fingerprints_only_with_similar_titles,
+                profile.similar_music_compare_fingerprints_only_with_similar_titles,
+            );
+            let check_type = profile
+                .similar_music_sub_audio_check_type
+                .as_ref()
+                .map(|check_type| check_type.replace("fingerprint", "content"));
+            o.set_parsed("search_method", &mut args.search_method, check_type.as_ref(), parse_checking_method_same_music)?;
+            o.set(
+                "minimum_segment_duration",
+                &mut args.minimum_segment_duration,
+                profile.similar_music_sub_minimal_fragment_duration_value,
+            );
+            o.set(
+                "maximum_difference",
+                &mut args.maximum_difference,
+                profile.similar_music_sub_maximum_difference_value.map(f64::from),
+            );
+            let music_similarity = music_similarity_from_profile(args.music_similarity, &profile);
+            o.set("music_similarity", &mut args.music_similarity, music_similarity);
+        }
+        Commands::InvalidSymlinks(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+        }
+        Commands::BrokenFiles(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+            let checked_types = checked_types_from_profile(&args.checked_types, &profile);
+            o.set("checked_types", &mut args.checked_types, checked_types);
+        }
+        Commands::SimilarVideos(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_delete_method(&o, &mut args.delete_method, &profile)?;
+            o.set(
+                "reference_directories",
+                &mut args.reference_directories.reference_directories,
+                profile.included_paths_referenced.clone(),
+            );
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            o.set("maximal_file_size", &mut args.maximal_file_size, kb_to_bytes(profile.maximum_file_size));
+            o.set("allow_hard_links", &mut args.allow_hard_links.allow_hard_links, profile.hide_hard_links.map(|hide| !hide));
+            o.set("ignore_same_size", &mut args.ignore_same_size.ignore_same_size, profile.similar_videos_sub_ignore_same_size);
+            o.set("tolerance", &mut args.tolerance, profile.similar_videos_sub_similarity);
+            o.set("skip_forward_amount", &mut args.skip_forward_amount, profile.similar_videos_skip_forward_amount);
+            o.set("scan_duration", &mut args.scan_duration, profile.similar_videos_vid_hash_duration);
+            o.set_parsed("crop_detect", &mut args.crop_detect, profile.similar_videos_crop_detect.as_ref(), parse_crop_detect)?;
+        }
+        Commands::BadExtensions(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+        }
+        Commands::BadNames(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+            o.set("uppercase_extension", &mut args.uppercase_extension, profile.bad_names_sub_uppercase_extension);
+            o.set("emoji_used", &mut args.emoji_used, profile.bad_names_sub_emoji_used);
+            o.set("space_at_start_or_end", &mut args.space_at_start_or_end, profile.bad_names_sub_space_at_start_end);
+            o.set("non_ascii_graphical", &mut args.non_ascii_graphical, profile.bad_names_sub_non_ascii);
+            o.set(
+                "remove_duplicated_non_alphanumeric",
+                &mut args.remove_duplicated_non_alphanumeric,
+                profile.bad_names_sub_remove_duplicated,
+            );
+            let restricted_charset = match (profile.bad_names_sub_restricted_charset_enabled, &profile.bad_names_sub_restricted_charset) {
+                (Some(true), Some(charset)) => Some(Some(charset.iter().collect())),
+                (Some(false), _) => Some(None),
+                _ => None,
+            };
+            o.set("restricted_charset", &mut args.restricted_charset, restricted_charset);
+        }
+        Commands::VideoOptimizer(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+        }
+        Commands::ExifRemover(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            o.set("ignored_tags", &mut args.ignored_tags, profile.ignored_exif_tags.clone().map(Some));
+        }
+        Commands::Audit(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            apply_simple_delete_method(&o, &mut args.delete_method, &profile);
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            let check_method = profile.duplicates_sub_check_method.as_ref().map(|method| method.replace("size_and_name", "size_name"));
+            o.set_parsed("search_method", &mut args.search_method, check_method.as_ref(), parse_checking_method_duplicate)?;
+            let broken_types = checked_types_from_profile(&args.broken_types, &profile);
+            o.set("broken_types", &mut args.broken_types, broken_types);
+        }
+        Commands::Watch(args) => {
+            apply_common(&o, &mut args.common_cli_items, &profile);
+            o.set("minimal_file_size", &mut args.minimal_file_size, kb_to_bytes(profile.minimum_file_size));
+            o.set_parsed("hash_type", &mut args.hash_type, profile.duplicates_sub_available_hash_type.as_ref(), parse_hash_type)?;
+            o.set("allow_hard_links", &mut args.allow_hard_links.allow_hard_links, profile.hide_hard_links.map(|hide| !hide));
+        }
+        Commands::Journal(_) | Commands::Quarantine(_) => {}
+    }
+
+    if let Some(common_cli_items) = command.get_common_cli_items()
+        && common_cli_items.directories.is_empty()
+        && common_cli_items.import_results.is_none()
+    {
+        return Err(format!(
+            "No directories to search - they need to be set with --directories or in profile \"{profile_name}\""
+        ));
+    }
+    Ok(())
+}
+
+fn apply_common(o: &ProfileOverrider, common_cli_items: &mut CommonCliItems, profile: &ScanProfile) {
+    o.set(
+        "thread_number",
+        &mut common_cli_items.thread_number,
+        profile.thread_number.map(|number| number.max(0) as usize),
+    );
+    o.set("directories", &mut common_cli_items.directories, profile.included_paths.clone());
+    o.set("excluded_directories", &mut common_cli_items.excluded_directories, profile.excluded_paths.clone());
+    o.set("excluded_items", &mut common_cli_items.excluded_items, split_comma_list(profile.excluded_items.as_ref()));
+    o.set(
+        "allowed_extensions",
+        &mut common_cli_items.allowed_extensions,
+        split_comma_list(profile.allowed_extensions.as_ref()),
+    );
+    o.set(
+        "excluded_extensions",
+        &mut common_cli_items.excluded_extensions,
+        split_comma_list(profile.excluded_extensions.as_ref()),
+    );
+    o.set("not_recursive", &mut common_cli_items.not_recursive, profile.recursive_search.map(|recursive| !recursive));
+    #[cfg(target_family = "unix")]
+    o.set(
+        "exclude_other_filesystems",
+        &mut common_cli_items.exclude_other_filesystems,
+        profile.ignore_other_file_systems,
+    );
+    o.set("disable_cache", &mut common_cli_items.disable_cache, profile.use_cache.map(|use_cache| !use_cache));
+}
+
+fn apply_delete_method(o: &ProfileOverrider, delete_method: &mut DMethod, profile: &ScanProfile) -> Result<(), String> {
+    o.set("move_to_trash", &mut delete_method.move_to_trash, profile.move_deleted_files_to_trash);
+    // Empty rules are allowed in GUI and mean that rules are not used
+    let keep_rules = profile.keep_rules.as_ref().filter(|keep_rules| !keep_rules.trim().is_empty());
+    o.set_parsed("keep_rules", &mut delete_method.keep_rules, keep_rules, |keep_rules| parse_keep_rules(keep_rules).map(Some))
+}
+
+fn apply_simple_delete_method(o: &ProfileOverrider, delete_method: &mut SDMethod, profile: &ScanProfile) {
+    o.set("move_to_trash", &mut delete_method.move_to_trash, profile.move_deleted_files_to_trash);
+}
+
+fn music_similarity_from_profile(current: MusicSimilarity, profile: &ScanProfile) -> Option<MusicSimilarity> {
+    let flags = [
+        (profile.similar_music_sub_title, MusicSimilarity::TRACK_TITLE),
+        (profile.similar_music_sub_artist, MusicSimilarity::TRACK_ARTIST),
+        (profile.similar_music_sub_year, MusicSimilarity::YEAR),
+        (profile.similar_music_sub_bitrate, MusicSimilarity::BITRATE),
+        (profile.similar_music_sub_genre, MusicSimilarity::GENRE),
+        (profile.similar_music_sub_length, MusicSimilarity::LENGTH),
+    ];
+    if flags.iter().all(|(enabled, _)| enabled.is_none()) {
+        return None;
+    }
+    let mut music_similarity = current;
+    for (enabled, flag) in flags {
+        if let Some(enabled) = enabled {
+            music_similarity.set(flag, enabled);
+        }
+    }
+    Some(music_similarity)
+}
+
+fn checked_types_from_profile(current: &[CheckedTypes], profile: &ScanProfile) -> Option<Vec<CheckedTypes>> {
+    let types = [
+        (profile.broken_files_sub_audio, CheckedTypes::AUDIO),
+        (profile.broken_files_sub_pdf, CheckedTypes::PDF),
+        (profile.broken_files_sub_archive, CheckedTypes::ARCHIVE),
+        (profile.broken_files_sub_image, CheckedTypes::IMAGE),
+        (profile.broken_files_sub_video, CheckedTypes::VIDEO),
+    ];
+    if types.iter().all(|(enabled, _)| enabled.is_none()) {
+        return None;
+    }
+    let mut checked_types: Vec<CheckedTypes> = current.to_vec();
+    for (enabled, checked_type) in types {
+        match enabled {
+            Some(true) if !checked_types.contains(&checked_type) => checked_types.push(checked_type),
+            Some(false) => checked_types.retain(|item| *item != checked_type),
+            _ => {}
+        }
+    }
+    Some(checked_types)
+}
@@ -2,9 +2,11 @@ use std::time::Duration;
 
 use crossbeam_channel::Receiver;
 use czkawka_core::common::model::ToolType;
+use czkawka_core::common::ndjson::NdjsonWriter;
 use czkawka_core::common::progress_data::{CurrentStage, ProgressData};
 use humansize::{BINARY, format_size};
 use indicatif::{ProgressBar, ProgressStyle};
+use log::error;
 
 pub(crate) fn connect_progress(progress_receiver: &Receiver<ProgressData>) {
     let mut pb = ProgressBar::new(1);
@@ -54,6 +56,15 @@ pub(crate) fn connect_progress(progress_receiver: &Receiver<ProgressData>) {
     pb.finish_and_clear();
 }
 
+pub(crate) fn connect_progress_ndjson(progress_receiver: &Receiver<ProgressData>) {
+    while let Ok(progress_data) = progress_receiver.recv() {
+        let stdout = std::io::stdout();
+        if let Err(e) = NdjsonWriter::new(stdout.lock(), progress_data.tool_type).write_progress(&progress_data) {
+            error!("Failed to print ndjson progress: {e}");
+        }
+    }
+}
+
 pub(crate) fn get_progress_message(progress_data: &ProgressData) -> String {
     match progress_data.sstage {
         CurrentStage::SameMusicReadingTags => "Reading tags",
@@ -62,8 +73,10 @@ pub(crate) fn get_progress_message(progress_data: &ProgressData) -> String {
         CurrentStage::SameMusicComparingFingerprints => "Comparing fingerprints",
         CurrentStage::DuplicatePreHashing => "Calculating prehashes",
         CurrentStage::DuplicateFullHashing => "Calculating hashes",
+        CurrentStage::DuplicateByteComparing => "Comparing content of files",
         CurrentStage::SimilarImagesCalculatingHashes => "Calculating image hashes",
         CurrentStage::SimilarImagesComparingHashes => "Comparing image hashes",
+        CurrentStage::SimilarImagesMatchingFeatures => "Matching image features",
         CurrentStage::SimilarVideosCalculatingHashes => "Reading similar values",
         CurrentStage::SimilarVideosCreatingThumbnails | CurrentStage::VideoOptimizerCreatingThumbnails => "Creating video thumbnails",
         CurrentStage::BrokenFilesChecking => "Checking broken files",
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use criterion::{Criterion, criterion_group, criterion_main};
+use czkawka_core::common::file_system::LocalFileSystem;
 use czkawka_core::common::model::HashType;
 use czkawka_core::tools::duplicate::{DuplicateEntry, hash_calculation};
 
@@ -17,13 +18,10 @@ fn setup_test_file(size: u64) -> PathBuf {
 }
 
 fn get_file_entry(size: u64) -> DuplicateEntry {
-    let path = setup_test_file(size);
-    DuplicateEntry {
-        path,
-        modified_date: 0,
-        size,
-        hash: String::new(),
-    }
+    let mut entry = DuplicateEntry::default();
+    entry.path = setup_test_file(size);
+    entry.size = size;
+    entry
 }
 
 fn benchmark_hash_calculation_vec<const FILE_SIZE: u64, const BUFFER_SIZE: usize>(c: &mut Criterion) {
@@ -34,6 +32,7 @@ fn benchmark_hash_calculation_vec<const FILE_SIZE: u64, const BUFFER_SIZE: usize
         b.iter(|| {
             let mut buffer = vec![0u8; BUFFER_SIZE];
             hash_calculation(
+                &LocalFileSystem,
                 black_box(&mut buffer),
                 black_box(&file_entry),
                 black_box(HashType::Blake3),
@@ -53,6 +52,7 @@ fn benchmark_hash_calculation_arr<const FILE_SIZE: u64, const BUFFER_SIZE: usize
         b.iter(|| {
             let mut buffer = [0u8; BUFFER_SIZE];
             hash_calculation(
+                &LocalFileSystem,
                 black_box(&mut buffer),
                 black_box(&file_entry),
                 black_box(HashType::Blake3),
@@ -14,6 +14,7 @@ core_cannot_read_metadata_file = Cannot read metadata of file {$file}, reason {$
 core_file_modified_before_epoch = File {$name} seems to have been modified before the Unix Epoch
 core_folder_modified_before_epoch = Folder {$name} seems to have been modified before the Unix Epoch
 core_file_no_modification_date = Unable to get modification date from file {$name}, reason {$reason}
+core_file_changed_since_scan = File {$name} was modified since it was checked with scan snapshot, so it was skipped
 core_folder_no_modification_date = Unable to get modification date from folder {$name}, reason {$reason}
 
 core_cannot_start_scan_no_included_paths = Cannot start scan, because there are no included paths
@@ -60,12 +61,15 @@ core_failed_to_crop_video_file = Failed to crop video file "{ $file }": { $reaso
 core_cropped_video_not_created = Cropped video file was not created: { $temp }
 core_unable_check_hash_of_file = Unable to check hash of file "{ $file }", reason { $reason }
 core_error_checking_hash_of_file = Error happened when checking hash of file "{ $file }", reason { $reason }
+core_unable_compare_content_of_file = Unable to compare content of file "{ $file }", reason { $reason }
+core_error_comparing_content_of_file = Error happened when comparing content of file "{ $file }", reason { $reason }
 core_image_zero_dimensions = Image has zero width or height "{ $path }"
 core_image_open_failed = Cannot open image file "{ $path }": { $reason }
 core_not_directory_remove = Trying to remove folder "{ $path }" which is not a directory
 core_cannot_read_directory = Cannot read directory "{ $path }"
 core_cannot_read_entry_from_directory = Cannot read entry from directory "{ $path }"
 core_folder_contains_file_inside = Folder contains file "{ $entry }" inside "{ $folder }"
+core_folder_contains_different_files = Folder "{ $folder }" was changed after scan(file "{ $file }" was added or removed), so it will not be removed
 core_unknown_directory_entry = Unable to determine file type of directory entry "{ $entry }" inside "{ $path }"
 core_video_width_exceeds_limit = Video width { $width } exceeds the limit of { $limit }
 core_video_height_exceeds_limit = Video height { $height } exceeds the limit of { $limit }
@@ -83,6 +87,9 @@ core_failed_to_generate_thumbnail = Failed to generate thumbnail for "{ $file }"
 core_failed_to_extract_frame_at_seek_time = Failed to extract frame at { $time } seconds from "{ $file }": { $reason }
 core_video_file_does_not_exist = Video file does not exist (could be removed between scan/later steps): "{ $path }"
 core_image_too_large = Image is too large ({ $width }x{ $height }) - more than supported { $max } pixels
+core_query_images_with_reference_paths = Query images cannot be used together with reference folders, because they are already used as reference
+core_query_images_with_match_crops = Query images cannot be used together with matching crops
+core_too_many_images_to_match_crops = Matching crops of images was skipped, because { $images } images were found, but the limit is { $limit }
 core_failed_to_get_video_metadata = Failed to get video metadata for file "{ $file }": { $reason }
 core_failed_to_get_video_codec = Failed to get video codec for file "{ $file }"
 core_failed_to_get_video_duration = Failed to get video duration for file "{ $file }"
@@ -92,6 +99,15 @@ core_failed_to_load_data_from_cache = Failed to load data from cache file { $fil
 core_failed_to_load_data_from_json_cache = Failed to load data from json cache file { $file }, reason { $reason }
 core_failed_to_replace_with_optimized = Failed to replace file "{ $file }" with optimized version: { $reason }
 core_failed_to_write_data_to_cache = Cannot write data to cache file "{ $file }", reason { $reason }
+core_failed_to_load_reference_index = Failed to load reference index from file "{ $file }", reason { $reason }
+core_failed_to_save_reference_index = Cannot save reference index to file "{ $file }", reason { $reason }
+core_reference_index_only_hash_mode = Reference index can be used only when searching duplicates by hash, so it will be ignored
+core_duplicate_folders_only_hash_mode = Duplicated folders can be found only when searching duplicates by hash without reference folders, so this option will be ignored
+core_keep_rules_empty = Keep rules are not set, so no items will be deleted
+core_partial_hash_cannot_delete = Files found by partial hash are only probable duplicates, so they will not be deleted or linked - use full hash to remove them
+core_archives_only_hash_mode = Files inside archives can be checked only when searching duplicates by hash, so this option will be ignored
+core_cannot_read_archive = Cannot read archive "{ $file }", reason { $reason }
+core_reference_index_uses_blake3 = Reference index contains Blake3 hashes, so Blake3 will be used instead of selected hash type
 core_properly_saved_cache_entries = Properly saved to file { $count } cache entries.
 core_video_processing_stopped_by_user = Video processing was stopped by user
 core_thumbnail_generation_stopped_by_user = Thumbnail generation was stopped by user
@@ -0,0 +1,421 @@
+//! Stable API for using Czkawka tools as a library.
+//!
+//! Every tool is configured with its own typed builder, and invalid settings are reported as [`BuildError`]
+//! before any file is touched. Scan returns a [`ScanReport`] with results of all tools in single [`ScanResults`] enum.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::sync::atomic::AtomicBool;
+//!
+//! use czkawka_core::api::{DuplicateFinder, HashType, ScanResults, Tool};
+//!
+//! let mut finder = DuplicateFinder::builder().paths(["/home/user/Downloads"]).hash(HashType::Blake3).build()?;
+//! let report = finder.run(&Arc::new(AtomicBool::new(false)), None)?;
+//! if let ScanResults::Duplicates(groups) = report.results {
+//!     for group in groups {
+//!         println!("{:?}", group.items.iter().map(|entry| &entry.path).collect::<Vec<_>>());
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Everything needed to configure tools and read their results is re-exported here.
+//! Other modules of this crate are shared with Czkawka frontends and may change between any versions.
+
+use std::fmt;
+use std::mem;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+pub use crossbeam_channel::Sender;
+
+pub use crate::common::model::{CheckingMethod, FileEntry, HashType, ToolType};
+pub use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::tool_data::CommonData;
+use crate::common::traits::Search;
+pub use crate::re_exported::{Cropdetect, FilterType, HashAlg};
+pub use crate::tools::bad_extensions::BadFileEntry;
+pub use crate::tools::bad_names::{BadNameEntry, NameIssues};
+pub use crate::tools::big_file::SearchMode;
+pub use crate::tools::broken_files::{BrokenEntry, CheckedTypes};
+pub use crate::tools::duplicate::DuplicateEntry;
+pub use crate::tools::empty_folder::FolderEntry;
+pub use crate::tools::exif_remover::ExifEntry;
+pub use crate::tools::invalid_symlinks::SymlinksFileEntry;
+pub use crate::tools::same_music::{MusicEntry, MusicSimilarity};
+pub use crate::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, ImageTransform, ImagesEntry};
+pub use crate::tools::similar_videos::VideosEntry;
+pub use crate::tools::temporary::TemporaryFileEntry;
+pub use tools::{
+    BadExtensions, BadNames, BigFile, BrokenFiles, DuplicateFinder, EmptyFiles, EmptyFolder, ExifRemover, InvalidSymlinks, SameMusic, SimilarImages, SimilarVideos, Temporary,
+};
+
+mod tools;
+
+#[cfg(test)]
+mod tests;
+
+/// Invalid settings of tool, returned by [`ToolBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// No path to scan was set.
+    NoIncludedPaths,
+    /// Minimal file size is bigger than maximal.
+    InvalidSizeRange { minimal: u64, maximal: u64 },
+    /// Value of tool specific setting is outside of allowed range.
+    InvalidValue { name: &'static str, message: String },
+    /// Paths, extensions or excluded items that cannot be used, e.g. not existing folders.
+    InvalidSettings(Vec<String>),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoIncludedPaths => write!(f, "At least one path to scan must be set"),
+            Self::InvalidSizeRange { minimal, maximal } => write!(f, "Minimal file size {minimal} is bigger than maximal file size {maximal}"),
+            Self::InvalidValue { name, message } => write!(f, "Invalid value of \"{name}\" - {message}"),
+            Self::InvalidSettings(problems) => write!(f, "Invalid settings - {}", problems.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Scan which could not be finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScanError {
+    /// Stop flag was set before scan ended.
+    Stopped,
+    /// Scan could not be started or continued.
+    Failed(String),
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stopped => write!(f, "Scan was stopped"),
+            Self::Failed(message) => write!(f, "Scan failed - {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Items considered to be the same, e.g. duplicated files or similar images.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ResultGroup<T> {
+    /// Item from reference folder, which is only compared with other items and should never be removed.
+    pub reference: Option<T>,
+    pub items: Vec<T>,
+}
+
+/// Results of any tool.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ScanResults {
+    Duplicates(Vec<ResultGroup<DuplicateEntry>>),
+    EmptyFolders(Vec<FolderEntry>),
+    EmptyFiles(Vec<FileEntry>),
+    BigFiles(Vec<FileEntry>),
+    TemporaryFiles(Vec<TemporaryFileEntry>),
+    InvalidSymlinks(Vec<SymlinksFileEntry>),
+    BrokenFiles(Vec<BrokenEntry>),
+    BadExtensions(Vec<BadFileEntry>),
+    BadNames(Vec<BadNameEntry>),
+    ExifFiles(Vec<ExifEntry>),
+    SimilarImages(Vec<ResultGroup<ImagesEntry>>),
+    SimilarVideos(Vec<ResultGroup<VideosEntry>>),
+    SameMusic(Vec<ResultGroup<MusicEntry>>),
+}
+
+impl ScanResults {
+    pub fn tool_type(&self) -> ToolType {
+        match self {
+            Self::Duplicates(_) => ToolType::Duplicate,
+            Self::EmptyFolders(_) => ToolType::EmptyFolders,
+            Self::EmptyFiles(_) => ToolType::EmptyFiles,
+            Self::BigFiles(_) => ToolType::BigFile,
+            Self::TemporaryFiles(_) => ToolType::TemporaryFiles,
+            Self::InvalidSymlinks(_) => ToolType::InvalidSymlinks,
+            Self::BrokenFiles(_) => ToolType::BrokenFiles,
+            Self::BadExtensions(_) => ToolType::BadExtensions,
+            Self::BadNames(_) => ToolType::BadNames,
+            Self::ExifFiles(_) => ToolType::ExifRemover,
+            Self::SimilarImages(_) => ToolType::SimilarImages,
+            Self::SimilarVideos(_) => ToolType::SimilarVideos,
+            Self::SameMusic(_) => ToolType::SameMusic,
+        }
+    }
+
+    /// Number of groups or single items, depending on tool.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Duplicates(groups) => groups.len(),
+            Self::SimilarImages(groups) => groups.len(),
+            Self::SimilarVideos(groups) => groups.len(),
+            Self::SameMusic(groups) => groups.len(),
+            Self::EmptyFolders(items) => items.len(),
+            Self::EmptyFiles(items) | Self::BigFiles(items) => items.len(),
+            Self::TemporaryFiles(items) => items.len(),
+            Self::InvalidSymlinks(items) => items.len(),
+            Self::BrokenFiles(items) => items.len(),
+            Self::BadExtensions(items) => items.len(),
+            Self::BadNames(items) => items.len(),
+            Self::ExifFiles(items) => items.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Results of finished scan.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ScanReport {
+    pub results: ScanResults,
+    /// Problems which did not stop scan, e.g. files that could not be read.
+    pub warnings: Vec<String>,
+}
+
+mod private {
+    use crate::common::traits::AllTraits;
+
+    // Tools are run through internal tool types, which are not part of public API
+    pub trait Sealed {
+        type Inner: AllTraits;
+
+        fn inner_mut(&mut self) -> &mut Self::Inner;
+    }
+}
+
+/// Tool which can be configured with [`ToolBuilder`] and run.
+///
+/// This trait is sealed - it is implemented only for tools from this crate.
+pub trait Tool: private::Sealed + Sized {
+    #[doc(hidden)]
+    type Options: Default;
+
+    fn builder() -> ToolBuilder<Self> {
+        ToolBuilder {
+            settings: CommonSettings::default(),
+            options: Self::Options::default(),
+        }
+    }
+
+    #[doc(hidden)]
+    fn from_options(options: Self::Options) -> Result<Self, BuildError>;
+
+    #[doc(hidden)]
+    fn collect_results(&self) -> ScanResults;
+
+    // Checks tool specific options, which depend on common settings
+    #[doc(hidden)]
+    fn validate(&self) -> Result<(), BuildError> {
+        Ok(())
+    }
+
+    /// Runs scan and returns its results. Every tool should be run only once.
+    ///
+    /// Progress is sent to `progress_sender` during scan, and setting `stop_flag` stops scan as soon as possible.
+    fn run(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> Result<ScanReport, ScanError> {
+        let tool = self.inner_mut();
+        tool.search(stop_flag, progress_sender);
+        let messages = mem::take(tool.get_text_messages_mut());
+        if let Some(critical) = messages.critical {
+            return Err(ScanError::Failed(critical));
+        }
+        if tool.get_stopped_search() {
+            return Err(ScanError::Stopped);
+        }
+
+        Ok(ScanReport {
+            results: self.collect_results(),
+            warnings: messages.errors.into_iter().chain(messages.warnings).collect(),
+        })
+    }
+}
+
+// Settings shared by all tools, unset values use defaults of tool
+struct CommonSettings {
+    included_paths: Vec<PathBuf>,
+    excluded_paths: Vec<PathBuf>,
+    reference_paths: Vec<PathBuf>,
+    excluded_items: Vec<String>,
+    allowed_extensions: Vec<String>,
+    excluded_extensions: Vec<String>,
+    recursive_search: bool,
+    use_cache: bool,
+    exclude_other_filesystems: bool,
+    hide_hard_links: Option<bool>,
+    minimal_file_size: Option<u64>,
+    maximal_file_size: Option<u64>,
+}
+
+impl Default for CommonSettings {
+    fn default() -> Self {
+        Self {
+            included_paths: Vec::new(),
+            excluded_paths: Vec::new(),
+            reference_paths: Vec::new(),
+            excluded_items: Vec::new(),
+            allowed_extensions: Vec::new(),
+            excluded_extensions: Vec::new(),
+            recursive_search: true,
+            use_cache: true,
+            exclude_other_filesystems: false,
+            hide_hard_links: None,
+            minimal_file_size: None,
+            maximal_file_size: None,
+        }
+    }
+}
+
+impl CommonSettings {
+    fn apply<T: Tool>(self, tool: &mut T) -> Result<(), BuildError> {
+        let tool = tool.inner_mut();
+        if self.included_paths.is_empty() {
+            return Err(BuildError::NoIncludedPaths);
+        }
+        if let (Some(minimal), Some(maximal)) = (self.minimal_file_size, self.maximal_file_size)
+            && minimal > maximal
+        {
+            return Err(BuildError::InvalidSizeRange { minimal, maximal });
+        }
+
+        let mut included_paths = self.included_paths;
+        if !self.reference_paths.is_empty() {
+            if !tool.get_tool_type().may_use_reference_paths() {
+                return Err(BuildError::InvalidValue {
+                    name: "reference_paths",
+                    message: format!("{:?} tool does not support reference paths", tool.get_tool_type()),
+                });
+            }
+            included_paths.extend_from_slice(&self.reference_paths);
+            tool.set_reference_paths(self.reference_paths);
+        }
+        tool.set_included_paths(included_paths);
+        tool.set_excluded_paths(self.excluded_paths);
+        tool.set_excluded_items(self.excluded_items);
+        tool.set_allowed_extensions(self.allowed_extensions);
+        tool.set_excluded_extensions(self.excluded_extensions);
+        tool.set_recursive_search(self.recursive_search);
+        tool.set_use_cache(self.use_cache);
+        tool.set_exclude_other_filesystems(self.exclude_other_filesystems);
+        if let Some(hide_hard_links) = self.hide_hard_links {
+            tool.set_hide_hard_links(hide_hard_links);
+        }
+        if let Some(minimal_file_size) = self.minimal_file_size {
+            tool.set_minimal_file_size(minimal_file_size);
+        }
+        if let Some(maximal_file_size) = self.maximal_file_size {
+            tool.set_maximal_file_size(maximal_file_size);
+        }
+
+        // Setters only report problems, so they are converted to error here, instead of being shown after scan
+        let messages = mem::take(tool.get_text_messages_mut());
+        let problems: Vec<String> = messages.critical.into_iter().chain(messages.errors).chain(messages.warnings).collect();
+        if !problems.is_empty() {
+            return Err(BuildError::InvalidSettings(problems));
+        }
+        Ok(())
+    }
+}
+
+/// Builder of tool, created with [`Tool::builder`].
+///
+/// Settings common for all tools are available for every builder, and tool specific ones only for given tool.
+#[must_use]
+pub struct ToolBuilder<T: Tool> {
+    settings: CommonSettings,
+    options: T::Options,
+}
+
+impl<T: Tool> ToolBuilder<T> {
+    /// Folders or files to scan. At least one is required.
+    pub fn paths<P: Into<PathBuf>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.settings.included_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn excluded_paths<P: Into<PathBuf>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.settings.excluded_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Folders or files which are scanned, but only compared with others - supported only by tools that group items.
+    pub fn reference_paths<P: Into<PathBuf>>(mut self, paths: impl IntoIterator<Item = P>) -> Self {
+        self.settings.reference_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Wildcards of excluded items, e.g. `*/.git/*`.
+    pub fn excluded_items<S: Into<String>>(mut self, items: impl IntoIterator<Item = S>) -> Self {
+        self.settings.excluded_items = items.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only files with these extensions are checked, all files are checked when empty.
+    pub fn allowed_extensions<S: Into<String>>(mut self, extensions: impl IntoIterator<Item = S>) -> Self {
+        self.settings.allowed_extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn excluded_extensions<S: Into<String>>(mut self, extensions: impl IntoIterator<Item = S>) -> Self {
+        self.settings.excluded_extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Enabled by default.
+    pub fn recursive(mut self, recursive_search: bool) -> Self {
+        self.settings.recursive_search = recursive_search;
+        self
+    }
+
+    /// Enabled by default.
+    pub fn use_cache(mut self, use_cache: bool) -> Self {
+        self.settings.use_cache = use_cache;
+        self
+    }
+
+    /// Supported only on Unix.
+    pub fn exclude_other_filesystems(mut self, exclude_other_filesystems: bool) -> Self {
+        self.settings.exclude_other_filesystems = exclude_other_filesystems;
+        self
+    }
+
+    /// Only one of hardlinked files is shown.
+    pub fn hide_hard_links(mut self, hide_hard_links: bool) -> Self {
+        self.settings.hide_hard_links = Some(hide_hard_links);
+        self
+    }
+
+    /// In bytes.
+    pub fn minimal_file_size(mut self, minimal_file_size: u64) -> Self {
+        self.settings.minimal_file_size = Some(minimal_file_size);
+        self
+    }
+
+    /// In bytes.
+    pub fn maximal_file_size(mut self, maximal_file_size: u64) -> Self {
+        self.settings.maximal_file_size = Some(maximal_file_size);
+        self
+    }
+
+    /// Checks settings and creates tool ready to run.
+    pub fn build(self) -> Result<T, BuildError> {
+        let mut tool = T::from_options(self.options)?;
+        self.settings.apply(&mut tool)?;
+        tool.validate()?;
+        Ok(tool)
+    }
+}
+
+fn check_value(valid: bool, name: &'static str, message: impl FnOnce() -> String) -> Result<(), BuildError> {
+    if valid { Ok(()) } else { Err(BuildError::InvalidValue { name, message: message() }) }
+}
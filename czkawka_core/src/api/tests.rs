@@ -0,0 +1,123 @@
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use tempfile::TempDir;
+
+use crate::api::{BuildError, CheckingMethod, DuplicateFinder, EmptyFiles, HashType, ScanError, ScanResults, SimilarImages, Tool, ToolType};
+
+#[test]
+fn test_build_without_paths() {
+    let result = DuplicateFinder::builder().hash(HashType::Xxh3).build();
+    assert_eq!(result.err(), Some(BuildError::NoIncludedPaths));
+}
+
+#[test]
+fn test_build_with_invalid_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let result = SimilarImages::builder().paths([path]).hash_size(12).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "hash_size", .. })));
+
+    let result = DuplicateFinder::builder().paths([path]).check_method(CheckingMethod::AudioTags).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "check_method", .. })));
+
+    let result = SimilarImages::builder().paths([path]).query_images([path.join("not_existing.jpg")]).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "query_images", .. })));
+
+    // Query images are already used as reference items
+    let reference_path = path.join("reference");
+    fs::create_dir(&reference_path).unwrap();
+    fs::write(path.join("query.jpg"), b"query").unwrap();
+    let result = SimilarImages::builder()
+        .paths([path])
+        .reference_paths([&reference_path])
+        .query_images([path.join("query.jpg")])
+        .build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "query_images", .. })));
+    let result = SimilarImages::builder().paths([path]).match_crops(true).query_images([path.join("query.jpg")]).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "query_images", .. })));
+    SimilarImages::builder().paths([path]).query_images([path.join("query.jpg")]).build().unwrap();
+
+    let result = SimilarImages::builder().paths([path]).series_max_interval(3600).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "series_max_interval", .. })));
+
+    let result = DuplicateFinder::builder().paths([path]).minimal_file_size(100).maximal_file_size(10).build();
+    assert_eq!(result.err(), Some(BuildError::InvalidSizeRange { minimal: 100, maximal: 10 }));
+
+    // Empty files are never grouped, so reference folders have no meaning
+    let result = EmptyFiles::builder().paths([path]).reference_paths([path]).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidValue { name: "reference_paths", .. })));
+}
+
+#[test]
+fn test_build_with_not_existing_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let result = EmptyFiles::builder().paths([temp_dir.path().join("not_existing")]).build();
+    assert!(matches!(result.err(), Some(BuildError::InvalidSettings(problems)) if !problems.is_empty()));
+}
+
+#[test]
+fn test_run_duplicate_finder() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("file1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file2.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("unique.txt"), b"unique content").unwrap();
+
+    let mut finder = DuplicateFinder::builder()
+        .paths([path])
+        .hash(HashType::Crc32)
+        .minimal_file_size(1)
+        .use_cache(false)
+        .build()
+        .unwrap();
+    let report = finder.run(&Arc::new(AtomicBool::new(false)), None).unwrap();
+
+    assert_eq!(report.results.tool_type(), ToolType::Duplicate);
+    let ScanResults::Duplicates(groups) = report.results else {
+        panic!("Expected duplicates");
+    };
+    assert_eq!(groups.len(), 1);
+    assert!(groups[0].reference.is_none());
+    let mut names: Vec<_> = groups[0].items.iter().map(|entry| entry.path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    names.sort();
+    assert_eq!(names, ["file1.txt", "file2.txt"]);
+}
+
+#[test]
+fn test_run_with_reference_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let scanned = temp_dir.path().join("scanned");
+    let reference = temp_dir.path().join("reference");
+    fs::create_dir_all(&scanned).unwrap();
+    fs::create_dir_all(&reference).unwrap();
+    fs::write(scanned.join("copy.txt"), b"same content").unwrap();
+    fs::write(reference.join("original.txt"), b"same content").unwrap();
+
+    let mut finder = DuplicateFinder::builder().paths([&scanned]).reference_paths([&reference]).use_cache(false).build().unwrap();
+    let report = finder.run(&Arc::new(AtomicBool::new(false)), None).unwrap();
+
+    let ScanResults::Duplicates(groups) = report.results else {
+        panic!("Expected duplicates");
+    };
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].reference.as_ref().map(|entry| entry.path.clone()), Some(reference.join("original.txt")));
+    assert_eq!(groups[0].items.len(), 1);
+    assert_eq!(groups[0].items[0].path, scanned.join("copy.txt"));
+}
+
+#[test]
+fn test_run_stopped_scan() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("empty.txt"), b"").unwrap();
+
+    let mut finder = EmptyFiles::builder().paths([temp_dir.path()]).build().unwrap();
+    let result = finder.run(&Arc::new(AtomicBool::new(true)), None);
+    assert_eq!(result.err(), Some(ScanError::Stopped));
+
+    let mut finder = EmptyFiles::builder().paths([temp_dir.path()]).build().unwrap();
+    let report = finder.run(&Arc::new(AtomicBool::new(false)), None).unwrap();
+    assert!(matches!(&report.results, ScanResults::EmptyFiles(files) if files.len() == 1));
+}
@@ -0,0 +1,768 @@
+use std::path::PathBuf;
+
+use crate::api::{
+    BuildError, CheckedTypes, CheckingMethod, Cropdetect, FilterType, HashAlg, HashType, MusicSimilarity, NameIssues, ResultGroup, ScanResults, SearchMode, Tool, ToolBuilder,
+    check_value, private,
+};
+use crate::tools::bad_extensions::BadExtensionsParameters;
+use crate::tools::bad_names::BadNamesParameters;
+use crate::tools::big_file::BigFileParameters;
+use crate::tools::broken_files::BrokenFilesParameters;
+use crate::tools::duplicate::{DEFAULT_MINIMAL_CACHE_SIZE, DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinderParameters, MAX_PARTIAL_HASH_SEGMENTS};
+use crate::tools::exif_remover::ExifRemoverParameters;
+use crate::tools::same_music::SameMusicParameters;
+use crate::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, MAX_SERIES_INTERVAL, SIMILAR_VALUES, SimilarImagesParameters};
+use crate::tools::similar_videos::{
+    ALLOWED_SKIP_FORWARD_AMOUNT, ALLOWED_VID_HASH_DURATION, DEFAULT_CROP_DETECT, DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION, DEFAULT_VIDEO_PERCENTAGE_FOR_THUMBNAIL,
+    MAX_TOLERANCE, SimilarVideosParameters,
+};
+
+// Options are not public, so new ones can be added without breaking API - defaults are the same as in CLI
+
+// Tools are wrapped, so setters and results of internal tool types are not part of public API
+macro_rules! wrap_tool {
+    ($(#[$doc:meta])* $tool:ident, $inner:ty) => {
+        $(#[$doc])*
+        pub struct $tool($inner);
+
+        impl private::Sealed for $tool {
+            type Inner = $inner;
+
+            fn inner_mut(&mut self) -> &mut Self::Inner {
+                &mut self.0
+            }
+        }
+    };
+}
+
+wrap_tool!(
+    /// Finds files with the same content, name or size.
+    DuplicateFinder,
+    crate::tools::duplicate::DuplicateFinder
+);
+wrap_tool!(
+    /// Finds empty files.
+    EmptyFiles,
+    crate::tools::empty_files::EmptyFiles
+);
+wrap_tool!(
+    /// Finds folders which contain only empty folders.
+    EmptyFolder,
+    crate::tools::empty_folder::EmptyFolder
+);
+wrap_tool!(
+    /// Finds the biggest or the smallest files.
+    BigFile,
+    crate::tools::big_file::BigFile
+);
+wrap_tool!(
+    /// Finds temporary files, e.g. `*.tmp` or `.DS_Store`.
+    Temporary,
+    crate::tools::temporary::Temporary
+);
+wrap_tool!(
+    /// Finds similar images.
+    SimilarImages,
+    crate::tools::similar_images::SimilarImages
+);
+wrap_tool!(
+    /// Finds similar videos.
+    SimilarVideos,
+    crate::tools::similar_videos::SimilarVideos
+);
+wrap_tool!(
+    /// Finds music files with the same tags or content.
+    SameMusic,
+    crate::tools::same_music::SameMusic
+);
+wrap_tool!(
+    /// Finds symlinks pointing to not existing files or to themselves.
+    InvalidSymlinks,
+    crate::tools::invalid_symlinks::InvalidSymlinks
+);
+wrap_tool!(
+    /// Finds files which cannot be opened.
+    BrokenFiles,
+    crate::tools::broken_files::BrokenFiles
+);
+wrap_tool!(
+    /// Finds files with extension not matching their content.
+    BadExtensions,
+    crate::tools::bad_extensions::BadExtensions
+);
+wrap_tool!(
+    /// Finds files with problematic names.
+    BadNames,
+    crate::tools::bad_names::BadNames
+);
+wrap_tool!(
+    /// Finds files containing EXIF metadata.
+    ExifRemover,
+    crate::tools::exif_remover::ExifRemover
+);
+
+fn groups<T: Clone>(groups: &[Vec<T>]) -> Vec<ResultGroup<T>> {
+    groups
+        .iter()
+        .map(|items| ResultGroup {
+            reference: None,
+            items: items.clone(),
+        })
+        .collect()
+}
+
+fn referenced_groups<'a, T: Clone + 'a>(groups: impl IntoIterator<Item = &'a (T, Vec<T>)>) -> Vec<ResultGroup<T>> {
+    groups
+        .into_iter()
+        .map(|(reference, items)| ResultGroup {
+            reference: Some(reference.clone()),
+            items: items.clone(),
+        })
+        .collect()
+}
+
+// Tools without any specific options
+macro_rules! impl_simple_tool {
+    ($tool:ty, $results:expr) => {
+        impl Tool for $tool {
+            type Options = ();
+
+            fn from_options((): Self::Options) -> Result<Self, BuildError> {
+                Ok(Self(Self::Inner::new()))
+            }
+
+            fn collect_results(&self) -> ScanResults {
+                let results: fn(&Self) -> ScanResults = $results;
+                results(self)
+            }
+        }
+    };
+}
+
+impl_simple_tool!(EmptyFiles, |tool| ScanResults::EmptyFiles(tool.0.get_empty_files().clone()));
+impl_simple_tool!(EmptyFolder, |tool| ScanResults::EmptyFolders(tool.0.get_empty_folder_list().values().cloned().collect()));
+impl_simple_tool!(Temporary, |tool| ScanResults::TemporaryFiles(tool.0.get_temporary_files().clone()));
+impl_simple_tool!(InvalidSymlinks, |tool| ScanResults::InvalidSymlinks(tool.0.get_invalid_symlinks().clone()));
+
+pub struct DuplicateOptions {
+    check_method: CheckingMethod,
+    hash_type: HashType,
+    case_sensitive_name_comparison: bool,
+    use_prehash_cache: bool,
+    partial_hash_segments: u64,
+    verify_byte_by_byte: bool,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            check_method: CheckingMethod::Hash,
+            hash_type: HashType::Blake3,
+            case_sensitive_name_comparison: false,
+            use_prehash_cache: false,
+            partial_hash_segments: DEFAULT_PARTIAL_HASH_SEGMENTS,
+            verify_byte_by_byte: false,
+        }
+    }
+}
+
+impl Tool for DuplicateFinder {
+    type Options = DuplicateOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value(
+            matches!(
+                options.check_method,
+                CheckingMethod::Name | CheckingMethod::SizeName | CheckingMethod::Size | CheckingMethod::PartialHash | CheckingMethod::Hash
+            ),
+            "check_method",
+            || format!("{:?} cannot be used to find duplicates", options.check_method),
+        )?;
+        check_value(options.partial_hash_segments <= MAX_PARTIAL_HASH_SEGMENTS, "partial_hash_segments", || {
+            format!("must be at most {MAX_PARTIAL_HASH_SEGMENTS}")
+        })?;
+
+        let params = DuplicateFinderParameters::new(
+            options.check_method,
+            options.hash_type,
+            options.use_prehash_cache,
+            DEFAULT_MINIMAL_CACHE_SIZE,
+            DEFAULT_MINIMAL_CACHE_SIZE,
+            options.case_sensitive_name_comparison,
+            false,
+            options.verify_byte_by_byte,
+            None,
+            false,
+            options.partial_hash_segments,
+            false,
+        );
+        Ok(Self(Self::Inner::new(params)))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        let results = if self.0.get_use_reference() {
+            match self.0.get_params().check_method {
+                CheckingMethod::Name => referenced_groups(self.0.get_files_with_identical_name_referenced().values()),
+                CheckingMethod::SizeName => referenced_groups(self.0.get_files_with_identical_size_names_referenced().values()),
+                CheckingMethod::Size => referenced_groups(self.0.get_files_with_identical_size_referenced().values()),
+                _ => referenced_groups(self.0.get_files_with_identical_hashes_referenced().values().flatten()),
+            }
+        } else {
+            match self.0.get_params().check_method {
+                CheckingMethod::Name => self.0.get_files_sorted_by_names().values().flat_map(|items| groups(std::slice::from_ref(items))).collect(),
+                CheckingMethod::SizeName => self
+                    .0
+                    .get_files_sorted_by_size_name()
+                    .values()
+                    .flat_map(|items| groups(std::slice::from_ref(items)))
+                    .collect(),
+                CheckingMethod::Size => self.0.get_files_sorted_by_size().values().flat_map(|items| groups(std::slice::from_ref(items))).collect(),
+                _ => self.0.get_files_sorted_by_hash().values().flat_map(|size_groups| groups(size_groups)).collect(),
+            }
+        };
+        ScanResults::Duplicates(results)
+    }
+}
+
+impl ToolBuilder<DuplicateFinder> {
+    /// [`CheckingMethod::Hash`] by default.
+    pub fn check_method(mut self, check_method: CheckingMethod) -> Self {
+        self.options.check_method = check_method;
+        self
+    }
+
+    pub fn hash(mut self, hash_type: HashType) -> Self {
+        self.options.hash_type = hash_type;
+        self
+    }
+
+    pub fn case_sensitive_names(mut self, case_sensitive_name_comparison: bool) -> Self {
+        self.options.case_sensitive_name_comparison = case_sensitive_name_comparison;
+        self
+    }
+
+    pub fn use_prehash_cache(mut self, use_prehash_cache: bool) -> Self {
+        self.options.use_prehash_cache = use_prehash_cache;
+        self
+    }
+
+    /// Number of evenly spaced blocks hashed with [`CheckingMethod::PartialHash`], besides first and last block.
+    pub fn partial_hash_segments(mut self, partial_hash_segments: u64) -> Self {
+        self.options.partial_hash_segments = partial_hash_segments;
+        self
+    }
+
+    /// Files with same hash are additionally compared byte by byte.
+    pub fn verify_byte_by_byte(mut self, verify_byte_by_byte: bool) -> Self {
+        self.options.verify_byte_by_byte = verify_byte_by_byte;
+        self
+    }
+}
+
+pub struct SimilarImagesOptions {
+    max_difference: u32,
+    hash_size: u8,
+    hash_alg: HashAlg,
+    image_filter: FilterType,
+    exclude_images_with_same_size: bool,
+    check_transforms: bool,
+    match_crops: bool,
+    match_crops_max_images: usize,
+    query_images: Vec<PathBuf>,
+    series_max_interval: u32,
+    calculate_quality: bool,
+}
+
+impl Default for SimilarImagesOptions {
+    fn default() -> Self {
+        Self {
+            max_difference: 5,
+            hash_size: 16,
+            hash_alg: HashAlg::Gradient,
+            image_filter: FilterType::Nearest,
+            exclude_images_with_same_size: false,
+            check_transforms: false,
+            match_crops: false,
+            match_crops_max_images: DEFAULT_MATCH_CROPS_MAX_IMAGES,
+            query_images: Vec::new(),
+            series_max_interval: 0,
+            calculate_quality: false,
+        }
+    }
+}
+
+impl Tool for SimilarImages {
+    type Options = SimilarImagesOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value([8, 16, 32, 64].contains(&options.hash_size), "hash_size", || "must be one of 8, 16, 32 or 64".to_string())?;
+        let max_allowed_difference = SIMILAR_VALUES.iter().flatten().copied().max().unwrap_or_default();
+        check_value(options.max_difference <= max_allowed_difference, "max_difference", || {
+            format!("must be at most {max_allowed_difference}")
+        })?;
+        check_value(options.match_crops_max_images > 0, "match_crops_max_images", || "must be greater than 0".to_string())?;
+        check_value(options.series_max_interval <= MAX_SERIES_INTERVAL, "series_max_interval", || {
+            format!("must be at most {MAX_SERIES_INTERVAL}")
+        })?;
+        if let Some(missing_image) = options.query_images.iter().find(|path| !path.is_file()) {
+            return Err(BuildError::InvalidValue {
+                name: "query_images",
+                message: format!("\"{}\" is not a file", missing_image.to_string_lossy()),
+            });
+        }
+
+        let params = SimilarImagesParameters {
+            calculate_quality: options.calculate_quality,
+            ..SimilarImagesParameters::new(
+                options.max_difference,
+                options.hash_size,
+                options.hash_alg,
+                options.image_filter,
+                options.exclude_images_with_same_size,
+                options.check_transforms,
+                options.match_crops,
+                options.match_crops_max_images,
+                options.series_max_interval,
+            )
+        };
+        let mut tool = Self::Inner::new(params);
+        tool.set_query_images(options.query_images);
+        Ok(Self(tool))
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.0
+            .check_query_images_settings()
+            .map_err(|message| BuildError::InvalidValue { name: "query_images", message })
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        if self.0.get_use_reference() {
+            ScanResults::SimilarImages(referenced_groups(self.0.get_similar_images_referenced()))
+        } else {
+            ScanResults::SimilarImages(groups(self.0.get_similar_images()))
+        }
+    }
+}
+
+impl ToolBuilder<SimilarImages> {
+    /// Maximal difference between hashes of similar images, 0 finds only almost identical images.
+    pub fn max_difference(mut self, max_difference: u32) -> Self {
+        self.options.max_difference = max_difference;
+        self
+    }
+
+    /// One of 8, 16, 32 or 64 - bigger hashes are more precise, but slower to compare.
+    pub fn hash_size(mut self, hash_size: u8) -> Self {
+        self.options.hash_size = hash_size;
+        self
+    }
+
+    pub fn hash_alg(mut self, hash_alg: HashAlg) -> Self {
+        self.options.hash_alg = hash_alg;
+        self
+    }
+
+    /// Filter used to resize images before hashing.
+    pub fn image_filter(mut self, image_filter: FilterType) -> Self {
+        self.options.image_filter = image_filter;
+        self
+    }
+
+    pub fn exclude_same_size(mut self, exclude_images_with_same_size: bool) -> Self {
+        self.options.exclude_images_with_same_size = exclude_images_with_same_size;
+        self
+    }
+
+    /// Also matches rotated and mirrored images, reported in [`ImagesEntry::transform`](crate::api::ImagesEntry::transform).
+    pub fn check_transforms(mut self, check_transforms: bool) -> Self {
+        self.options.check_transforms = check_transforms;
+        self
+    }
+
+    /// Also matches images containing large part of other image, e.g. crops or screenshots, reported in [`ImagesEntry::overlap_ratio`](crate::api::ImagesEntry::overlap_ratio).
+    /// Every pair of images is compared, so this is skipped with a warning when more images are found.
+    pub fn match_crops(mut self, match_crops: bool) -> Self {
+        self.options.match_crops = match_crops;
+        self
+    }
+
+    /// Maximal number of images compared when matching crops, by default [`DEFAULT_MATCH_CROPS_MAX_IMAGES`](crate::api::DEFAULT_MATCH_CROPS_MAX_IMAGES).
+    pub fn match_crops_max_images(mut self, match_crops_max_images: usize) -> Self {
+        self.options.match_crops_max_images = match_crops_max_images;
+        self
+    }
+
+    /// Instead of grouping all images, finds only images similar to given ones, sorted from the most similar.
+    /// Results are returned as groups with query image as reference.
+    pub fn query_images<P: Into<PathBuf>>(mut self, query_images: impl IntoIterator<Item = P>) -> Self {
+        self.options.query_images = query_images.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Instead of grouping similar images, groups photos taken within given number of seconds one after another, e.g. bursts.
+    /// Photos in group are sorted from the sharpest, see [`ImagesEntry::sharpness`](crate::api::ImagesEntry::sharpness). 0 disables this mode, at most 60 seconds.
+    pub fn series_max_interval(mut self, series_max_interval: u32) -> Self {
+        self.options.series_max_interval = series_max_interval;
+        self
+    }
+
+    /// Also calculates [`ImagesEntry::sharpness`](crate::api::ImagesEntry::sharpness), JPEG quality and upscaling of every image, which makes hashing slower.
+    pub fn calculate_quality(mut self, calculate_quality: bool) -> Self {
+        self.options.calculate_quality = calculate_quality;
+        self
+    }
+}
+
+pub struct SimilarVideosOptions {
+    tolerance: i32,
+    exclude_videos_with_same_size: bool,
+    skip_forward_amount: u32,
+    duration: u32,
+    crop_detect: Cropdetect,
+}
+
+impl Default for SimilarVideosOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 10,
+            exclude_videos_with_same_size: false,
+            skip_forward_amount: DEFAULT_SKIP_FORWARD_AMOUNT,
+            duration: DEFAULT_VID_HASH_DURATION,
+            crop_detect: DEFAULT_CROP_DETECT,
+        }
+    }
+}
+
+impl Tool for SimilarVideos {
+    type Options = SimilarVideosOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value((0..=MAX_TOLERANCE).contains(&options.tolerance), "tolerance", || {
+            format!("must be between 0 and {MAX_TOLERANCE}")
+        })?;
+        check_value(ALLOWED_SKIP_FORWARD_AMOUNT.contains(&options.skip_forward_amount), "skip_forward_amount", || {
+            format!("must be in range {ALLOWED_SKIP_FORWARD_AMOUNT:?}")
+        })?;
+        check_value(ALLOWED_VID_HASH_DURATION.contains(&options.duration), "duration", || {
+            format!("must be in range {ALLOWED_VID_HASH_DURATION:?}")
+        })?;
+
+        let params = SimilarVideosParameters::new(
+            options.tolerance,
+            options.exclude_videos_with_same_size,
+            options.skip_forward_amount,
+            options.duration,
+            options.crop_detect,
+            false,
+            DEFAULT_VIDEO_PERCENTAGE_FOR_THUMBNAIL,
+            false,
+            2,
+        );
+        Ok(Self(Self::Inner::new(params)))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        if self.0.get_use_reference() {
+            ScanResults::SimilarVideos(referenced_groups(self.0.get_similar_videos_referenced()))
+        } else {
+            ScanResults::SimilarVideos(groups(self.0.get_similar_videos()))
+        }
+    }
+}
+
+impl ToolBuilder<SimilarVideos> {
+    /// Maximal difference between similar videos, from 0 to 20.
+    pub fn tolerance(mut self, tolerance: i32) -> Self {
+        self.options.tolerance = tolerance;
+        self
+    }
+
+    pub fn exclude_same_size(mut self, exclude_videos_with_same_size: bool) -> Self {
+        self.options.exclude_videos_with_same_size = exclude_videos_with_same_size;
+        self
+    }
+
+    /// Seconds skipped from start of video before hashing.
+    pub fn skip_forward_amount(mut self, skip_forward_amount: u32) -> Self {
+        self.options.skip_forward_amount = skip_forward_amount;
+        self
+    }
+
+    /// Seconds of video used to calculate hash.
+    pub fn duration(mut self, duration: u32) -> Self {
+        self.options.duration = duration;
+        self
+    }
+
+    pub fn crop_detect(mut self, crop_detect: Cropdetect) -> Self {
+        self.options.crop_detect = crop_detect;
+        self
+    }
+}
+
+pub struct SameMusicOptions {
+    music_similarity: MusicSimilarity,
+    approximate_comparison: bool,
+    check_type: CheckingMethod,
+    minimum_segment_duration: f32,
+    maximum_difference: f64,
+    compare_fingerprints_only_with_similar_titles: bool,
+    minimum_match_coverage: f32,
+}
+
+impl Default for SameMusicOptions {
+    fn default() -> Self {
+        Self {
+            music_similarity: MusicSimilarity::TRACK_TITLE | MusicSimilarity::TRACK_ARTIST,
+            approximate_comparison: false,
+            check_type: CheckingMethod::AudioTags,
+            minimum_segment_duration: 10.0,
+            maximum_difference: 2.0,
+            compare_fingerprints_only_with_similar_titles: false,
+            minimum_match_coverage: 0.0,
+        }
+    }
+}
+
+impl Tool for SameMusic {
+    type Options = SameMusicOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value(
+            matches!(options.check_type, CheckingMethod::AudioTags | CheckingMethod::AudioContent),
+            "check_method",
+            || format!("{:?} cannot be used to find similar music", options.check_type),
+        )?;
+        check_value(!options.music_similarity.is_empty(), "music_similarity", || "at least one tag must be compared".to_string())?;
+        check_value(
+            options.minimum_segment_duration > 0.0 && options.minimum_segment_duration < 3600.0,
+            "minimum_segment_duration",
+            || "must be between 0 and 3600 seconds".to_string(),
+        )?;
+        check_value(options.maximum_difference > 0.0 && options.maximum_difference < 10.0, "maximum_difference", || {
+            "must be between 0 and 10".to_string()
+        })?;
+        check_value((0.0..=100.0).contains(&options.minimum_match_coverage), "minimum_match_coverage", || {
+            "must be between 0 and 100".to_string()
+        })?;
+
+        let params = SameMusicParameters::new(
+            options.music_similarity,
+            options.approximate_comparison,
+            options.check_type,
+            options.minimum_segment_duration,
+            options.maximum_difference,
+            options.compare_fingerprints_only_with_similar_titles,
+            options.minimum_match_coverage,
+        );
+        Ok(Self(Self::Inner::new(params)))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        if self.0.get_use_reference() {
+            ScanResults::SameMusic(referenced_groups(self.0.get_similar_music_referenced()))
+        } else {
+            ScanResults::SameMusic(groups(self.0.get_duplicated_music_entries()))
+        }
+    }
+}
+
+impl ToolBuilder<SameMusic> {
+    /// [`CheckingMethod::AudioTags`] or [`CheckingMethod::AudioContent`], tags are compared by default.
+    pub fn check_method(mut self, check_type: CheckingMethod) -> Self {
+        self.options.check_type = check_type;
+        self
+    }
+
+    /// Tags which must be the same, used only when comparing tags.
+    pub fn music_similarity(mut self, music_similarity: MusicSimilarity) -> Self {
+        self.options.music_similarity = music_similarity;
+        self
+    }
+
+    pub fn approximate_comparison(mut self, approximate_comparison: bool) -> Self {
+        self.options.approximate_comparison = approximate_comparison;
+        self
+    }
+
+    /// Minimal length in seconds of matched fragment, used only when comparing content.
+    pub fn minimum_segment_duration(mut self, minimum_segment_duration: f32) -> Self {
+        self.options.minimum_segment_duration = minimum_segment_duration;
+        self
+    }
+
+    /// Maximal difference between fingerprints, used only when comparing content.
+    pub fn maximum_difference(mut self, maximum_difference: f64) -> Self {
+        self.options.maximum_difference = maximum_difference;
+        self
+    }
+
+    pub fn compare_fingerprints_only_with_similar_titles(mut self, compare_fingerprints_only_with_similar_titles: bool) -> Self {
+        self.options.compare_fingerprints_only_with_similar_titles = compare_fingerprints_only_with_similar_titles;
+        self
+    }
+
+    /// Percent of file which must be covered by matched fragments.
+    pub fn minimum_match_coverage(mut self, minimum_match_coverage: f32) -> Self {
+        self.options.minimum_match_coverage = minimum_match_coverage;
+        self
+    }
+}
+
+pub struct BigFileOptions {
+    number_of_files: usize,
+    search_mode: SearchMode,
+}
+
+impl Default for BigFileOptions {
+    fn default() -> Self {
+        Self {
+            number_of_files: 50,
+            search_mode: SearchMode::BiggestFiles,
+        }
+    }
+}
+
+impl Tool for BigFile {
+    type Options = BigFileOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value(options.number_of_files > 0, "number_of_files", || "must be bigger than 0".to_string())?;
+        Ok(Self(Self::Inner::new(BigFileParameters::new(options.number_of_files, options.search_mode))))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        ScanResults::BigFiles(self.0.get_big_files().clone())
+    }
+}
+
+impl ToolBuilder<BigFile> {
+    pub fn number_of_files(mut self, number_of_files: usize) -> Self {
+        self.options.number_of_files = number_of_files;
+        self
+    }
+
+    pub fn search_mode(mut self, search_mode: SearchMode) -> Self {
+        self.options.search_mode = search_mode;
+        self
+    }
+}
+
+pub struct BrokenFilesOptions {
+    checked_types: CheckedTypes,
+}
+
+impl Default for BrokenFilesOptions {
+    fn default() -> Self {
+        Self {
+            checked_types: CheckedTypes::PDF | CheckedTypes::AUDIO | CheckedTypes::IMAGE | CheckedTypes::ARCHIVE,
+        }
+    }
+}
+
+impl Tool for BrokenFiles {
+    type Options = BrokenFilesOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value(!options.checked_types.is_empty(), "checked_types", || {
+            "at least one type of files must be checked".to_string()
+        })?;
+        Ok(Self(Self::Inner::new(BrokenFilesParameters::new(options.checked_types))))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        ScanResults::BrokenFiles(self.0.get_broken_files().clone())
+    }
+}
+
+impl ToolBuilder<BrokenFiles> {
+    pub fn checked_types(mut self, checked_types: CheckedTypes) -> Self {
+        self.options.checked_types = checked_types;
+        self
+    }
+}
+
+pub struct BadNamesOptions {
+    checked_issues: NameIssues,
+}
+
+impl Default for BadNamesOptions {
+    fn default() -> Self {
+        Self {
+            checked_issues: NameIssues::all(),
+        }
+    }
+}
+
+impl Tool for BadNames {
+    type Options = BadNamesOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        check_value(!options.checked_issues.is_empty(), "checked_issues", || "at least one issue must be checked".to_string())?;
+        Ok(Self(Self::Inner::new(BadNamesParameters::new(options.checked_issues))))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        ScanResults::BadNames(self.0.get_bad_names_files().clone())
+    }
+}
+
+impl ToolBuilder<BadNames> {
+    /// All issues are checked by default.
+    pub fn checked_issues(mut self, checked_issues: NameIssues) -> Self {
+        self.options.checked_issues = checked_issues;
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct BadExtensionsOptions {
+    include_files_without_extension: bool,
+}
+
+impl Tool for BadExtensions {
+    type Options = BadExtensionsOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        let mut params = BadExtensionsParameters::new();
+        params.include_files_without_extension = options.include_files_without_extension;
+        Ok(Self(Self::Inner::new(params)))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        ScanResults::BadExtensions(self.0.get_bad_extensions_files().clone())
+    }
+}
+
+impl ToolBuilder<BadExtensions> {
+    pub fn include_files_without_extension(mut self, include_files_without_extension: bool) -> Self {
+        self.options.include_files_without_extension = include_files_without_extension;
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct ExifRemoverOptions {
+    ignored_tags: Vec<String>,
+}
+
+impl Tool for ExifRemover {
+    type Options = ExifRemoverOptions;
+
+    fn from_options(options: Self::Options) -> Result<Self, BuildError> {
+        Ok(Self(Self::Inner::new(ExifRemoverParameters::new(options.ignored_tags))))
+    }
+
+    fn collect_results(&self) -> ScanResults {
+        ScanResults::ExifFiles(self.0.get_exif_files().clone())
+    }
+}
+
+impl ToolBuilder<ExifRemover> {
+    /// Files which contain only these tags are not reported.
+    pub fn ignored_tags<S: Into<String>>(mut self, ignored_tags: impl IntoIterator<Item = S>) -> Self {
+        self.options.ignored_tags = ignored_tags.into_iter().map(Into::into).collect();
+        self
+    }
+}
@@ -25,11 +25,13 @@ use crate::flc;
 use crate::helpers::messages::Messages;
 
 pub(crate) const CACHE_VERSION: u8 = 100;
-pub(crate) const CACHE_DUPLICATE_VERSION: u8 = 100;
-pub(crate) const CACHE_IMAGE_VERSION: u8 = 100;
+pub(crate) const CACHE_DUPLICATE_VERSION: u8 = 110;
+pub(crate) const CACHE_IMAGE_VERSION: u8 = 140;
 pub(crate) const CACHE_VIDEO_VERSION: u8 = 110;
 pub(crate) const CACHE_BROKEN_FILES_VERSION: u8 = 110;
 pub(crate) const CACHE_VIDEO_OPTIMIZE_VERSION: u8 = 110;
+pub(crate) const CACHE_SCAN_SNAPSHOT_VERSION: u8 = 110;
+pub(crate) const CACHE_SAME_MUSIC_VERSION: u8 = 110;
 
 const MEMORY_LIMIT: u64 = 8 * 1024 * 1024 * 1024;
 const CLEANING_TIMESTAMPS_FILE: &str = "cleaning_timestamps.json";
@@ -346,7 +348,7 @@ where
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
@@ -12,8 +12,8 @@ use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::common::cache::{
-    CACHE_BROKEN_FILES_VERSION, CACHE_CLEANING_INTERVAL_SECONDS, CACHE_DUPLICATE_VERSION, CACHE_IMAGE_VERSION, CACHE_VERSION, CACHE_VIDEO_OPTIMIZE_VERSION, CACHE_VIDEO_VERSION,
-    CLEANING_TIMESTAMPS_FILE, MEMORY_LIMIT,
+    CACHE_BROKEN_FILES_VERSION, CACHE_CLEANING_INTERVAL_SECONDS, CACHE_DUPLICATE_VERSION, CACHE_IMAGE_VERSION, CACHE_SAME_MUSIC_VERSION, CACHE_VERSION,
+    CACHE_VIDEO_OPTIMIZE_VERSION, CACHE_VIDEO_VERSION, CLEANING_TIMESTAMPS_FILE, MEMORY_LIMIT,
 };
 use crate::common::config_cache_path::get_config_cache_path;
 use crate::common::traits::ResultEntry;
@@ -154,9 +154,9 @@ impl CacheType {
     fn from_filename(filename: &str) -> Option<Self> {
         if filename.starts_with("cache_duplicates_") && filename.ends_with(&format!("_{CACHE_DUPLICATE_VERSION}.bin")) {
             Some(Self::Duplicates)
-        } else if filename == format!("cache_same_music_tags_{CACHE_VERSION}.bin") {
+        } else if filename == format!("cache_same_music_tags_{CACHE_SAME_MUSIC_VERSION}.bin") {
             Some(Self::MusicTags)
-        } else if filename == format!("cache_same_music_fingerprints_{CACHE_VERSION}.bin") {
+        } else if filename == format!("cache_same_music_fingerprints_{CACHE_SAME_MUSIC_VERSION}.bin") {
             Some(Self::MusicFingerprints)
         } else if filename.starts_with("cache_similar_images_") && filename.ends_with(&format!("_{CACHE_IMAGE_VERSION}.bin")) {
             Some(Self::SimilarImages)
@@ -564,11 +564,11 @@ mod tests {
             Some(CacheType::Duplicates)
         ));
         assert!(matches!(
-            CacheType::from_filename(&format!("cache_same_music_tags_{CACHE_VERSION}.bin")),
+            CacheType::from_filename(&format!("cache_same_music_tags_{CACHE_SAME_MUSIC_VERSION}.bin")),
             Some(CacheType::MusicTags)
         ));
         assert!(matches!(
-            CacheType::from_filename(&format!("cache_same_music_fingerprints_{CACHE_VERSION}.bin")),
+            CacheType::from_filename(&format!("cache_same_music_fingerprints_{CACHE_SAME_MUSIC_VERSION}.bin")),
             Some(CacheType::MusicFingerprints)
         ));
         assert!(matches!(
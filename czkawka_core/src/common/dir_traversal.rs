@@ -1,6 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(target_family = "unix")]
 use std::fs;
-use std::fs::{DirEntry, FileType, Metadata};
+use std::mem;
 #[cfg(target_family = "unix")]
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
@@ -12,13 +13,16 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use log::debug;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::common::directories::Directories;
 use crate::common::extensions::Extensions;
+use crate::common::file_system::{FileSystem, FsDirEntry, FsEntryType, FsMetadata, LocalFileSystem};
 use crate::common::items::ExcludedItems;
 use crate::common::model::{CheckingMethod, FileEntry, ToolType};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::shared_traversal::SharedTraversal;
 use crate::common::tool_data::CommonToolData;
 use crate::flc;
 
@@ -28,12 +32,20 @@ pub enum Collect {
     Files,
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-enum EntryType {
-    File,
-    Dir,
-    Symlink,
-    Other,
+// Content of single folder remembered from previous traversal
+// Files and subfolders are stored already filtered, so snapshot is only valid for the same scan settings
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FolderSnapshotEntry {
+    pub modified_date_nanos: u128,
+    pub folders: Vec<PathBuf>,
+    pub files: Vec<FileEntry>,
+}
+
+// Folders whose modification date did not change since last traversal are not read again, but their content is taken from snapshot
+// Changing file content in place does not update modification date of its folder, so files from snapshot are checked again before using them
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FolderSnapshot {
+    pub folders: HashMap<PathBuf, FolderSnapshotEntry>,
 }
 
 pub struct DirTraversalBuilder<'b, F> {
@@ -51,6 +63,9 @@ pub struct DirTraversalBuilder<'b, F> {
     excluded_items: Option<ExcludedItems>,
     extensions: Option<Extensions>,
     tool_type: ToolType,
+    folder_snapshot: Option<&'b mut FolderSnapshot>,
+    shared_traversal: Option<Arc<SharedTraversal>>,
+    file_system: Option<Arc<dyn FileSystem>>,
 }
 
 #[derive(Debug)]
@@ -69,6 +84,9 @@ pub struct DirTraversal<'b, F> {
     checking_method: CheckingMethod,
     tool_type: ToolType,
     collect: Collect,
+    folder_snapshot: Option<&'b mut FolderSnapshot>,
+    shared_traversal: Option<Arc<SharedTraversal>>,
+    file_system: Arc<dyn FileSystem>,
 }
 
 impl Default for DirTraversalBuilder<'_, ()> {
@@ -94,6 +112,9 @@ impl DirTraversalBuilder<'_, ()> {
             extensions: None,
             excluded_items: None,
             tool_type: ToolType::None,
+            folder_snapshot: None,
+            shared_traversal: None,
+            file_system: None,
         }
     }
 }
@@ -109,6 +130,8 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
         self.maximal_file_size = Some(common_tool_data.maximal_file_size);
         self.tool_type = common_tool_data.tool_type;
         self.directories = Some(common_tool_data.directories.clone());
+        self.shared_traversal = common_tool_data.shared_traversal.clone();
+        self.file_system = Some(common_tool_data.file_system.clone());
         self
     }
 
@@ -142,6 +165,12 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
         self
     }
 
+    // Snapshot is used to skip reading unchanged folders and is replaced with current state of folders after successful traversal
+    pub(crate) fn folder_snapshot(mut self, folder_snapshot: Option<&'b mut FolderSnapshot>) -> Self {
+        self.folder_snapshot = folder_snapshot;
+        self
+    }
+
     pub(crate) fn group_by<G, T>(self, group_by: G) -> DirTraversalBuilder<'b, G>
     where
         G: Fn(&FileEntry) -> T,
@@ -161,6 +190,9 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
             collect: self.collect,
             checking_method: self.checking_method,
             tool_type: self.tool_type,
+            folder_snapshot: self.folder_snapshot,
+            shared_traversal: self.shared_traversal,
+            file_system: self.file_system,
         }
     }
 
@@ -180,6 +212,9 @@ impl<'b, F> DirTraversalBuilder<'b, F> {
             extensions: self.extensions.unwrap_or_default(),
             recursive_search: self.recursive_search,
             tool_type: self.tool_type,
+            folder_snapshot: self.folder_snapshot,
+            shared_traversal: self.shared_traversal,
+            file_system: self.file_system.unwrap_or_else(|| Arc::new(LocalFileSystem)),
         }
     }
 }
@@ -192,18 +227,6 @@ pub enum DirTraversalResult<T: Ord + PartialOrd> {
     Stopped,
 }
 
-fn entry_type(file_type: FileType) -> EntryType {
-    if file_type.is_dir() {
-        EntryType::Dir
-    } else if file_type.is_symlink() {
-        EntryType::Symlink
-    } else if file_type.is_file() {
-        EntryType::File
-    } else {
-        EntryType::Other
-    }
-}
-
 impl<F, T> DirTraversal<'_, F>
 where
     F: Fn(&FileEntry) -> T,
@@ -231,18 +254,42 @@ where
             minimal_file_size,
             maximal_file_size,
             stop_flag,
+            folder_snapshot,
+            shared_traversal,
+            file_system,
             ..
         } = self;
+        let file_system = file_system.as_ref();
+
+        // Files were already collected by traversal shared with other tools, so only filters of current tool are applied
+        if collect == Collect::Files
+            && let Some(shared_traversal) = shared_traversal
+        {
+            for fe in shared_traversal.filtered_files(&extensions, minimal_file_size, maximal_file_size) {
+                let key = (self.group_by)(&fe);
+                grouped_file_entries.entry(key).or_default().push(fe);
+            }
+            progress_handler.join_thread();
+            return DirTraversalResult::SuccessFiles {
+                grouped_file_entries,
+                warnings: all_warnings,
+            };
+        }
+
+        // Snapshot only makes sense when collecting files, symlinks are always checked from scratch
+        let mut folder_snapshot = folder_snapshot.filter(|_| collect == Collect::Files);
+        let previous_folder_snapshot = folder_snapshot.as_mut().map(|snapshot| mem::take(&mut snapshot.folders)).unwrap_or_default();
+        let use_folder_snapshot = folder_snapshot.is_some();
+        let mut current_folder_snapshot: HashMap<PathBuf, FolderSnapshotEntry> = HashMap::new();
 
         let mut file_results = Vec::new();
         // File traversal
         while let Some(current_file) = files_to_check.pop() {
-            let Some(metadata) = common_get_metadata_from_path(&current_file, &mut all_warnings) else {
+            let Some(metadata) = common_get_metadata_from_path(file_system, &current_file, &mut all_warnings) else {
                 continue;
             };
-            let file_type = metadata.file_type();
-            match (entry_type(file_type), collect) {
-                (EntryType::File, Collect::Files) => {
+            match (metadata.entry_type, collect) {
+                (FsEntryType::File, Collect::Files) => {
                     progress_handler.increase_items(1);
                     process_file_in_file_mode_path_check(
                         &current_file,
@@ -255,14 +302,14 @@ where
                         maximal_file_size,
                     );
                 }
-                (EntryType::File, Collect::InvalidSymlinks) => {
+                (FsEntryType::File, Collect::InvalidSymlinks) => {
                     progress_handler.increase_items(1);
                 }
-                (EntryType::Symlink, Collect::InvalidSymlinks) => {
+                (FsEntryType::Symlink, Collect::InvalidSymlinks) => {
                     progress_handler.increase_items(1);
                     process_symlink_in_symlink_mode_path_check(&current_file, &metadata, &mut all_warnings, &mut file_results, &extensions, &excluded_items);
                 }
-                (EntryType::Symlink | EntryType::Dir | EntryType::Other, _) => {
+                (FsEntryType::Symlink | FsEntryType::Dir | FsEntryType::Other, _) => {
                     // nothing to do
                 }
             }
@@ -288,8 +335,27 @@ where
                     let mut warnings = Vec::new();
                     let mut fe_result = Vec::new();
 
-                    let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
-                        return Some((dir_result, warnings, fe_result));
+                    let folder_modified_date_nanos = if use_folder_snapshot {
+                        get_folder_modified_date_nanos(file_system, &current_folder)
+                    } else {
+                        None
+                    };
+                    if let Some(modified_date_nanos) = folder_modified_date_nanos
+                        && let Some(snapshot_entry) = previous_folder_snapshot.get(&current_folder)
+                        && snapshot_entry.modified_date_nanos == modified_date_nanos
+                        && snapshot_files_unchanged(file_system, &snapshot_entry.files)
+                    {
+                        progress_handler.increase_items(snapshot_entry.files.len());
+                        return Some((
+                            snapshot_entry.folders.clone(),
+                            warnings,
+                            snapshot_entry.files.clone(),
+                            Some((current_folder, snapshot_entry.clone())),
+                        ));
+                    }
+
+                    let Some(read_dir) = common_read_dir(file_system, &current_folder, &mut warnings) else {
+                        return Some((dir_result, warnings, fe_result, None));
                     };
 
                     let mut counter = 0;
@@ -302,15 +368,15 @@ where
                         let Some(entry_data) = common_get_entry_data(&entry, &mut warnings, &current_folder) else {
                             continue;
                         };
-                        let Ok(file_type) = entry_data.file_type() else { continue };
 
-                        match (entry_type(file_type), collect) {
-                            (EntryType::Dir, Collect::Files | Collect::InvalidSymlinks) => {
+                        match (entry_data.entry_type, collect) {
+                            (FsEntryType::Dir, Collect::Files | Collect::InvalidSymlinks) => {
                                 process_dir_in_file_symlink_mode(recursive_search, entry_data, &directories, &mut dir_result, &mut warnings, &excluded_items);
                             }
-                            (EntryType::File, Collect::Files) => {
+                            (FsEntryType::File, Collect::Files) => {
                                 counter += 1;
                                 process_file_in_file_mode(
+                                    file_system,
                                     entry_data,
                                     &mut warnings,
                                     &mut fe_result,
@@ -321,14 +387,14 @@ where
                                     maximal_file_size,
                                 );
                             }
-                            (EntryType::File, Collect::InvalidSymlinks) => {
+                            (FsEntryType::File, Collect::InvalidSymlinks) => {
                                 counter += 1;
                             }
-                            (EntryType::Symlink, Collect::InvalidSymlinks) => {
+                            (FsEntryType::Symlink, Collect::InvalidSymlinks) => {
                                 counter += 1;
-                                process_symlink_in_symlink_mode(entry_data, &mut warnings, &mut fe_result, &extensions, &directories, &excluded_items);
+                                process_symlink_in_symlink_mode(file_system, entry_data, &mut warnings, &mut fe_result, &extensions, &directories, &excluded_items);
                             }
-                            (EntryType::Symlink, Collect::Files) | (EntryType::Other, _) => {
+                            (FsEntryType::Symlink, Collect::Files) | (FsEntryType::Other, _) => {
                                 // nothing to do
                             }
                         }
@@ -337,16 +403,31 @@ where
                         // Increase counter in batch, because usually it may be slow to add multiple times atomic value
                         progress_handler.increase_items(counter);
                     }
-                    Some((dir_result, warnings, fe_result))
+
+                    // Folders with read errors are not saved, to read them again in next scan
+                    let snapshot_entry = folder_modified_date_nanos.filter(|_| warnings.is_empty()).map(|modified_date_nanos| {
+                        (
+                            current_folder,
+                            FolderSnapshotEntry {
+                                modified_date_nanos,
+                                folders: dir_result.clone(),
+                                files: fe_result.clone(),
+                            },
+                        )
+                    });
+                    Some((dir_result, warnings, fe_result, snapshot_entry))
                 })
                 .while_some()
                 .collect();
 
-            let required_size = segments.iter().map(|(segment, _, _)| segment.len()).sum::<usize>();
+            let required_size = segments.iter().map(|(segment, _, _, _)| segment.len()).sum::<usize>();
             folders_to_check = Vec::with_capacity(required_size);
 
             // Process collected data
-            for (segment, warnings, mut fe_result) in segments {
+            for (segment, warnings, mut fe_result, snapshot_entry) in segments {
+                if let Some((folder, snapshot_entry)) = snapshot_entry {
+                    current_folder_snapshot.insert(folder, snapshot_entry);
+                }
                 folders_to_check.extend(segment);
                 all_warnings.extend(warnings);
                 fe_result.sort_by_cached_key(|fe| fe.path.to_string_lossy().to_string());
@@ -359,6 +440,15 @@ where
 
         progress_handler.join_thread();
 
+        if let Some(folder_snapshot) = folder_snapshot {
+            debug!(
+                "Folder snapshot contains {} folders, previously {} folders",
+                current_folder_snapshot.len(),
+                previous_folder_snapshot.len()
+            );
+            folder_snapshot.folders = current_folder_snapshot;
+        }
+
         debug!("Collected {} files", grouped_file_entries.values().map(Vec::len).sum::<usize>());
 
         match collect {
@@ -370,8 +460,18 @@ where
     }
 }
 
+// Folder with any file changed since snapshot was created, is read again to get current size and modification date of its files
+fn snapshot_files_unchanged(file_system: &dyn FileSystem, files: &[FileEntry]) -> bool {
+    files.iter().all(|fe| {
+        file_system
+            .metadata(&fe.path)
+            .is_ok_and(|metadata| metadata.is_file() && metadata.len == fe.size && get_modified_time(&metadata, &mut Vec::new(), &fe.path, false) == fe.modified_date)
+    })
+}
+
 fn process_file_in_file_mode(
-    entry_data: &DirEntry,
+    file_system: &dyn FileSystem,
+    entry_data: &FsDirEntry,
     warnings: &mut Vec<String>,
     fe_result: &mut Vec<FileEntry>,
     extensions: &Extensions,
@@ -380,11 +480,11 @@ fn process_file_in_file_mode(
     minimal_file_size: u64,
     maximal_file_size: u64,
 ) {
-    if !extensions.check_if_entry_have_valid_extension(&entry_data.file_name()) {
+    if !extensions.check_if_entry_have_valid_extension(entry_data.file_name()) {
         return;
     }
 
-    let current_file_name = entry_data.path();
+    let current_file_name = entry_data.path.clone();
     if excluded_items.is_excluded(&current_file_name) {
         return;
     }
@@ -401,14 +501,14 @@ fn process_file_in_file_mode(
     #[cfg(windows)]
     let _ = directories; // Silence unused variable warning on Windows
 
-    let Some(metadata) = common_get_metadata_dir(entry_data, warnings, &current_file_name) else {
+    let Some(metadata) = common_get_metadata_dir(file_system, entry_data, warnings, &current_file_name) else {
         return;
     };
 
-    if (minimal_file_size..=maximal_file_size).contains(&metadata.len()) {
+    if (minimal_file_size..=maximal_file_size).contains(&metadata.len) {
         // Creating new file entry
         let fe: FileEntry = FileEntry {
-            size: metadata.len(),
+            size: metadata.len,
             modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
             path: current_file_name,
         };
@@ -416,11 +516,11 @@ fn process_file_in_file_mode(
         fe_result.push(fe);
     }
 }
-// Same as above, but working with Path instead of DirEntry
+// Same as above, but working with Path instead of FsDirEntry
 // Sadly this cannot be merged, due to a little crazy optimizations done in this functions
 fn process_file_in_file_mode_path_check(
     path: &Path,
-    metadata: &Metadata,
+    metadata: &FsMetadata,
     warnings: &mut Vec<String>,
     fe_result: &mut Vec<FileEntry>,
     extensions: &Extensions,
@@ -439,10 +539,10 @@ fn process_file_in_file_mode_path_check(
         return;
     }
 
-    if (minimal_file_size..=maximal_file_size).contains(&metadata.len()) {
+    if (minimal_file_size..=maximal_file_size).contains(&metadata.len) {
         // Creating new file entry
         let fe: FileEntry = FileEntry {
-            size: metadata.len(),
+            size: metadata.len,
             modified_date: get_modified_time(metadata, warnings, path, false),
             path: path.to_path_buf(),
         };
@@ -453,7 +553,7 @@ fn process_file_in_file_mode_path_check(
 
 fn process_dir_in_file_symlink_mode(
     recursive_search: bool,
-    entry_data: &DirEntry,
+    entry_data: &FsDirEntry,
     directories: &Directories,
     dir_result: &mut Vec<PathBuf>,
     warnings: &mut Vec<String>,
@@ -463,7 +563,7 @@ fn process_dir_in_file_symlink_mode(
         return;
     }
 
-    let dir_path = entry_data.path();
+    let dir_path = entry_data.path.clone();
     if directories.is_excluded(&dir_path) {
         return;
     }
@@ -488,18 +588,19 @@ fn process_dir_in_file_symlink_mode(
 }
 
 fn process_symlink_in_symlink_mode(
-    entry_data: &DirEntry,
+    file_system: &dyn FileSystem,
+    entry_data: &FsDirEntry,
     warnings: &mut Vec<String>,
     fe_result: &mut Vec<FileEntry>,
     extensions: &Extensions,
     directories: &Directories,
     excluded_items: &ExcludedItems,
 ) {
-    if !extensions.check_if_entry_have_valid_extension(&entry_data.file_name()) {
+    if !extensions.check_if_entry_have_valid_extension(entry_data.file_name()) {
         return;
     }
 
-    let current_file_name = entry_data.path();
+    let current_file_name = entry_data.path.clone();
     if excluded_items.is_excluded(&current_file_name) {
         return;
     }
@@ -516,13 +617,13 @@ fn process_symlink_in_symlink_mode(
     #[cfg(windows)]
     let _ = directories; // Silence unused variable warning on Windows
 
-    let Some(metadata) = common_get_metadata_dir(entry_data, warnings, &current_file_name) else {
+    let Some(metadata) = common_get_metadata_dir(file_system, entry_data, warnings, &current_file_name) else {
         return;
     };
 
     // Creating new file entry
     let fe: FileEntry = FileEntry {
-        size: metadata.len(),
+        size: metadata.len,
         modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
         path: current_file_name,
     };
@@ -531,7 +632,7 @@ fn process_symlink_in_symlink_mode(
 }
 fn process_symlink_in_symlink_mode_path_check(
     path: &Path,
-    metadata: &Metadata,
+    metadata: &FsMetadata,
     warnings: &mut Vec<String>,
     fe_result: &mut Vec<FileEntry>,
     extensions: &Extensions,
@@ -550,7 +651,7 @@ fn process_symlink_in_symlink_mode_path_check(
 
     // Creating new file entry
     let fe: FileEntry = FileEntry {
-        size: metadata.len(),
+        size: metadata.len,
         modified_date: get_modified_time(metadata, warnings, path, false),
         path: path.to_path_buf(),
     };
@@ -558,16 +659,16 @@ fn process_symlink_in_symlink_mode_path_check(
     fe_result.push(fe);
 }
 
-pub(crate) fn common_read_dir(current_folder: &Path, warnings: &mut Vec<String>) -> Option<Vec<Result<DirEntry, std::io::Error>>> {
-    match fs::read_dir(current_folder) {
-        Ok(t) => Some(t.collect()),
+pub(crate) fn common_read_dir(file_system: &dyn FileSystem, current_folder: &Path, warnings: &mut Vec<String>) -> Option<Vec<Result<FsDirEntry, std::io::Error>>> {
+    match file_system.read_dir(current_folder) {
+        Ok(t) => Some(t),
         Err(e) => {
             warnings.push(flc!("core_cannot_open_dir", dir = current_folder.to_string_lossy().to_string(), reason = e.to_string()));
             None
         }
     }
 }
-pub(crate) fn common_get_entry_data<'a>(entry: &'a Result<DirEntry, std::io::Error>, warnings: &mut Vec<String>, current_folder: &Path) -> Option<&'a DirEntry> {
+pub(crate) fn common_get_entry_data<'a>(entry: &'a Result<FsDirEntry, std::io::Error>, warnings: &mut Vec<String>, current_folder: &Path) -> Option<&'a FsDirEntry> {
     let entry_data = match entry {
         Ok(t) => t,
         Err(e) => {
@@ -581,8 +682,9 @@ pub(crate) fn common_get_entry_data<'a>(entry: &'a Result<DirEntry, std::io::Err
     };
     Some(entry_data)
 }
-pub(crate) fn common_get_metadata_dir(entry_data: &DirEntry, warnings: &mut Vec<String>, current_folder: &Path) -> Option<Metadata> {
-    let metadata: Metadata = match entry_data.metadata() {
+// Like metadata of directory entry, doesn't follow symlinks
+pub(crate) fn common_get_metadata_dir(file_system: &dyn FileSystem, entry_data: &FsDirEntry, warnings: &mut Vec<String>, current_folder: &Path) -> Option<FsMetadata> {
+    let metadata = match file_system.symlink_metadata(&entry_data.path) {
         Ok(t) => t,
         Err(e) => {
             warnings.push(flc!(
@@ -596,8 +698,8 @@ pub(crate) fn common_get_metadata_dir(entry_data: &DirEntry, warnings: &mut Vec<
     Some(metadata)
 }
 
-pub(crate) fn common_get_metadata_from_path(path: &Path, warnings: &mut Vec<String>) -> Option<Metadata> {
-    let metadata: Metadata = match fs::metadata(path) {
+pub(crate) fn common_get_metadata_from_path(file_system: &dyn FileSystem, path: &Path, warnings: &mut Vec<String>) -> Option<FsMetadata> {
+    let metadata = match file_system.metadata(path) {
         Ok(t) => t,
         Err(e) => {
             warnings.push(flc!("core_cannot_read_metadata_file", file = path.to_string_lossy().to_string(), reason = e.to_string()));
@@ -607,8 +709,13 @@ pub(crate) fn common_get_metadata_from_path(path: &Path, warnings: &mut Vec<Stri
     Some(metadata)
 }
 
-pub(crate) fn get_modified_time(metadata: &Metadata, warnings: &mut Vec<String>, current_file_name: &Path, is_folder: bool) -> u64 {
-    match metadata.modified() {
+fn get_folder_modified_date_nanos(file_system: &dyn FileSystem, path: &Path) -> Option<u128> {
+    let modified = file_system.metadata(path).ok()?.modified.ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+pub(crate) fn get_modified_time(metadata: &FsMetadata, warnings: &mut Vec<String>, current_file_name: &Path, is_folder: bool) -> u64 {
+    match &metadata.modified {
         Ok(t) => match t.duration_since(UNIX_EPOCH) {
             Ok(d) => d.as_secs(),
             Err(_inspected) => {
@@ -625,13 +732,13 @@ pub(crate) fn get_modified_time(metadata: &Metadata, warnings: &mut Vec<String>,
                 warnings.push(flc!(
                     "core_folder_no_modification_date",
                     name = current_file_name.to_string_lossy().to_string(),
-                    reason = e.to_string()
+                    reason = e.clone()
                 ));
             } else {
                 warnings.push(flc!(
                     "core_file_no_modification_date",
                     name = current_file_name.to_string_lossy().to_string(),
-                    reason = e.to_string()
+                    reason = e.clone()
                 ));
             }
             0
@@ -667,6 +774,7 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::common::file_system::LocalFileSystem;
     use crate::common::tool_data::*;
 
     impl CommonData for CommonToolData {
@@ -709,7 +817,7 @@ mod tests {
         let secs = NOW.duration_since(SystemTime::UNIX_EPOCH).expect("Cannot fail calculating duration since epoch").as_secs();
 
         let mut common_data = CommonToolData::new(ToolType::SimilarImages);
-        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec());
+        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec(), &LocalFileSystem);
         common_data.set_minimal_file_size(0);
 
         match DirTraversalBuilder::new()
@@ -760,7 +868,7 @@ mod tests {
         let secs = NOW.duration_since(SystemTime::UNIX_EPOCH).expect("Cannot fail calculating duration since epoch").as_secs();
 
         let mut common_data = CommonToolData::new(ToolType::SimilarImages);
-        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec());
+        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec(), &LocalFileSystem);
         common_data.set_minimal_file_size(0);
 
         match DirTraversalBuilder::new()
@@ -806,7 +914,7 @@ mod tests {
         let secs = NOW.duration_since(SystemTime::UNIX_EPOCH).expect("Cannot fail duration from epoch").as_secs();
 
         let mut common_data = CommonToolData::new(ToolType::SimilarImages);
-        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec());
+        common_data.directories.set_included_paths([dir.path().to_owned()].to_vec(), &LocalFileSystem);
         common_data.set_minimal_file_size(0);
 
         match DirTraversalBuilder::new()
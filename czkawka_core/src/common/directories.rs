@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 #[cfg(target_family = "unix")]
 use std::{fs, os::unix::fs::MetadataExt};
 
+use crate::common::file_system::FileSystem;
 use crate::common::normalize_windows_path;
 use crate::common::traits::ResultEntry;
 use crate::flc;
@@ -30,7 +31,7 @@ impl Directories {
         Default::default()
     }
 
-    pub(crate) fn set_reference_paths(&mut self, reference_paths: Vec<PathBuf>) -> Messages {
+    pub(crate) fn set_reference_paths(&mut self, reference_paths: Vec<PathBuf>, file_system: &dyn FileSystem) -> Messages {
         self.reference_files = Vec::new();
         self.reference_directories = Vec::new();
         let paths = if cfg!(target_family = "windows") {
@@ -39,10 +40,10 @@ impl Directories {
             reference_paths.clone()
         };
         self.original_reference_paths = reference_paths;
-        self.process_paths(paths, true, false)
+        self.process_paths(paths, true, false, file_system)
     }
 
-    pub(crate) fn set_included_paths(&mut self, included_paths: Vec<PathBuf>) -> Messages {
+    pub(crate) fn set_included_paths(&mut self, included_paths: Vec<PathBuf>, file_system: &dyn FileSystem) -> Messages {
         self.included_files = Vec::new();
         self.included_directories = Vec::new();
         let paths = if cfg!(target_family = "windows") {
@@ -51,10 +52,10 @@ impl Directories {
             included_paths.clone()
         };
         self.original_included_paths = included_paths;
-        self.process_paths(paths, false, false)
+        self.process_paths(paths, false, false, file_system)
     }
 
-    pub(crate) fn set_excluded_paths(&mut self, excluded_paths: Vec<PathBuf>) -> Messages {
+    pub(crate) fn set_excluded_paths(&mut self, excluded_paths: Vec<PathBuf>, file_system: &dyn FileSystem) -> Messages {
         self.excluded_files = Vec::new();
         self.excluded_directories = Vec::new();
         let paths = if cfg!(target_family = "windows") {
@@ -63,10 +64,10 @@ impl Directories {
             excluded_paths.clone()
         };
         self.original_excluded_paths = excluded_paths;
-        self.process_paths(paths, false, true)
+        self.process_paths(paths, false, true, file_system)
     }
 
-    fn process_paths(&mut self, paths: Vec<PathBuf>, is_reference: bool, is_excluded: bool) -> Messages {
+    fn process_paths(&mut self, paths: Vec<PathBuf>, is_reference: bool, is_excluded: bool, file_system: &dyn FileSystem) -> Messages {
         let mut messages: Messages = Messages::new();
 
         if paths.is_empty() {
@@ -79,12 +80,12 @@ impl Directories {
                 break;
             }
 
-            let (dir, msg) = Self::canonicalize_and_clear_path(&path, is_excluded);
+            let (dir, msg) = Self::canonicalize_and_clear_path(&path, is_excluded, file_system);
 
             messages.extend_with_another_messages(msg);
 
-            if let Some(dir) = dir {
-                match (dir.is_file(), is_reference, is_excluded) {
+            if let Some((dir, is_file)) = dir {
+                match (is_file, is_reference, is_excluded) {
                     (false, true, false) => self.reference_directories.push(dir),
                     (false, false, false) => self.included_directories.push(dir),
                     (false, false, true) => self.excluded_directories.push(dir),
@@ -100,38 +101,41 @@ impl Directories {
         messages
     }
 
-    fn canonicalize_and_clear_path(path: &Path, is_excluded: bool) -> (Option<PathBuf>, Messages) {
+    // Returns also info if path points to file
+    fn canonicalize_and_clear_path(path: &Path, is_excluded: bool, file_system: &dyn FileSystem) -> (Option<(PathBuf, bool)>, Messages) {
         let mut messages = Messages::new();
         let mut path = path.to_path_buf();
-        if !path.exists() {
+        let Ok(metadata) = file_system.metadata(&path) else {
             if !is_excluded {
                 messages.warnings.push(flc!("core_path_must_exists", path = path.to_string_lossy().to_string()));
             }
             return (None, messages);
-        }
+        };
 
-        if !path.is_dir() && !path.is_file() {
+        if !metadata.is_dir() && !metadata.is_file() {
             messages.warnings.push(flc!("core_must_be_directory_or_file", path = path.to_string_lossy().to_string()));
             return (None, messages);
         }
 
-        // Try to canonicalize them
-        if cfg!(windows) {
-            // Only canonicalize if it's not a network path
-            // This can be done by checking if path starts with \\?\UNC\
-            if let Ok(dir_can) = path.canonicalize()
-                && let Some(dir_can_str) = dir_can.to_string_lossy().strip_prefix(r"\\?\")
-                && dir_can_str.chars().nth(1) == Some(':')
-            {
-                path = PathBuf::from(dir_can_str);
-            }
-        } else {
-            if let Ok(dir) = path.canonicalize() {
-                path = dir;
+        // Try to canonicalize them, paths on other filesystems are used as they are
+        if file_system.is_local() {
+            if cfg!(windows) {
+                // Only canonicalize if it's not a network path
+                // This can be done by checking if path starts with \\?\UNC\
+                if let Ok(dir_can) = path.canonicalize()
+                    && let Some(dir_can_str) = dir_can.to_string_lossy().strip_prefix(r"\\?\")
+                    && dir_can_str.chars().nth(1) == Some(':')
+                {
+                    path = PathBuf::from(dir_can_str);
+                }
+            } else {
+                if let Ok(dir) = path.canonicalize() {
+                    path = dir;
+                }
             }
         }
 
-        (Some(path), messages)
+        (Some((path, metadata.is_file())), messages)
     }
 
     #[cfg(target_family = "unix")]
@@ -139,9 +143,14 @@ impl Directories {
         self.exclude_other_filesystems = Some(exclude_other_filesystems);
     }
 
-    pub(crate) fn optimize_directories(&mut self, recursive_search: bool, skip_exist_check: bool) -> Result<Messages, Messages> {
+    pub(crate) fn optimize_directories(&mut self, recursive_search: bool, skip_exist_check: bool, file_system: &dyn FileSystem) -> Result<Messages, Messages> {
         let mut messages: Messages = Messages::new();
 
+        // Device ids are available only on local filesystem
+        if !file_system.is_local() {
+            self.exclude_other_filesystems = Some(false);
+        }
+
         if self.original_included_paths.is_empty() {
             messages.critical = Some(flc!("core_cannot_start_scan_no_included_paths"));
             return Err(messages);
@@ -200,7 +209,7 @@ impl Directories {
                 &mut self.included_files,
                 &mut self.included_directories,
             ] {
-                kk.retain(|path| path.exists());
+                kk.retain(|path| file_system.metadata(path).is_ok());
             }
         }
 
@@ -309,11 +318,12 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
+    use crate::common::file_system::LocalFileSystem;
 
     #[test]
     fn test_no_included_paths_errors() {
         let mut d = Directories::new();
-        let msgs = d.optimize_directories(true, true).unwrap_err();
+        let msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap_err();
         assert!(msgs.critical.is_some());
     }
 
@@ -325,7 +335,7 @@ mod tests {
         d.included_directories.push(p.clone());
         d.original_included_paths.push(p.clone());
         d.original_included_paths.push(p.clone());
-        let _msgs = d.optimize_directories(true, true).unwrap();
+        let _msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap();
         assert_eq!(d.included_directories, vec![p]);
     }
 
@@ -337,7 +347,7 @@ mod tests {
         d.included_directories.push(sub.clone());
         d.original_included_paths.push(sub);
         d.excluded_directories.push(base);
-        let _msgs = d.optimize_directories(true, true).unwrap_err();
+        let _msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap_err();
         assert_eq!(d.included_directories, Vec::<PathBuf>::new());
     }
 
@@ -352,7 +362,7 @@ mod tests {
         d.original_included_paths.push(PathBuf::from("/home/Pulpit"));
 
         // use recursive_search = true and skip_exist_check = true as requested
-        let msgs = d.optimize_directories(true, true).unwrap();
+        let msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap();
         // only root should remain after dedup
         assert_eq!(d.included_directories, vec![PathBuf::from("/")]);
         assert!(msgs.critical.is_none());
@@ -368,7 +378,7 @@ mod tests {
         d.original_excluded_paths.push(PathBuf::from("/this/include/sub"));
         d.original_excluded_paths.push(PathBuf::from("/other/place"));
 
-        let _msgs = d.optimize_directories(true, true).unwrap();
+        let _msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap();
         assert_eq!(d.included_directories, vec![PathBuf::from("/this/include")]);
         assert_eq!(d.excluded_directories, vec![PathBuf::from("/this/include/sub")]);
     }
@@ -387,7 +397,7 @@ mod tests {
         d.reference_files.push(PathBuf::from("/a/included_file.txt"));
         d.reference_files.push(PathBuf::from("/other/file2.txt"));
 
-        let _msgs = d.optimize_directories(true, true).unwrap();
+        let _msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap();
 
         assert_eq!(d.included_directories, vec![PathBuf::from("/a")]);
         assert_eq!(d.excluded_directories, Vec::<PathBuf>::new());
@@ -405,7 +415,7 @@ mod tests {
         d.included_files = Vec::new();
         d.reference_files = Vec::new();
 
-        let msgs = d.optimize_directories(true, true).unwrap_err();
+        let msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap_err();
         assert!(msgs.critical.is_some());
     }
 
@@ -421,7 +431,7 @@ mod tests {
         d.excluded_directories.push(PathBuf::from("/base/file"));
         d.original_excluded_paths.push(PathBuf::from("/base/file"));
 
-        let _msgs = d.optimize_directories(true, true).unwrap();
+        let _msgs = d.optimize_directories(true, true, &LocalFileSystem).unwrap();
         // included_files should be removed because it equals an excluded directory
         assert!(d.included_files.is_empty());
         // excluded_directories should be retained as it's inside included_directories
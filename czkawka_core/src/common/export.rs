@@ -0,0 +1,206 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, params};
+use serde::Serialize;
+
+use crate::common::journal::current_time;
+use crate::common::ndjson::{NDJSON_SCHEMA_VERSION, NdjsonItem, NdjsonLine, NdjsonRecord};
+
+// Single exported file with id of group that it belongs to(None for tools without groups)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedFile {
+    pub group_id: Option<usize>,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+    pub reference: bool,
+    // Tool specific fields as json object
+    pub details: String,
+}
+
+impl ExportedFile {
+    fn new(group_id: Option<usize>, item: &NdjsonItem) -> Self {
+        Self {
+            group_id,
+            path: item.path.clone(),
+            size: item.size,
+            modified_date: item.modified_date,
+            reference: item.reference,
+            details: if item.details.is_empty() {
+                String::new()
+            } else {
+                serde_json::Value::Object(item.details.clone()).to_string()
+            },
+        }
+    }
+}
+
+pub fn collect_exported_files(lines: &[NdjsonLine]) -> Vec<ExportedFile> {
+    let mut files = Vec::new();
+    for line in lines {
+        match &line.record {
+            NdjsonRecord::Group { index, items } => files.extend(items.iter().map(|item| ExportedFile::new(Some(*index), item))),
+            NdjsonRecord::Entry { item } => files.push(ExportedFile::new(None, item)),
+            NdjsonRecord::Progress(_) | NdjsonRecord::Message { .. } | NdjsonRecord::Summary { .. } | NdjsonRecord::Change { .. } => {}
+        }
+    }
+    files
+}
+
+// One row per file, files from same group share group_id
+pub fn write_results_as_csv<W: Write>(lines: &[NdjsonLine], writer: W) -> io::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for file in collect_exported_files(lines) {
+        csv_writer.serialize(file).map_err(io::Error::other)?;
+    }
+    csv_writer.flush()
+}
+
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE scans (
+    id INTEGER PRIMARY KEY,
+    tool TEXT NOT NULL,
+    schema_version INTEGER NOT NULL,
+    created INTEGER NOT NULL,
+    included_paths TEXT NOT NULL
+);
+CREATE TABLE groups (
+    id INTEGER PRIMARY KEY,
+    scan_id INTEGER NOT NULL REFERENCES scans(id),
+    group_index INTEGER NOT NULL,
+    files_number INTEGER NOT NULL,
+    total_size INTEGER NOT NULL
+);
+CREATE TABLE files (
+    id INTEGER PRIMARY KEY,
+    scan_id INTEGER NOT NULL REFERENCES scans(id),
+    group_id INTEGER REFERENCES groups(id),
+    path TEXT NOT NULL,
+    folder TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    modified_date INTEGER NOT NULL,
+    is_reference INTEGER NOT NULL,
+    details TEXT NOT NULL
+);
+CREATE INDEX files_group_id ON files(group_id);
+";
+
+// Database is always created from scratch, so results of previous export with same name are removed
+pub fn save_results_as_sqlite(lines: &[NdjsonLine], file_name: &Path, included_paths: &[PathBuf]) -> io::Result<()> {
+    if file_name.exists() {
+        std::fs::remove_file(file_name)?;
+    }
+    let mut connection = Connection::open(file_name).map_err(io::Error::other)?;
+    write_results_to_sqlite(&mut connection, lines, included_paths).map_err(io::Error::other)
+}
+
+fn write_results_to_sqlite(connection: &mut Connection, lines: &[NdjsonLine], included_paths: &[PathBuf]) -> rusqlite::Result<()> {
+    connection.execute_batch(SQLITE_SCHEMA)?;
+    let transaction = connection.transaction()?;
+
+    let tool = lines.first().map(|line| line.tool).unwrap_or_default();
+    let tool_name = serde_json::to_value(tool).ok().and_then(|value| value.as_str().map(str::to_string)).unwrap_or_default();
+    let included_paths = included_paths.iter().map(|path| path.to_string_lossy()).collect::<Vec<_>>().join("\n");
+    transaction.execute(
+        "INSERT INTO scans (tool, schema_version, created, included_paths) VALUES (?1, ?2, ?3, ?4)",
+        params![tool_name, NDJSON_SCHEMA_VERSION, current_time(), included_paths],
+    )?;
+    let scan_id = transaction.last_insert_rowid();
+
+    {
+        let mut insert_group = transaction.prepare("INSERT INTO groups (scan_id, group_index, files_number, total_size) VALUES (?1, ?2, ?3, ?4)")?;
+        let mut insert_file =
+            transaction.prepare("INSERT INTO files (scan_id, group_id, path, folder, size, modified_date, is_reference, details) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)")?;
+        let mut insert_item = |group_id: Option<i64>, item: &NdjsonItem| {
+            let file = ExportedFile::new(None, item);
+            let folder = file.path.parent().map(|parent| parent.to_string_lossy().to_string()).unwrap_or_default();
+            insert_file.execute(params![
+                scan_id,
+                group_id,
+                file.path.to_string_lossy(),
+                folder,
+                file.size,
+                file.modified_date,
+                file.reference,
+                file.details
+            ])
+        };
+        for line in lines {
+            match &line.record {
+                NdjsonRecord::Group { index, items } => {
+                    let total_size: u64 = items.iter().map(|item| item.size).sum();
+                    insert_group.execute(params![scan_id, index, items.len(), total_size])?;
+                    let group_id = transaction.last_insert_rowid();
+                    for item in items {
+                        insert_item(Some(group_id), item)?;
+                    }
+                }
+                NdjsonRecord::Entry { item } => {
+                    insert_item(None, item)?;
+                }
+                NdjsonRecord::Progress(_) | NdjsonRecord::Message { .. } | NdjsonRecord::Summary { .. } | NdjsonRecord::Change { .. } => {}
+            }
+        }
+    }
+
+    transaction.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::model::{FileEntry, ToolType};
+    use crate::common::ndjson::{NdjsonCollector, NdjsonWriter};
+
+    fn example_lines() -> Vec<NdjsonLine> {
+        let entry = |path: &str, size: u64| FileEntry {
+            path: PathBuf::from(path),
+            size,
+            modified_date: 5,
+        };
+        let mut writer = NdjsonWriter::new(NdjsonCollector::default(), ToolType::Duplicate);
+        writer
+            .write_groups(&vec![
+                vec![entry("/data/a/1.txt", 10), entry("/data/b/1.txt", 10)],
+                vec![entry("/data/a/2.txt", 7), entry("/data/a, b/2.txt", 7)],
+            ])
+            .unwrap();
+        writer.write_summary().unwrap();
+        writer.into_inner().lines
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let mut output = Vec::new();
+        write_results_as_csv(&example_lines(), &mut output).unwrap();
+
+        let text = String::from_utf8(output).unwrap();
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], "group_id,path,size,modified_date,reference,details");
+        assert_eq!(rows[1], "0,/data/a/1.txt,10,5,false,");
+        assert_eq!(rows[4], "1,\"/data/a, b/2.txt\",7,5,false,");
+    }
+
+    #[test]
+    fn test_export_sqlite() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("results.sqlite");
+        std::fs::write(&db_path, b"old content").unwrap();
+
+        save_results_as_sqlite(&example_lines(), &db_path, &[PathBuf::from("/data")]).unwrap();
+
+        let connection = Connection::open(&db_path).unwrap();
+        let tool: String = connection.query_row("SELECT tool FROM scans", [], |row| row.get(0)).unwrap();
+        assert_eq!(tool, "duplicate");
+        let groups: i64 = connection.query_row("SELECT COUNT(*) FROM groups", [], |row| row.get(0)).unwrap();
+        assert_eq!(groups, 2);
+        let size_in_folder: i64 = connection.query_row("SELECT SUM(size) FROM files WHERE folder = '/data/a'", [], |row| row.get(0)).unwrap();
+        assert_eq!(size_in_folder, 17);
+        let files_in_first_group: i64 = connection.query_row("SELECT files_number FROM groups WHERE group_index = 0", [], |row| row.get(0)).unwrap();
+        assert_eq!(files_in_first_group, 2);
+    }
+}
@@ -0,0 +1,357 @@
+use std::fmt::Debug;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+#[cfg(target_family = "unix")]
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsEntryType {
+    File,
+    Dir,
+    Symlink,
+    Other,
+}
+
+impl From<fs::FileType> for FsEntryType {
+    fn from(file_type: fs::FileType) -> Self {
+        if file_type.is_dir() {
+            Self::Dir
+        } else if file_type.is_symlink() {
+            Self::Symlink
+        } else if file_type.is_file() {
+            Self::File
+        } else {
+            Self::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FsDirEntry {
+    pub path: PathBuf,
+    pub entry_type: FsEntryType,
+}
+
+impl FsDirEntry {
+    pub fn file_name(&self) -> &std::ffi::OsStr {
+        self.path.file_name().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FsMetadata {
+    pub entry_type: FsEntryType,
+    pub len: u64,
+    // Error is kept as text, because not every filesystem can provide modification date
+    pub modified: Result<SystemTime, String>,
+    // Used to find hard links, None when filesystem doesn't expose inodes
+    pub inode: Option<u64>,
+}
+
+impl FsMetadata {
+    pub fn is_dir(&self) -> bool {
+        self.entry_type == FsEntryType::Dir
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.entry_type == FsEntryType::File
+    }
+}
+
+impl From<fs::Metadata> for FsMetadata {
+    fn from(metadata: fs::Metadata) -> Self {
+        Self {
+            entry_type: metadata.file_type().into(),
+            len: metadata.len(),
+            modified: metadata.modified().map_err(|e| e.to_string()),
+            #[cfg(target_family = "unix")]
+            inode: Some(metadata.ino()),
+            #[cfg(not(target_family = "unix"))]
+            inode: None,
+        }
+    }
+}
+
+pub trait FsFile: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> FsFile for T {}
+
+// Operations needed to scan and modify files, so tools may work not only on local disks but also e.g. on remote servers
+// Paths are always absolute paths inside given filesystem
+pub trait FileSystem: Debug + Send + Sync {
+    // Local filesystem supports also operations not available through this trait, like trash, reflinks or quarantine
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    // Entries with errors are returned separately, so one broken entry doesn't hide the rest of folder
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<FsDirEntry>>>;
+
+    // Follows symlinks
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata>;
+
+    // Returns info about symlink itself, not its target
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata>;
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn FsFile>>;
+
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()>;
+
+    // Reads at most buffer length bytes starting at offset, fewer bytes are read only at the end of file
+    fn read_range(&self, path: &Path, offset: u64, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut file = self.open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut filled = 0;
+        while let Some(remaining) = buffer.get_mut(filled..)
+            && !remaining.is_empty()
+        {
+            match file.read(remaining) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(filled)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalFileSystem;
+
+impl FileSystem for LocalFileSystem {
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<FsDirEntry>>> {
+        Ok(fs::read_dir(path)?
+            .map(|entry| {
+                let entry = entry?;
+                Ok(FsDirEntry {
+                    entry_type: entry.file_type()?.into(),
+                    path: entry.path(),
+                })
+            })
+            .collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        fs::metadata(path).map(Into::into)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        fs::symlink_metadata(path).map(Into::into)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn FsFile>> {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        fs::hard_link(original, link)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::model::{CheckingMethod, HashType};
+    use crate::common::tool_data::{CommonData, DeleteMethod};
+    use crate::common::traits::Search;
+    use crate::tools::big_file::{BigFile, BigFileParameters, SearchMode};
+    use crate::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinder, DuplicateFinderParameters};
+    use crate::tools::empty_files::EmptyFiles;
+    use crate::tools::temporary::Temporary;
+
+    const REMOTE_ROOT: &str = "/czkawka_remote_stand_in";
+
+    // Acts like remote filesystem - its paths don't exist locally, so tools work only when they use this trait
+    #[derive(Debug)]
+    struct RemoteStandIn {
+        root: PathBuf,
+    }
+
+    impl RemoteStandIn {
+        fn to_local(&self, path: &Path) -> io::Result<PathBuf> {
+            let relative = path
+                .strip_prefix(REMOTE_ROOT)
+                .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "path outside remote root"))?;
+            Ok(self.root.join(relative))
+        }
+    }
+
+    impl FileSystem for RemoteStandIn {
+        fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<FsDirEntry>>> {
+            Ok(LocalFileSystem
+                .read_dir(&self.to_local(path)?)?
+                .into_iter()
+                .map(|entry| {
+                    entry.map(|entry| FsDirEntry {
+                        path: path.join(entry.file_name()),
+                        ..entry
+                    })
+                })
+                .collect())
+        }
+
+        fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+            LocalFileSystem.metadata(&self.to_local(path)?)
+        }
+
+        fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+            LocalFileSystem.symlink_metadata(&self.to_local(path)?)
+        }
+
+        fn open(&self, path: &Path) -> io::Result<Box<dyn FsFile>> {
+            LocalFileSystem.open(&self.to_local(path)?)
+        }
+
+        fn remove_file(&self, path: &Path) -> io::Result<()> {
+            LocalFileSystem.remove_file(&self.to_local(path)?)
+        }
+
+        fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+            LocalFileSystem.rename(&self.to_local(from)?, &self.to_local(to)?)
+        }
+
+        fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+            LocalFileSystem.hard_link(&self.to_local(original)?, &self.to_local(link)?)
+        }
+    }
+
+    fn set_remote_paths<T: CommonData>(tool: &mut T, root: &Path) {
+        tool.set_file_system(Arc::new(RemoteStandIn { root: root.to_path_buf() }));
+        tool.set_included_paths(vec![PathBuf::from(REMOTE_ROOT)]);
+        tool.set_use_cache(false);
+    }
+
+    #[test]
+    fn test_local_file_system() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("folder")).unwrap();
+        fs::write(root.join("file.txt"), b"0123456789").unwrap();
+
+        let file_system = LocalFileSystem;
+        let mut entries: Vec<_> = file_system.read_dir(root).unwrap().into_iter().map(Result::unwrap).collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].file_name(), "file.txt");
+        assert_eq!(entries[0].entry_type, FsEntryType::File);
+        assert_eq!(entries[1].entry_type, FsEntryType::Dir);
+
+        let metadata = file_system.metadata(&root.join("file.txt")).unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.len, 10);
+        metadata.modified.unwrap();
+
+        let mut buffer = [0; 4];
+        assert_eq!(file_system.read_range(&root.join("file.txt"), 8, &mut buffer).unwrap(), 2);
+        assert_eq!(&buffer[..2], b"89");
+        assert_eq!(file_system.read_range(&root.join("file.txt"), 2, &mut buffer).unwrap(), 4);
+        assert_eq!(&buffer, b"2345");
+
+        file_system.hard_link(&root.join("file.txt"), &root.join("link.txt")).unwrap();
+        file_system.rename(&root.join("link.txt"), &root.join("renamed.txt")).unwrap();
+        file_system.remove_file(&root.join("file.txt")).unwrap();
+        assert_eq!(fs::read(root.join("renamed.txt")).unwrap(), b"0123456789");
+        file_system.metadata(&root.join("file.txt")).unwrap_err();
+    }
+
+    #[test]
+    fn test_cache_is_not_used_on_non_local_file_system() {
+        let mut empty_files = EmptyFiles::new();
+        empty_files.set_use_cache(true);
+        assert!(empty_files.get_use_cache());
+
+        empty_files.set_file_system(Arc::new(RemoteStandIn { root: PathBuf::from("/") }));
+        assert!(!empty_files.get_use_cache());
+    }
+
+    #[test]
+    fn test_tools_on_non_local_file_system() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let remote = Path::new(REMOTE_ROOT);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("old.txt"), b"same content").unwrap();
+        fs::write(root.join("sub/new.txt"), b"same content").unwrap();
+        fs::write(root.join("big.bin"), vec![0; 1000]).unwrap();
+        fs::write(root.join("empty.txt"), b"").unwrap();
+        fs::write(root.join("sub/file.tmp"), b"temporary").unwrap();
+        let old_time = SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options().write(true).open(root.join("old.txt")).unwrap().set_modified(old_time).unwrap();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+
+        let mut big_files = BigFile::new(BigFileParameters::new(1, SearchMode::BiggestFiles));
+        set_remote_paths(&mut big_files, root);
+        big_files.search(&stop_flag, None);
+        assert_eq!(big_files.get_big_files().iter().map(|e| e.path.clone()).collect::<Vec<_>>(), [remote.join("big.bin")]);
+
+        let mut empty_files = EmptyFiles::new();
+        set_remote_paths(&mut empty_files, root);
+        empty_files.search(&stop_flag, None);
+        assert_eq!(empty_files.get_empty_files().iter().map(|e| e.path.clone()).collect::<Vec<_>>(), [remote.join("empty.txt")]);
+
+        let mut temporary = Temporary::new();
+        set_remote_paths(&mut temporary, root);
+        temporary.search(&stop_flag, None);
+        assert_eq!(
+            temporary.get_temporary_files().iter().map(|e| e.path.clone()).collect::<Vec<_>>(),
+            [remote.join("sub/file.tmp")]
+        );
+
+        let params = DuplicateFinderParameters::new(
+            CheckingMethod::Hash,
+            HashType::Blake3,
+            false,
+            0,
+            0,
+            true,
+            false,
+            false,
+            None,
+            false,
+            DEFAULT_PARTIAL_HASH_SEGMENTS,
+            false,
+        );
+        let mut duplicates = DuplicateFinder::new(params.clone());
+        set_remote_paths(&mut duplicates, root);
+        duplicates.set_move_to_trash(true);
+        duplicates.set_delete_method(DeleteMethod::AllExceptOldest);
+        duplicates.search(&stop_flag, None);
+        assert_eq!(duplicates.get_information().number_of_duplicated_files_by_hash, 1);
+        // Trash is available only for local files, so nothing is removed
+        assert_eq!(duplicates.get_text_messages().errors.len(), 1, "{:?}", duplicates.get_text_messages().errors);
+        assert!(root.join("sub/new.txt").exists());
+
+        let mut duplicates = DuplicateFinder::new(params);
+        set_remote_paths(&mut duplicates, root);
+        duplicates.set_delete_method(DeleteMethod::AllExceptOldest);
+        duplicates.search(&stop_flag, None);
+        assert!(duplicates.get_text_messages().errors.is_empty(), "{:?}", duplicates.get_text_messages().errors);
+        assert!(root.join("old.txt").exists());
+        assert!(!root.join("sub/new.txt").exists());
+    }
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use image::{DynamicImage, ImageReader};
 use log::{error, trace};
-use nom_exif::{ExifIter, ExifTag, MediaParser, MediaSource};
+use nom_exif::{EntryValue, ExifIter, ExifTag, MediaParser, MediaSource};
 
 use crate::common::consts::{HEIC_EXTENSIONS, IMAGE_RS_EXTENSIONS, RAW_IMAGE_EXTENSIONS};
 use crate::common::create_crash_message;
@@ -211,6 +211,58 @@ pub(crate) fn get_rotation_from_exif(path: &str) -> Result<Option<ExifOrientatio
     })
 }
 
+// Files without EXIF or with broken one, are treated as having no tags
+pub(crate) fn count_exif_tags(path: &Path) -> usize {
+    let res = panic::catch_unwind(|| {
+        let mut parser = MediaParser::new();
+        let ms = MediaSource::file_path(path).ok()?;
+        if !ms.has_exif() {
+            return None;
+        }
+        let exif_iter: ExifIter = parser.parse(ms).ok()?;
+        Some(exif_iter.count())
+    });
+
+    res.unwrap_or_else(|_| {
+        let message = create_crash_message("nom-exif", &path.to_string_lossy(), "https://github.com/mindeng/nom-exif");
+        error!("{message}");
+        None
+    })
+    .unwrap_or_default()
+}
+
+// Time when photo was taken, in seconds since epoch - time without offset is usually local time of camera, so it is read as if it was UTC and may be compared only with other EXIF times
+pub(crate) fn get_capture_time_from_exif(path: &Path) -> Option<i64> {
+    let res = panic::catch_unwind(|| {
+        let mut parser = MediaParser::new();
+        let ms = MediaSource::file_path(path).ok()?;
+        if !ms.has_exif() {
+            return None;
+        }
+        let exif_iter: ExifIter = parser.parse(ms).ok()?;
+        let mut create_date = None;
+        for exif_entry in exif_iter {
+            let timestamp = match exif_entry.get_value() {
+                Some(EntryValue::Time(time)) => time.timestamp(),
+                Some(EntryValue::NaiveDateTime(time)) => time.and_utc().timestamp(),
+                _ => continue,
+            };
+            match exif_entry.tag() {
+                Some(ExifTag::DateTimeOriginal) => return Some(timestamp),
+                Some(ExifTag::CreateDate) => create_date = Some(timestamp),
+                _ => {}
+            }
+        }
+        create_date
+    });
+
+    res.unwrap_or_else(|_| {
+        let message = create_crash_message("nom-exif", &path.to_string_lossy(), "https://github.com/mindeng/nom-exif");
+        error!("{message}");
+        None
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,196 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crossbeam_channel::Sender;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::common::dir_traversal::get_modified_time;
+use crate::common::file_system::{FileSystem, LocalFileSystem};
+use crate::common::model::WorkContinueStatus;
+use crate::common::progress_data::ProgressData;
+use crate::common::traits::{AllTraits, ResultEntry};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub loaded_entries: usize,
+    // Entries removed or modified since results were saved
+    pub changed_entries: usize,
+}
+
+// Groups are saved differently when reference folders were used, so both formats need to be accepted
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum SavedResults<N, R> {
+    Referenced(R),
+    Normal(N),
+}
+
+pub(crate) fn load_json_results<T: DeserializeOwned>(file_name: &Path) -> Result<T, String> {
+    let file = File::open(file_name).map_err(|e| format!("Cannot open results file \"{}\" - {e}", file_name.to_string_lossy()))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|e| {
+        format!(
+            "Cannot load results from \"{}\" - {e} (file needs to be saved by the same tool with the same search method)",
+            file_name.to_string_lossy()
+        )
+    })
+}
+
+// Files are compared by size and modification date, folders only by modification date, because their size is not saved
+pub fn is_entry_unchanged<T: ResultEntry>(entry: &T) -> bool {
+    // Files inside archives are never modified by app, so there is no reason to check them
+    if entry.is_read_only() {
+        return true;
+    }
+    let Ok(metadata) = LocalFileSystem.symlink_metadata(entry.get_path()) else {
+        return false;
+    };
+    let modified_date = get_modified_time(&metadata, &mut Vec::new(), entry.get_path(), metadata.is_dir());
+    if metadata.is_file() && metadata.len != entry.get_size() {
+        return false;
+    }
+    modified_date == entry.get_modified_date()
+}
+
+impl ImportSummary {
+    pub(crate) fn filter_entries<T: ResultEntry>(&mut self, entries: Vec<T>) -> Vec<T> {
+        let entries: Vec<T> = entries
+            .into_iter()
+            .filter(|entry| {
+                let unchanged = is_entry_unchanged(entry);
+                if !unchanged {
+                    self.changed_entries += 1;
+                }
+                unchanged
+            })
+            .collect();
+        self.loaded_entries += entries.len();
+        entries
+    }
+
+    // Group is dropped when less than 2 entries are left, because there is nothing to compare
+    pub(crate) fn filter_group<T: ResultEntry>(&mut self, group: Vec<T>) -> Option<Vec<T>> {
+        let group = self.filter_entries(group);
+        if group.len() < 2 {
+            self.loaded_entries -= group.len();
+            return None;
+        }
+        Some(group)
+    }
+
+    pub(crate) fn filter_referenced_group<T: ResultEntry>(&mut self, (reference, group): (T, Vec<T>)) -> Option<(T, Vec<T>)> {
+        if !is_entry_unchanged(&reference) {
+            self.changed_entries += 1;
+            return None;
+        }
+        let group = self.filter_entries(group);
+        if group.is_empty() {
+            return None;
+        }
+        Some((reference, group))
+    }
+
+    pub(crate) fn filter_groups<T: ResultEntry>(&mut self, groups: Vec<Vec<T>>) -> Vec<Vec<T>> {
+        groups.into_iter().filter_map(|group| self.filter_group(group)).collect()
+    }
+
+    pub(crate) fn filter_referenced_groups<T: ResultEntry>(&mut self, groups: Vec<(T, Vec<T>)>) -> Vec<(T, Vec<T>)> {
+        groups.into_iter().filter_map(|group| self.filter_referenced_group(group)).collect()
+    }
+}
+
+// Loads results instead of searching for them and then deletes items like search does, errors and summary are saved in tool messages
+pub fn import_results_instead_of_search<T: AllTraits>(tool: &mut T, file_name: &Path, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
+    match tool.import_results(file_name) {
+        Ok(summary) => {
+            tool.get_text_messages_mut().messages.push(format!(
+                "Loaded {} entries from \"{}\", {} entries were skipped because they were changed or removed",
+                summary.loaded_entries,
+                file_name.to_string_lossy(),
+                summary.changed_entries
+            ));
+            if tool.delete_files(stop_flag, progress_sender) == WorkContinueStatus::Stop {
+                tool.get_cd_mut().stopped_search = true;
+            }
+        }
+        Err(e) => {
+            tool.get_text_messages_mut().critical = Some(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn file_entry(path: &Path) -> FileEntry {
+        let metadata = LocalFileSystem.metadata(path).unwrap();
+        FileEntry {
+            path: path.to_path_buf(),
+            size: metadata.len,
+            modified_date: get_modified_time(&metadata, &mut Vec::new(), path, false),
+        }
+    }
+
+    #[test]
+    fn test_filter_changed_entries() {
+        let temp_dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..4).map(|idx| temp_dir.path().join(format!("{idx}.txt"))).collect();
+        for path in &paths {
+            fs::write(path, b"content").unwrap();
+        }
+        let entries: Vec<FileEntry> = paths.iter().map(|path| file_entry(path)).collect();
+        fs::write(&paths[1], b"changed content").unwrap();
+        fs::remove_file(&paths[2]).unwrap();
+
+        let mut summary = ImportSummary::default();
+        let groups = summary.filter_groups(vec![
+            vec![entries[0].clone(), entries[3].clone()],
+            vec![entries[1].clone(), entries[2].clone(), entries[3].clone()],
+        ]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            summary,
+            ImportSummary {
+                loaded_entries: 2,
+                changed_entries: 2
+            }
+        );
+
+        let mut summary = ImportSummary::default();
+        let referenced = summary.filter_referenced_groups(vec![
+            (entries[0].clone(), vec![entries[1].clone(), entries[3].clone()]),
+            (entries[2].clone(), vec![entries[3].clone()]),
+        ]);
+        assert_eq!(referenced.len(), 1);
+        assert_eq!(referenced[0].1.len(), 1);
+        assert_eq!(
+            summary,
+            ImportSummary {
+                loaded_entries: 1,
+                changed_entries: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_saved_results_formats() {
+        let normal: SavedResults<Vec<Vec<FileEntry>>, Vec<(FileEntry, Vec<FileEntry>)>> =
+            serde_json::from_str(r#"[[{"path":"/a","size":1,"modified_date":2},{"path":"/b","size":1,"modified_date":2}]]"#).unwrap();
+        assert!(matches!(normal, SavedResults::Normal(groups) if groups[0].len() == 2));
+
+        let referenced: SavedResults<Vec<Vec<FileEntry>>, Vec<(FileEntry, Vec<FileEntry>)>> =
+            serde_json::from_str(r#"[[{"path":"/a","size":1,"modified_date":2},[{"path":"/b","size":1,"modified_date":2}]]]"#).unwrap();
+        assert!(matches!(referenced, SavedResults::Referenced(groups) if groups[0].1.len() == 1));
+    }
+}
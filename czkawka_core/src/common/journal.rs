@@ -0,0 +1,597 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::common::config_cache_path::get_config_cache_path;
+use crate::common::quarantine::restore_quarantined_item;
+
+// Every destructive operation done on files is appended to journal, so it can be later reverted(if possible)
+// Each run of operations is saved to separate session file in "journal" subfolder of cache folder
+// First line of file contains session info, every next line is single JSON encoded entry
+const JOURNAL_FOLDER_NAME: &str = "journal";
+const JOURNAL_FILE_EXTENSION: &str = "jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalAction {
+    Delete,
+    MoveToTrash,
+    Quarantine,
+    Move,
+    Rename,
+    HardLink,
+    Reflink,
+    Symlink,
+    CleanExif,
+}
+
+impl JournalAction {
+    pub fn is_revertible(self) -> bool {
+        !matches!(self, Self::Delete | Self::CleanExif)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub action: JournalAction,
+    pub original_path: PathBuf,
+    // Destination of move/rename/quarantine or source of link
+    pub target: Option<PathBuf>,
+    // State of file after operation, checked before reverting it
+    // Empty for folders, unreadable files and operations which cannot be reverted
+    pub file_state: Option<JournalFileState>,
+    pub time: u64,
+}
+
+// Size and modification date allow to notice most changes without reading file, hash confirms that content is still same
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalFileState {
+    pub size: u64,
+    pub modified_date: u64,
+    // Blake3 hash of whole content
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct JournalSessionInfo {
+    source: String,
+    started: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalSession {
+    pub id: String,
+    pub source: String,
+    pub started: u64,
+    pub entries: Vec<JournalEntry>,
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JournalRevertResult {
+    pub reverted: usize,
+    pub errors: Vec<String>,
+}
+
+pub(crate) fn current_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+pub fn get_journal_folder() -> Option<PathBuf> {
+    Some(get_config_cache_path()?.cache_folder.join(JOURNAL_FOLDER_NAME))
+}
+
+// Size and modification date of file, folders are not checked
+fn read_file_metadata(path: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    match metadata.modified().map(|modified| modified.duration_since(UNIX_EPOCH)) {
+        Ok(Ok(duration)) => Some((metadata.len(), duration.as_secs())),
+        _ => {
+            warn!("Cannot read modification date of \"{}\"", path.to_string_lossy());
+            None
+        }
+    }
+}
+
+pub fn calculate_content_hash(path: &Path) -> Option<String> {
+    let mut hasher = blake3::Hasher::new();
+    match File::open(path).and_then(|file| hasher.update_reader(file).map(|_| ())) {
+        Ok(()) => Some(hasher.finalize().to_hex().to_string()),
+        Err(e) => {
+            warn!("Cannot calculate journal hash of \"{}\" - {e}", path.to_string_lossy());
+            None
+        }
+    }
+}
+
+// Used to verify, that reverted file was not changed after operation
+// Blake3 hash already calculated by tool may be passed, to not read whole file again
+pub fn read_file_state(path: &Path, known_hash: Option<&str>) -> Option<JournalFileState> {
+    let (size, modified_date) = read_file_metadata(path)?;
+    let hash = match known_hash {
+        Some(hash) => hash.to_string(),
+        None => calculate_content_hash(path)?,
+    };
+    Some(JournalFileState { size, modified_date, hash })
+}
+
+// Writer of single session, file is created only when first entry is recorded, so sessions without any operation are not saved
+// Default journal doesn't save anything
+#[derive(Debug, Default)]
+pub struct Journal {
+    folder: Option<PathBuf>,
+    session_info: JournalSessionInfo,
+    session_id: String,
+    file: Mutex<Option<File>>,
+}
+
+impl Journal {
+    // When config/cache path is not available, journal silently ignores all operations
+    pub fn new(source: &str) -> Self {
+        Self::new_in_folder(get_journal_folder(), source)
+    }
+
+    pub(crate) fn new_in_folder(folder: Option<PathBuf>, source: &str) -> Self {
+        let started = current_time();
+        Self {
+            folder,
+            session_id: format!("{started}_{:08x}", rand::random::<u32>()),
+            session_info: JournalSessionInfo {
+                source: source.to_string(),
+                started,
+            },
+            file: Mutex::new(None),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.folder.is_some()
+    }
+
+    // Runs operation and, if it succeeded, records it in journal
+    pub fn record_operation<T, E>(&self, action: JournalAction, original_path: &Path, target: Option<&Path>, operation: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        self.record_operation_with_hash(action, original_path, target, None, operation)
+    }
+
+    // Known hash is Blake3 hash of content of file, which is same before and after operation
+    // State of file is saved from place where it is available after operation, because only there it can be checked when reverting
+    pub fn record_operation_with_hash<T, E>(
+        &self,
+        action: JournalAction,
+        original_path: &Path,
+        target: Option<&Path>,
+        known_hash: Option<&str>,
+        operation: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        if !self.is_enabled() {
+            return operation();
+        }
+        // Trashed file is not available until it is restored
+        let state_before_operation = if action == JournalAction::MoveToTrash {
+            read_file_state(original_path, known_hash)
+        } else {
+            None
+        };
+        let result = operation()?;
+
+        let file_state = match action {
+            JournalAction::Delete | JournalAction::CleanExif => None,
+            JournalAction::MoveToTrash => state_before_operation,
+            JournalAction::Move | JournalAction::Rename | JournalAction::Quarantine => target.and_then(|target| read_file_state(target, known_hash)),
+            JournalAction::HardLink | JournalAction::Reflink | JournalAction::Symlink => read_file_state(original_path, known_hash),
+        };
+        let entry = JournalEntry {
+            action,
+            original_path: original_path.to_path_buf(),
+            target: target.map(Path::to_path_buf),
+            file_state,
+            time: current_time(),
+        };
+        if let Err(e) = self.append_entry(&entry) {
+            error!("Cannot save entry to journal - {e}");
+        }
+        Ok(result)
+    }
+
+    fn append_entry(&self, entry: &JournalEntry) -> Result<(), String> {
+        let Some(folder) = &self.folder else {
+            return Ok(());
+        };
+        let mut file_guard = self.file.lock().map_err(|e| e.to_string())?;
+        if file_guard.is_none() {
+            fs::create_dir_all(folder).map_err(|e| format!("Cannot create journal folder \"{}\" - {e}", folder.to_string_lossy()))?;
+            let file_path = folder.join(format!("{}.{JOURNAL_FILE_EXTENSION}", self.session_id));
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&file_path)
+                .map_err(|e| format!("Cannot open journal file \"{}\" - {e}", file_path.to_string_lossy()))?;
+            write_json_line(&mut file, &self.session_info)?;
+            *file_guard = Some(file);
+        }
+        let file = file_guard.as_mut().expect("Journal file was just opened");
+        write_json_line(file, entry)
+    }
+}
+
+fn write_json_line<T: Serialize>(file: &mut File, item: &T) -> Result<(), String> {
+    let mut line = serde_json::to_string(item).map_err(|e| e.to_string())?;
+    line.push('\n');
+    file.write_all(line.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn load_session(path: &Path) -> Result<JournalSession, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot open journal file \"{}\" - {e}", path.to_string_lossy()))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let info_line = lines
+        .next()
+        .ok_or_else(|| format!("Journal file \"{}\" is empty", path.to_string_lossy()))?
+        .map_err(|e| e.to_string())?;
+    let info: JournalSessionInfo = serde_json::from_str(&info_line).map_err(|e| format!("Cannot parse journal file \"{}\" - {e}", path.to_string_lossy()))?;
+
+    let mut entries = Vec::new();
+    for line in lines {
+        let line = line.map_err(|e| e.to_string())?;
+        // Last line may be broken when app was killed during writing, so it is better to skip it than to lose whole session
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("Skipping broken journal entry in \"{}\" - {e}", path.to_string_lossy()),
+        }
+    }
+
+    Ok(JournalSession {
+        id: path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+        source: info.source,
+        started: info.started,
+        entries,
+        path: path.to_path_buf(),
+    })
+}
+
+// Returns sessions sorted from the newest one
+pub fn list_journal_sessions() -> Result<Vec<JournalSession>, String> {
+    let Some(folder) = get_journal_folder() else {
+        return Err("Cannot find journal folder, because config/cache folder is not available".to_string());
+    };
+    list_journal_sessions_in_folder(&folder)
+}
+
+pub(crate) fn list_journal_sessions_in_folder(folder: &Path) -> Result<Vec<JournalSession>, String> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+    let read_dir = fs::read_dir(folder).map_err(|e| format!("Cannot read journal folder \"{}\" - {e}", folder.to_string_lossy()))?;
+
+    let mut sessions = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != JOURNAL_FILE_EXTENSION) {
+            continue;
+        }
+        match load_session(&path) {
+            Ok(session) => sessions.push(session),
+            Err(e) => warn!("{e}"),
+        }
+    }
+    sessions.sort_by(|a, b| b.started.cmp(&a.started).then_with(|| b.id.cmp(&a.id)));
+    Ok(sessions)
+}
+
+pub fn find_journal_session(id: &str) -> Result<JournalSession, String> {
+    list_journal_sessions()?
+        .into_iter()
+        .find(|session| session.id == id)
+        .ok_or_else(|| format!("Cannot find journal session \"{id}\""))
+}
+
+fn file_changed_error(path: &Path) -> String {
+    format!("Content of \"{}\" was changed after operation", path.to_string_lossy())
+}
+
+fn check_file_state(path: &Path, expected_state: Option<&JournalFileState>) -> Result<(), String> {
+    let Some(expected_state) = expected_state else {
+        return Ok(());
+    };
+    // Content is hashed only when size and modification date are still same
+    let is_unchanged =
+        read_file_metadata(path) == Some((expected_state.size, expected_state.modified_date)) && calculate_content_hash(path).is_some_and(|hash| hash == expected_state.hash);
+    if !is_unchanged {
+        return Err(file_changed_error(path));
+    }
+    Ok(())
+}
+
+fn move_back(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::symlink_metadata(to).is_ok() {
+        return Err(format!("Cannot restore \"{}\", because it already exists", to.to_string_lossy()));
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // Rename fails when moving between partitions
+    fs::copy(from, to).map_err(|e| format!("Cannot copy \"{}\" to \"{}\" - {e}", from.to_string_lossy(), to.to_string_lossy()))?;
+    fs::remove_file(from).map_err(|e| format!("Cannot remove \"{}\" - {e}", from.to_string_lossy()))
+}
+
+// Replaces link with independent copy of its content, so file no longer shares data with other file
+fn replace_link_with_copy(path: &Path, expected_state: Option<&JournalFileState>) -> Result<(), String> {
+    check_file_state(path, expected_state)?;
+    let parent = path.parent().ok_or_else(|| format!("Cannot find parent folder of \"{}\"", path.to_string_lossy()))?;
+    let temp_path = parent.join(format!("{}.czkawka_tmp", rand::random::<u128>()));
+
+    fs::copy(path, &temp_path).map_err(|e| format!("Cannot copy content of \"{}\" - {e}", path.to_string_lossy()))?;
+    let result = fs::rename(&temp_path, path).map_err(|e| format!("Cannot replace \"{}\" - {e}", path.to_string_lossy()));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))))]
+fn restore_from_trash(path: &Path, expected_state: Option<&JournalFileState>) -> Result<(), String> {
+    let trash_item = trash::os_limited::list()
+        .map_err(|e| format!("Cannot list trash items - {e}"))?
+        .into_iter()
+        .filter(|item| item.original_path() == path)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| format!("Cannot find \"{}\" in trash", path.to_string_lossy()))?;
+    // Other file with same path may have been trashed later, so it is checked before restoring it in place of original file
+    if let Some(expected_state) = expected_state {
+        let metadata = trash::os_limited::metadata(&trash_item).map_err(|e| format!("Cannot read trashed \"{}\" - {e}", path.to_string_lossy()))?;
+        if metadata.size != trash::TrashItemSize::Bytes(expected_state.size) {
+            return Err(file_changed_error(path));
+        }
+    }
+    trash::os_limited::restore_all([trash_item]).map_err(|e| format!("Cannot restore \"{}\" from trash - {e}", path.to_string_lossy()))
+}
+
+#[cfg(not(any(target_os = "windows", all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android")))))]
+fn restore_from_trash(path: &Path, _expected_state: Option<&JournalFileState>) -> Result<(), String> {
+    Err(format!("Restoring \"{}\" from trash is not supported on this platform", path.to_string_lossy()))
+}
+
+pub fn revert_journal_entry(entry: &JournalEntry) -> Result<(), String> {
+    let original_path = &entry.original_path;
+    match entry.action {
+        JournalAction::Delete => Err(format!("\"{}\" was permanently deleted and cannot be restored", original_path.to_string_lossy())),
+        JournalAction::CleanExif => Err(format!("Removed EXIF tags from \"{}\" cannot be restored", original_path.to_string_lossy())),
+        JournalAction::MoveToTrash => {
+            restore_from_trash(original_path, entry.file_state.as_ref())?;
+            check_file_state(original_path, entry.file_state.as_ref())
+        }
+        JournalAction::Move | JournalAction::Rename | JournalAction::Quarantine => {
+            let target = entry
+                .target
+                .as_ref()
+                .ok_or_else(|| format!("Journal entry of \"{}\" doesn't contain target path", original_path.to_string_lossy()))?;
+            check_file_state(target, entry.file_state.as_ref())?;
+            if entry.action == JournalAction::Quarantine {
+                // Restored file must be also removed from quarantine manifest, to not be restored again from there
+                restore_quarantined_item(target)
+            } else {
+                move_back(target, original_path)
+            }
+        }
+        JournalAction::HardLink | JournalAction::Reflink | JournalAction::Symlink => replace_link_with_copy(original_path, entry.file_state.as_ref()),
+    }
+}
+
+// Entries are reverted from the last one, because later operations may depend on earlier ones
+// Successfully reverted entries are removed from session, so it is possible to retry reverting rest of them
+pub fn revert_journal_session(session: &JournalSession) -> JournalRevertResult {
+    let mut result = JournalRevertResult::default();
+    let mut remaining_entries = Vec::new();
+
+    for entry in session.entries.iter().rev() {
+        match revert_journal_entry(entry) {
+            Ok(()) => result.reverted += 1,
+            Err(e) => {
+                result.errors.push(e);
+                // Not revertible entries would only clutter session list
+                if entry.action.is_revertible() {
+                    remaining_entries.push(entry.clone());
+                }
+            }
+        }
+    }
+    remaining_entries.reverse();
+
+    if let Err(e) = save_remaining_entries(session, &remaining_entries) {
+        result.errors.push(e);
+    }
+    result
+}
+
+fn save_remaining_entries(session: &JournalSession, remaining_entries: &[JournalEntry]) -> Result<(), String> {
+    if remaining_entries.is_empty() {
+        return fs::remove_file(&session.path).map_err(|e| format!("Cannot remove journal file \"{}\" - {e}", session.path.to_string_lossy()));
+    }
+    let mut file = File::create(&session.path).map_err(|e| format!("Cannot save journal file \"{}\" - {e}", session.path.to_string_lossy()))?;
+    write_json_line(
+        &mut file,
+        &JournalSessionInfo {
+            source: session.source.clone(),
+            started: session.started,
+        },
+    )?;
+    for entry in remaining_entries {
+        write_json_line(&mut file, entry)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::make_hard_link;
+    use crate::common::quarantine::{Quarantine, list_quarantine_sessions};
+
+    #[test]
+    fn test_journal_is_not_created_without_operations() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+
+        let res: Result<(), String> = journal.record_operation(JournalAction::Delete, &dir.path().join("missing"), None, || Err("failed".to_string()));
+        res.unwrap_err();
+
+        assert!(!journal_folder.exists());
+        assert!(list_journal_sessions_in_folder(&journal_folder).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revert_move_and_rename() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let original = dir.path().join("original.txt");
+        let moved = dir.path().join("moved.txt");
+        fs::write(&original, "content").unwrap();
+
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal
+            .record_operation(JournalAction::Move, &original, Some(&moved), || fs::rename(&original, &moved))
+            .unwrap();
+
+        let sessions = list_journal_sessions_in_folder(&journal_folder).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].source, "Test");
+        assert_eq!(sessions[0].entries.len(), 1);
+        assert_eq!(sessions[0].entries[0].target.as_deref(), Some(moved.as_path()));
+        let file_state = sessions[0].entries[0].file_state.clone().unwrap();
+        assert_eq!(file_state.size, 7);
+        assert_eq!(file_state.hash, blake3::hash(b"content").to_hex().to_string());
+
+        let result = revert_journal_session(&sessions[0]);
+        assert_eq!(result.reverted, 1);
+        assert!(result.errors.is_empty());
+        assert_eq!(fs::read_to_string(&original).unwrap(), "content");
+        assert!(!moved.exists());
+        assert!(list_journal_sessions_in_folder(&journal_folder).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revert_is_stopped_when_content_changed() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let original = dir.path().join("original.txt");
+        let renamed = dir.path().join("renamed.txt");
+        let deleted = dir.path().join("deleted.txt");
+        fs::write(&original, "content").unwrap();
+        fs::write(&deleted, "deleted").unwrap();
+
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal
+            .record_operation(JournalAction::Rename, &original, Some(&renamed), || fs::rename(&original, &renamed))
+            .unwrap();
+        journal.record_operation(JournalAction::Delete, &deleted, None, || fs::remove_file(&deleted)).unwrap();
+        fs::write(&renamed, "changed content").unwrap();
+
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        // Deleted file cannot be restored, so its state is not needed
+        assert_eq!(session.entries[1].file_state, None);
+        let result = revert_journal_session(&session);
+        assert_eq!(result.reverted, 0);
+        assert_eq!(result.errors.len(), 2);
+        assert!(!original.exists());
+
+        // Only entry which may be reverted in future is kept
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        assert_eq!(session.entries.len(), 1);
+        assert_eq!(session.entries[0].action, JournalAction::Rename);
+    }
+
+    #[test]
+    fn test_revert_is_stopped_when_content_changed_without_changing_size_and_date() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let original = dir.path().join("original.txt");
+        let moved = dir.path().join("moved.txt");
+        fs::write(&original, "content").unwrap();
+
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal
+            .record_operation(JournalAction::Move, &original, Some(&moved), || fs::rename(&original, &moved))
+            .unwrap();
+        let modified = fs::metadata(&moved).unwrap().modified().unwrap();
+        fs::write(&moved, "CONTENT").unwrap();
+        File::options().write(true).open(&moved).unwrap().set_modified(modified).unwrap();
+
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        let result = revert_journal_session(&session);
+        assert_eq!(result.reverted, 0);
+        assert_eq!(result.errors.len(), 1);
+        assert!(!original.exists());
+    }
+
+    #[test]
+    fn test_known_hash_is_saved_without_reading_file() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let src = dir.path().join("src.txt");
+        let dst = dir.path().join("dst.txt");
+        fs::write(&src, "content").unwrap();
+        fs::write(&dst, "content").unwrap();
+
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal
+            .record_operation_with_hash(JournalAction::HardLink, &dst, Some(&src), Some("known_hash"), || make_hard_link(&src, &dst))
+            .unwrap();
+
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        assert_eq!(session.entries[0].file_state.as_ref().map(|state| state.hash.as_str()), Some("known_hash"));
+    }
+
+    #[test]
+    fn test_revert_quarantine_updates_manifest() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let quarantine_folder = dir.path().join("quarantine");
+        let file = dir.path().join("file.txt");
+        fs::write(&file, "content").unwrap();
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        let quarantine_path = quarantine.get_quarantine_path(&file);
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal
+            .record_operation(JournalAction::Quarantine, &file, Some(&quarantine_path), || quarantine.move_to_quarantine(&file))
+            .unwrap();
+
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        let result = revert_journal_session(&session);
+        assert_eq!(result.reverted, 1);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "content");
+        assert!(list_quarantine_sessions(&quarantine_folder).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revert_hardlink_creates_independent_copy() {
+        let dir = tempdir().unwrap();
+        let journal_folder = dir.path().join("journal");
+        let src = dir.path().join("src.txt");
+        let dst = dir.path().join("dst.txt");
+        fs::write(&src, "content").unwrap();
+        fs::write(&dst, "content").unwrap();
+
+        let journal = Journal::new_in_folder(Some(journal_folder.clone()), "Test");
+        journal.record_operation(JournalAction::HardLink, &dst, Some(&src), || make_hard_link(&src, &dst)).unwrap();
+
+        let session = list_journal_sessions_in_folder(&journal_folder).unwrap().remove(0);
+        let result = revert_journal_session(&session);
+        assert_eq!(result.reverted, 1);
+        assert!(result.errors.is_empty());
+
+        fs::write(&src, "new content").unwrap();
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "content");
+    }
+}
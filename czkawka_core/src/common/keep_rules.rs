@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::common::image::count_exif_tags;
+use crate::common::traits::ResultEntry;
+
+// Single rule used to choose which item from group should be kept
+// Rules are written as comma separated list e.g. "in:/archive,not-contains:Copy of,shortest-path,newest"
+// Every next rule is used only to break ties of previous rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeepRule {
+    Newest,
+    Oldest,
+    Biggest,
+    Smallest,
+    ShortestPath,
+    LongestPath,
+    // Prefer items inside given folder
+    InFolder(PathBuf),
+    // Prefer items outside given folder
+    NotInFolder(PathBuf),
+    // Prefer items which path contains given text(case insensitive)
+    Contains(String),
+    NotContains(String),
+    // Prefer items with bigger number of EXIF tags - requires reading files
+    MostExif,
+    // Prefer items with highest bitrate, works only with music and videos
+    HighestBitrate,
+    // Prefer the sharpest photo, works only with similar images
+    Sharpest,
+    // Prefer image with the best combination of resolution, compression and sharpness, works only with similar images
+    HighestQuality,
+}
+
+impl KeepRule {
+    // Rules using values, which are calculated only by similar images tool
+    pub fn works_only_with_similar_images(&self) -> bool {
+        matches!(self, Self::Sharpest | Self::HighestQuality)
+    }
+
+    // Lower score means that item is more preferred to be kept
+    fn score<T: ResultEntry>(&self, item: &T) -> i128 {
+        let path = item.get_path();
+        match self {
+            Self::Newest => -i128::from(item.get_modified_date()),
+            Self::Oldest => i128::from(item.get_modified_date()),
+            Self::Biggest => -i128::from(item.get_size()),
+            Self::Smallest => i128::from(item.get_size()),
+            Self::ShortestPath => path.as_os_str().len() as i128,
+            Self::LongestPath => -(path.as_os_str().len() as i128),
+            Self::InFolder(folder) => i128::from(!path.starts_with(folder)),
+            Self::NotInFolder(folder) => i128::from(path.starts_with(folder)),
+            // Rules may be created directly, not only parsed, so text may be not lowercased yet
+            Self::Contains(text) => i128::from(!path.to_string_lossy().to_lowercase().contains(&text.to_lowercase())),
+            Self::NotContains(text) => i128::from(path.to_string_lossy().to_lowercase().contains(&text.to_lowercase())),
+            Self::MostExif => -(count_exif_tags(path) as i128),
+            Self::HighestBitrate => -i128::from(item.get_bitrate().unwrap_or_default()),
+            Self::Sharpest => -(f64::from(item.get_sharpness().unwrap_or_default()) * 1000.0) as i128,
+            Self::HighestQuality => -(item.get_quality_score().unwrap_or_default() * 1000.0) as i128,
+        }
+    }
+}
+
+impl FromStr for KeepRule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        if let Some((name, argument)) = rule.split_once(':') {
+            let argument = argument.trim();
+            if argument.is_empty() {
+                return Err(format!("Rule \"{name}\" requires non empty argument"));
+            }
+            return match name.trim().to_lowercase().as_str() {
+                "in" => Ok(Self::InFolder(PathBuf::from(argument))),
+                "not-in" => Ok(Self::NotInFolder(PathBuf::from(argument))),
+                "contains" => Ok(Self::Contains(argument.to_lowercase())),
+                "not-contains" => Ok(Self::NotContains(argument.to_lowercase())),
+                _ => Err(format!("Unknown rule \"{name}\" (allowed rules with argument: in, not-in, contains, not-contains)")),
+            };
+        }
+
+        match rule.to_lowercase().as_str() {
+            "newest" => Ok(Self::Newest),
+            "oldest" => Ok(Self::Oldest),
+            "biggest" => Ok(Self::Biggest),
+            "smallest" => Ok(Self::Smallest),
+            "shortest-path" => Ok(Self::ShortestPath),
+            "longest-path" => Ok(Self::LongestPath),
+            "most-exif" => Ok(Self::MostExif),
+            "highest-bitrate" => Ok(Self::HighestBitrate),
+            "sharpest" => Ok(Self::Sharpest),
+            "highest-quality" => Ok(Self::HighestQuality),
+            _ => Err(format!(
+                "Unknown rule \"{rule}\" (allowed: newest, oldest, biggest, smallest, shortest-path, longest-path, most-exif, highest-bitrate, sharpest, highest-quality, in:PATH, not-in:PATH, contains:TEXT, not-contains:TEXT)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeepRules {
+    rules: Vec<KeepRule>,
+}
+
+impl KeepRules {
+    pub fn new(rules: Vec<KeepRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn get_rules(&self) -> &[KeepRule] {
+        &self.rules
+    }
+
+    // Sorts items from the most to the least preferred one to keep
+    // Items equal according to all rules, keep their original order
+    pub fn rank<T: ResultEntry>(&self, items: Vec<T>) -> Vec<T> {
+        rank_by_rules(&self.rules, items)
+    }
+}
+
+pub(crate) fn rank_by_rules<T: ResultEntry>(rules: &[KeepRule], mut items: Vec<T>) -> Vec<T> {
+    if rules.is_empty() {
+        return items;
+    }
+    // Some rules like EXIF counting are expensive, so scores are computed only once per item
+    items.sort_by_cached_key(|item| rules.iter().map(|rule| rule.score(item)).collect::<Vec<_>>());
+    items
+}
+
+impl FromStr for KeepRules {
+    type Err = String;
+
+    fn from_str(rules: &str) -> Result<Self, Self::Err> {
+        let rules = rules
+            .split(',')
+            .filter(|rule| !rule.trim().is_empty())
+            .map(KeepRule::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TestEntry {
+        path: PathBuf,
+        modified_date: u64,
+        size: u64,
+        sharpness: Option<f32>,
+        quality_score: Option<f64>,
+    }
+
+    impl ResultEntry for TestEntry {
+        fn get_path(&self) -> &Path {
+            &self.path
+        }
+        fn get_modified_date(&self) -> u64 {
+            self.modified_date
+        }
+        fn get_size(&self) -> u64 {
+            self.size
+        }
+        fn get_sharpness(&self) -> Option<f32> {
+            self.sharpness
+        }
+        fn get_quality_score(&self) -> Option<f64> {
+            self.quality_score
+        }
+    }
+
+    fn entry(path: &str, modified_date: u64, size: u64) -> TestEntry {
+        TestEntry {
+            path: PathBuf::from(path),
+            modified_date,
+            size,
+            sharpness: None,
+            quality_score: None,
+        }
+    }
+
+    fn image_entry(path: &str, sharpness: Option<f32>, quality_score: Option<f64>) -> TestEntry {
+        TestEntry {
+            sharpness,
+            quality_score,
+            ..entry(path, 10, 5)
+        }
+    }
+
+    fn ranked_paths(rules: &str, items: Vec<TestEntry>) -> Vec<String> {
+        let rules = KeepRules::from_str(rules).unwrap();
+        rules.rank(items).into_iter().map(|e| e.path.to_string_lossy().to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_keep_rules() {
+        let rules = KeepRules::from_str("in:/archive, not-contains:Copy of,shortest-path,NEWEST").unwrap();
+        assert_eq!(
+            rules.get_rules(),
+            &[
+                KeepRule::InFolder(PathBuf::from("/archive")),
+                KeepRule::NotContains("copy of".to_string()),
+                KeepRule::ShortestPath,
+                KeepRule::Newest
+            ]
+        );
+        assert!(KeepRules::from_str("").unwrap().is_empty());
+        KeepRules::from_str("the-best").unwrap_err();
+        KeepRules::from_str("in:").unwrap_err();
+        KeepRules::from_str("unknown:value").unwrap_err();
+    }
+
+    #[test]
+    fn test_similar_images_only_rules() {
+        let rules = KeepRules::from_str("sharpest,highest-quality,highest-bitrate,newest").unwrap();
+        let similar_images_only: Vec<_> = rules.get_rules().iter().map(KeepRule::works_only_with_similar_images).collect();
+        assert_eq!(similar_images_only, [true, true, false, false]);
+    }
+
+    #[test]
+    fn test_rules_are_chained_as_tie_breakers() {
+        let items = vec![
+            entry("/home/Copy of photo.jpg", 30, 10),
+            entry("/archive/2020/photo.jpg", 10, 10),
+            entry("/archive/photo.jpg", 20, 10),
+            entry("/home/photo.jpg", 40, 10),
+        ];
+
+        assert_eq!(
+            ranked_paths("in:/archive,shortest-path", items.clone()),
+            ["/archive/photo.jpg", "/archive/2020/photo.jpg", "/home/photo.jpg", "/home/Copy of photo.jpg"]
+        );
+        assert_eq!(
+            ranked_paths("not-contains:copy of,newest", items.clone()),
+            ["/home/photo.jpg", "/archive/photo.jpg", "/archive/2020/photo.jpg", "/home/Copy of photo.jpg"]
+        );
+        assert_eq!(ranked_paths("not-in:/archive,oldest", items)[0], "/home/Copy of photo.jpg");
+    }
+
+    #[test]
+    fn test_contains_rules_ignore_case_of_created_rules() {
+        let items = vec![entry("/home/Copy of photo.jpg", 10, 5), entry("/home/photo.jpg", 10, 5)];
+
+        let rules = KeepRules::new(vec![KeepRule::NotContains("COPY OF".to_string())]);
+        assert_eq!(rules.rank(items.clone())[0].path, Path::new("/home/photo.jpg"));
+        let rules = KeepRules::new(vec![KeepRule::Contains("Copy Of".to_string())]);
+        assert_eq!(rules.rank(items)[0].path, Path::new("/home/Copy of photo.jpg"));
+    }
+
+    #[test]
+    fn test_equal_items_keep_original_order() {
+        let items = vec![entry("/b", 10, 5), entry("/a", 10, 5), entry("/c", 10, 50)];
+
+        assert_eq!(ranked_paths("newest", items.clone()), ["/b", "/a", "/c"]);
+        assert_eq!(ranked_paths("smallest,oldest", items.clone()), ["/b", "/a", "/c"]);
+        assert_eq!(ranked_paths("biggest", items.clone()), ["/c", "/b", "/a"]);
+        assert_eq!(ranked_paths("highest-bitrate", items), ["/b", "/a", "/c"]);
+    }
+
+    #[test]
+    fn test_image_quality_rules() {
+        let items = vec![
+            image_entry("/unknown", None, None),
+            image_entry("/blurred", Some(15.5), Some(120.25)),
+            image_entry("/sharp", Some(300.0), Some(120.5)),
+            image_entry("/best", Some(250.0), Some(900.0)),
+        ];
+
+        // Entries without metrics are treated as the worst ones
+        assert_eq!(ranked_paths("sharpest", items.clone()), ["/sharp", "/best", "/blurred", "/unknown"]);
+        assert_eq!(ranked_paths("highest-quality", items), ["/best", "/sharp", "/blurred", "/unknown"]);
+    }
+}
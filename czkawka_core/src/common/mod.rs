@@ -4,20 +4,34 @@ pub mod config_cache_path;
 pub mod consts;
 pub mod dir_traversal;
 pub mod directories;
+#[cfg(feature = "export")]
+pub mod export;
 pub mod extensions;
 pub mod ffmpeg_utils;
+pub mod file_system;
 pub mod image;
+pub mod import;
 pub mod items;
+pub mod journal;
+pub mod keep_rules;
 pub mod logger;
 pub mod model;
+pub mod ndjson;
 pub mod process_utils;
+pub mod profile;
 pub mod progress_data;
 pub mod progress_stop_handler;
+pub mod quarantine;
+#[cfg(feature = "sftp")]
+pub mod sftp_file_system;
+pub mod shared_traversal;
 pub mod tool_data;
 pub mod traits;
 pub mod video_utils;
+pub mod watch;
 
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::io::Error;
 use std::path::{Path, PathBuf};
@@ -29,6 +43,7 @@ use items::SingleExcludedItem;
 use log::debug;
 
 use crate::common::consts::DEFAULT_WORKER_THREAD_SIZE;
+use crate::common::file_system::{FileSystem, LocalFileSystem};
 use crate::flc;
 
 static NUMBER_OF_THREADS: std::sync::LazyLock<Mutex<Option<usize>>> = std::sync::LazyLock::new(|| Mutex::new(None));
@@ -199,6 +214,71 @@ pub fn remove_folder_if_contains_only_empty_folders<P: AsRef<Path>>(path: P, rem
     }
 }
 
+/// Checks if folder still contains exactly `expected_files`(paths relative to folder, also in subfolders, with their sizes), so files which
+/// appeared or were replaced after scan are not removed. Folders expected to be empty may contain only empty folders.
+pub fn check_if_folder_contains_expected_files<P: AsRef<Path>>(path: P, expected_files: &[(PathBuf, u64)]) -> Result<(), String> {
+    if expected_files.is_empty() {
+        return check_if_folder_contains_only_empty_folders(path);
+    }
+
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return Err(flc!("core_not_directory_remove", path = path.to_string_lossy()));
+    }
+
+    let mut found_files = BTreeSet::new();
+    let mut folders_to_check = vec![path.to_path_buf()];
+    while let Some(folder) = folders_to_check.pop() {
+        let Ok(read_dir) = folder.read_dir() else {
+            return Err(flc!("core_cannot_read_directory", path = folder.to_string_lossy().to_string()));
+        };
+        for entry in read_dir {
+            let Ok(entry) = entry else {
+                return Err(flc!("core_cannot_read_entry_from_directory", path = folder.to_string_lossy().to_string()));
+            };
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                folders_to_check.push(entry.path());
+            } else {
+                let entry_path = entry.path();
+                let metadata = entry
+                    .metadata()
+                    .map_err(|e| flc!("core_cannot_read_metadata_file", file = entry_path.to_string_lossy().to_string(), reason = e.to_string()))?;
+                found_files.insert((entry_path.strip_prefix(path).map(Path::to_path_buf).unwrap_or(entry_path), metadata.len()));
+            }
+        }
+    }
+    // File with the same name, but different size was replaced after scan
+    let expected_files: BTreeSet<(PathBuf, u64)> = expected_files.iter().cloned().collect();
+    if let Some((different_file, _size)) = found_files.symmetric_difference(&expected_files).next() {
+        return Err(flc!(
+            "core_folder_contains_different_files",
+            folder = path.to_string_lossy(),
+            file = different_file.to_string_lossy()
+        ));
+    }
+    Ok(())
+}
+
+/// Remove the folder if it still contains exactly `expected_files`(paths relative to folder, also in subfolders, with their sizes).
+/// If `remove_to_trash` is set, the folder will instead be sent to the system's recycle bin/trash equivalent rather than being deleted.
+///
+/// Note: if used on Android or iOS platforms, ensure `remove_to_trash` is false, as trash is not supported
+/// and will always return an [`Error`].
+pub fn remove_folder_if_contains_expected_files<P: AsRef<Path>>(path: P, expected_files: &[(PathBuf, u64)], remove_to_trash: bool) -> Result<(), String> {
+    if expected_files.is_empty() {
+        return remove_folder_if_contains_only_empty_folders(path, remove_to_trash);
+    }
+
+    check_if_folder_contains_expected_files(&path, expected_files)?;
+
+    let path = path.as_ref();
+    if remove_to_trash {
+        trash_delete(path).map_err(|e| format!("Cannot move folder \"{}\" to trash, reason {e}", path.to_string_lossy()))
+    } else {
+        fs::remove_dir_all(path).map_err(|e| format!("Cannot remove directory \"{}\", reason {e}", path.to_string_lossy()))
+    }
+}
+
 /// Remove a single file. If `remove_to_trash` is set, the folder will instead be sent to the system's
 /// recycle bin/trash equivalent rather than being deleted.
 ///
@@ -351,14 +431,17 @@ pub fn normalize_windows_path<P: AsRef<Path>>(path_to_change: P) -> PathBuf {
 // Function to create hardlink, when destination exists
 // This is always true in this app, because creating hardlink, to newly created file is pointless
 pub fn make_hard_link<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
-    let src = src.as_ref();
-    let dst = dst.as_ref();
+    make_hard_link_with_file_system(&LocalFileSystem, src.as_ref(), dst.as_ref())
+}
+
+// Destination is first renamed to temporary file, so it can be restored when hardlink cannot be created
+pub fn make_hard_link_with_file_system(file_system: &dyn FileSystem, src: &Path, dst: &Path) -> io::Result<()> {
     let dst_dir = dst.parent().ok_or_else(|| Error::other("No parent"))?;
     let mut temp;
     let mut attempts = MAX_SYMLINK_HARDLINK_ATTEMPTS;
     loop {
         temp = dst_dir.join(format!("{}.czkawka_tmp", rand::random::<u128>()));
-        if !temp.exists() {
+        if file_system.symlink_metadata(&temp).is_err() {
             break;
         }
         attempts -= 1;
@@ -366,19 +449,126 @@ pub fn make_hard_link<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Res
             return Err(Error::other("Cannot choose temporary file for hardlink creation"));
         }
     }
-    fs::rename(dst, temp.as_path())?;
-    match fs::hard_link(src, dst) {
+    file_system.rename(dst, temp.as_path())?;
+    match file_system.hard_link(src, dst) {
         Ok(()) => {
-            fs::remove_file(&temp)?;
+            file_system.remove_file(&temp)?;
             Ok(())
         }
         Err(e) => {
-            let _ = fs::rename(&temp, dst);
+            let _ = file_system.rename(&temp, dst);
             Err(e)
         }
     }
 }
 
+// Dedupe range length is limited on some filesystems(e.g. Btrfs accepts at most 16 MiB per call), so bigger files are processed in chunks
+#[cfg(target_os = "linux")]
+const REFLINK_CHUNK_SIZE: u64 = 16 * 1024 * 1024;
+#[cfg(target_os = "linux")]
+const FILE_DEDUPE_RANGE_DIFFERS: i32 = 1;
+#[cfg(target_os = "linux")]
+const FIDEDUPERANGE: libc::Ioctl = libc::_IOWR::<FileDedupeRange>(0x94, 54);
+
+// Structures from linux/fs.h
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct FileDedupeRange {
+    src_offset: u64,
+    src_length: u64,
+    dest_count: u16,
+    reserved1: u16,
+    reserved2: u32,
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct FileDedupeRangeInfo {
+    dest_fd: i64,
+    dest_offset: u64,
+    bytes_deduped: u64,
+    status: i32,
+    reserved: u32,
+}
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct FileDedupeRangeWithInfo {
+    range: FileDedupeRange,
+    info: FileDedupeRangeInfo,
+}
+
+// Shares data extents of src with dst(copy-on-write), so each file keeps its own inode, permissions and modification date
+// FIDEDUPERANGE is used instead of FICLONE, because kernel compares content of both files before sharing anything,
+// so file modified after scan cannot be lost
+#[cfg(target_os = "linux")]
+pub fn make_reflink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let src_file = fs::File::open(src)?;
+    // Without write access, dedupe is allowed only for file owner(and only on newer kernels)
+    let dst_file = match fs::OpenOptions::new().read(true).write(true).open(&dst) {
+        Ok(t) => t,
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => fs::File::open(&dst)?,
+        Err(e) => return Err(e),
+    };
+
+    let src_size = src_file.metadata()?.len();
+    if src_size != dst_file.metadata()?.len() {
+        return Err(Error::other("Files have different sizes"));
+    }
+
+    let mut offset = 0;
+    while offset < src_size {
+        let mut dedupe_range = FileDedupeRangeWithInfo {
+            range: FileDedupeRange {
+                src_offset: offset,
+                src_length: (src_size - offset).min(REFLINK_CHUNK_SIZE),
+                dest_count: 1,
+                reserved1: 0,
+                reserved2: 0,
+            },
+            info: FileDedupeRangeInfo {
+                dest_fd: i64::from(dst_file.as_raw_fd()),
+                dest_offset: offset,
+                bytes_deduped: 0,
+                status: 0,
+                reserved: 0,
+            },
+        };
+        // SAFETY:
+        // Structure has exactly one info entry as declared in dest_count and both file descriptors are open during the call
+        let result = unsafe { libc::ioctl(src_file.as_raw_fd(), FIDEDUPERANGE, &raw mut dedupe_range) };
+        if result != 0 {
+            return Err(reflink_error(io::Error::last_os_error()));
+        }
+        if dedupe_range.info.status == FILE_DEDUPE_RANGE_DIFFERS {
+            return Err(Error::other("Content of files differs"));
+        }
+        if dedupe_range.info.status < 0 {
+            return Err(reflink_error(io::Error::from_raw_os_error(-dedupe_range.info.status)));
+        }
+        if dedupe_range.info.bytes_deduped == 0 {
+            return Err(Error::other("Filesystem did not share any data between files"));
+        }
+        offset += dedupe_range.info.bytes_deduped;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn reflink_error(e: Error) -> Error {
+    match e.raw_os_error() {
+        Some(libc::EOPNOTSUPP | libc::ENOTTY | libc::EINVAL | libc::EXDEV) => Error::new(io::ErrorKind::Unsupported, format!("Filesystem doesn't support reflinks ({e})")),
+        _ => e,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn make_reflink<P: AsRef<Path>, Q: AsRef<Path>>(_src: P, _dst: Q) -> io::Result<()> {
+    Err(Error::new(io::ErrorKind::Unsupported, "Reflinks are supported only on Linux"))
+}
+
 #[cfg(any(target_family = "unix", target_family = "windows"))]
 pub fn make_file_symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()> {
     let src = src.as_ref();
@@ -666,6 +856,49 @@ mod test {
         Ok(())
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_make_reflink() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let (src, dst) = (dir.path().join("a"), dir.path().join("b"));
+        fs::write(&src, b"same content of files")?;
+        fs::write(&dst, b"same content of files")?;
+        let dst_metadata_before = fs::metadata(&dst)?;
+
+        match make_reflink(&src, &dst) {
+            Ok(()) => {}
+            // Tmp folder may be on filesystem without reflink support(e.g. ext4 or tmpfs)
+            Err(e) if e.kind() == io::ErrorKind::Unsupported => return Ok(()),
+            Err(e) => return Err(e),
+        }
+
+        let dst_metadata_after = fs::metadata(&dst)?;
+        assert_inode(&dst_metadata_before, &dst_metadata_after);
+        assert_different_inode(&fs::metadata(&src)?, &dst_metadata_after);
+        assert_eq!(dst_metadata_before.permissions(), dst_metadata_after.permissions());
+        assert_eq!(dst_metadata_before.modified()?, dst_metadata_after.modified()?);
+        assert_eq!(fs::read(&dst)?, b"same content of files");
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_make_reflink_fails_with_different_files() -> io::Result<()> {
+        let dir = tempfile::Builder::new().tempdir()?;
+        let (src, dst, dst_other_size) = (dir.path().join("a"), dir.path().join("b"), dir.path().join("c"));
+        fs::write(&src, b"content of first file")?;
+        fs::write(&dst, b"content of other file")?;
+        fs::write(&dst_other_size, b"short")?;
+
+        assert!(make_reflink(&src, &dst).is_err());
+        assert!(make_reflink(&src, &dst_other_size).is_err());
+        assert!(make_reflink(dir.path().join("not_existing"), &dst).is_err());
+
+        assert_eq!(fs::read(&dst)?, b"content of other file");
+        assert_eq!(fs::read(&dst_other_size)?, b"short");
+        Ok(())
+    }
+
     #[cfg(any(target_family = "unix", target_family = "windows"))]
     #[test]
     fn test_make_file_symlink() -> io::Result<()> {
@@ -6,7 +6,8 @@ use xxhash_rust::xxh3::Xxh3;
 use crate::common::traits::ResultEntry;
 use crate::tools::duplicate::MyHasher;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ToolType {
     Duplicate,
     EmptyFolders,
@@ -42,9 +43,14 @@ pub enum CheckingMethod {
     Hash,
     AudioTags,
     AudioContent,
+    // Hash of only some parts of file, so results are only probable duplicates
+    PartialHash,
+    // Similar images, whose hashes are additionally compared with keypoint features, to find crops
+    ImageFeatures,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct FileEntry {
     pub path: PathBuf,
     pub size: u64,
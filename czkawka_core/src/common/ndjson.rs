@@ -0,0 +1,331 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::common::model::ToolType;
+use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::traits::ResultEntry;
+use crate::common::watch::{WatchChangeKind, WatchEvent};
+use crate::helpers::messages::Messages;
+
+// Increased only when existing fields are removed or change their meaning - adding new fields is not a breaking change
+pub const NDJSON_SCHEMA_VERSION: u32 = 1;
+
+// Every line of output is a single json object with same header fields, e.g.
+// {"schema_version":1,"tool":"duplicate","type":"group","index":0,"items":[...]}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NdjsonLine {
+    pub schema_version: u32,
+    pub tool: ToolType,
+    #[serde(flatten)]
+    pub record: NdjsonRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonRecord {
+    Progress(NdjsonProgress),
+    // Group of similar/duplicated items, used by tools that compare files with each other
+    Group { index: usize, items: Vec<NdjsonItem> },
+    // Single item, used by tools that check every file separately
+    Entry { item: NdjsonItem },
+    Message { level: NdjsonMessageLevel, text: String },
+    // Change of results noticed while watching folders, sent only after summary of initial results
+    Change { change: WatchChangeKind, item: NdjsonItem },
+    // Always last line of results
+    Summary { groups: usize, entries: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NdjsonMessageLevel {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NdjsonProgress {
+    pub stage: CurrentStage,
+    pub current_stage_idx: u8,
+    pub max_stage_idx: u8,
+    pub entries_checked: usize,
+    pub entries_to_check: usize,
+    pub bytes_checked: u64,
+    pub bytes_to_check: u64,
+}
+
+impl From<&ProgressData> for NdjsonProgress {
+    fn from(progress_data: &ProgressData) -> Self {
+        Self {
+            stage: progress_data.sstage,
+            current_stage_idx: progress_data.current_stage_idx,
+            max_stage_idx: progress_data.max_stage_idx,
+            entries_checked: progress_data.entries_checked,
+            entries_to_check: progress_data.entries_to_check,
+            bytes_checked: progress_data.bytes_checked,
+            bytes_to_check: progress_data.bytes_to_check,
+        }
+    }
+}
+
+// Fields shared by all tools are always available at top level, while tool specific ones(hash, similarity, tags etc.) are in details
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NdjsonItem {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_date: u64,
+    // Item from reference folder, which is never removed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reference: bool,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub details: Map<String, Value>,
+}
+
+impl NdjsonItem {
+    pub fn new<T: ResultEntry + Serialize>(entry: &T, reference: bool) -> Self {
+        let mut details = match serde_json::to_value(entry) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        for duplicated_key in ["path", "size", "modified_date"] {
+            details.remove(duplicated_key);
+        }
+
+        Self {
+            path: entry.get_path().to_path_buf(),
+            size: entry.get_size(),
+            modified_date: entry.get_modified_date(),
+            reference,
+            details,
+        }
+    }
+
+    // Files(relative to folder) with their sizes saved with duplicated folders, empty for other items
+    pub fn get_folder_files(&self) -> Vec<(PathBuf, u64)> {
+        self.details.get("files").and_then(|files| serde_json::from_value(files.clone()).ok()).unwrap_or_default()
+    }
+}
+
+impl ResultEntry for NdjsonItem {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+    fn get_modified_date(&self) -> u64 {
+        self.modified_date
+    }
+    fn get_size(&self) -> u64 {
+        self.size
+    }
+    fn get_hash(&self) -> Option<&str> {
+        self.details.get("hash").and_then(Value::as_str).filter(|hash| !hash.is_empty())
+    }
+    fn is_read_only(&self) -> bool {
+        self.details.get("archive_path").is_some_and(|archive_path| !archive_path.is_null())
+            || self.details.get("from_reference_index").and_then(Value::as_bool).unwrap_or_default()
+    }
+}
+
+// Destination of records - text stream or in-memory list used by other export formats
+pub trait NdjsonOutput {
+    fn write_line(&mut self, line: NdjsonLine) -> io::Result<()>;
+}
+
+impl<W: Write> NdjsonOutput for W {
+    // Every line is flushed immediately, so consumers can process it while scan is still running
+    fn write_line(&mut self, line: NdjsonLine) -> io::Result<()> {
+        serde_json::to_writer(&mut *self, &line)?;
+        self.write_all(b"\n")?;
+        self.flush()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NdjsonCollector {
+    pub lines: Vec<NdjsonLine>,
+}
+
+impl NdjsonOutput for NdjsonCollector {
+    fn write_line(&mut self, line: NdjsonLine) -> io::Result<()> {
+        self.lines.push(line);
+        Ok(())
+    }
+}
+
+pub struct NdjsonWriter<W: NdjsonOutput> {
+    writer: W,
+    tool: ToolType,
+    groups: usize,
+    entries: usize,
+}
+
+impl<W: NdjsonOutput> NdjsonWriter<W> {
+    pub fn new(writer: W, tool: ToolType) -> Self {
+        Self {
+            writer,
+            tool,
+            groups: 0,
+            entries: 0,
+        }
+    }
+
+    pub fn write_record(&mut self, record: NdjsonRecord) -> io::Result<()> {
+        self.writer.write_line(NdjsonLine {
+            schema_version: NDJSON_SCHEMA_VERSION,
+            tool: self.tool,
+            record,
+        })
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn write_progress(&mut self, progress_data: &ProgressData) -> io::Result<()> {
+        self.write_record(NdjsonRecord::Progress(NdjsonProgress::from(progress_data)))
+    }
+
+    pub fn write_entries<'a, T: ResultEntry + Serialize + 'a>(&mut self, entries: impl IntoIterator<Item = &'a T>) -> io::Result<()> {
+        for entry in entries {
+            self.entries += 1;
+            self.write_record(NdjsonRecord::Entry {
+                item: NdjsonItem::new(entry, false),
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn write_groups<'a, T: ResultEntry + Serialize + 'a>(&mut self, groups: impl IntoIterator<Item = &'a Vec<T>>) -> io::Result<()> {
+        for group in groups {
+            let items = group.iter().map(|entry| NdjsonItem::new(entry, false)).collect();
+            self.write_group(items)?;
+        }
+        Ok(())
+    }
+
+    // Reference item is always first item of group
+    pub fn write_referenced_groups<'a, T: ResultEntry + Serialize + 'a>(&mut self, groups: impl IntoIterator<Item = &'a (T, Vec<T>)>) -> io::Result<()> {
+        for (reference, group) in groups {
+            let items = std::iter::once(NdjsonItem::new(reference, true))
+                .chain(group.iter().map(|entry| NdjsonItem::new(entry, false)))
+                .collect();
+            self.write_group(items)?;
+        }
+        Ok(())
+    }
+
+    fn write_group(&mut self, items: Vec<NdjsonItem>) -> io::Result<()> {
+        let index = self.groups;
+        self.groups += 1;
+        self.entries += items.len();
+        self.write_record(NdjsonRecord::Group { index, items })
+    }
+
+    pub fn write_messages(&mut self, messages: &Messages) -> io::Result<()> {
+        let all_messages = messages
+            .critical
+            .iter()
+            .map(|text| (NdjsonMessageLevel::Critical, text))
+            .chain(messages.errors.iter().map(|text| (NdjsonMessageLevel::Error, text)))
+            .chain(messages.warnings.iter().map(|text| (NdjsonMessageLevel::Warning, text)))
+            .chain(messages.messages.iter().map(|text| (NdjsonMessageLevel::Info, text)));
+        for (level, text) in all_messages {
+            self.write_record(NdjsonRecord::Message { level, text: text.clone() })?;
+        }
+        Ok(())
+    }
+
+    pub fn write_watch_events(&mut self, events: &[WatchEvent]) -> io::Result<()> {
+        for event in events {
+            self.write_record(NdjsonRecord::Change {
+                change: event.change,
+                item: event.item.clone(),
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn write_summary(&mut self) -> io::Result<()> {
+        self.write_record(NdjsonRecord::Summary {
+            groups: self.groups,
+            entries: self.entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn file_entry(path: &str, size: u64) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(path),
+            size,
+            modified_date: 100,
+        }
+    }
+
+    fn parse_lines(output: &[u8]) -> Vec<NdjsonLine> {
+        String::from_utf8(output.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_ndjson_groups_and_summary() {
+        let mut output = Vec::new();
+        let mut writer = NdjsonWriter::new(&mut output, ToolType::Duplicate);
+        writer.write_groups(&vec![vec![file_entry("/a", 10), file_entry("/b", 10)]]).unwrap();
+        writer.write_referenced_groups(&vec![(file_entry("/ref", 5), vec![file_entry("/c", 5)])]).unwrap();
+        writer.write_summary().unwrap();
+
+        let lines = parse_lines(&output);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.schema_version == NDJSON_SCHEMA_VERSION && line.tool == ToolType::Duplicate));
+        let NdjsonRecord::Group { index, items } = &lines[1].record else {
+            panic!("Expected group, got {:?}", lines[1].record);
+        };
+        assert_eq!(*index, 1);
+        assert_eq!(items.iter().map(|item| item.reference).collect::<Vec<_>>(), [true, false]);
+        assert_eq!(items[0].path, PathBuf::from("/ref"));
+        // Shared fields are not repeated in details
+        assert!(items[0].details.is_empty());
+        assert_eq!(lines[2].record, NdjsonRecord::Summary { groups: 2, entries: 4 });
+    }
+
+    #[test]
+    fn test_ndjson_line_format() {
+        let mut output = Vec::new();
+        let mut writer = NdjsonWriter::new(&mut output, ToolType::BigFile);
+        writer.write_entries(&[file_entry("/big", 1000)]).unwrap();
+        let mut messages = Messages::new();
+        messages.warnings.push("warning".to_string());
+        writer.write_messages(&messages).unwrap();
+        writer.write_progress(&ProgressData::get_empty_state(CurrentStage::CollectingFiles)).unwrap();
+        writer
+            .write_watch_events(&[WatchEvent {
+                change: WatchChangeKind::Removed,
+                item: NdjsonItem::new(&file_entry("/big", 1000), false),
+            }])
+            .unwrap();
+
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"schema_version":1,"tool":"big_file","type":"entry","item":{"path":"/big","size":1000,"modified_date":100}}"#
+        );
+        assert_eq!(lines[1], r#"{"schema_version":1,"tool":"big_file","type":"message","level":"warning","text":"warning"}"#);
+        assert!(lines[2].starts_with(r#"{"schema_version":1,"tool":"big_file","type":"progress","stage":"collecting_files","#));
+        assert_eq!(
+            lines[3],
+            r#"{"schema_version":1,"tool":"big_file","type":"change","change":"removed","item":{"path":"/big","size":1000,"modified_date":100}}"#
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::config_cache_path::get_config_cache_path;
+
+pub const PROFILE_EXTENSION: &str = "toml";
+
+// Scan settings stored in TOML file, field names and values are the same as in Krokiet presets,
+// so preset exported from GUI can be used directly by CLI
+// Every field is optional, missing ones are not changed - GUI only fields are ignored when loading
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanProfile {
+    pub included_paths: Option<Vec<PathBuf>>,
+    pub included_paths_referenced: Option<Vec<PathBuf>>,
+    pub excluded_paths: Option<Vec<PathBuf>>,
+    // Comma separated values
+    pub excluded_items: Option<String>,
+    pub keep_rules: Option<String>,
+    pub allowed_extensions: Option<String>,
+    pub excluded_extensions: Option<String>,
+    // In KB
+    pub minimum_file_size: Option<i32>,
+    pub maximum_file_size: Option<i32>,
+    pub recursive_search: Option<bool>,
+    pub use_cache: Option<bool>,
+    pub move_deleted_files_to_trash: Option<bool>,
+    pub ignore_other_file_systems: Option<bool>,
+    pub thread_number: Option<i32>,
+    pub hide_hard_links: Option<bool>,
+
+    pub duplicate_use_prehash: Option<bool>,
+    // In bytes
+    pub duplicate_minimal_hash_cache_size: Option<i32>,
+    pub duplicate_minimal_prehash_cache_size: Option<i32>,
+    pub duplicates_sub_check_method: Option<String>,
+    pub duplicates_sub_available_hash_type: Option<String>,
+    pub duplicates_sub_name_case_sensitive: Option<bool>,
+    pub duplicates_sub_verify_byte_by_byte: Option<bool>,
+
+    pub biggest_files_sub_method: Option<String>,
+    pub biggest_files_sub_number_of_files: Option<i32>,
+
+    pub similar_images_sub_hash_size: Option<String>,
+    pub similar_images_sub_hash_alg: Option<String>,
+    pub similar_images_sub_resize_algorithm: Option<String>,
+    pub similar_images_sub_ignore_same_size: Option<bool>,
+    pub similar_images_sub_check_transforms: Option<bool>,
+    pub similar_images_sub_match_crops: Option<bool>,
+    pub similar_images_sub_match_crops_max_images: Option<usize>,
+    pub similar_images_sub_series_interval: Option<u32>,
+    pub similar_images_sub_calculate_quality: Option<bool>,
+    pub similar_images_sub_similarity: Option<i32>,
+
+    pub similar_videos_sub_ignore_same_size: Option<bool>,
+    pub similar_videos_sub_similarity: Option<i32>,
+    pub similar_videos_skip_forward_amount: Option<u32>,
+    pub similar_videos_vid_hash_duration: Option<u32>,
+    pub similar_videos_crop_detect: Option<String>,
+
+    pub similar_music_sub_audio_check_type: Option<String>,
+    pub similar_music_sub_approximate_comparison: Option<bool>,
+    pub similar_music_compare_fingerprints_only_with_similar_titles: Option<bool>,
+    pub similar_music_sub_title: Option<bool>,
+    pub similar_music_sub_artist: Option<bool>,
+    pub similar_music_sub_year: Option<bool>,
+    pub similar_music_sub_bitrate: Option<bool>,
+    pub similar_music_sub_genre: Option<bool>,
+    pub similar_music_sub_length: Option<bool>,
+    pub similar_music_sub_maximum_difference_value: Option<f32>,
+    pub similar_music_sub_minimal_fragment_duration_value: Option<f32>,
+
+    pub broken_files_sub_audio: Option<bool>,
+    pub broken_files_sub_pdf: Option<bool>,
+    pub broken_files_sub_archive: Option<bool>,
+    pub broken_files_sub_image: Option<bool>,
+    pub broken_files_sub_video: Option<bool>,
+
+    pub bad_names_sub_uppercase_extension: Option<bool>,
+    pub bad_names_sub_emoji_used: Option<bool>,
+    pub bad_names_sub_space_at_start_end: Option<bool>,
+    pub bad_names_sub_non_ascii: Option<bool>,
+    pub bad_names_sub_restricted_charset_enabled: Option<bool>,
+    pub bad_names_sub_restricted_charset: Option<Vec<char>>,
+    pub bad_names_sub_remove_duplicated: Option<bool>,
+
+    pub ignored_exif_tags: Option<String>,
+}
+
+// Named profiles are kept in "profiles" folder inside config folder
+pub fn get_profiles_folder() -> Option<PathBuf> {
+    Some(get_config_cache_path()?.config_folder.join("profiles"))
+}
+
+// Accepts path to profile file or name of profile from profiles folder(without extension)
+pub fn find_profile_file(name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(name);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let Some(profiles_folder) = get_profiles_folder() else {
+        return Err(format!("Cannot find profile \"{name}\" - config folder is not available"));
+    };
+    let profile_file = profiles_folder.join(format!("{name}.{PROFILE_EXTENSION}"));
+    if profile_file.is_file() {
+        Ok(profile_file)
+    } else {
+        Err(format!(
+            "Cannot find profile \"{name}\" - it is not a file and \"{}\" doesn't exist",
+            profile_file.to_string_lossy()
+        ))
+    }
+}
+
+pub fn load_profile_from_file(profile_file: &Path) -> Result<ScanProfile, String> {
+    let content = fs::read_to_string(profile_file).map_err(|e| format!("Cannot read profile \"{}\" - {e}", profile_file.to_string_lossy()))?;
+    toml::from_str(&content).map_err(|e| format!("Cannot parse profile \"{}\" - {e}", profile_file.to_string_lossy()))
+}
+
+pub fn load_profile(name: &str) -> Result<ScanProfile, String> {
+    load_profile_from_file(&find_profile_file(name)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_load_profile_exported_from_gui() {
+        let temp_dir = tempdir().unwrap();
+        let profile_file = temp_dir.path().join("photos.toml");
+        fs::write(
+            &profile_file,
+            r#"
+included_paths = ["/home/user/Photos"]
+excluded_items = "*/.git/*,*/cache/*"
+minimum_file_size = 16
+recursive_search = false
+similar_images_sub_hash_alg = "gradient"
+bad_names_sub_restricted_charset = ["_", "-"]
+# Fields used only by GUI are ignored
+similar_images_show_image_preview = true
+
+[column_sizes]
+duplicates = [100.0, 200.0]
+"#,
+        )
+        .unwrap();
+
+        let profile = load_profile(&profile_file.to_string_lossy()).unwrap();
+        assert_eq!(profile.included_paths, Some(vec![PathBuf::from("/home/user/Photos")]));
+        assert_eq!(profile.excluded_items.as_deref(), Some("*/.git/*,*/cache/*"));
+        assert_eq!(profile.minimum_file_size, Some(16));
+        assert_eq!(profile.recursive_search, Some(false));
+        assert_eq!(profile.similar_images_sub_hash_alg.as_deref(), Some("gradient"));
+        assert_eq!(profile.bad_names_sub_restricted_charset, Some(vec!['_', '-']));
+        assert_eq!(profile.use_cache, None);
+        assert_eq!(profile.excluded_paths, None);
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        let temp_dir = tempdir().unwrap();
+        let profile_file = temp_dir.path().join("broken.toml");
+        fs::write(&profile_file, "minimum_file_size = \"big\"").unwrap();
+
+        let err = load_profile_from_file(&profile_file).unwrap_err();
+        assert!(err.contains("Cannot parse profile"), "{err}");
+    }
+}
@@ -1,4 +1,5 @@
 use log::error;
+use serde::{Deserialize, Serialize};
 
 use crate::common::model::{CheckingMethod, ToolType};
 // Empty files
@@ -25,6 +26,7 @@ use crate::common::model::{CheckingMethod, ToolType};
 // 0 - Collecting files
 // 1 - Scanning images
 // 2 - Comparing hashes
+// 3 - FEATURES - Matching features
 
 // Similar videos
 // 0 - Collecting files
@@ -67,6 +69,7 @@ use crate::common::model::{CheckingMethod, ToolType};
 // Renaming files
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct ProgressData {
     pub sstage: CurrentStage,
     pub checking_method: CheckingMethod,
@@ -95,7 +98,8 @@ impl ProgressData {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CurrentStage {
     DeletingFiles,
     RenamingFiles,
@@ -115,6 +119,7 @@ pub enum CurrentStage {
     DuplicateScanningSize,
     DuplicatePreHashing,
     DuplicateFullHashing,
+    DuplicateByteComparing,
 
     SameMusicCacheSavingTags,
     SameMusicCacheLoadingTags,
@@ -127,6 +132,7 @@ pub enum CurrentStage {
 
     SimilarImagesCalculatingHashes,
     SimilarImagesComparingHashes,
+    SimilarImagesMatchingFeatures,
     SimilarVideosCalculatingHashes,
     SimilarVideosCreatingThumbnails,
     BrokenFilesChecking,
@@ -176,7 +182,8 @@ impl ProgressData {
 
         let tool_type_checking_method: Option<ToolType> = match self.checking_method {
             CheckingMethod::AudioTags | CheckingMethod::AudioContent => Some(ToolType::SameMusic),
-            CheckingMethod::Name | CheckingMethod::SizeName | CheckingMethod::Size | CheckingMethod::Hash => Some(ToolType::Duplicate),
+            CheckingMethod::Name | CheckingMethod::SizeName | CheckingMethod::Size | CheckingMethod::PartialHash | CheckingMethod::Hash => Some(ToolType::Duplicate),
+            CheckingMethod::ImageFeatures => Some(ToolType::SimilarImages),
             CheckingMethod::None => None,
         };
         if let Some(tool_type) = tool_type_checking_method {
@@ -198,7 +205,8 @@ impl ProgressData {
             | CurrentStage::DuplicateScanningSizeName
             | CurrentStage::DuplicateScanningSize
             | CurrentStage::DuplicatePreHashing
-            | CurrentStage::DuplicateFullHashing => Some(ToolType::Duplicate),
+            | CurrentStage::DuplicateFullHashing
+            | CurrentStage::DuplicateByteComparing => Some(ToolType::Duplicate),
             CurrentStage::SameMusicCacheLoadingTags
             | CurrentStage::SameMusicCacheSavingTags
             | CurrentStage::SameMusicCacheLoadingFingerprints
@@ -207,7 +215,9 @@ impl ProgressData {
             | CurrentStage::SameMusicReadingTags
             | CurrentStage::SameMusicComparingFingerprints
             | CurrentStage::SameMusicCalculatingFingerprints => Some(ToolType::SameMusic),
-            CurrentStage::SimilarImagesCalculatingHashes | CurrentStage::SimilarImagesComparingHashes => Some(ToolType::SimilarImages),
+            CurrentStage::SimilarImagesCalculatingHashes | CurrentStage::SimilarImagesComparingHashes | CurrentStage::SimilarImagesMatchingFeatures => {
+                Some(ToolType::SimilarImages)
+            }
             CurrentStage::SimilarVideosCalculatingHashes | CurrentStage::SimilarVideosCreatingThumbnails => Some(ToolType::SimilarVideos),
             CurrentStage::BrokenFilesChecking => Some(ToolType::BrokenFiles),
             CurrentStage::BadExtensionsChecking => Some(ToolType::BadExtensions),
@@ -224,11 +234,15 @@ impl ProgressData {
 impl ToolType {
     pub(crate) fn get_max_stage(self, checking_method: CheckingMethod) -> u8 {
         match self {
-            Self::Duplicate => 6,
+            Self::Duplicate => 7,
             Self::EmptyFolders | Self::EmptyFiles | Self::InvalidSymlinks | Self::BigFile | Self::TemporaryFiles => 0,
             Self::BrokenFiles | Self::BadExtensions | Self::BadNames => 1,
-            Self::SimilarImages | Self::SimilarVideos | Self::VideoOptimizer => 2,
+            Self::SimilarVideos | Self::VideoOptimizer => 2,
             Self::ExifRemover => 3,
+            Self::SimilarImages => match checking_method {
+                CheckingMethod::ImageFeatures => 3,
+                _ => 2,
+            },
             Self::None => unreachable!("ToolType::None is not allowed"),
             Self::SameMusic => match checking_method {
                 CheckingMethod::AudioTags => 4,
@@ -267,8 +281,10 @@ impl CurrentStage {
             Self::DuplicateCacheLoading => 4,
             Self::DuplicateFullHashing => 5,
             Self::DuplicateCacheSaving => 6,
+            Self::DuplicateByteComparing => 7,
             Self::SimilarImagesCalculatingHashes => 1,
             Self::SimilarImagesComparingHashes => 2,
+            Self::SimilarImagesMatchingFeatures => 3,
             Self::SimilarVideosCalculatingHashes => 1,
             Self::SimilarVideosCreatingThumbnails => 2,
             Self::BrokenFilesChecking => 1,
@@ -316,13 +332,15 @@ mod tests {
 
     #[test]
     fn test_tool_type_and_current_stage_integration() {
-        assert_eq!(ToolType::Duplicate.get_max_stage(CheckingMethod::Hash), 6);
+        assert_eq!(ToolType::Duplicate.get_max_stage(CheckingMethod::Hash), 7);
         assert_eq!(ToolType::SameMusic.get_max_stage(CheckingMethod::AudioTags), 4);
         assert_eq!(ToolType::SameMusic.get_max_stage(CheckingMethod::AudioContent), 7);
         assert_eq!(ToolType::SimilarImages.get_max_stage(CheckingMethod::None), 2);
+        assert_eq!(ToolType::SimilarImages.get_max_stage(CheckingMethod::ImageFeatures), 3);
         assert_eq!(ToolType::BrokenFiles.get_max_stage(CheckingMethod::None), 1);
 
         assert_eq!(CurrentStage::DuplicateFullHashing.get_current_stage(), 5);
+        assert_eq!(CurrentStage::DuplicateByteComparing.get_current_stage(), 7);
         assert_eq!(CurrentStage::SameMusicComparingFingerprints.get_current_stage(), 7);
         assert!(CurrentStage::DeletingFiles.is_special_non_tool_stage());
         assert!(!CurrentStage::CollectingFiles.is_special_non_tool_stage());
@@ -346,7 +364,7 @@ mod tests {
             sstage: CurrentStage::DuplicateFullHashing,
             checking_method: CheckingMethod::Hash,
             current_stage_idx: 5,
-            max_stage_idx: 6,
+            max_stage_idx: 7,
             entries_checked: 50,
             entries_to_check: 100,
             bytes_checked: 1000,
@@ -362,8 +380,8 @@ mod tests {
         ProgressData {
             sstage: CurrentStage::DuplicateFullHashing,
             checking_method: CheckingMethod::Hash,
-            current_stage_idx: 7,
-            max_stage_idx: 6,
+            current_stage_idx: 8,
+            max_stage_idx: 7,
             entries_checked: 0,
             entries_to_check: 100,
             bytes_checked: 0,
@@ -380,7 +398,7 @@ mod tests {
             sstage: CurrentStage::DuplicateFullHashing,
             checking_method: CheckingMethod::Hash,
             current_stage_idx: 5,
-            max_stage_idx: 6,
+            max_stage_idx: 7,
             entries_checked: 150,
             entries_to_check: 100,
             bytes_checked: 0,
@@ -0,0 +1,409 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+// Quarantine is folder, where files are moved instead of being removed
+// Every run creates separate session folder, which contains files with their original folder structure and manifest
+// e.g. "/home/user/a.txt" is moved to "QUARANTINE/1735689600_0a1b2c3d/home/user/a.txt"
+const MANIFEST_FILE_NAME: &str = "manifest.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub original_path: PathBuf,
+    pub quarantine_path: PathBuf,
+    pub time: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuarantineSession {
+    pub id: String,
+    pub started: u64,
+    pub entries: Vec<QuarantineEntry>,
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QuarantineRestoreResult {
+    pub restored: usize,
+    pub errors: Vec<String>,
+}
+
+fn current_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+// Writer of single quarantine session, folder and manifest are created only when first item is moved
+pub struct Quarantine {
+    session_folder: PathBuf,
+    manifest: Mutex<Option<File>>,
+}
+
+impl Quarantine {
+    pub fn new(quarantine_folder: &Path) -> Self {
+        let session_id = format!("{}_{:08x}", current_time(), rand::random::<u32>());
+        Self {
+            session_folder: quarantine_folder.join(session_id),
+            manifest: Mutex::new(None),
+        }
+    }
+
+    // Absolute path is mapped into session folder, so files with same names from different folders don't collide
+    pub fn get_quarantine_path(&self, path: &Path) -> PathBuf {
+        let mut quarantine_path = self.session_folder.clone();
+        for component in path.components() {
+            match component {
+                Component::Prefix(prefix) => quarantine_path.push(prefix.as_os_str().to_string_lossy().replace([':', '\\', '?', '/'], "")),
+                Component::Normal(name) => quarantine_path.push(name),
+                Component::RootDir | Component::CurDir | Component::ParentDir => {}
+            }
+        }
+        quarantine_path
+    }
+
+    pub fn move_to_quarantine(&self, path: &Path) -> Result<(), String> {
+        let quarantine_path = self.get_quarantine_path(path);
+        if fs::symlink_metadata(&quarantine_path).is_ok() {
+            return Err(format!("\"{}\" is already in quarantine", path.to_string_lossy()));
+        }
+        if let Some(parent) = quarantine_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Cannot create quarantine folder \"{}\" - {e}", parent.to_string_lossy()))?;
+        }
+        move_item(path, &quarantine_path)?;
+
+        let entry = QuarantineEntry {
+            original_path: path.to_path_buf(),
+            quarantine_path,
+            time: current_time(),
+        };
+        // Without manifest entry, file would be still available in quarantine, but it couldn't be restored automatically
+        self.append_to_manifest(&entry)
+            .map_err(|e| format!("Moved \"{}\" to quarantine, but failed to save it in manifest - {e}", path.to_string_lossy()))
+    }
+
+    fn append_to_manifest(&self, entry: &QuarantineEntry) -> Result<(), String> {
+        let mut manifest_guard = self.manifest.lock().map_err(|e| e.to_string())?;
+        if manifest_guard.is_none() {
+            let manifest_path = self.session_folder.join(MANIFEST_FILE_NAME);
+            let file = OpenOptions::new().create(true).append(true).open(&manifest_path).map_err(|e| e.to_string())?;
+            *manifest_guard = Some(file);
+        }
+        let manifest = manifest_guard.as_mut().expect("Manifest was just opened");
+        let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+        line.push('\n');
+        manifest.write_all(line.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+// Rename is the fastest way, but works only inside same partition, so otherwise items are copied and removed
+fn move_item(from: &Path, to: &Path) -> Result<(), String> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    let map_error = |e: std::io::Error| format!("Cannot move \"{}\" to \"{}\" - {e}", from.to_string_lossy(), to.to_string_lossy());
+    let file_type = fs::symlink_metadata(from).map_err(map_error)?.file_type();
+    if file_type.is_dir() {
+        copy_folder(from, to).map_err(map_error)?;
+        fs::remove_dir_all(from).map_err(map_error)
+    } else {
+        if file_type.is_symlink() {
+            copy_symlink(from, to).map_err(map_error)?;
+        } else {
+            fs::copy(from, to).map_err(map_error)?;
+        }
+        fs::remove_file(from).map_err(map_error)
+    }
+}
+
+fn copy_folder(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_folder(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+// Symlinks are copied as links, because copying content would fail on broken links and would duplicate linked data
+#[cfg(target_family = "unix")]
+fn copy_symlink(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+#[cfg(target_family = "windows")]
+fn copy_symlink(from: &Path, to: &Path) -> std::io::Result<()> {
+    let link_target = fs::read_link(from)?;
+    if fs::metadata(from).is_ok_and(|metadata| metadata.is_dir()) {
+        std::os::windows::fs::symlink_dir(link_target, to)
+    } else {
+        std::os::windows::fs::symlink_file(link_target, to)
+    }
+}
+
+#[cfg(not(any(target_family = "unix", target_family = "windows")))]
+fn copy_symlink(from: &Path, _to: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::other(format!("Cannot copy symlink \"{}\" on this platform", from.to_string_lossy())))
+}
+
+fn load_session(session_folder: &Path) -> Result<QuarantineSession, String> {
+    let id = session_folder.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let started = id.split('_').next().and_then(|time| time.parse().ok()).unwrap_or_default();
+
+    let manifest_path = session_folder.join(MANIFEST_FILE_NAME);
+    let file = File::open(&manifest_path).map_err(|e| format!("Cannot open quarantine manifest \"{}\" - {e}", manifest_path.to_string_lossy()))?;
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("Skipping broken quarantine manifest entry in \"{}\" - {e}", manifest_path.to_string_lossy()),
+        }
+    }
+
+    Ok(QuarantineSession {
+        id,
+        started,
+        entries,
+        path: session_folder.to_path_buf(),
+    })
+}
+
+// Returns sessions sorted from the newest one
+pub fn list_quarantine_sessions(quarantine_folder: &Path) -> Result<Vec<QuarantineSession>, String> {
+    if !quarantine_folder.exists() {
+        return Ok(Vec::new());
+    }
+    let read_dir = fs::read_dir(quarantine_folder).map_err(|e| format!("Cannot read quarantine folder \"{}\" - {e}", quarantine_folder.to_string_lossy()))?;
+
+    let mut sessions = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if !path.join(MANIFEST_FILE_NAME).is_file() {
+            continue;
+        }
+        match load_session(&path) {
+            Ok(session) => sessions.push(session),
+            Err(e) => warn!("{e}"),
+        }
+    }
+    sessions.sort_by(|a, b| b.started.cmp(&a.started).then_with(|| b.id.cmp(&a.id)));
+    Ok(sessions)
+}
+
+fn restore_entry(entry: &QuarantineEntry) -> Result<(), String> {
+    if fs::symlink_metadata(&entry.original_path).is_ok() {
+        return Err(format!("Cannot restore \"{}\", because it already exists", entry.original_path.to_string_lossy()));
+    }
+    if let Some(parent) = entry.original_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Cannot create folder \"{}\" - {e}", parent.to_string_lossy()))?;
+    }
+    move_item(&entry.quarantine_path, &entry.original_path)
+}
+
+// Session folder is removed when it contains nothing more to restore
+fn update_session(session: &QuarantineSession, remaining_entries: &[&QuarantineEntry]) -> Result<(), String> {
+    if remaining_entries.is_empty() {
+        fs::remove_dir_all(&session.path).map_err(|e| format!("Cannot remove quarantine session folder \"{}\" - {e}", session.path.to_string_lossy()))
+    } else {
+        save_manifest(&session.path, remaining_entries)
+    }
+}
+
+// Restored entries are removed from manifest
+pub fn restore_quarantine_session(session: &QuarantineSession) -> QuarantineRestoreResult {
+    let mut result = QuarantineRestoreResult::default();
+    let mut remaining_entries = Vec::new();
+
+    for entry in &session.entries {
+        match restore_entry(entry) {
+            Ok(()) => result.restored += 1,
+            Err(e) => {
+                result.errors.push(e);
+                remaining_entries.push(entry);
+            }
+        }
+    }
+
+    if let Err(e) = update_session(session, &remaining_entries) {
+        result.errors.push(e);
+    }
+    result
+}
+
+// Restores single item by its path inside quarantine, e.g. when reverting journal entry
+pub fn restore_quarantined_item(quarantine_path: &Path) -> Result<(), String> {
+    // Quarantined files may be also named like manifest, so session is found by entry saved in manifest
+    let session = quarantine_path
+        .ancestors()
+        .skip(1)
+        .filter(|folder| folder.join(MANIFEST_FILE_NAME).is_file())
+        .filter_map(|folder| load_session(folder).ok())
+        .find(|session| session.entries.iter().any(|entry| entry.quarantine_path == quarantine_path))
+        .ok_or_else(|| format!("Cannot find \"{}\" in any quarantine manifest", quarantine_path.to_string_lossy()))?;
+
+    let (restored_entries, remaining_entries): (Vec<_>, Vec<_>) = session.entries.iter().partition(|entry| entry.quarantine_path == quarantine_path);
+    for entry in restored_entries {
+        restore_entry(entry)?;
+    }
+    update_session(&session, &remaining_entries)
+}
+
+fn save_manifest(session_folder: &Path, entries: &[&QuarantineEntry]) -> Result<(), String> {
+    let manifest_path = session_folder.join(MANIFEST_FILE_NAME);
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        content.push('\n');
+    }
+    fs::write(&manifest_path, content).map_err(|e| format!("Cannot save quarantine manifest \"{}\" - {e}", manifest_path.to_string_lossy()))
+}
+
+// Permanently removes sessions older than given number of days, returns number of removed sessions
+pub fn purge_quarantine(quarantine_folder: &Path, older_than_days: u32) -> Result<usize, String> {
+    let minimal_time = current_time().saturating_sub(u64::from(older_than_days) * 24 * 60 * 60);
+
+    let mut removed_sessions = 0;
+    for session in list_quarantine_sessions(quarantine_folder)? {
+        if session.started >= minimal_time {
+            continue;
+        }
+        fs::remove_dir_all(&session.path).map_err(|e| format!("Cannot purge quarantine session folder \"{}\" - {e}", session.path.to_string_lossy()))?;
+        removed_sessions += 1;
+    }
+    Ok(removed_sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test_quarantine_keeps_structure_and_restores_files() {
+        let dir = tempdir().unwrap();
+        let quarantine_folder = dir.path().join("quarantine");
+        let file_a = dir.path().join("data").join("a.txt");
+        let file_b = dir.path().join("other").join("a.txt");
+        fs::create_dir_all(file_a.parent().unwrap()).unwrap();
+        fs::create_dir_all(file_b.parent().unwrap()).unwrap();
+        fs::write(&file_a, "a").unwrap();
+        fs::write(&file_b, "b").unwrap();
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        quarantine.move_to_quarantine(&file_a).unwrap();
+        quarantine.move_to_quarantine(&file_b).unwrap();
+        assert!(!file_a.exists());
+        assert!(!file_b.exists());
+
+        let quarantine_path = quarantine.get_quarantine_path(&file_a);
+        assert!(quarantine_path.starts_with(&quarantine_folder));
+        assert!(quarantine_path.ends_with(Path::new("data").join("a.txt")));
+        assert_eq!(fs::read_to_string(&quarantine_path).unwrap(), "a");
+
+        let sessions = list_quarantine_sessions(&quarantine_folder).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].entries.len(), 2);
+
+        let result = restore_quarantine_session(&sessions[0]);
+        assert_eq!(result.restored, 2);
+        assert!(result.errors.is_empty());
+        assert_eq!(fs::read_to_string(&file_a).unwrap(), "a");
+        assert_eq!(fs::read_to_string(&file_b).unwrap(), "b");
+        assert!(list_quarantine_sessions(&quarantine_folder).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_does_not_overwrite_existing_files() {
+        let dir = tempdir().unwrap();
+        let quarantine_folder = dir.path().join("quarantine");
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "old").unwrap();
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        quarantine.move_to_quarantine(&file).unwrap();
+        fs::write(&file, "new").unwrap();
+
+        let session = list_quarantine_sessions(&quarantine_folder).unwrap().remove(0);
+        let result = restore_quarantine_session(&session);
+        assert_eq!(result.restored, 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "new");
+        assert_eq!(list_quarantine_sessions(&quarantine_folder).unwrap()[0].entries.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_single_item_updates_manifest() {
+        let dir = tempdir().unwrap();
+        let quarantine_folder = dir.path().join("quarantine");
+        let file_a = dir.path().join("a.txt");
+        let file_b = dir.path().join("b.txt");
+        fs::write(&file_a, "a").unwrap();
+        fs::write(&file_b, "b").unwrap();
+
+        let quarantine = Quarantine::new(&quarantine_folder);
+        quarantine.move_to_quarantine(&file_a).unwrap();
+        quarantine.move_to_quarantine(&file_b).unwrap();
+
+        restore_quarantined_item(&quarantine.get_quarantine_path(&file_a)).unwrap();
+        assert_eq!(fs::read_to_string(&file_a).unwrap(), "a");
+        let sessions = list_quarantine_sessions(&quarantine_folder).unwrap();
+        assert_eq!(sessions[0].entries.len(), 1);
+        assert_eq!(sessions[0].entries[0].original_path, file_b);
+
+        restore_quarantined_item(&quarantine.get_quarantine_path(&file_b)).unwrap();
+        assert_eq!(fs::read_to_string(&file_b).unwrap(), "b");
+        assert!(list_quarantine_sessions(&quarantine_folder).unwrap().is_empty());
+        restore_quarantined_item(&quarantine.get_quarantine_path(&file_b)).unwrap_err();
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn test_copy_folder_keeps_dangling_symlinks() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("source");
+        let destination = dir.path().join("destination");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("file.txt"), "content").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("missing.txt"), source.join("nested").join("dangling")).unwrap();
+        std::os::unix::fs::symlink("file.txt", source.join("relative")).unwrap();
+
+        copy_folder(&source, &destination).unwrap();
+
+        assert_eq!(fs::read_to_string(destination.join("file.txt")).unwrap(), "content");
+        let dangling = destination.join("nested").join("dangling");
+        assert!(fs::symlink_metadata(&dangling).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&dangling).unwrap(), dir.path().join("missing.txt"));
+        assert!(fs::symlink_metadata(destination.join("relative")).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(destination.join("relative")).unwrap(), Path::new("file.txt"));
+    }
+
+    #[test]
+    fn test_purge_quarantine() {
+        let dir = tempdir().unwrap();
+        let quarantine_folder = dir.path().join("quarantine");
+        let old_session = quarantine_folder.join("1000_00000000");
+        fs::create_dir_all(&old_session).unwrap();
+        fs::write(old_session.join(MANIFEST_FILE_NAME), "").unwrap();
+
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "a").unwrap();
+        Quarantine::new(&quarantine_folder).move_to_quarantine(&file).unwrap();
+
+        assert_eq!(purge_quarantine(&quarantine_folder, 30).unwrap(), 1);
+        assert!(!old_session.exists());
+        assert_eq!(list_quarantine_sessions(&quarantine_folder).unwrap().len(), 1);
+    }
+}
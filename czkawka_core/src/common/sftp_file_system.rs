@@ -0,0 +1,437 @@
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use directories_next::BaseDirs;
+use ssh2::{CheckResult, FileStat, KnownHostFileKind, Session, Sftp};
+
+use crate::common::file_system::{FileSystem, FsDirEntry, FsEntryType, FsFile, FsMetadata};
+
+#[derive(Debug, Clone, Default)]
+pub struct SftpConnection {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    // When neither password nor private key is set, keys from ssh agent are used
+    pub password: Option<String>,
+    pub private_key: Option<PathBuf>,
+    // Host key of server must be present in this file, ~/.ssh/known_hosts is used when not set
+    pub known_hosts: Option<PathBuf>,
+}
+
+impl SftpConnection {
+    // Accepts `user@host` or `user@host:port`
+    pub fn from_address(address: &str) -> Result<Self, String> {
+        let (user, host_port) = address
+            .split_once('@')
+            .ok_or_else(|| format!("SFTP address \"{address}\" must be in format user@host[:port]"))?;
+        let (host, port) = match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse::<u16>().map_err(|e| format!("Invalid port in SFTP address \"{address}\" - {e}"))?),
+            None => (host_port, 22),
+        };
+        if user.is_empty() || host.is_empty() {
+            return Err(format!("SFTP address \"{address}\" must be in format user@host[:port]"));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            user: user.to_string(),
+            password: None,
+            private_key: None,
+            known_hosts: None,
+        })
+    }
+}
+
+// Operations of SFTP channel used by file system, separated from ssh2 to test mapping of their results without server
+trait SftpSession: Send + Sync {
+    fn readdir(&self, path: &Path) -> Result<Vec<(PathBuf, FileStat)>, ssh2::Error>;
+    fn stat(&self, path: &Path) -> Result<FileStat, ssh2::Error>;
+    fn lstat(&self, path: &Path) -> Result<FileStat, ssh2::Error>;
+    fn open(&self, path: &Path) -> Result<Box<dyn FsFile>, ssh2::Error>;
+    fn unlink(&self, path: &Path) -> Result<(), ssh2::Error>;
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), ssh2::Error>;
+}
+
+impl SftpSession for Sftp {
+    fn readdir(&self, path: &Path) -> Result<Vec<(PathBuf, FileStat)>, ssh2::Error> {
+        Self::readdir(self, path)
+    }
+    fn stat(&self, path: &Path) -> Result<FileStat, ssh2::Error> {
+        Self::stat(self, path)
+    }
+    fn lstat(&self, path: &Path) -> Result<FileStat, ssh2::Error> {
+        Self::lstat(self, path)
+    }
+    fn open(&self, path: &Path) -> Result<Box<dyn FsFile>, ssh2::Error> {
+        Ok(Box::new(Self::open(self, path)?))
+    }
+    fn unlink(&self, path: &Path) -> Result<(), ssh2::Error> {
+        Self::unlink(self, path)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), ssh2::Error> {
+        Self::rename(self, from, to, None)
+    }
+}
+
+// Whole session is protected by mutex inside ssh2, so operations from multiple threads are executed one by one
+pub struct SftpFileSystem {
+    address: String,
+    sftp: Box<dyn SftpSession>,
+    // Sftp channel is closed together with session, so it must be kept alive - empty in tests
+    _session: Option<Session>,
+}
+
+impl Debug for SftpFileSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SftpFileSystem").field("address", &self.address).finish()
+    }
+}
+
+impl SftpFileSystem {
+    pub fn connect(connection: &SftpConnection) -> Result<Self, String> {
+        let address = format!("{}@{}:{}", connection.user, connection.host, connection.port);
+        let map_error = |e: &dyn std::fmt::Display| format!("Cannot connect to SFTP server \"{address}\" - {e}");
+
+        let tcp = TcpStream::connect((connection.host.as_str(), connection.port)).map_err(|e| map_error(&e))?;
+        let mut session = Session::new().map_err(|e| map_error(&e))?;
+        session.set_tcp_stream(tcp);
+        session.set_timeout(Duration::from_secs(60).as_millis() as u32);
+        session.handshake().map_err(|e| map_error(&e))?;
+        // Credentials must not be sent before server is verified, otherwise anyone in the middle could collect them
+        verify_host_key(&session, connection).map_err(|e| map_error(&e))?;
+
+        if let Some(private_key) = &connection.private_key {
+            session
+                .userauth_pubkey_file(&connection.user, None, private_key, connection.password.as_deref())
+                .map_err(|e| map_error(&e))?;
+        } else if let Some(password) = &connection.password {
+            session.userauth_password(&connection.user, password).map_err(|e| map_error(&e))?;
+        } else {
+            session.userauth_agent(&connection.user).map_err(|e| map_error(&e))?;
+        }
+        if !session.authenticated() {
+            return Err(map_error(&"authentication failed"));
+        }
+
+        let sftp = session.sftp().map_err(|e| map_error(&e))?;
+        Ok(Self {
+            address,
+            sftp: Box::new(sftp),
+            _session: Some(session),
+        })
+    }
+}
+
+fn verify_host_key(session: &Session, connection: &SftpConnection) -> Result<(), String> {
+    let known_hosts_file = match &connection.known_hosts {
+        Some(known_hosts_file) => known_hosts_file.clone(),
+        None => BaseDirs::new()
+            .map(|dirs| dirs.home_dir().join(".ssh").join("known_hosts"))
+            .ok_or_else(|| "cannot find home directory with known_hosts file".to_string())?,
+    };
+    let (host_key, _key_type) = session.host_key().ok_or_else(|| "server didn't send its host key".to_string())?;
+
+    let mut known_hosts = session.known_hosts().map_err(|e| e.to_string())?;
+    known_hosts
+        .read_file(&known_hosts_file, KnownHostFileKind::OpenSSH)
+        .map_err(|e| format!("cannot read known hosts file \"{}\" - {e}", known_hosts_file.to_string_lossy()))?;
+    host_key_check_result(known_hosts.check_port(&connection.host, connection.port, host_key), &known_hosts_file)
+}
+
+// Only known and matching host key allows to continue, every other result closes connection
+fn host_key_check_result(check_result: CheckResult, known_hosts_file: &Path) -> Result<(), String> {
+    let known_hosts_file = known_hosts_file.to_string_lossy();
+    match check_result {
+        CheckResult::Match => Ok(()),
+        CheckResult::NotFound => Err(format!(
+            "host key of server is not present in \"{known_hosts_file}\", connect to server with ssh first to verify and save it"
+        )),
+        CheckResult::Mismatch => Err(format!(
+            "host key of server doesn't match key saved in \"{known_hosts_file}\", someone may be intercepting the connection"
+        )),
+        CheckResult::Failure => Err(format!("failed to check host key of server with \"{known_hosts_file}\"")),
+    }
+}
+
+fn entry_type(stat: &FileStat) -> FsEntryType {
+    let file_type = stat.file_type();
+    if file_type.is_dir() {
+        FsEntryType::Dir
+    } else if file_type.is_symlink() {
+        FsEntryType::Symlink
+    } else if file_type.is_file() {
+        FsEntryType::File
+    } else {
+        FsEntryType::Other
+    }
+}
+
+fn metadata_from_stat(stat: &FileStat) -> FsMetadata {
+    FsMetadata {
+        entry_type: entry_type(stat),
+        len: stat.size.unwrap_or(0),
+        modified: stat
+            .mtime
+            .map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime))
+            .ok_or_else(|| "server didn't send modification date".to_string()),
+        inode: None,
+    }
+}
+
+impl FileSystem for SftpFileSystem {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<FsDirEntry>>> {
+        let entries = self.sftp.readdir(path).map_err(io::Error::from)?;
+        Ok(entries
+            .into_iter()
+            .map(|(path, stat)| {
+                Ok(FsDirEntry {
+                    entry_type: entry_type(&stat),
+                    path,
+                })
+            })
+            .collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        Ok(metadata_from_stat(&self.sftp.stat(path).map_err(io::Error::from)?))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        Ok(metadata_from_stat(&self.sftp.lstat(path).map_err(io::Error::from)?))
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn FsFile>> {
+        self.sftp.open(path).map_err(io::Error::from)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.sftp.unlink(path).map_err(io::Error::from)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.sftp.rename(from, to).map_err(io::Error::from)
+    }
+
+    fn hard_link(&self, _original: &Path, _link: &Path) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "SFTP protocol doesn't support creating hard links"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::io::{Cursor, Read};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+    use std::thread;
+
+    use ssh2::ErrorCode;
+
+    use super::*;
+
+    // Values of LIBSSH2_SFTP_S_IF* and LIBSSH2_FX_NO_SUCH_FILE
+    const S_IFDIR: u32 = 0o040_000;
+    const S_IFREG: u32 = 0o100_000;
+    const S_IFLNK: u32 = 0o120_000;
+    const FX_NO_SUCH_FILE: i32 = 2;
+
+    // In-memory stand-in of SFTP server, entries are stored by full path like paths returned by ssh2
+    #[derive(Default)]
+    struct StandInSession {
+        entries: Mutex<BTreeMap<PathBuf, (FileStat, Vec<u8>)>>,
+    }
+
+    impl StandInSession {
+        fn with_entries(entries: &[(&str, u32, &[u8])]) -> Self {
+            let entries = entries
+                .iter()
+                .map(|(path, file_type, content)| {
+                    let stat = FileStat {
+                        size: Some(content.len() as u64),
+                        uid: None,
+                        gid: None,
+                        perm: Some(file_type | 0o644),
+                        atime: None,
+                        mtime: Some(1_000),
+                    };
+                    (PathBuf::from(path), (stat, content.to_vec()))
+                })
+                .collect();
+            Self { entries: Mutex::new(entries) }
+        }
+
+        fn get(&self, path: &Path) -> Result<(FileStat, Vec<u8>), ssh2::Error> {
+            self.entries
+                .lock()
+                .unwrap()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| ssh2::Error::new(ErrorCode::SFTP(FX_NO_SUCH_FILE), "no such file"))
+        }
+    }
+
+    impl SftpSession for StandInSession {
+        fn readdir(&self, path: &Path) -> Result<Vec<(PathBuf, FileStat)>, ssh2::Error> {
+            self.get(path)?;
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(entry_path, _)| entry_path.parent() == Some(path))
+                .map(|(entry_path, (stat, _))| (entry_path.clone(), stat.clone()))
+                .collect())
+        }
+        // Symlinks in stand-in always point to regular file
+        fn stat(&self, path: &Path) -> Result<FileStat, ssh2::Error> {
+            let (mut stat, _) = self.get(path)?;
+            if stat.file_type().is_symlink() {
+                stat.perm = Some(S_IFREG | 0o644);
+            }
+            Ok(stat)
+        }
+        fn lstat(&self, path: &Path) -> Result<FileStat, ssh2::Error> {
+            Ok(self.get(path)?.0)
+        }
+        fn open(&self, path: &Path) -> Result<Box<dyn FsFile>, ssh2::Error> {
+            Ok(Box::new(Cursor::new(self.get(path)?.1)))
+        }
+        fn unlink(&self, path: &Path) -> Result<(), ssh2::Error> {
+            self.get(path)?;
+            self.entries.lock().unwrap().remove(path);
+            Ok(())
+        }
+        fn rename(&self, from: &Path, to: &Path) -> Result<(), ssh2::Error> {
+            let entry = self.get(from)?;
+            let mut entries = self.entries.lock().unwrap();
+            entries.remove(from);
+            entries.insert(to.to_path_buf(), entry);
+            Ok(())
+        }
+    }
+
+    fn stand_in_file_system() -> SftpFileSystem {
+        SftpFileSystem {
+            address: "user@stand-in:22".to_string(),
+            sftp: Box::new(StandInSession::with_entries(&[
+                ("/data", S_IFDIR, b""),
+                ("/data/photo.jpg", S_IFREG, b"photo"),
+                ("/data/link.jpg", S_IFLNK, b""),
+                ("/data/folder", S_IFDIR, b""),
+            ])),
+            _session: None,
+        }
+    }
+
+    #[test]
+    fn test_sftp_read_dir_and_metadata() {
+        let file_system = stand_in_file_system();
+
+        let entries: Vec<_> = file_system
+            .read_dir(Path::new("/data"))
+            .unwrap()
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (entry.path, entry.entry_type)
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (PathBuf::from("/data/folder"), FsEntryType::Dir),
+                (PathBuf::from("/data/link.jpg"), FsEntryType::Symlink),
+                (PathBuf::from("/data/photo.jpg"), FsEntryType::File),
+            ]
+        );
+
+        let metadata = file_system.metadata(Path::new("/data/photo.jpg")).unwrap();
+        assert_eq!((metadata.entry_type, metadata.len), (FsEntryType::File, 5));
+        assert_eq!(metadata.modified, Ok(UNIX_EPOCH + Duration::from_secs(1_000)));
+        assert_eq!(file_system.metadata(Path::new("/data/link.jpg")).unwrap().entry_type, FsEntryType::File);
+        assert_eq!(file_system.symlink_metadata(Path::new("/data/link.jpg")).unwrap().entry_type, FsEntryType::Symlink);
+
+        assert_eq!(file_system.read_dir(Path::new("/missing")).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(file_system.metadata(Path::new("/missing")).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_sftp_open_remove_and_rename() {
+        let file_system = stand_in_file_system();
+
+        let mut content = String::new();
+        file_system.open(Path::new("/data/photo.jpg")).unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "photo");
+
+        file_system.rename(Path::new("/data/photo.jpg"), Path::new("/data/renamed.jpg")).unwrap();
+        assert_eq!(file_system.metadata(Path::new("/data/photo.jpg")).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(file_system.metadata(Path::new("/data/renamed.jpg")).unwrap().len, 5);
+
+        file_system.remove_file(Path::new("/data/renamed.jpg")).unwrap();
+        assert_eq!(file_system.open(Path::new("/data/renamed.jpg")).err().unwrap().kind(), io::ErrorKind::NotFound);
+        assert_eq!(file_system.remove_file(Path::new("/data/renamed.jpg")).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        let error = file_system.hard_link(Path::new("/data/folder"), Path::new("/data/link")).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_sftp_connect_reports_server_errors() {
+        // Server, which closes connection instead of sending SSH banner
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || drop(listener.accept().unwrap()));
+
+        let connection = SftpConnection::from_address(&format!("user@127.0.0.1:{port}")).unwrap();
+        let error = SftpFileSystem::connect(&connection).unwrap_err();
+        assert!(error.starts_with(&format!("Cannot connect to SFTP server \"user@127.0.0.1:{port}\"")), "{error}");
+        server.join().unwrap();
+
+        // Nothing listens on port of closed listener
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let connection = SftpConnection::from_address(&format!("user@127.0.0.1:{port}")).unwrap();
+        SftpFileSystem::connect(&connection).unwrap_err();
+    }
+
+    #[test]
+    fn test_sftp_host_key_must_be_known() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let known_hosts_file = temp_dir.path().join("known_hosts");
+        std::fs::write(
+            &known_hosts_file,
+            "[example.com]:2222 ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIAEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEB\n",
+        )
+        .unwrap();
+        let key_blob = |key_byte: u8| {
+            let mut blob = b"\0\0\0\x0bssh-ed25519\0\0\0\x20".to_vec();
+            blob.extend([key_byte; 32]);
+            blob
+        };
+
+        let session = Session::new().unwrap();
+        let mut known_hosts = session.known_hosts().unwrap();
+        known_hosts.read_file(&known_hosts_file, KnownHostFileKind::OpenSSH).unwrap();
+        let check = |host: &str, port: u16, key_byte: u8| host_key_check_result(known_hosts.check_port(host, port, &key_blob(key_byte)), &known_hosts_file);
+
+        check("example.com", 2222, 1).unwrap();
+        assert!(check("example.com", 2222, 2).unwrap_err().contains("doesn't match"));
+        assert!(check("other.com", 2222, 1).unwrap_err().contains("is not present"));
+        assert!(check("example.com", 22, 1).unwrap_err().contains("is not present"));
+    }
+
+    #[test]
+    fn test_sftp_connection_from_address() {
+        let connection = SftpConnection::from_address("user@example.com").unwrap();
+        assert_eq!((connection.user.as_str(), connection.host.as_str(), connection.port), ("user", "example.com", 22));
+
+        let connection = SftpConnection::from_address("user@example.com:2222").unwrap();
+        assert_eq!(connection.port, 2222);
+
+        SftpConnection::from_address("example.com").unwrap_err();
+        SftpConnection::from_address("user@example.com:port").unwrap_err();
+        SftpConnection::from_address("@example.com").unwrap_err();
+    }
+}
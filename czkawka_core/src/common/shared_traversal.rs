@@ -0,0 +1,264 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use log::debug;
+use rayon::prelude::*;
+
+use crate::common::dir_traversal::{common_get_entry_data, common_get_metadata_dir, common_get_metadata_from_path, common_read_dir, get_modified_time};
+use crate::common::extensions::Extensions;
+use crate::common::file_system::FsEntryType;
+use crate::common::model::{CheckingMethod, FileEntry, ToolType};
+use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::tool_data::CommonToolData;
+
+// Folder visited during shared traversal, with files that are directly inside it
+#[derive(Debug, Clone, Default)]
+pub struct TraversedFolder {
+    pub path: PathBuf,
+    // None for included folders
+    pub parent_path: Option<PathBuf>,
+    pub modified_date: u64,
+    // Folder contains something other than visited subfolders - files, symlinks, excluded or unreadable folders
+    pub has_content: bool,
+    pub files: Vec<FileEntry>,
+}
+
+// Result of single walk over included folders, used by multiple tools instead of walking the same tree again
+// Only filters that are the same for all tools(excluded folders and items) are applied, so every tool can still use its own extensions and size limits
+#[derive(Debug, Clone, Default)]
+pub struct SharedTraversal {
+    pub folders: Vec<TraversedFolder>,
+    // Files that were directly included, not found inside folders
+    pub included_files: Vec<FileEntry>,
+    pub recursive_search: bool,
+    pub warnings: Vec<String>,
+}
+
+impl SharedTraversal {
+    // Files from subfolders are returned only with recursive search, but subfolders are always visited, because empty folders need them
+    pub fn files(&self) -> impl Iterator<Item = &FileEntry> {
+        self.folders
+            .iter()
+            .filter(|folder| self.recursive_search || folder.parent_path.is_none())
+            .flat_map(|folder| folder.files.iter())
+            .chain(self.included_files.iter())
+    }
+
+    pub fn files_number(&self) -> usize {
+        self.files().count()
+    }
+
+    pub(crate) fn filtered_files(&self, extensions: &Extensions, minimal_file_size: u64, maximal_file_size: u64) -> Vec<FileEntry> {
+        self.files()
+            .filter(|fe| (minimal_file_size..=maximal_file_size).contains(&fe.size))
+            .filter(|fe| fe.path.file_name().is_some_and(|file_name| extensions.check_if_entry_have_valid_extension(file_name)))
+            .cloned()
+            .collect()
+    }
+}
+
+// Directories must be already optimized, otherwise the same folders may be visited multiple times
+#[fun_time(message = "collect_shared_traversal", level = "debug")]
+pub(crate) fn collect_shared_traversal(
+    common_data: &CommonToolData,
+    tool_type: ToolType,
+    stop_flag: &Arc<AtomicBool>,
+    progress_sender: Option<&Sender<ProgressData>>,
+) -> Option<SharedTraversal> {
+    let directories = &common_data.directories;
+    let excluded_items = &common_data.excluded_items;
+    let file_system = common_data.file_system.as_ref();
+
+    let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::CollectingFiles, 0, (tool_type, CheckingMethod::None), 0);
+
+    let mut shared_traversal = SharedTraversal {
+        recursive_search: common_data.recursive_search,
+        ..Default::default()
+    };
+
+    for file in &directories.included_files {
+        let Some(metadata) = common_get_metadata_from_path(file_system, file, &mut shared_traversal.warnings) else {
+            continue;
+        };
+        if metadata.is_file() && !excluded_items.is_excluded(file) {
+            progress_handler.increase_items(1);
+            shared_traversal.included_files.push(FileEntry {
+                size: metadata.len,
+                modified_date: get_modified_time(&metadata, &mut shared_traversal.warnings, file, false),
+                path: file.clone(),
+            });
+        }
+    }
+
+    let mut folders_to_check: Vec<TraversedFolder> = directories
+        .included_directories
+        .iter()
+        .map(|path| TraversedFolder {
+            path: path.clone(),
+            ..Default::default()
+        })
+        .collect();
+
+    while !folders_to_check.is_empty() {
+        if check_if_stop_received(stop_flag) {
+            progress_handler.join_thread();
+            return None;
+        }
+
+        let segments: Vec<_> = folders_to_check
+            .into_par_iter()
+            .map(|mut current_folder| {
+                let mut subfolders = Vec::new();
+                let mut warnings = Vec::new();
+
+                let Some(read_dir) = common_read_dir(file_system, &current_folder.path, &mut warnings) else {
+                    current_folder.has_content = true;
+                    return (current_folder, subfolders, warnings);
+                };
+
+                let mut counter = 0;
+                for entry in read_dir {
+                    let Some(entry_data) = common_get_entry_data(&entry, &mut warnings, &current_folder.path) else {
+                        continue;
+                    };
+                    let entry_path = entry_data.path.clone();
+
+                    if entry_data.entry_type == FsEntryType::Dir {
+                        if excluded_items.is_excluded(&entry_path) || directories.is_excluded(&entry_path) {
+                            current_folder.has_content = true;
+                            continue;
+                        }
+                        if is_on_other_filesystem(common_data, &entry_path, &mut warnings) {
+                            continue;
+                        }
+                        let Some(metadata) = common_get_metadata_dir(file_system, entry_data, &mut warnings, &entry_path) else {
+                            current_folder.has_content = true;
+                            continue;
+                        };
+                        subfolders.push(TraversedFolder {
+                            modified_date: get_modified_time(&metadata, &mut warnings, &entry_path, true),
+                            path: entry_path,
+                            parent_path: Some(current_folder.path.clone()),
+                            ..Default::default()
+                        });
+                        continue;
+                    }
+
+                    current_folder.has_content = true;
+                    if entry_data.entry_type != FsEntryType::File {
+                        continue;
+                    }
+                    counter += 1;
+                    if excluded_items.is_excluded(&entry_path) || is_on_other_filesystem(common_data, &entry_path, &mut warnings) {
+                        continue;
+                    }
+                    let Some(metadata) = common_get_metadata_dir(file_system, entry_data, &mut warnings, &entry_path) else {
+                        continue;
+                    };
+                    current_folder.files.push(FileEntry {
+                        size: metadata.len,
+                        modified_date: get_modified_time(&metadata, &mut warnings, &entry_path, false),
+                        path: entry_path,
+                    });
+                }
+                if counter > 0 {
+                    // Increase counter in batch, because usually it may be slow to add multiple times atomic value
+                    progress_handler.increase_items(counter);
+                }
+                current_folder.files.sort_by_cached_key(|fe| fe.path.to_string_lossy().to_string());
+
+                (current_folder, subfolders, warnings)
+            })
+            .collect();
+
+        let required_size = segments.iter().map(|(_, subfolders, _)| subfolders.len()).sum::<usize>();
+        folders_to_check = Vec::with_capacity(required_size);
+
+        for (folder, subfolders, warnings) in segments {
+            shared_traversal.folders.push(folder);
+            folders_to_check.extend(subfolders);
+            shared_traversal.warnings.extend(warnings);
+        }
+    }
+
+    progress_handler.join_thread();
+
+    debug!(
+        "Shared traversal visited {} folders and found {} files",
+        shared_traversal.folders.len(),
+        shared_traversal.files_number()
+    );
+
+    Some(shared_traversal)
+}
+
+#[cfg(target_family = "unix")]
+fn is_on_other_filesystem(common_data: &CommonToolData, path: &Path, warnings: &mut Vec<String>) -> bool {
+    if !common_data.directories.exclude_other_filesystems() {
+        return false;
+    }
+    match common_data.directories.is_on_other_filesystems(path) {
+        Ok(is_on_other_filesystem) => is_on_other_filesystem,
+        Err(e) => {
+            warnings.push(e);
+            false
+        }
+    }
+}
+
+#[cfg(not(target_family = "unix"))]
+fn is_on_other_filesystem(_common_data: &CommonToolData, _path: &Path, _warnings: &mut Vec<String>) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::file_system::LocalFileSystem;
+    use crate::common::tool_data::CommonData;
+    use crate::tools::empty_files::EmptyFiles;
+
+    #[test]
+    fn test_shared_traversal() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("empty/inner")).unwrap();
+        fs::create_dir_all(root.join("data/sub")).unwrap();
+        fs::create_dir_all(root.join("excluded")).unwrap();
+        fs::write(root.join("data/a.txt"), b"a").unwrap();
+        fs::write(root.join("data/sub/b.tmp"), b"").unwrap();
+        fs::write(root.join("excluded/c.txt"), b"c").unwrap();
+
+        let mut tool = EmptyFiles::new();
+        tool.set_included_paths(vec![root.to_path_buf()]);
+        tool.set_excluded_paths(vec![root.join("excluded")]);
+        tool.get_cd_mut().directories.optimize_directories(true, false, &LocalFileSystem).unwrap();
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let shared_traversal = collect_shared_traversal(tool.get_cd(), ToolType::EmptyFiles, &stop_flag, None).unwrap();
+
+        let folder = |path: PathBuf| shared_traversal.folders.iter().find(|folder| folder.path == path).unwrap();
+        assert_eq!(shared_traversal.folders.len(), 5);
+        // Excluded folder is not visited, but its parent is not empty
+        assert!(folder(root.to_path_buf()).has_content);
+        assert!(!folder(root.join("empty")).has_content);
+        assert!(!folder(root.join("empty/inner")).has_content);
+        assert_eq!(folder(root.join("empty/inner")).parent_path, Some(root.join("empty")));
+        assert_eq!(shared_traversal.files_number(), 2);
+
+        let mut extensions = Extensions::new();
+        extensions.set_allowed_extensions(vec!["txt".to_string()]);
+        extensions.set_and_validate_extensions(None).unwrap();
+        let filtered = shared_traversal.filtered_files(&extensions, 0, u64::MAX);
+        assert_eq!(filtered.iter().map(|fe| fe.path.clone()).collect::<Vec<_>>(), [root.join("data/a.txt")]);
+        assert_eq!(shared_traversal.filtered_files(&Extensions::new(), 0, 0).len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Duration;
@@ -10,16 +10,22 @@ use rayon::prelude::*;
 
 use crate::common::directories::Directories;
 use crate::common::extensions::Extensions;
+use crate::common::file_system::{FileSystem, LocalFileSystem};
 use crate::common::items::ExcludedItems;
+use crate::common::journal::{Journal, JournalAction};
+use crate::common::keep_rules::{KeepRule, KeepRules, rank_by_rules};
 use crate::common::model::{CheckingMethod, ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::check_if_stop_received;
+use crate::common::quarantine::{Quarantine, purge_quarantine};
+use crate::common::shared_traversal::SharedTraversal;
 use crate::common::traits::ResultEntry;
-use crate::common::{make_hard_link, remove_folder_if_contains_only_empty_folders, remove_single_file};
+use crate::common::{check_if_folder_contains_expected_files, make_hard_link_with_file_system, make_reflink, remove_folder_if_contains_expected_files, remove_single_file};
+use crate::flc;
 use crate::helpers::delayed_sender::DelayedSender;
 use crate::helpers::messages::Messages;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct CommonToolData {
     pub(crate) tool_type: ToolType,
     pub(crate) text_messages: Messages,
@@ -37,7 +43,14 @@ pub struct CommonToolData {
     pub(crate) use_reference_folders: bool,
     pub(crate) dry_run: bool,
     pub(crate) move_to_trash: bool,
+    pub(crate) quarantine_folder: Option<PathBuf>,
+    pub(crate) quarantine_purge_days: u32,
     pub(crate) hide_hard_links: bool,
+    pub(crate) keep_rules: KeepRules,
+    // Set when files were already collected by traversal shared with other tools, so they are only filtered instead of searched again
+    pub(crate) shared_traversal: Option<Arc<SharedTraversal>>,
+    // Filesystem on which included paths are searched and modified, local one by default
+    pub(crate) file_system: Arc<dyn FileSystem>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -61,20 +74,21 @@ pub enum DeleteItemType<T: ResultEntry + Sized + Send + Sync> {
     DeletingFiles(Vec<T>),
     DeletingFolders(Vec<T>),
     HardlinkingFiles(Vec<(T, Vec<T>)>),
+    ReflinkingFiles(Vec<(T, Vec<T>)>),
 }
 
 impl<T: ResultEntry + Sized + Send + Sync> DeleteItemType<T> {
     fn calculate_size_to_delete(&self) -> u64 {
         match &self {
             Self::DeletingFiles(items) | Self::DeletingFolders(items) => items.iter().map(|item| item.get_size()).sum(),
-            Self::HardlinkingFiles(items) => items.iter().map(|(item, _)| item.get_size()).sum(),
+            Self::HardlinkingFiles(items) | Self::ReflinkingFiles(items) => items.iter().map(|(item, _)| item.get_size()).sum(),
         }
     }
 
     fn calculate_entries_to_delete(&self) -> usize {
         match &self {
             Self::DeletingFiles(items) | Self::DeletingFolders(items) => items.len(),
-            Self::HardlinkingFiles(items) => items.iter().map(|(_original, files)| files.len()).sum(),
+            Self::HardlinkingFiles(items) | Self::ReflinkingFiles(items) => items.iter().map(|(_original, files)| files.len()).sum(),
         }
     }
 }
@@ -89,10 +103,39 @@ pub enum DeleteMethod {
     OneOldest,
     OneNewest,
     HardLink,
+    Reflink,
     AllExceptBiggest,
     AllExceptSmallest,
     OneBiggest,
     OneSmallest,
+    AllExceptBestByRules,    // Keeps first item ranked by keep rules
+    AllExceptHighestQuality, // Works only with similar images
+}
+
+const RULES_NEWEST: &[KeepRule] = &[KeepRule::Newest];
+const RULES_OLDEST: &[KeepRule] = &[KeepRule::Oldest];
+const RULES_BIGGEST: &[KeepRule] = &[KeepRule::Biggest];
+const RULES_SMALLEST: &[KeepRule] = &[KeepRule::Smallest];
+const RULES_HIGHEST_QUALITY: &[KeepRule] = &[KeepRule::HighestQuality];
+
+impl DeleteMethod {
+    pub fn works_only_with_similar_images(self) -> bool {
+        self == Self::AllExceptHighestQuality
+    }
+
+    // Simple delete methods are just predefined keep rules
+    fn get_ranking_rules(self, keep_rules: &KeepRules) -> &[KeepRule] {
+        match self {
+            Self::AllExceptNewest | Self::OneOldest => RULES_NEWEST,
+            Self::AllExceptOldest | Self::OneNewest => RULES_OLDEST,
+            Self::AllExceptBiggest | Self::OneSmallest => RULES_BIGGEST,
+            Self::AllExceptSmallest | Self::OneBiggest => RULES_SMALLEST,
+            Self::AllExceptHighestQuality => RULES_HIGHEST_QUALITY,
+            // Oldest file was always used as original file when linking
+            Self::HardLink | Self::Reflink if keep_rules.is_empty() => RULES_OLDEST,
+            Self::HardLink | Self::Reflink | Self::AllExceptBestByRules | Self::Delete | Self::None => keep_rules.get_rules(),
+        }
+    }
 }
 
 impl CommonToolData {
@@ -114,11 +157,46 @@ impl CommonToolData {
             use_reference_folders: false,
             dry_run: false,
             move_to_trash: false,
+            quarantine_folder: None,
+            quarantine_purge_days: 0,
             hide_hard_links: false,
+            keep_rules: KeepRules::default(),
+            shared_traversal: None,
+            file_system: Arc::new(LocalFileSystem),
         }
     }
 }
 
+// Items are sorted from the most to the least preferred one to keep
+fn sort_items_to_delete<T: ResultEntry>(delete_method: DeleteMethod, keep_rules: &KeepRules, items: Vec<T>) -> Vec<T> {
+    rank_by_rules(delete_method.get_ranking_rules(keep_rules), items)
+}
+
+// Selects items from each group, that should be removed with given delete method(hardlinking and reflinking is not handled here)
+#[expect(clippy::indexing_slicing)] // Safe, because input is always checked to have at least 1 element
+pub(crate) fn select_items_to_delete<T: ResultEntry + Clone>(delete_method: DeleteMethod, keep_rules: &KeepRules, groups: Vec<Vec<T>>) -> Vec<T> {
+    groups
+        .into_iter()
+        .flat_map(|values| {
+            // TODO - probably a little too much cloning, so later could be this optimized
+            let len = values.len();
+            let all_values = sort_items_to_delete(delete_method, keep_rules, values);
+            match delete_method {
+                DeleteMethod::Delete => &all_values,
+                DeleteMethod::AllExceptNewest
+                | DeleteMethod::AllExceptOldest
+                | DeleteMethod::AllExceptBiggest
+                | DeleteMethod::AllExceptSmallest
+                | DeleteMethod::AllExceptBestByRules
+                | DeleteMethod::AllExceptHighestQuality => &all_values[1..],
+                DeleteMethod::OneOldest | DeleteMethod::OneNewest | DeleteMethod::OneBiggest | DeleteMethod::OneSmallest => &all_values[(len - 1)..],
+                DeleteMethod::HardLink | DeleteMethod::Reflink | DeleteMethod::None => unreachable!("HardLink, Reflink and None should be handled before"),
+            }
+            .to_vec()
+        })
+        .collect()
+}
+
 pub trait CommonData {
     type Info;
     type Parameters;
@@ -139,6 +217,10 @@ pub trait CommonData {
     fn get_tool_type(&self) -> ToolType {
         self.get_cd().tool_type
     }
+    // Journal saves Blake3 hash of every changed file, so only such hashes of results can be reused there
+    fn has_blake3_hashes_of_whole_files(&self) -> bool {
+        false
+    }
 
     fn set_hide_hard_links(&mut self, hide_hard_links: bool) {
         self.get_cd_mut().hide_hard_links = hide_hard_links;
@@ -157,8 +239,9 @@ pub trait CommonData {
     fn set_use_cache(&mut self, use_cache: bool) {
         self.get_cd_mut().use_cache = use_cache;
     }
+    // Cache entries are identified only by path, so files from remote file systems would be mixed with local ones
     fn get_use_cache(&self) -> bool {
-        self.get_cd().use_cache
+        self.get_cd().use_cache && self.get_cd().file_system.is_local()
     }
 
     fn set_delete_outdated_cache(&mut self, delete_outdated_cache: bool) {
@@ -236,6 +319,20 @@ pub trait CommonData {
     fn get_delete_method(&self) -> DeleteMethod {
         self.get_cd().delete_method
     }
+    fn set_keep_rules(&mut self, keep_rules: KeepRules) {
+        self.get_cd_mut().keep_rules = keep_rules;
+    }
+    fn get_keep_rules(&self) -> &KeepRules {
+        &self.get_cd().keep_rules
+    }
+    // Without any rule, random item from each group would be kept
+    fn are_keep_rules_set_if_needed(&mut self) -> bool {
+        if self.get_cd().delete_method == DeleteMethod::AllExceptBestByRules && self.get_cd().keep_rules.is_empty() {
+            self.get_cd_mut().text_messages.warnings.push(flc!("core_keep_rules_empty"));
+            return false;
+        }
+        true
+    }
 
     // Only used for internal deleting - probably only useful in CLI, but not in GUI which probably uses its own delete method selection
     fn set_move_to_trash(&mut self, move_to_trash: bool) {
@@ -245,18 +342,39 @@ pub trait CommonData {
         self.get_cd().move_to_trash
     }
 
+    // When quarantine folder is set, items are moved there instead of being removed or moved to trash
+    // Sessions older than `purge_days`(0 disables purging) are permanently removed before deleting
+    fn set_quarantine(&mut self, quarantine_folder: Option<PathBuf>, purge_days: u32) {
+        self.get_cd_mut().quarantine_folder = quarantine_folder;
+        self.get_cd_mut().quarantine_purge_days = purge_days;
+    }
+    fn get_quarantine_folder(&self) -> Option<&Path> {
+        self.get_cd().quarantine_folder.as_deref()
+    }
+
+    // Paths are checked on this filesystem, so it must be set before any paths
+    fn set_file_system(&mut self, file_system: Arc<dyn FileSystem>) {
+        self.get_cd_mut().file_system = file_system;
+    }
+    fn get_file_system(&self) -> &Arc<dyn FileSystem> {
+        &self.get_cd().file_system
+    }
+
     fn set_included_paths(&mut self, included_paths: Vec<PathBuf>) {
-        let messages = self.get_cd_mut().directories.set_included_paths(included_paths);
+        let file_system = self.get_cd().file_system.clone();
+        let messages = self.get_cd_mut().directories.set_included_paths(included_paths, file_system.as_ref());
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
     }
 
     fn set_excluded_paths(&mut self, excluded_paths: Vec<PathBuf>) {
-        let messages = self.get_cd_mut().directories.set_excluded_paths(excluded_paths);
+        let file_system = self.get_cd().file_system.clone();
+        let messages = self.get_cd_mut().directories.set_excluded_paths(excluded_paths, file_system.as_ref());
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
     }
 
     fn set_reference_paths(&mut self, reference_paths: Vec<PathBuf>) {
-        let messages = self.get_cd_mut().directories.set_reference_paths(reference_paths);
+        let file_system = self.get_cd().file_system.clone();
+        let messages = self.get_cd_mut().directories.set_reference_paths(reference_paths, file_system.as_ref());
         self.get_cd_mut().text_messages.extend_with_another_messages(messages);
     }
 
@@ -282,8 +400,9 @@ pub trait CommonData {
     #[expect(clippy::result_unit_err)]
     fn prepare_items(&mut self, tool_extensions: Option<&[&str]>) -> Result<(), ()> {
         let recursive_search = self.get_cd().recursive_search;
+        let file_system = self.get_cd().file_system.clone();
         // Optimizes directories and removes recursive calls
-        match self.get_cd_mut().directories.optimize_directories(recursive_search, false) {
+        match self.get_cd_mut().directories.optimize_directories(recursive_search, false, file_system.as_ref()) {
             Ok(messages) => {
                 self.get_cd_mut().text_messages.extend_with_another_messages(messages);
             }
@@ -317,7 +436,6 @@ pub trait CommonData {
         }
     }
 
-    #[expect(clippy::indexing_slicing)] // Safe, because input is always checked to have at least 1 element
     fn delete_advanced_elements_and_add_to_messages<T: ResultEntry + Sized + Send + Sync + Clone>(
         &mut self,
         stop_flag: &Arc<AtomicBool>,
@@ -325,43 +443,35 @@ pub trait CommonData {
         files_to_process: Vec<Vec<T>>,
     ) -> WorkContinueStatus {
         let delete_method = self.get_cd().delete_method;
-        let sorting_by_size = matches!(
-            delete_method,
-            DeleteMethod::AllExceptBiggest | DeleteMethod::AllExceptSmallest | DeleteMethod::OneBiggest | DeleteMethod::OneSmallest
-        );
-        let sort_items = |mut input: Vec<T>| -> Vec<T> {
-            input.sort_unstable_by_key(if sorting_by_size { ResultEntry::get_size } else { ResultEntry::get_modified_date });
-            input
-        };
+        if !self.are_keep_rules_set_if_needed() {
+            return WorkContinueStatus::Continue;
+        }
 
-        let delete_results = if delete_method == DeleteMethod::HardLink {
+        // Read-only items cannot be removed or replaced by link, nor be used as source of it
+        // They must be removed before choosing item to keep, otherwise all removable copies could be deleted
+        let files_to_process = files_to_process
+            .into_iter()
+            .map(|values| values.into_iter().filter(|e| !e.is_read_only()).collect::<Vec<_>>())
+            .filter(|values| values.len() > 1)
+            .collect::<Vec<_>>();
+
+        let delete_results = if matches!(delete_method, DeleteMethod::HardLink | DeleteMethod::Reflink) {
             let res = files_to_process
                 .into_iter()
                 .map(|values| {
-                    let mut all_values = sort_items(values);
+                    let mut all_values = sort_items_to_delete(delete_method, &self.get_cd().keep_rules, values);
                     let original = all_values.remove(0);
                     (original, all_values)
                 })
                 .collect::<Vec<_>>();
-            self.delete_elements(stop_flag, progress_sender, DeleteItemType::HardlinkingFiles(res))
+            let delete_item_type = if delete_method == DeleteMethod::Reflink {
+                DeleteItemType::ReflinkingFiles(res)
+            } else {
+                DeleteItemType::HardlinkingFiles(res)
+            };
+            self.delete_elements(stop_flag, progress_sender, delete_item_type)
         } else {
-            let res = files_to_process
-                .into_iter()
-                .flat_map(|values| {
-                    // TODO - probably a little too much cloning, so later could be this optimized
-                    let len = values.len();
-                    let all_values = sort_items(values);
-                    match delete_method {
-                        DeleteMethod::Delete => &all_values,
-                        DeleteMethod::AllExceptNewest | DeleteMethod::AllExceptBiggest => &all_values[..(len - 1)],
-                        DeleteMethod::AllExceptOldest | DeleteMethod::AllExceptSmallest => &all_values[1..],
-                        DeleteMethod::OneOldest | DeleteMethod::OneSmallest => &all_values[..1],
-                        DeleteMethod::OneNewest | DeleteMethod::OneBiggest => &all_values[(len - 1)..],
-                        DeleteMethod::HardLink | DeleteMethod::None => unreachable!("HardLink and None should be handled before"),
-                    }
-                    .to_vec()
-                })
-                .collect::<Vec<_>>();
+            let res = select_items_to_delete(delete_method, &self.get_cd().keep_rules, files_to_process);
             self.delete_elements(stop_flag, progress_sender, DeleteItemType::DeletingFiles(res))
         };
 
@@ -386,6 +496,7 @@ pub trait CommonData {
         progress.entries_to_check = delete_item_type.calculate_entries_to_delete();
 
         let is_hardlinking = matches!(delete_item_type, DeleteItemType::HardlinkingFiles(_));
+        let is_reflinking = matches!(delete_item_type, DeleteItemType::ReflinkingFiles(_));
 
         let msg_common = format!(
             "{} items, total size: {} bytes, dry_run: {dry_run}",
@@ -394,11 +505,45 @@ pub trait CommonData {
         );
         if is_hardlinking {
             info!("Hardlinking {msg_common}");
+        } else if is_reflinking {
+            info!("Reflinking {msg_common}");
         } else {
             info!("Deleting {msg_common}");
         }
 
+        let mut delete_result = DeleteResult::default();
+        let file_system = self.get_cd().file_system.as_ref();
+        let is_deleting_folders = matches!(delete_item_type, DeleteItemType::DeletingFolders(_));
+        if !file_system.is_local() && (move_to_trash || is_reflinking || is_deleting_folders || self.get_cd().quarantine_folder.is_some()) {
+            delete_result
+                .errors
+                .push("Moving to trash or quarantine, reflinking and removing folders are supported only on local filesystem".to_string());
+            return delete_result;
+        }
+
+        let quarantine = match &self.get_cd().quarantine_folder {
+            Some(quarantine_folder) if !dry_run => {
+                if self.get_cd().quarantine_purge_days > 0 {
+                    match purge_quarantine(quarantine_folder, self.get_cd().quarantine_purge_days) {
+                        Ok(0) => {}
+                        Ok(removed_sessions) => delete_result.infos.push(format!("Purged {removed_sessions} old quarantine sessions")),
+                        Err(e) => delete_result.errors.push(e),
+                    }
+                }
+                Some(Quarantine::new(quarantine_folder))
+            }
+            _ => None,
+        };
+
         let delayed_sender = progress_sender.map(|e| DelayedSender::new(e.clone(), Duration::from_millis(200)));
+        // Journal can verify and revert only local files
+        let journal = if file_system.is_local() {
+            Journal::new(&format!("{:?}", self.get_cd().tool_type))
+        } else {
+            Journal::default()
+        };
+        let journal_delete_action = if move_to_trash { JournalAction::MoveToTrash } else { JournalAction::Delete };
+        let reuse_hashes = self.has_blake3_hashes_of_whole_files();
 
         let bytes_processed = Arc::new(std::sync::atomic::AtomicU64::new(0));
         let files_processed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
@@ -423,10 +568,33 @@ pub trait CommonData {
                         return Some(vec![(e, None)]);
                     }
 
-                    let delete_res = if matches!(delete_item_type, DeleteItemType::DeletingFiles(_)) {
-                        remove_single_file(e.get_path(), move_to_trash)
+                    let is_deleting_files = matches!(delete_item_type, DeleteItemType::DeletingFiles(_));
+                    let delete_res = if let Some(quarantine) = &quarantine {
+                        let quarantine_path = quarantine.get_quarantine_path(e.get_path());
+                        journal.record_operation_with_hash(
+                            JournalAction::Quarantine,
+                            e.get_path(),
+                            Some(&quarantine_path),
+                            e.get_hash().filter(|_| reuse_hashes),
+                            || {
+                                if !is_deleting_files {
+                                    check_if_folder_contains_expected_files(e.get_path(), e.get_folder_files())?;
+                                }
+                                quarantine.move_to_quarantine(e.get_path())
+                            },
+                        )
                     } else {
-                        remove_folder_if_contains_only_empty_folders(e.get_path(), move_to_trash)
+                        journal.record_operation_with_hash(journal_delete_action, e.get_path(), None, e.get_hash().filter(|_| reuse_hashes), || {
+                            if is_deleting_files && !file_system.is_local() {
+                                file_system
+                                    .remove_file(e.get_path())
+                                    .map_err(|err| flc!("core_error_removing", file = e.get_path().to_string_lossy().to_string(), error = err.to_string()))
+                            } else if is_deleting_files {
+                                remove_single_file(e.get_path(), move_to_trash)
+                            } else {
+                                remove_folder_if_contains_expected_files(e.get_path(), e.get_folder_files(), move_to_trash)
+                            }
+                        })
                     };
 
                     match delete_res {
@@ -437,7 +605,7 @@ pub trait CommonData {
                 .while_some()
                 .flatten()
                 .collect::<Vec<_>>(),
-            DeleteItemType::HardlinkingFiles(ref items) => items
+            DeleteItemType::HardlinkingFiles(ref items) | DeleteItemType::ReflinkingFiles(ref items) => items
                 .into_par_iter()
                 .map(|(original, files)| {
                     if check_if_stop_received(stop_flag) {
@@ -459,10 +627,33 @@ pub trait CommonData {
                     let res = files
                         .iter()
                         .map(|file| {
-                            let err = match make_hard_link(original.get_path(), file.get_path()) {
+                            let (link_result, action) = if is_reflinking {
+                                (
+                                    journal.record_operation_with_hash(
+                                        JournalAction::Reflink,
+                                        file.get_path(),
+                                        Some(original.get_path()),
+                                        file.get_hash().filter(|_| reuse_hashes),
+                                        || make_reflink(original.get_path(), file.get_path()),
+                                    ),
+                                    "reflink",
+                                )
+                            } else {
+                                (
+                                    journal.record_operation_with_hash(
+                                        JournalAction::HardLink,
+                                        file.get_path(),
+                                        Some(original.get_path()),
+                                        file.get_hash().filter(|_| reuse_hashes),
+                                        || make_hard_link_with_file_system(file_system, original.get_path(), file.get_path()),
+                                    ),
+                                    "hardlink",
+                                )
+                            };
+                            let err = match link_result {
                                 Ok(()) => None,
                                 Err(err) => Some(format!(
-                                    "Failed to hardlink \"{}\" to \"{}\": {err}",
+                                    "Failed to {action} \"{}\" to \"{}\": {err}",
                                     original.get_path().to_string_lossy(),
                                     file.get_path().to_string_lossy()
                                 )),
@@ -478,8 +669,6 @@ pub trait CommonData {
                 .collect::<Vec<_>>(),
         };
 
-        let mut delete_result = DeleteResult::default();
-
         for (file_entry, delete_err) in res {
             if let Some(err) = delete_err {
                 delete_result.errors.push(err);
@@ -492,6 +681,8 @@ pub trait CommonData {
                             file_entry.get_path().to_string_lossy(),
                             file_entry.get_path().to_string_lossy()
                         ));
+                    } else if is_reflinking {
+                        delete_result.infos.push(format!("Would reflink: \"{}\"", file_entry.get_path().to_string_lossy()));
                     } else {
                         delete_result.infos.push(format!("Would delete: \"{}\"", file_entry.get_path().to_string_lossy()));
                     }
@@ -502,8 +693,13 @@ pub trait CommonData {
         }
 
         if !dry_run {
-            let action = if is_hardlinking { "hardlink" } else { "delete" };
-            let action2 = if is_hardlinking { "hardlinked" } else { "deleted" };
+            let (action, action2) = if is_hardlinking {
+                ("hardlink", "hardlinked")
+            } else if is_reflinking {
+                ("reflink", "reflinked")
+            } else {
+                ("delete", "deleted")
+            };
             info!(
                 "{} items {action2}, {} gained, {} failed to {action}",
                 delete_result.deleted_files,
@@ -557,7 +753,9 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::common::cache::tests::setup_cache_path;
     use crate::common::model::FileEntry;
+    use crate::tools::duplicate::DuplicateEntry;
 
     // Mock implementation for testing
     struct MockTool {
@@ -583,6 +781,8 @@ mod tests {
 
     impl MockTool {
         fn new() -> Self {
+            // Deleting files writes journal into cache folder
+            setup_cache_path();
             Self {
                 common_data: CommonToolData::new(ToolType::Duplicate),
             }
@@ -642,6 +842,35 @@ mod tests {
         ]);
         assert_eq!(hardlink_files.calculate_size_to_delete(), 400);
         assert_eq!(hardlink_files.calculate_entries_to_delete(), 3);
+
+        let reflink_files = DeleteItemType::ReflinkingFiles(vec![(files[0].clone(), vec![files[1].clone(), files[2].clone()])]);
+        assert_eq!(reflink_files.calculate_size_to_delete(), 100);
+        assert_eq!(reflink_files.calculate_entries_to_delete(), 2);
+    }
+
+    #[test]
+    fn test_delete_elements_reflink_keeps_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let original = temp_dir.path().join("original.txt");
+        let duplicate = temp_dir.path().join("duplicate.txt");
+        fs::write(&original, "same content").unwrap();
+        fs::write(&duplicate, "same content").unwrap();
+
+        let entry = |path: &PathBuf| FileEntry {
+            path: path.clone(),
+            size: 12,
+            modified_date: 1,
+        };
+
+        let tool = MockTool::new();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let delete_result = tool.delete_elements(&stop_flag, None, DeleteItemType::ReflinkingFiles(vec![(entry(&original), vec![entry(&duplicate)])]));
+
+        // Depending on filesystem, reflink may be unsupported, but in both cases files must stay untouched
+        assert_eq!(delete_result.deleted_files + delete_result.failed_to_delete_files, 1);
+        assert_eq!(delete_result.errors.len(), delete_result.failed_to_delete_files);
+        assert_eq!(fs::read_to_string(&original).unwrap(), "same content");
+        assert_eq!(fs::read_to_string(&duplicate).unwrap(), "same content");
     }
 
     #[test]
@@ -858,6 +1087,43 @@ mod tests {
         assert!(file3.exists(), "Newest file should be kept");
     }
 
+    #[test]
+    fn test_delete_advanced_elements_skips_read_only_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let file1 = temp_dir.path().join("file1.txt");
+        let file2 = temp_dir.path().join("file2.txt");
+        let file3 = temp_dir.path().join("file3.txt");
+        fs::write(&file1, "a").unwrap();
+        fs::write(&file2, "a").unwrap();
+        fs::write(&file3, "a").unwrap();
+
+        let entry = |path: &PathBuf, modified_date: u64, archive_path: Option<PathBuf>| DuplicateEntry {
+            path: path.clone(),
+            size: 1,
+            modified_date,
+            hash: "hash".to_string(),
+            archive_path,
+            from_reference_index: false,
+        };
+        // Entries inside archives are the newest ones, so they would be kept, if were not removed before choosing file to keep
+        let archive = temp_dir.path().join("archive.zip");
+        let files_groups = vec![
+            vec![entry(&archive.join("file.txt"), 10, Some(archive.clone())), entry(&file1, 1, None), entry(&file2, 2, None)],
+            vec![entry(&archive.join("other.txt"), 10, Some(archive.clone())), entry(&file3, 1, None)],
+        ];
+
+        let mut tool = MockTool::new();
+        tool.common_data.delete_method = DeleteMethod::AllExceptNewest;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let status = tool.delete_advanced_elements_and_add_to_messages(&stop_flag, None, files_groups);
+
+        assert_eq!(status, WorkContinueStatus::Continue);
+        assert!(!file1.exists(), "Older file should be deleted");
+        assert!(file2.exists(), "Newest file outside archive should be kept");
+        assert!(file3.exists(), "Only file outside archive should be kept");
+    }
+
     #[test]
     fn test_delete_advanced_elements_all_except_oldest() {
         let temp_dir = TempDir::new().unwrap();
@@ -1090,6 +1356,112 @@ mod tests {
         assert!(!file2.exists(), "All files should be deleted");
     }
 
+    #[test]
+    fn test_delete_advanced_elements_by_keep_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("archive")).unwrap();
+        let file1 = temp_dir.path().join("Copy of file.txt");
+        let file2 = temp_dir.path().join("archive").join("file.txt");
+        let file3 = temp_dir.path().join("file.txt");
+        for file in [&file1, &file2, &file3] {
+            fs::write(file, "a").unwrap();
+        }
+
+        let files_group = vec![
+            [&file1, &file2, &file3]
+                .into_iter()
+                .map(|path| FileEntry {
+                    path: path.clone(),
+                    size: 1,
+                    modified_date: 1,
+                })
+                .collect::<Vec<_>>(),
+        ];
+
+        let mut tool = MockTool::new();
+        tool.common_data.delete_method = DeleteMethod::AllExceptBestByRules;
+        tool.set_keep_rules(format!("not-contains:copy of,in:{}", temp_dir.path().join("archive").to_string_lossy()).parse().unwrap());
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let status = tool.delete_advanced_elements_and_add_to_messages(&stop_flag, None, files_group);
+
+        assert_eq!(status, WorkContinueStatus::Continue, "Should continue");
+        assert!(!file1.exists(), "File with \"Copy of\" in name should be deleted");
+        assert!(file2.exists(), "File inside archive should be kept");
+        assert!(!file3.exists(), "File outside archive should be deleted");
+    }
+
+    #[test]
+    fn test_delete_elements_to_quarantine() {
+        let temp_dir = TempDir::new().unwrap();
+        let quarantine_folder = temp_dir.path().join("quarantine");
+        let file1 = temp_dir.path().join("file1.txt");
+        let file2 = temp_dir.path().join("file2.txt");
+        fs::write(&file1, "a").unwrap();
+        fs::write(&file2, "a").unwrap();
+
+        let files_group = vec![vec![
+            FileEntry {
+                path: file1.clone(),
+                size: 1,
+                modified_date: 1,
+            },
+            FileEntry {
+                path: file2.clone(),
+                size: 1,
+                modified_date: 2,
+            },
+        ]];
+
+        let mut tool = MockTool::new();
+        tool.common_data.delete_method = DeleteMethod::AllExceptNewest;
+        tool.set_quarantine(Some(quarantine_folder.clone()), 30);
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let status = tool.delete_advanced_elements_and_add_to_messages(&stop_flag, None, files_group);
+
+        assert_eq!(status, WorkContinueStatus::Continue, "Should continue");
+        assert!(!file1.exists(), "Older file should be moved to quarantine");
+        assert!(file2.exists(), "Newest file should be kept");
+
+        let sessions = crate::common::quarantine::list_quarantine_sessions(&quarantine_folder).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].entries[0].original_path, file1);
+        assert_eq!(fs::read_to_string(&sessions[0].entries[0].quarantine_path).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_delete_advanced_elements_by_keep_rules_without_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let file1 = temp_dir.path().join("file1.txt");
+        let file2 = temp_dir.path().join("file2.txt");
+        fs::write(&file1, "a").unwrap();
+        fs::write(&file2, "a").unwrap();
+
+        let files_group = vec![vec![
+            FileEntry {
+                path: file1.clone(),
+                size: 1,
+                modified_date: 1,
+            },
+            FileEntry {
+                path: file2.clone(),
+                size: 1,
+                modified_date: 2,
+            },
+        ]];
+
+        let mut tool = MockTool::new();
+        tool.common_data.delete_method = DeleteMethod::AllExceptBestByRules;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let status = tool.delete_advanced_elements_and_add_to_messages(&stop_flag, None, files_group);
+
+        assert_eq!(status, WorkContinueStatus::Continue, "Should continue");
+        assert!(file1.exists() && file2.exists(), "Nothing should be deleted without rules");
+        assert_eq!(tool.common_data.text_messages.warnings.len(), 1);
+    }
+
     #[test]
     fn test_delete_advanced_elements_multiple_groups() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,6 +1,7 @@
+use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
@@ -8,9 +9,16 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use serde::Serialize;
 
+#[cfg(feature = "export")]
+use crate::common::export::{save_results_as_sqlite, write_results_as_csv};
+use crate::common::import::ImportSummary;
 use crate::common::model::WorkContinueStatus;
+#[cfg(feature = "export")]
+use crate::common::ndjson::NdjsonCollector;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::CommonData;
+use crate::common::watch::WatchUpdate;
 
 pub trait DebugPrint {
     fn debug_print(&self);
@@ -104,6 +112,33 @@ pub trait PrintResults: CommonData {
         Ok(())
     }
 
+    // Results are written as groups or entries, depending on tool
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()>;
+
+    #[fun_time(message = "print_results_as_ndjson", level = "debug")]
+    fn print_results_as_ndjson<T: NdjsonOutput>(&self, writer: T) -> std::io::Result<T> {
+        let mut ndjson_writer = NdjsonWriter::new(writer, self.get_cd().tool_type);
+        self.write_results_as_ndjson(&mut ndjson_writer)?;
+        ndjson_writer.write_messages(self.get_text_messages())?;
+        ndjson_writer.write_summary()?;
+        Ok(ndjson_writer.into_inner())
+    }
+
+    #[cfg(feature = "export")]
+    #[fun_time(message = "save_results_to_file_as_csv", level = "debug")]
+    fn save_results_to_file_as_csv(&self, file_name: &str) -> std::io::Result<()> {
+        let collector = self.print_results_as_ndjson(NdjsonCollector::default())?;
+        let file_handler = File::create(file_name)?;
+        write_results_as_csv(&collector.lines, BufWriter::new(file_handler))
+    }
+
+    #[cfg(feature = "export")]
+    #[fun_time(message = "save_results_to_file_as_sqlite", level = "debug")]
+    fn save_results_to_file_as_sqlite(&self, file_name: &str) -> std::io::Result<()> {
+        let collector = self.print_results_as_ndjson(NdjsonCollector::default())?;
+        save_results_as_sqlite(&collector.lines, Path::new(file_name), &self.get_cd().directories.original_included_paths)
+    }
+
     fn save_all_in_one(&self, folder: &str, base_file_name: &str) -> std::io::Result<()> {
         let pretty_name = format!("{folder}/{base_file_name}_pretty.json");
         self.save_results_to_file_as_json(&pretty_name, true)?;
@@ -111,6 +146,13 @@ pub trait PrintResults: CommonData {
         self.save_results_to_file_as_json(&compact_name, false)?;
         let txt_name = format!("{folder}/{base_file_name}.txt");
         self.print_results_to_file(&txt_name)?;
+        #[cfg(feature = "export")]
+        {
+            let csv_name = format!("{folder}/{base_file_name}.csv");
+            self.save_results_to_file_as_csv(&csv_name)?;
+            let sqlite_name = format!("{folder}/{base_file_name}.sqlite");
+            self.save_results_to_file_as_sqlite(&sqlite_name)?;
+        }
         Ok(())
     }
 }
@@ -126,14 +168,55 @@ pub trait FixingItems {
     fn fix_items(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>, fix_params: Self::FixParams);
 }
 
+pub trait ImportingResults {
+    // Loads results saved by `save_results_to_file_as_json`, so they can be deleted/moved/linked without rescanning
+    // Entries which were changed or removed since saving are skipped
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String>;
+}
+
+// Tools which results may be updated after changes of single files, without scanning all folders again
+pub trait WatchingResults {
+    // Must be called after search, prepares data needed to update results later
+    fn prepare_watching(&mut self, stop_flag: &Arc<AtomicBool>) -> Result<(), String>;
+
+    // Checks current state of changed paths and updates results
+    fn apply_watch_changes(&mut self, changed_paths: &BTreeSet<PathBuf>) -> WatchUpdate;
+
+    // Saves data collected while watching, e.g. hashes of new files
+    fn finish_watching(&mut self) {}
+}
+
 pub trait ResultEntry {
     fn get_path(&self) -> &Path;
     fn get_modified_date(&self) -> u64;
     fn get_size(&self) -> u64;
+    // Files(relative to folder) with their sizes, which folder entry should contain, when removing it
+    fn get_folder_files(&self) -> &[(PathBuf, u64)] {
+        &[]
+    }
+    // Hash of whole content, its type depends on tool settings
+    fn get_hash(&self) -> Option<&str> {
+        None
+    }
+    // Read-only entries(e.g. files inside archives) are shown in results, but are never removed or linked
+    fn is_read_only(&self) -> bool {
+        false
+    }
+    // Only music and videos know their bitrate
+    fn get_bitrate(&self) -> Option<u64> {
+        None
+    }
+    // Only similar images know their sharpness and quality
+    fn get_sharpness(&self) -> Option<f32> {
+        None
+    }
+    fn get_quality_score(&self) -> Option<f64> {
+        None
+    }
 }
 
 pub trait Search {
     fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>);
 }
 
-pub trait AllTraits: DebugPrint + PrintResults + DeletingItems + CommonData + Search {}
+pub trait AllTraits: DebugPrint + PrintResults + DeletingItems + ImportingResults + CommonData + Search {}
@@ -0,0 +1,250 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, unbounded};
+use notify::event::{AccessKind, AccessMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::common::dir_traversal::get_modified_time;
+use crate::common::file_system::FsEntryType;
+use crate::common::model::FileEntry;
+use crate::common::ndjson::NdjsonItem;
+use crate::common::progress_stop_handler::check_if_stop_received;
+use crate::common::tool_data::CommonToolData;
+
+// How often stop flag is checked, while waiting for first change
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchChangeKind {
+    // Item became part of results, e.g. new file is duplicate of already existing one
+    Added,
+    // Item is no longer part of results - it was removed, modified or lost all its duplicates
+    Removed,
+    // Item was replaced by hardlink to other file from its group
+    Hardlinked,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchEvent {
+    pub change: WatchChangeKind,
+    pub item: NdjsonItem,
+}
+
+// Result of applying single batch of changes to tool results
+#[derive(Debug, Default)]
+pub struct WatchUpdate {
+    pub events: Vec<WatchEvent>,
+    pub warnings: Vec<String>,
+}
+
+// Receives notifications about changes inside included folders(inotify on Linux)
+// Only paths are kept, because tools check current state of changed files anyway
+pub struct FolderWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<notify::Event>>,
+}
+
+impl FolderWatcher {
+    // Must be created after search, which checks and optimizes included paths
+    pub fn new(common_data: &CommonToolData) -> Result<Self, String> {
+        if !common_data.file_system.is_local() {
+            return Err("Watching folders is supported only on local filesystem".to_string());
+        }
+
+        let directories = &common_data.directories;
+        let (sender, receiver) = unbounded();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })
+        .map_err(|e| format!("Cannot start watching folders - {e}"))?;
+
+        let recursive_mode = if common_data.recursive_search {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        for folder in &directories.included_directories {
+            watcher
+                .watch(folder, recursive_mode)
+                .map_err(|e| format!("Cannot watch folder \"{}\" - {e}", folder.to_string_lossy()))?;
+        }
+        // Included files may be replaced by new files, which can be noticed only by watching their parents
+        let file_parents: BTreeSet<&Path> = directories.included_files.iter().filter_map(|file| file.parent()).collect();
+        for folder in file_parents {
+            watcher
+                .watch(folder, RecursiveMode::NonRecursive)
+                .map_err(|e| format!("Cannot watch folder \"{}\" - {e}", folder.to_string_lossy()))?;
+        }
+
+        Ok(Self { _watcher: watcher, receiver })
+    }
+
+    // Blocks until first change and then waits until no new change arrives for debounce time, so e.g. copied file is checked only once
+    // Returns None when stop flag was set
+    pub fn wait_for_changes(&self, stop_flag: &Arc<AtomicBool>, debounce: Duration) -> Option<(BTreeSet<PathBuf>, Vec<String>)> {
+        let mut changed_paths = BTreeSet::new();
+        let mut warnings = Vec::new();
+        loop {
+            if check_if_stop_received(stop_flag) {
+                return None;
+            }
+
+            let received_anything = !changed_paths.is_empty() || !warnings.is_empty();
+            match self.receiver.recv_timeout(if received_anything { debounce } else { STOP_CHECK_INTERVAL }) {
+                Ok(Ok(event)) => {
+                    // Files are opened and read also by tools itself, so only finished writes are interesting
+                    if let EventKind::Access(access_kind) = event.kind
+                        && access_kind != AccessKind::Close(AccessMode::Write)
+                    {
+                        continue;
+                    }
+                    changed_paths.extend(event.paths);
+                }
+                Ok(Err(e)) => warnings.push(format!("Error while watching folders - {e}")),
+                Err(RecvTimeoutError::Timeout) if received_anything => return Some((changed_paths, warnings)),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+}
+
+// Current state of changed paths, that are inside included folders
+#[derive(Debug, Default)]
+pub(crate) struct ChangedFiles {
+    // Existing files, also found inside new folders
+    pub(crate) files: Vec<FileEntry>,
+    // Items with these paths or inside them, no longer exist or are not files
+    pub(crate) removed_paths: Vec<PathBuf>,
+    pub(crate) warnings: Vec<String>,
+}
+
+impl ChangedFiles {
+    pub(crate) fn is_removed(&self, path: &Path) -> bool {
+        self.removed_paths.iter().any(|removed_path| path.starts_with(removed_path))
+    }
+
+    pub(crate) fn is_changed(&self, path: &Path) -> bool {
+        self.is_removed(path) || self.files.iter().any(|file| file.path == path)
+    }
+}
+
+pub(crate) fn collect_changed_files(common_data: &CommonToolData, changed_paths: &BTreeSet<PathBuf>) -> ChangedFiles {
+    let mut changed_files = ChangedFiles::default();
+    let mut paths_to_check: Vec<PathBuf> = changed_paths.iter().filter(|path| is_watched_path(common_data, path)).cloned().collect();
+
+    while let Some(path) = paths_to_check.pop() {
+        let Ok(metadata) = common_data.file_system.symlink_metadata(&path) else {
+            changed_files.removed_paths.push(path);
+            continue;
+        };
+        match metadata.entry_type {
+            FsEntryType::File => {
+                if path
+                    .file_name()
+                    .is_some_and(|file_name| common_data.extensions.check_if_entry_have_valid_extension(file_name))
+                {
+                    changed_files.files.push(FileEntry {
+                        size: metadata.len,
+                        modified_date: get_modified_time(&metadata, &mut changed_files.warnings, &path, false),
+                        path,
+                    });
+                }
+            }
+            // Folder may be moved into watched folder with files inside, which don't get separate notifications
+            FsEntryType::Dir if common_data.recursive_search => match common_data.file_system.read_dir(&path) {
+                Ok(entries) => paths_to_check.extend(entries.into_iter().flatten().map(|entry| entry.path).filter(|path| is_watched_path(common_data, path))),
+                Err(e) => changed_files.warnings.push(format!("Cannot read folder \"{}\" - {e}", path.to_string_lossy())),
+            },
+            FsEntryType::Dir => {}
+            FsEntryType::Symlink | FsEntryType::Other => changed_files.removed_paths.push(path),
+        }
+    }
+    changed_files
+}
+
+fn is_watched_path(common_data: &CommonToolData, path: &Path) -> bool {
+    let directories = &common_data.directories;
+    let is_included = directories.included_files.iter().any(|file| file == path)
+        || directories.included_directories.iter().any(|folder| {
+            if common_data.recursive_search {
+                path.starts_with(folder) && path != folder
+            } else {
+                path.parent() == Some(folder.as_path())
+            }
+        });
+    is_included && !common_data.excluded_items.is_excluded(path) && !path.ancestors().any(|ancestor| directories.is_excluded(ancestor))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::common::file_system::LocalFileSystem;
+    use crate::common::tool_data::CommonData;
+    use crate::tools::empty_files::EmptyFiles;
+
+    #[test]
+    fn test_collect_changed_files() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("moved_in/inner")).unwrap();
+        fs::create_dir_all(root.join("excluded")).unwrap();
+        fs::write(root.join("new.txt"), b"new").unwrap();
+        fs::write(root.join("moved_in/inner/a.txt"), b"a").unwrap();
+        fs::write(root.join("excluded/b.txt"), b"b").unwrap();
+
+        let mut tool = EmptyFiles::new();
+        tool.set_included_paths(vec![root.to_path_buf()]);
+        tool.set_excluded_paths(vec![root.join("excluded")]);
+        tool.set_recursive_search(true);
+        tool.get_cd_mut().directories.optimize_directories(true, false, &LocalFileSystem).unwrap();
+
+        let changed_paths = BTreeSet::from([
+            root.join("new.txt"),
+            root.join("moved_in"),
+            root.join("excluded/b.txt"),
+            root.join("removed.txt"),
+            temp_dir.path().parent().unwrap().join("outside.txt"),
+        ]);
+        let changed_files = collect_changed_files(tool.get_cd(), &changed_paths);
+
+        let mut files: Vec<_> = changed_files.files.iter().map(|file| file.path.clone()).collect();
+        files.sort();
+        assert_eq!(files, [root.join("moved_in/inner/a.txt"), root.join("new.txt")]);
+        assert_eq!(changed_files.removed_paths, [root.join("removed.txt")]);
+        assert!(changed_files.is_removed(&root.join("removed.txt")));
+        assert!(changed_files.is_changed(&root.join("new.txt")));
+        assert!(!changed_files.is_changed(&root.join("excluded/b.txt")));
+    }
+
+    #[test]
+    fn test_folder_watcher_notices_new_file() {
+        let temp_dir = tempdir().unwrap();
+        let mut tool = EmptyFiles::new();
+        tool.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        tool.get_cd_mut().directories.optimize_directories(true, false, &LocalFileSystem).unwrap();
+        let folder_watcher = FolderWatcher::new(tool.get_cd()).unwrap();
+
+        fs::write(temp_dir.path().join("new.txt"), b"").unwrap();
+        // Stop flag is set only to not block test forever, if notification is lost
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_flag_clone = stop_flag.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_secs(10));
+            stop_flag_clone.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+        let (changed_paths, warnings) = folder_watcher.wait_for_changes(&stop_flag, Duration::from_millis(100)).unwrap();
+        assert!(changed_paths.contains(&temp_dir.path().join("new.txt")));
+        assert!(warnings.is_empty());
+    }
+}
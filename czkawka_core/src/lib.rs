@@ -1,6 +1,13 @@
+pub mod api;
+
+// Internals shared with Czkawka frontends, without stable API - library users should use `api` module
+#[doc(hidden)]
 pub mod common;
+#[doc(hidden)]
 pub mod helpers;
+#[doc(hidden)]
 pub mod localizer_core;
+#[doc(hidden)]
 pub mod tools;
 
 pub mod re_exported {
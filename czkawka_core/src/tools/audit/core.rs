@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
+
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use log::debug;
+
+use crate::common::model::ToolType;
+use crate::common::progress_data::ProgressData;
+use crate::common::shared_traversal::collect_shared_traversal;
+use crate::common::tool_data::{CommonData, CommonToolData};
+use crate::common::traits::Search;
+use crate::helpers::messages::Messages;
+use crate::tools::audit::{Audit, AuditTool, Info};
+
+macro_rules! with_audit_tool {
+    ($audit_tool:expr, $tool:ident => $body:expr) => {
+        match $audit_tool {
+            AuditTool::Duplicates($tool) => $body,
+            AuditTool::EmptyFiles($tool) => $body,
+            AuditTool::EmptyFolders($tool) => $body,
+            AuditTool::Temporary($tool) => $body,
+            AuditTool::BrokenFiles($tool) => $body,
+        }
+    };
+}
+
+impl AuditTool {
+    pub fn get_tool_type(&self) -> ToolType {
+        with_audit_tool!(self, tool => tool.get_tool_type())
+    }
+
+    pub fn found_any_items(&self) -> bool {
+        with_audit_tool!(self, tool => tool.found_any_items())
+    }
+
+    pub fn get_stopped_search(&self) -> bool {
+        with_audit_tool!(self, tool => tool.get_stopped_search())
+    }
+
+    fn get_cd(&self) -> &CommonToolData {
+        with_audit_tool!(self, tool => tool.get_cd())
+    }
+
+    fn get_cd_mut(&mut self) -> &mut CommonToolData {
+        with_audit_tool!(self, tool => tool.get_cd_mut())
+    }
+
+    fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
+        with_audit_tool!(self, tool => tool.search(stop_flag, progress_sender));
+    }
+}
+
+impl Audit {
+    pub fn new() -> Self {
+        Self {
+            information: Info::default(),
+            text_messages: Messages::new(),
+            stopped_search: false,
+            tools: Vec::new(),
+        }
+    }
+
+    // Tools are run in the same order as they were added
+    pub fn add_tool(&mut self, tool: AuditTool) {
+        self.tools.push(tool);
+    }
+
+    pub fn found_any_items(&self) -> bool {
+        self.tools.iter().any(AuditTool::found_any_items)
+    }
+
+    #[fun_time(message = "audit", level = "info")]
+    pub fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
+        let start_time = Instant::now();
+
+        let () = (|| {
+            let Some(first_tool) = self.tools.first() else {
+                self.text_messages.critical = Some("No tool was selected for audit".to_string());
+                return;
+            };
+            let first_tool_type = first_tool.get_tool_type();
+            let first_common_data = first_tool.get_cd().clone();
+
+            // Messages about optimized directories are added later by every tool
+            let mut traversal_common_data = first_common_data.clone();
+            let recursive_search = traversal_common_data.recursive_search;
+            if let Err(messages) = traversal_common_data
+                .directories
+                .optimize_directories(recursive_search, false, first_common_data.file_system.as_ref())
+            {
+                self.text_messages.extend_with_another_messages(messages);
+                return;
+            }
+
+            let traversal_start_time = Instant::now();
+            let Some(shared_traversal) = collect_shared_traversal(&traversal_common_data, first_tool_type, stop_flag, progress_sender) else {
+                self.stopped_search = true;
+                return;
+            };
+            self.information.traversal_time = traversal_start_time.elapsed();
+            self.information.number_of_traversed_folders = shared_traversal.folders.len();
+            self.information.number_of_traversed_files = shared_traversal.files_number();
+            self.text_messages.warnings.extend(shared_traversal.warnings.iter().cloned());
+            self.text_messages.messages.push(format!(
+                "Traversed {} folders with {} files once for {} tools",
+                self.information.number_of_traversed_folders,
+                self.information.number_of_traversed_files,
+                self.tools.len()
+            ));
+
+            let shared_traversal = Arc::new(shared_traversal);
+            for tool in &mut self.tools {
+                let common_data = tool.get_cd_mut();
+                common_data.directories = first_common_data.directories.clone();
+                common_data.excluded_items = first_common_data.excluded_items.clone();
+                common_data.recursive_search = first_common_data.recursive_search;
+                common_data.shared_traversal = Some(shared_traversal.clone());
+
+                tool.search(stop_flag, progress_sender);
+
+                // Next searches of the same tool should read folders again
+                tool.get_cd_mut().shared_traversal = None;
+                if tool.get_stopped_search() {
+                    self.stopped_search = true;
+                    return;
+                }
+            }
+        })();
+
+        self.information.scanning_time = start_time.elapsed();
+        debug!(
+            "Audit took {:?}, including {:?} of traversal",
+            self.information.scanning_time, self.information.traversal_time
+        );
+    }
+}
@@ -0,0 +1,63 @@
+pub mod core;
+#[cfg(test)]
+mod tests;
+
+use std::time::Duration;
+
+use crate::helpers::messages::Messages;
+use crate::tools::broken_files::BrokenFiles;
+use crate::tools::duplicate::DuplicateFinder;
+use crate::tools::empty_files::EmptyFiles;
+use crate::tools::empty_folder::EmptyFolder;
+use crate::tools::temporary::Temporary;
+
+// Tools which can use files collected by shared traversal
+// Duplicate finder is boxed, because it is much bigger than other tools
+pub enum AuditTool {
+    Duplicates(Box<DuplicateFinder>),
+    EmptyFiles(EmptyFiles),
+    EmptyFolders(EmptyFolder),
+    Temporary(Temporary),
+    BrokenFiles(BrokenFiles),
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct Info {
+    pub number_of_traversed_folders: usize,
+    pub number_of_traversed_files: usize,
+    pub traversal_time: Duration,
+    pub scanning_time: Duration,
+}
+
+// Runs multiple tools on the same folders, but reads folders only once and gives every tool files that match its own filters
+// Included/excluded paths, excluded items and recursive search of first tool are used by all tools
+pub struct Audit {
+    information: Info,
+    text_messages: Messages,
+    stopped_search: bool,
+    tools: Vec<AuditTool>,
+}
+
+impl Default for Audit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Audit {
+    pub const fn get_information(&self) -> Info {
+        self.information
+    }
+
+    pub const fn get_text_messages(&self) -> &Messages {
+        &self.text_messages
+    }
+
+    pub const fn get_stopped_search(&self) -> bool {
+        self.stopped_search
+    }
+
+    pub fn get_tools(&self) -> &[AuditTool] {
+        &self.tools
+    }
+}
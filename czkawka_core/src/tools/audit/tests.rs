@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use tempfile::TempDir;
+
+use crate::common::model::{CheckingMethod, HashType};
+use crate::common::tool_data::CommonData;
+use crate::common::traits::Search;
+use crate::tools::audit::{Audit, AuditTool};
+use crate::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
+use crate::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinder, DuplicateFinderParameters};
+use crate::tools::empty_files::EmptyFiles;
+use crate::tools::empty_folder::EmptyFolder;
+use crate::tools::temporary::Temporary;
+
+fn create_test_tree(path: &Path) {
+    let test_resources = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_resources");
+    fs::create_dir_all(path.join("data/sub")).unwrap();
+    fs::create_dir_all(path.join("empty_dir/inner")).unwrap();
+    fs::create_dir_all(path.join("excluded")).unwrap();
+    fs::write(path.join("data/a.txt"), b"same content").unwrap();
+    fs::write(path.join("data/sub/b.txt"), b"same content").unwrap();
+    fs::write(path.join("data/c.log"), b"same content").unwrap();
+    fs::write(path.join("data/empty.txt"), b"").unwrap();
+    fs::write(path.join("data/sub/old.tmp"), b"temporary").unwrap();
+    fs::write(path.join("excluded/d.txt"), b"same content").unwrap();
+    fs::write(path.join("excluded/excluded.tmp"), b"temporary").unwrap();
+
+    let mut broken_image = fs::read(test_resources.join("images").join("normal.jpg")).unwrap();
+    for byte in broken_image.iter_mut().take(10) {
+        *byte = 0x11;
+    }
+    fs::write(path.join("data/broken.jpg"), broken_image).unwrap();
+}
+
+fn configure<T: CommonData>(tool: &mut T, path: &Path) {
+    tool.set_included_paths(vec![path.to_path_buf()]);
+    tool.set_excluded_paths(vec![path.join("excluded")]);
+    tool.set_recursive_search(true);
+    tool.set_use_cache(false);
+}
+
+fn create_duplicates(path: &Path) -> DuplicateFinder {
+    let params = DuplicateFinderParameters::new(
+        CheckingMethod::Hash,
+        HashType::Blake3,
+        false,
+        0,
+        0,
+        true,
+        false,
+        false,
+        None,
+        false,
+        DEFAULT_PARTIAL_HASH_SEGMENTS,
+        false,
+    );
+    let mut tool = DuplicateFinder::new(params);
+    configure(&mut tool, path);
+    // Every tool uses its own filters on shared list of files
+    tool.set_allowed_extensions(vec!["txt".to_string()]);
+    tool.set_minimal_file_size(1);
+    tool
+}
+
+fn create_simple_tools(path: &Path) -> (EmptyFiles, EmptyFolder, Temporary, BrokenFiles) {
+    let mut empty_files = EmptyFiles::new();
+    configure(&mut empty_files, path);
+    let mut empty_folders = EmptyFolder::new();
+    configure(&mut empty_folders, path);
+    let mut temporary = Temporary::new();
+    configure(&mut temporary, path);
+    let mut broken_files = BrokenFiles::new(BrokenFilesParameters::new(CheckedTypes::IMAGE));
+    configure(&mut broken_files, path);
+    (empty_files, empty_folders, temporary, broken_files)
+}
+
+#[test]
+fn test_audit_gives_same_results_as_separate_tools() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    create_test_tree(path);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+
+    let mut duplicates = create_duplicates(path);
+    let (mut empty_files, mut empty_folders, mut temporary, mut broken_files) = create_simple_tools(path);
+    duplicates.search(&stop_flag, None);
+    empty_files.search(&stop_flag, None);
+    empty_folders.search(&stop_flag, None);
+    temporary.search(&stop_flag, None);
+    broken_files.search(&stop_flag, None);
+
+    let mut audit = Audit::new();
+    let (audit_empty_files, audit_empty_folders, audit_temporary, audit_broken_files) = create_simple_tools(path);
+    audit.add_tool(AuditTool::Duplicates(Box::new(create_duplicates(path))));
+    audit.add_tool(AuditTool::EmptyFiles(audit_empty_files));
+    audit.add_tool(AuditTool::EmptyFolders(audit_empty_folders));
+    audit.add_tool(AuditTool::Temporary(audit_temporary));
+    audit.add_tool(AuditTool::BrokenFiles(audit_broken_files));
+    audit.search(&stop_flag, None);
+
+    assert!(!audit.get_stopped_search());
+    assert!(audit.get_text_messages().critical.is_none());
+    // Root, data, data/sub, empty_dir and empty_dir/inner
+    assert_eq!(audit.get_information().number_of_traversed_folders, 5);
+    assert_eq!(audit.get_information().number_of_traversed_files, 6);
+
+    let [
+        AuditTool::Duplicates(audit_duplicates),
+        AuditTool::EmptyFiles(audit_empty_files),
+        AuditTool::EmptyFolders(audit_empty_folders),
+        AuditTool::Temporary(audit_temporary),
+        AuditTool::BrokenFiles(audit_broken_files),
+    ] = audit.get_tools()
+    else {
+        panic!("Tools should be kept in the same order as they were added");
+    };
+
+    assert_eq!(audit_duplicates.get_information().number_of_duplicated_files_by_hash, 1);
+    assert_eq!(
+        audit_duplicates.get_information().number_of_duplicated_files_by_hash,
+        duplicates.get_information().number_of_duplicated_files_by_hash
+    );
+    assert_eq!(audit_empty_files.get_empty_files(), empty_files.get_empty_files());
+    assert_eq!(audit_empty_files.get_empty_files().len(), 1);
+    assert_eq!(
+        audit_empty_folders.get_empty_folder_list().keys().collect::<Vec<_>>(),
+        empty_folders.get_empty_folder_list().keys().collect::<Vec<_>>()
+    );
+    assert_eq!(audit_empty_folders.get_empty_folder_list().len(), 1);
+    let temporary_paths = |tool: &Temporary| tool.get_temporary_files().iter().map(|fe| fe.path.clone()).collect::<Vec<_>>();
+    assert_eq!(temporary_paths(audit_temporary), temporary_paths(&temporary));
+    assert_eq!(temporary_paths(audit_temporary), [path.join("data/sub/old.tmp")]);
+    assert_eq!(audit_broken_files.get_broken_files().len(), broken_files.get_broken_files().len());
+    assert_eq!(audit_broken_files.get_broken_files().len(), 1);
+    assert!(audit.found_any_items());
+}
+
+#[test]
+fn test_audit_without_tools() {
+    let mut audit = Audit::new();
+    audit.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(audit.get_text_messages().critical.is_some());
+    assert!(!audit.found_any_items());
+}
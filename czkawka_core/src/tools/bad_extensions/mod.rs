@@ -7,13 +7,14 @@ mod workarounds;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::model::FileEntry;
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
-#[derive(Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
 pub struct BadFileEntry {
     pub path: PathBuf,
     pub modified_date: u64,
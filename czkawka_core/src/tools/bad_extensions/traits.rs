@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -6,12 +7,23 @@ use std::time::Instant;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, ImportingResults, PrintResults, Search};
 use crate::tools::bad_extensions::{BadExtensions, BadExtensionsFixParams, BadExtensionsParameters, Info};
 
+impl ImportingResults for BadExtensions {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.bad_extensions_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_files_with_bad_extension = self.bad_extensions_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for BadExtensions {}
 
 impl Search for BadExtensions {
@@ -86,6 +98,10 @@ impl PrintResults for BadExtensions {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.bad_extensions_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.bad_extensions_files)
+    }
 }
 
 impl CommonData for BadExtensions {
@@ -9,6 +9,7 @@ use log::debug;
 use rayon::prelude::*;
 
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
+use crate::common::journal::{Journal, JournalAction};
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
@@ -104,6 +105,7 @@ impl BadNames {
 
     #[fun_time(message = "fix_bad_names", level = "debug")]
     pub fn fix_bad_names(&mut self, _fix_params: NameFixerParams, stop_flag: &Arc<AtomicBool>) {
+        let journal = Journal::new("BadNames");
         let warnings: Vec<_> = mem::take(&mut self.bad_names_files)
             .into_par_iter()
             .map(|entry| {
@@ -113,7 +115,7 @@ impl BadNames {
 
                 let new_path = entry.path.with_file_name(&entry.new_name);
 
-                match fs::rename(&entry.path, &new_path) {
+                match journal.record_operation(JournalAction::Rename, &entry.path, Some(&new_path), || fs::rename(&entry.path, &new_path)) {
                     Ok(()) => Some(None),
                     Err(e) => Some(Some(format!("Failed to rename {:?}: {}", entry.path, e))),
                 }
@@ -13,6 +13,7 @@ use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
 pub struct BadNameEntry {
     pub path: PathBuf,
     pub modified_date: u64,
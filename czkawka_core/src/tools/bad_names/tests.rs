@@ -4,6 +4,7 @@ mod tests2 {
     use std::sync::Arc;
     use std::sync::atomic::AtomicBool;
 
+    use crate::common::file_system::LocalFileSystem;
     use crate::common::tool_data::CommonData;
     use crate::common::traits::Search;
     use crate::tools::bad_names::{BadNames, BadNamesParameters, NameIssues};
@@ -23,7 +24,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -47,7 +48,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -71,7 +72,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -95,7 +96,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -119,7 +120,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -143,7 +144,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: false,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -167,7 +168,7 @@ mod tests2 {
             remove_duplicated_non_alphanumeric: true,
         });
         let mut bad_names = BadNames::new(params);
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
@@ -183,7 +184,7 @@ mod tests2 {
         fs::write(&test_file, "test").unwrap();
 
         let mut bad_names = BadNames::new(BadNamesParameters::new(NameIssues::all()));
-        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+        bad_names.get_cd_mut().directories.set_included_paths(vec![temp_dir.path().to_path_buf()], &LocalFileSystem);
 
         let stop_flag = Arc::new(AtomicBool::new(false));
         bad_names.search(&stop_flag, None);
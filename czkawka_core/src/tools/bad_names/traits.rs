@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -6,13 +7,24 @@ use std::time::Instant;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, ImportingResults, PrintResults, Search};
 use crate::flc;
 use crate::tools::bad_names::{BadNames, BadNamesParameters, Info, NameFixerParams};
 
+impl ImportingResults for BadNames {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.bad_names_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_files_with_bad_names = self.bad_names_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for BadNames {}
 
 impl Search for BadNames {
@@ -79,6 +91,10 @@ impl PrintResults for BadNames {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.bad_names_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.bad_names_files)
+    }
 }
 
 impl DeletingItems for BadNames {
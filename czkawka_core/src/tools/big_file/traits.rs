@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -7,12 +8,23 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use humansize::{BINARY, format_size};
 
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::tools::big_file::{BigFile, BigFileParameters, Info, SearchMode};
 
+impl ImportingResults for BigFile {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.big_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_real_files = self.big_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for BigFile {}
 
 impl DeletingItems for BigFile {
@@ -70,6 +82,10 @@ impl PrintResults for BigFile {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.big_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.big_files)
+    }
 }
 
 impl Search for BigFile {
@@ -16,6 +16,7 @@ use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
 pub struct BrokenEntry {
     pub path: PathBuf,
     pub modified_date: u64,
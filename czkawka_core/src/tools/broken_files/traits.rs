@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -8,13 +9,24 @@ use fun_time::fun_time;
 
 use crate::common::consts::{AUDIO_FILES_EXTENSIONS, IMAGE_RS_BROKEN_FILES_EXTENSIONS, PDF_FILES_EXTENSIONS, VIDEO_FILES_EXTENSIONS, ZIP_FILES_EXTENSIONS};
 use crate::common::ffmpeg_utils::check_if_ffprobe_ffmpeg_exists;
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::flc;
 use crate::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes, Info};
 
+impl ImportingResults for BrokenFiles {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.broken_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_broken_files = self.broken_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for BrokenFiles {}
 
 impl Search for BrokenFiles {
@@ -100,6 +112,10 @@ impl PrintResults for BrokenFiles {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.broken_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.broken_files)
+    }
 }
 impl DeletingItems for BrokenFiles {
     #[fun_time(message = "delete_files", level = "debug")]
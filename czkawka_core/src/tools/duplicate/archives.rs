@@ -0,0 +1,302 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use flate2::read::GzDecoder;
+use fun_time::fun_time;
+use rayon::prelude::*;
+
+use crate::common::file_system::FileSystem;
+use crate::common::model::{CheckingMethod, FileEntry, HashType, WorkContinueStatus};
+use crate::common::progress_stop_handler::check_if_stop_received;
+use crate::flc;
+use crate::tools::duplicate::{DuplicateEntry, DuplicateFinder, PREHASHING_BUFFER_SIZE, THREAD_BUFFER};
+
+// Separates path of archive from path of file inside it, e.g. "archive.zip!/inner/path"
+pub const ARCHIVE_PATH_SEPARATOR: &str = "!/";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArchiveType {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+fn get_archive_type(path: &Path) -> Option<ArchiveType> {
+    let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+    if file_name.ends_with(".zip") {
+        Some(ArchiveType::Zip)
+    } else if file_name.ends_with(".tar") {
+        Some(ArchiveType::Tar)
+    } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        Some(ArchiveType::TarGz)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn is_supported_archive(path: &Path) -> bool {
+    get_archive_type(path).is_some()
+}
+
+fn create_archive_entry(archive: &FileEntry, inner_path: &str, size: u64) -> DuplicateEntry {
+    DuplicateEntry {
+        path: PathBuf::from(format!("{}{ARCHIVE_PATH_SEPARATOR}{inner_path}", archive.path.to_string_lossy())),
+        // Files inside archive are changed only together with archive
+        modified_date: archive.modified_date,
+        size,
+        hash: String::new(),
+        archive_path: Some(archive.path.clone()),
+        from_reference_index: false,
+    }
+}
+
+fn open_tar_archive(file_system: &dyn FileSystem, path: &Path, archive_type: ArchiveType) -> io::Result<tar::Archive<Box<dyn Read>>> {
+    let file = BufReader::new(file_system.open(path)?);
+    let reader: Box<dyn Read> = if archive_type == ArchiveType::TarGz {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+// Tar archives don't have central directory like zip, so whole archive needs to be read(and decompressed) to list files
+pub(crate) fn list_archive_entries(file_system: &dyn FileSystem, archive: &FileEntry) -> Result<Vec<DuplicateEntry>, String> {
+    let map_error = |e: &dyn ToString| flc!("core_cannot_read_archive", file = archive.path.to_string_lossy(), reason = e.to_string());
+    let Some(archive_type) = get_archive_type(&archive.path) else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    if archive_type == ArchiveType::Zip {
+        let file = file_system.open(&archive.path).map_err(|e| map_error(&e))?;
+        let mut zip_archive = zip::ZipArchive::new(BufReader::new(file)).map_err(|e| map_error(&e))?;
+        for idx in 0..zip_archive.len() {
+            let zip_file = zip_archive.by_index_raw(idx).map_err(|e| map_error(&e))?;
+            // Encrypted files cannot be read without password
+            if zip_file.is_file() && !zip_file.encrypted() {
+                entries.push(create_archive_entry(archive, zip_file.name(), zip_file.size()));
+            }
+        }
+    } else {
+        let mut tar_archive = open_tar_archive(file_system, &archive.path, archive_type).map_err(|e| map_error(&e))?;
+        for tar_entry in tar_archive.entries().map_err(|e| map_error(&e))? {
+            let tar_entry = tar_entry.map_err(|e| map_error(&e))?;
+            if tar_entry.header().entry_type().is_file() {
+                let inner_path = tar_entry.path().map_err(|e| map_error(&e))?.to_string_lossy().to_string();
+                entries.push(create_archive_entry(archive, &inner_path, tar_entry.size()));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+// Gives access to content of normal file or file inside archive
+pub(crate) fn with_entry_reader<T>(file_system: &dyn FileSystem, file_entry: &DuplicateEntry, reader_function: impl FnOnce(&mut dyn Read) -> T) -> io::Result<T> {
+    let Some(archive_path) = &file_entry.archive_path else {
+        let mut file = file_system.open(&file_entry.path)?;
+        return Ok(reader_function(&mut *file));
+    };
+
+    let not_found = || io::Error::new(io::ErrorKind::NotFound, "file not found inside archive");
+    let path = file_entry.path.to_string_lossy();
+    let inner_path = path
+        .strip_prefix(archive_path.to_string_lossy().as_ref())
+        .and_then(|path| path.strip_prefix(ARCHIVE_PATH_SEPARATOR))
+        .ok_or_else(not_found)?;
+    let archive_type = get_archive_type(archive_path).ok_or_else(not_found)?;
+
+    if archive_type == ArchiveType::Zip {
+        let mut zip_archive = zip::ZipArchive::new(BufReader::new(file_system.open(archive_path)?)).map_err(io::Error::other)?;
+        let mut zip_file = zip_archive.by_name(inner_path).map_err(io::Error::other)?;
+        return Ok(reader_function(&mut zip_file));
+    }
+
+    let mut tar_archive = open_tar_archive(file_system, archive_path, archive_type)?;
+    for tar_entry in tar_archive.entries()? {
+        let mut tar_entry = tar_entry?;
+        if tar_entry.header().entry_type().is_file() && tar_entry.path()?.to_string_lossy() == inner_path {
+            return Ok(reader_function(&mut tar_entry));
+        }
+    }
+    Err(not_found())
+}
+
+// Prehash and full hash of file inside tar archive, both calculated during same read of archive
+#[derive(Debug, Clone)]
+pub(crate) struct ArchiveEntryHashes {
+    pub(crate) prehash: String,
+    pub(crate) hash: String,
+}
+
+fn is_inside_tar_archive(file_entry: &DuplicateEntry) -> bool {
+    file_entry
+        .archive_path
+        .as_deref()
+        .and_then(get_archive_type)
+        .is_some_and(|archive_type| archive_type != ArchiveType::Zip)
+}
+
+fn hash_tar_entry(reader: &mut dyn Read, buffer: &mut [u8], hash_type: HashType, stop_flag: &Arc<AtomicBool>) -> io::Result<Option<ArchiveEntryHashes>> {
+    let prehasher = &mut *hash_type.hasher();
+    let hasher = &mut *hash_type.hasher();
+    let mut prehashed_bytes = 0;
+    loop {
+        let n = match reader.read(buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let data = buffer.get(..n).unwrap_or_default();
+        let prehash_part = data.get(..n.min(PREHASHING_BUFFER_SIZE as usize - prehashed_bytes)).unwrap_or_default();
+        prehasher.update(prehash_part);
+        prehashed_bytes += prehash_part.len();
+        hasher.update(data);
+        if check_if_stop_received(stop_flag) {
+            return Ok(None);
+        }
+    }
+    Ok(Some(ArchiveEntryHashes {
+        prehash: prehasher.finalize(),
+        hash: hasher.finalize(),
+    }))
+}
+
+// Returns None when stopped, files not found in archive are left in wanted_files
+fn read_tar_archive_hashes(
+    file_system: &dyn FileSystem,
+    archive_path: &Path,
+    wanted_files: &mut HashMap<String, PathBuf>,
+    hash_type: HashType,
+    hashes: &mut Vec<(PathBuf, Result<ArchiveEntryHashes, String>)>,
+    stop_flag: &Arc<AtomicBool>,
+) -> io::Result<Option<()>> {
+    let archive_type = get_archive_type(archive_path).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unsupported archive"))?;
+    let mut tar_archive = open_tar_archive(file_system, archive_path, archive_type)?;
+    for tar_entry in tar_archive.entries()? {
+        let mut tar_entry = tar_entry?;
+        if !tar_entry.header().entry_type().is_file() {
+            continue;
+        }
+        // When archive contains same path multiple times, first file is used, same as in with_entry_reader
+        let Some(path) = wanted_files.remove(tar_entry.path()?.to_string_lossy().as_ref()) else {
+            continue;
+        };
+        let hash_result = THREAD_BUFFER.with_borrow_mut(|buffer| hash_tar_entry(&mut tar_entry, buffer, hash_type, stop_flag));
+        match hash_result {
+            Ok(Some(entry_hashes)) => hashes.push((path, Ok(entry_hashes))),
+            Ok(None) => return Ok(None),
+            Err(e) => hashes.push((
+                path.clone(),
+                Err(flc!("core_error_checking_hash_of_file", file = path.to_string_lossy(), reason = e.to_string())),
+            )),
+        }
+        if wanted_files.is_empty() {
+            break;
+        }
+    }
+    Ok(Some(()))
+}
+
+// Tar archives can only be read from start, so opening archive separately for every file inside would decompress it again and again
+// Instead all needed files from archive are hashed during single read
+// Returns None when stopped
+pub(crate) fn hash_files_from_tar_archives<'a>(
+    file_system: &dyn FileSystem,
+    file_entries: impl Iterator<Item = &'a DuplicateEntry>,
+    hash_type: HashType,
+    stop_flag: &Arc<AtomicBool>,
+) -> Option<HashMap<PathBuf, Result<ArchiveEntryHashes, String>>> {
+    let mut files_by_archive: BTreeMap<&Path, HashMap<String, PathBuf>> = BTreeMap::new();
+    for file_entry in file_entries.filter(|file_entry| is_inside_tar_archive(file_entry)) {
+        let Some(archive_path) = &file_entry.archive_path else {
+            continue;
+        };
+        let path = file_entry.path.to_string_lossy();
+        if let Some(inner_path) = path
+            .strip_prefix(archive_path.to_string_lossy().as_ref())
+            .and_then(|path| path.strip_prefix(ARCHIVE_PATH_SEPARATOR))
+        {
+            files_by_archive.entry(archive_path).or_default().insert(inner_path.to_string(), file_entry.path.clone());
+        }
+    }
+
+    let results: Vec<Vec<(PathBuf, Result<ArchiveEntryHashes, String>)>> = files_by_archive
+        .into_par_iter()
+        .map(|(archive_path, mut wanted_files)| {
+            let mut hashes = Vec::new();
+            let reason = match read_tar_archive_hashes(file_system, archive_path, &mut wanted_files, hash_type, &mut hashes, stop_flag) {
+                Ok(Some(())) => "file not found inside archive".to_string(),
+                Ok(None) => return None,
+                Err(e) => e.to_string(),
+            };
+            for path in wanted_files.into_values() {
+                let error = flc!("core_unable_check_hash_of_file", file = path.to_string_lossy(), reason = reason.clone());
+                hashes.push((path, Err(error)));
+            }
+            Some(hashes)
+        })
+        .while_some()
+        .collect();
+
+    if check_if_stop_received(stop_flag) {
+        return None;
+    }
+    Some(results.into_iter().flatten().collect())
+}
+
+impl DuplicateFinder {
+    pub(crate) fn check_archives_settings(&mut self) {
+        if self.params.check_archives && self.params.check_method != CheckingMethod::Hash {
+            self.common_data.text_messages.warnings.push(flc!("core_archives_only_hash_mode"));
+            self.params.check_archives = false;
+        }
+    }
+
+    // Files from tar archives, which don't have hashes yet, are hashed during single read of each archive
+    pub(crate) fn hash_tar_archive_entries<'a>(&mut self, file_entries: impl Iterator<Item = &'a DuplicateEntry>, stop_flag: &Arc<AtomicBool>) -> WorkContinueStatus {
+        if !self.get_params().check_archives {
+            return WorkContinueStatus::Continue;
+        }
+        let new_entries = file_entries.filter(|file_entry| !self.archive_entry_hashes.contains_key(&file_entry.path));
+        match hash_files_from_tar_archives(self.common_data.file_system.as_ref(), new_entries, self.get_params().hash_type, stop_flag) {
+            Some(hashes) => {
+                self.archive_entry_hashes.extend(hashes);
+                WorkContinueStatus::Continue
+            }
+            None => WorkContinueStatus::Stop,
+        }
+    }
+
+    // Files inside archives, grouped by size
+    #[fun_time(message = "collect_archive_entries", level = "debug")]
+    pub(crate) fn collect_archive_entries(&mut self, grouped_file_entries: &[(u64, Vec<FileEntry>)]) -> BTreeMap<u64, Vec<DuplicateEntry>> {
+        let mut archive_entries: BTreeMap<u64, Vec<DuplicateEntry>> = BTreeMap::new();
+        if !self.get_params().check_archives {
+            return archive_entries;
+        }
+
+        let file_system = self.common_data.file_system.as_ref();
+        let results: Vec<_> = grouped_file_entries
+            .par_iter()
+            .flat_map(|(_size, files)| files)
+            .filter(|file| is_supported_archive(&file.path))
+            .map(|file| list_archive_entries(file_system, file))
+            .collect();
+        for result in results {
+            match result {
+                Ok(entries) => {
+                    for entry in entries {
+                        archive_entries.entry(entry.size).or_default().push(entry);
+                    }
+                }
+                Err(e) => self.common_data.text_messages.warnings.push(e),
+            }
+        }
+        archive_entries
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 use std::{mem, thread};
 
@@ -13,14 +13,19 @@ use log::debug;
 use rayon::prelude::*;
 
 use crate::common::cache::{CACHE_DUPLICATE_VERSION, load_cache_from_file_generalized_by_size, save_cache_to_file_generalized};
-use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
+use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult, get_modified_time};
+use crate::common::file_system::FileSystem;
 use crate::common::model::{CheckingMethod, FileEntry, HashType, ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
+use crate::flc;
+use crate::tools::duplicate::reference_index::{ReferenceIndex, ReferenceIndexEntry};
+use crate::tools::duplicate::snapshot::{DuplicateScanSnapshot, SizeGroupMembers, load_duplicate_scan_snapshot, save_duplicate_scan_snapshot, size_group_members};
 use crate::tools::duplicate::{
-    DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, Info, PREHASHING_BUFFER_SIZE, THREAD_BUFFER, filter_hard_links, hash_calculation, hash_calculation_limit,
+    ComparedFile, DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, Info, PREHASHING_BUFFER_SIZE, THREAD_BUFFER, compare_files_content_with_failed_file,
+    filter_hard_links, hash_calculation, hash_calculation_limit, partial_hash_bytes_to_read, partial_hash_calculation,
 };
 
 impl DuplicateFinder {
@@ -37,9 +42,221 @@ impl DuplicateFinder {
             files_with_identical_size_referenced: Default::default(),
             files_with_identical_hashes_referenced: Default::default(),
             params,
+            scan_snapshot: None,
+            reference_index: None,
+            folder_files: Default::default(),
+            duplicated_folders: Vec::new(),
+            folder_subsets: Vec::new(),
+            archive_entry_hashes: Default::default(),
+            watch_state: None,
+            hardlink_new_duplicates: false,
         }
     }
 
+    // All settings that may change list of collected files or groups
+    fn scan_snapshot_settings(&self) -> String {
+        let params = self.get_params();
+        format!(
+            "{:?}|{:?}|{}|{}|{}|{:?}|{:?}|{:?}|{}|{}|{}|{}",
+            params.check_method,
+            params.hash_type,
+            params.partial_hash_segments,
+            params.case_sensitive_name_comparison,
+            params.verify_byte_by_byte,
+            self.common_data.directories,
+            self.common_data.extensions,
+            self.common_data.excluded_items.get_excluded_items(),
+            self.common_data.recursive_search,
+            self.common_data.minimal_file_size,
+            self.common_data.maximal_file_size,
+            self.common_data.hide_hard_links,
+        )
+    }
+
+    #[fun_time(message = "load_scan_snapshot", level = "debug")]
+    pub(crate) fn load_scan_snapshot(&mut self) {
+        // Content of reference index may change without changing its path, so snapshot could contain outdated groups
+        if !self.get_params().use_scan_snapshot
+            || !matches!(self.get_params().check_method, CheckingMethod::Size | CheckingMethod::PartialHash | CheckingMethod::Hash)
+            || self.get_params().reference_index_path.is_some()
+        {
+            return;
+        }
+        let settings = self.scan_snapshot_settings();
+        let (messages, snapshot) = load_duplicate_scan_snapshot(&settings);
+        self.get_text_messages_mut().extend_with_another_messages(messages);
+        self.scan_snapshot = Some(snapshot.unwrap_or_else(|| DuplicateScanSnapshot::new(settings)));
+    }
+
+    #[fun_time(message = "save_scan_snapshot", level = "debug")]
+    pub(crate) fn save_scan_snapshot(&mut self) {
+        if let Some(snapshot) = self.scan_snapshot.take() {
+            let messages = save_duplicate_scan_snapshot(&snapshot);
+            self.get_text_messages_mut().extend_with_another_messages(messages);
+        }
+    }
+
+    // Size groups with exactly the same files as in previous scan, don't need to be hashed again
+    #[fun_time(message = "take_unchanged_size_groups_from_snapshot", level = "debug")]
+    fn take_unchanged_size_groups_from_snapshot(&mut self) -> (BTreeMap<u64, (SizeGroupMembers, Vec<Vec<DuplicateEntry>>)>, BTreeMap<u64, SizeGroupMembers>) {
+        let mut unchanged_groups = BTreeMap::new();
+        let mut changed_groups = BTreeMap::new();
+        let Some(snapshot) = self.scan_snapshot.as_mut() else {
+            return (unchanged_groups, changed_groups);
+        };
+
+        let mut previous_hash_groups = mem::take(&mut snapshot.hash_groups);
+        for (size, entries) in mem::take(&mut self.files_with_identical_size) {
+            let members = size_group_members(&entries);
+            match previous_hash_groups.remove(&size) {
+                Some((previous_members, hash_groups)) if previous_members == members => {
+                    unchanged_groups.insert(size, (members, hash_groups));
+                }
+                _ => {
+                    changed_groups.insert(size, members);
+                    self.files_with_identical_size.insert(size, entries);
+                }
+            }
+        }
+
+        debug!(
+            "Reused {} size groups from scan snapshot, {} size groups needs to be hashed",
+            unchanged_groups.len(),
+            changed_groups.len()
+        );
+        (unchanged_groups, changed_groups)
+    }
+
+    fn update_snapshot_hash_groups(
+        &mut self,
+        unchanged_groups: BTreeMap<u64, (SizeGroupMembers, Vec<Vec<DuplicateEntry>>)>,
+        changed_groups: BTreeMap<u64, SizeGroupMembers>,
+        save_changed_groups: bool,
+    ) {
+        let Some(snapshot) = self.scan_snapshot.as_mut() else {
+            return;
+        };
+
+        for (size, members) in changed_groups {
+            // Files that failed to be hashed, must be checked again in next scan
+            if save_changed_groups {
+                let hash_groups = self.files_with_identical_hashes.get(&size).cloned().unwrap_or_default();
+                snapshot.hash_groups.insert(size, (members, hash_groups));
+            }
+        }
+        for (size, (members, hash_groups)) in unchanged_groups {
+            if !hash_groups.is_empty() {
+                self.files_with_identical_hashes.entry(size).or_default().extend(hash_groups.iter().cloned());
+            }
+            snapshot.hash_groups.insert(size, (members, hash_groups));
+        }
+    }
+
+    // Files modified in place are taken from scan snapshot with their old size, date and hash, so they may be in wrong groups
+    // Such files are skipped, to not delete or replace with link file that may not be a duplicate anymore
+    #[fun_time(message = "remove_files_changed_since_scan", level = "debug")]
+    pub(crate) fn remove_files_changed_since_scan(&mut self, groups: Vec<Vec<DuplicateEntry>>) -> Vec<Vec<DuplicateEntry>> {
+        if !self.get_params().use_scan_snapshot {
+            return groups;
+        }
+
+        let (groups, changed_files) = split_files_changed_since_scan(self.common_data.file_system.as_ref(), groups);
+        self.common_data.text_messages.warnings.extend(
+            changed_files
+                .into_iter()
+                .map(|path| flc!("core_file_changed_since_scan", name = path.to_string_lossy().to_string())),
+        );
+        groups
+    }
+
+    #[fun_time(message = "load_reference_index", level = "debug")]
+    pub(crate) fn load_reference_index(&mut self) -> Result<(), String> {
+        let Some(reference_index_path) = self.get_params().reference_index_path.clone() else {
+            return Ok(());
+        };
+        if self.get_params().check_method != CheckingMethod::Hash {
+            self.common_data.text_messages.warnings.push(flc!("core_reference_index_only_hash_mode"));
+            return Ok(());
+        }
+
+        let reference_index = ReferenceIndex::load_from_file(&reference_index_path)?;
+        debug!("Loaded reference index with {} files", reference_index.entries.len());
+
+        if self.params.hash_type != HashType::Blake3 {
+            self.common_data.text_messages.messages.push(flc!("core_reference_index_uses_blake3"));
+            self.params.hash_type = HashType::Blake3;
+        }
+        self.common_data.use_reference_folders = true;
+        self.reference_index = Some(reference_index);
+        Ok(())
+    }
+
+    // Hashes all files from included folders, to be able to use them as reference files in later scans
+    #[fun_time(message = "create_reference_index", level = "debug")]
+    pub fn create_reference_index(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> Option<ReferenceIndex> {
+        if self.prepare_items(None).is_err() {
+            return None;
+        }
+
+        let result = DirTraversalBuilder::new()
+            .common_data(&self.common_data)
+            .group_by(|fe| fe.size)
+            .stop_flag(stop_flag)
+            .progress_sender(progress_sender)
+            .checking_method(CheckingMethod::Hash)
+            .build()
+            .run();
+        let files_to_hash: Vec<DuplicateEntry> = match result {
+            DirTraversalResult::SuccessFiles { grouped_file_entries, warnings } => {
+                self.common_data.text_messages.warnings.extend(warnings);
+                grouped_file_entries.into_values().flatten().map(FileEntry::into_duplicate_entry).collect()
+            }
+            DirTraversalResult::Stopped => return None,
+        };
+
+        let progress_handler = prepare_thread_handler_common(
+            progress_sender,
+            CurrentStage::DuplicateFullHashing,
+            files_to_hash.len(),
+            (ToolType::Duplicate, CheckingMethod::Hash),
+            files_to_hash.iter().map(|e| e.size).sum::<u64>(),
+        );
+
+        let file_system = self.common_data.file_system.as_ref();
+        let hashed_files: Vec<Result<ReferenceIndexEntry, String>> = files_to_hash
+            .into_par_iter()
+            .map(|file_entry| {
+                let hash_result =
+                    THREAD_BUFFER.with_borrow_mut(|buffer| hash_calculation(file_system, buffer, &file_entry, HashType::Blake3, progress_handler.size_counter(), stop_flag));
+                progress_handler.increase_items(1);
+                match hash_result {
+                    Ok(Some(hash)) => Some(Ok(ReferenceIndexEntry {
+                        path: file_entry.path,
+                        size: file_entry.size,
+                        hash,
+                    })),
+                    Ok(None) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .while_some()
+            .collect();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return None;
+        }
+
+        let mut entries = Vec::with_capacity(hashed_files.len());
+        for hashed_file in hashed_files {
+            match hashed_file {
+                Ok(entry) => entries.push(entry),
+                Err(e) => self.common_data.text_messages.warnings.push(e),
+            }
+        }
+        Some(ReferenceIndex::new(entries))
+    }
+
     #[fun_time(message = "check_files_name", level = "debug")]
     pub(crate) fn check_files_name(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
         let group_by_func = if self.get_params().case_sensitive_name_comparison {
@@ -113,7 +330,7 @@ impl DuplicateFinder {
         }
     }
 
-    fn calculate_name_stats(&mut self) {
+    pub(crate) fn calculate_name_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for (_fe, vector) in self.files_with_identical_names_referenced.values() {
                 self.information.number_of_duplicated_files_by_name += vector.len();
@@ -207,7 +424,7 @@ impl DuplicateFinder {
         }
     }
 
-    fn calculate_size_name_stats(&mut self) {
+    pub(crate) fn calculate_size_name_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for ((size, _name), (_fe, vector)) in &self.files_with_identical_size_names_referenced {
                 self.information.number_of_duplicated_files_by_size_name += vector.len();
@@ -225,12 +442,14 @@ impl DuplicateFinder {
 
     #[fun_time(message = "check_files_size", level = "debug")]
     pub(crate) fn check_files_size(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        let check_method = self.get_params().check_method;
         let result = DirTraversalBuilder::new()
             .common_data(&self.common_data)
             .group_by(|fe| fe.size)
             .stop_flag(stop_flag)
             .progress_sender(progress_sender)
-            .checking_method(self.get_params().check_method)
+            .checking_method(check_method)
+            .folder_snapshot(self.scan_snapshot.as_mut().map(|snapshot| &mut snapshot.folders))
             .build()
             .run();
 
@@ -239,31 +458,53 @@ impl DuplicateFinder {
                 self.common_data.text_messages.warnings.extend(warnings);
 
                 let grouped_file_entries: Vec<(u64, Vec<FileEntry>)> = grouped_file_entries.into_iter().collect();
+                self.count_files_in_folders(grouped_file_entries.iter().flat_map(|(_size, files)| files));
                 let rayon_max_len = if self.get_hide_hard_links() { 3 } else { 100 };
 
+                let archive_entries = self.collect_archive_entries(&grouped_file_entries);
+                if check_if_stop_received(stop_flag) {
+                    return WorkContinueStatus::Stop;
+                }
+
+                // Single files are also needed, if they may be duplicates of files from reference index or archives
+                let reference_index_sizes = self.reference_index.as_ref().map(ReferenceIndex::sizes).unwrap_or_default();
+                let is_size_needed = |size: u64, files: usize| {
+                    let files = files + archive_entries.get(&size).map_or(0, Vec::len);
+                    files > 1 || (files == 1 && reference_index_sizes.contains(&size))
+                };
+
                 let start_time = Instant::now();
                 // We only gather files with more than 1 entry, because only this will be later used
                 let initial_size = grouped_file_entries
                     .iter()
-                    .map(|(_size, vec)| if vec.len() > 1 { vec.len() as u64 } else { 0 })
+                    .map(|(size, vec)| if is_size_needed(*size, vec.len()) { vec.len() as u64 } else { 0 })
                     .sum::<u64>();
                 self.files_with_identical_size = grouped_file_entries
                     .into_par_iter()
                     .with_max_len(rayon_max_len)
                     .filter_map(|(size, vec)| {
-                        if vec.len() <= 1 {
+                        if !is_size_needed(size, vec.len()) {
                             return None;
                         }
 
-                        let vector = if self.get_hide_hard_links() { filter_hard_links(vec) } else { vec };
+                        let vector = if self.get_hide_hard_links() {
+                            filter_hard_links(vec, self.common_data.file_system.as_ref())
+                        } else {
+                            vec
+                        };
 
-                        if vector.len() > 1 {
+                        if is_size_needed(size, vector.len()) {
                             Some((size, vector.into_iter().map(FileEntry::into_duplicate_entry).collect()))
                         } else {
                             None
                         }
                     })
                     .collect();
+                for (size, entries) in &archive_entries {
+                    if is_size_needed(*size, 0) {
+                        self.files_with_identical_size.entry(*size).or_default().extend(entries.iter().cloned());
+                    }
+                }
                 let filtered_size = self.files_with_identical_size.values().map(|v| v.len() as u64).sum::<u64>();
                 debug!(
                     "check_file_size - filtered hard links in {:?}, removed {} hardlinks ({} -> {})",
@@ -291,7 +532,7 @@ impl DuplicateFinder {
         }
     }
 
-    fn calculate_size_stats(&mut self) {
+    pub(crate) fn calculate_size_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for (size, (_fe, vector)) in &self.files_with_identical_size_referenced {
                 self.information.number_of_duplicated_files_by_size += vector.len();
@@ -340,7 +581,7 @@ impl DuplicateFinder {
         let mut records_already_cached: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
         let mut non_cached_files_to_check: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
 
-        if self.get_params().use_prehash_cache {
+        if self.get_params().use_prehash_cache && self.get_file_system().is_local() {
             let (messages, loaded_items) = load_cache_from_file_generalized_by_size::<DuplicateEntry>(
                 &get_duplicate_cache_file(self.get_params().hash_type, true),
                 self.get_delete_outdated_cache(),
@@ -369,7 +610,7 @@ impl DuplicateFinder {
         loaded_hash_map: BTreeMap<u64, Vec<DuplicateEntry>>,
         pre_hash_results: Vec<(u64, BTreeMap<String, Vec<DuplicateEntry>>, Vec<String>)>,
     ) {
-        if self.get_params().use_prehash_cache {
+        if self.get_params().use_prehash_cache && self.get_file_system().is_local() {
             // All results = records already cached + computed results
             let mut save_cache_to_hashmap: BTreeMap<String, DuplicateEntry> = Default::default();
 
@@ -384,7 +625,8 @@ impl DuplicateFinder {
             for (size, hash_map, _errors) in pre_hash_results {
                 if size >= self.get_params().minimal_prehash_cache_file_size {
                     for vec_file_entry in hash_map.into_values() {
-                        for file_entry in vec_file_entry {
+                        // Files inside archives don't exist on disk, so they cannot be cached
+                        for file_entry in vec_file_entry.into_iter().filter(|e| !e.is_read_only()) {
                             save_cache_to_hashmap.insert(file_entry.path.to_string_lossy().to_string(), file_entry);
                         }
                     }
@@ -432,10 +674,17 @@ impl DuplicateFinder {
                 .sum::<u64>(),
         );
 
+        if self.hash_tar_archive_entries(non_cached_files_to_check.values().flatten(), stop_flag) == WorkContinueStatus::Stop {
+            progress_handler.join_thread();
+            return WorkContinueStatus::Stop;
+        }
+
         // Convert to vector to be able to use with_max_len method from rayon
         let non_cached_files_to_check: Vec<(u64, Vec<DuplicateEntry>)> = non_cached_files_to_check.into_iter().collect();
 
         debug!("Starting calculating prehash");
+        let file_system = self.common_data.file_system.as_ref();
+        let archive_entry_hashes = &self.archive_entry_hashes;
         #[expect(clippy::type_complexity)]
         let pre_hash_results: Vec<(u64, BTreeMap<String, Vec<DuplicateEntry>>, Vec<String>)> = non_cached_files_to_check
             .into_par_iter()
@@ -449,7 +698,14 @@ impl DuplicateFinder {
                         if check_if_stop_received(stop_flag) {
                             return None;
                         }
-                        match hash_calculation_limit(buffer, &file_entry, check_type, PREHASHING_BUFFER_SIZE, progress_handler.size_counter()) {
+                        let hash_result = match archive_entry_hashes.get(&file_entry.path) {
+                            Some(entry_hashes) => {
+                                progress_handler.size_counter().fetch_add(PREHASHING_BUFFER_SIZE.min(size), Ordering::Relaxed);
+                                entry_hashes.as_ref().map(|entry_hashes| entry_hashes.prehash.clone()).map_err(Clone::clone)
+                            }
+                            None => hash_calculation_limit(file_system, buffer, &file_entry, check_type, PREHASHING_BUFFER_SIZE, progress_handler.size_counter()),
+                        };
+                        match hash_result {
                             Ok(hash_string) => {
                                 file_entry.hash = hash_string.clone();
                                 hashmap_with_hash.entry(hash_string).or_default().push(file_entry);
@@ -547,13 +803,10 @@ impl DuplicateFinder {
         let mut records_already_cached: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
         let mut non_cached_files_to_check: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
 
-        if self.common_data.use_cache {
+        if self.get_use_cache() {
             debug!("full_hashing_load_cache_at_start - using cache");
-            let (messages, loaded_items) = load_cache_from_file_generalized_by_size::<DuplicateEntry>(
-                &get_duplicate_cache_file(self.get_params().hash_type, false),
-                self.get_delete_outdated_cache(),
-                &pre_checked_map,
-            );
+            let (messages, loaded_items) =
+                load_cache_from_file_generalized_by_size::<DuplicateEntry>(&self.get_full_hash_cache_file(), self.get_delete_outdated_cache(), &pre_checked_map);
             self.get_text_messages_mut().extend_with_another_messages(messages);
             loaded_hash_map = loaded_items.unwrap_or_default();
 
@@ -579,7 +832,7 @@ impl DuplicateFinder {
         full_hash_results: &mut Vec<(u64, BTreeMap<String, Vec<DuplicateEntry>>, Vec<String>)>,
         loaded_hash_map: BTreeMap<u64, Vec<DuplicateEntry>>,
     ) {
-        if !self.common_data.use_cache {
+        if !self.get_use_cache() {
             return;
         }
         'main: for (size, vec_file_entry) in records_already_cached {
@@ -609,14 +862,14 @@ impl DuplicateFinder {
         }
         for (_size, hashmap, _errors) in full_hash_results {
             for vec_file_entry in hashmap.values() {
-                for file_entry in vec_file_entry {
+                for file_entry in vec_file_entry.iter().filter(|e| !e.is_read_only()) {
                     all_results.insert(file_entry.path.to_string_lossy().to_string(), file_entry.clone());
                 }
             }
         }
 
         let messages = save_cache_to_file_generalized(
-            &get_duplicate_cache_file(self.get_params().hash_type, false),
+            &self.get_full_hash_cache_file(),
             &all_results,
             self.common_data.save_also_as_json,
             self.get_params().minimal_cache_file_size,
@@ -624,6 +877,23 @@ impl DuplicateFinder {
         self.get_text_messages_mut().extend_with_another_messages(messages);
     }
 
+    // Partial hashes are different from full hashes, so they cannot be kept in same cache file
+    pub(crate) fn get_full_hash_cache_file(&self) -> String {
+        if self.get_params().check_method == CheckingMethod::PartialHash {
+            get_duplicate_partial_cache_file(self.get_params().hash_type, self.get_params().partial_hash_segments)
+        } else {
+            get_duplicate_cache_file(self.get_params().hash_type, false)
+        }
+    }
+
+    fn get_bytes_to_hash(&self, size: u64) -> u64 {
+        if self.get_params().check_method == CheckingMethod::PartialHash {
+            partial_hash_bytes_to_read(size, self.get_params().partial_hash_segments)
+        } else {
+            size
+        }
+    }
+
     #[fun_time(message = "full_hashing", level = "debug")]
     fn full_hashing(
         &mut self,
@@ -649,16 +919,26 @@ impl DuplicateFinder {
             CurrentStage::DuplicateFullHashing,
             non_cached_files_to_check.values().map(Vec::len).sum(),
             self.get_test_type(),
-            non_cached_files_to_check.iter().map(|(size, items)| (*size) * items.len() as u64).sum::<u64>(),
+            non_cached_files_to_check
+                .iter()
+                .map(|(size, items)| self.get_bytes_to_hash(*size) * items.len() as u64)
+                .sum::<u64>(),
         );
 
         let non_cached_files_to_check: Vec<(u64, Vec<DuplicateEntry>)> = non_cached_files_to_check.into_iter().collect();
 
         let check_type = self.get_params().hash_type;
+        let partial_hash_segments = (self.get_params().check_method == CheckingMethod::PartialHash).then_some(self.get_params().partial_hash_segments);
         debug!(
             "Starting full hashing of {} files",
             non_cached_files_to_check.iter().map(|(_size, v)| v.len() as u64).sum::<u64>()
         );
+        if self.hash_tar_archive_entries(non_cached_files_to_check.iter().flat_map(|(_size, files)| files), stop_flag) == WorkContinueStatus::Stop {
+            progress_handler.join_thread();
+            return WorkContinueStatus::Stop;
+        }
+        let file_system = self.common_data.file_system.as_ref();
+        let archive_entry_hashes = &self.archive_entry_hashes;
         let mut full_hash_results: Vec<(u64, BTreeMap<String, Vec<DuplicateEntry>>, Vec<String>)> = non_cached_files_to_check
             .into_par_iter()
             .with_max_len(3)
@@ -672,7 +952,15 @@ impl DuplicateFinder {
                             return None;
                         }
 
-                        match hash_calculation(buffer, &file_entry, check_type, progress_handler.size_counter(), stop_flag) {
+                        let hash_result = match (archive_entry_hashes.get(&file_entry.path), partial_hash_segments) {
+                            (Some(entry_hashes), _) => {
+                                progress_handler.size_counter().fetch_add(size, Ordering::Relaxed);
+                                entry_hashes.as_ref().map(|entry_hashes| Some(entry_hashes.hash.clone())).map_err(Clone::clone)
+                            }
+                            (None, Some(segments)) => partial_hash_calculation(file_system, buffer, &file_entry, check_type, segments, progress_handler.size_counter(), stop_flag),
+                            (None, None) => hash_calculation(file_system, buffer, &file_entry, check_type, progress_handler.size_counter(), stop_flag),
+                        };
+                        match hash_result {
                             Ok(hash_string) => {
                                 if let Some(hash_string) = hash_string {
                                     file_entry.hash = hash_string.clone();
@@ -703,10 +991,11 @@ impl DuplicateFinder {
 
         progress_handler.join_thread();
 
+        let keep_single_files = self.reference_index.is_some();
         for (size, hash_map, mut errors) in full_hash_results {
             self.common_data.text_messages.warnings.append(&mut errors);
             for (_hash, vec_file_entry) in hash_map {
-                if vec_file_entry.len() > 1 {
+                if vec_file_entry.len() > 1 || keep_single_files {
                     self.files_with_identical_hashes.entry(size).or_default().push(vec_file_entry);
                 }
             }
@@ -715,6 +1004,137 @@ impl DuplicateFinder {
         WorkContinueStatus::Continue
     }
 
+    // Hashes may collide(especially with CRC32), so before reporting groups, content of files is compared and groups are split if needed
+    #[fun_time(message = "verify_hash_groups_byte_by_byte", level = "debug")]
+    pub(crate) fn verify_hash_groups_byte_by_byte(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        if !self.get_params().verify_byte_by_byte || self.files_with_identical_hashes.is_empty() {
+            return WorkContinueStatus::Continue;
+        }
+
+        let groups_to_check: Vec<(u64, Vec<DuplicateEntry>)> = mem::take(&mut self.files_with_identical_hashes)
+            .into_iter()
+            .flat_map(|(size, vec_vec_file_entry)| vec_vec_file_entry.into_iter().map(move |vec_file_entry| (size, vec_file_entry)))
+            .collect();
+        let groups_before_verification = groups_to_check.len();
+
+        // First file in each group is only used as a pattern, so it is not counted
+        let progress_handler = prepare_thread_handler_common(
+            progress_sender,
+            CurrentStage::DuplicateByteComparing,
+            groups_to_check.iter().map(|(_size, vec_file_entry)| vec_file_entry.len() - 1).sum(),
+            self.get_test_type(),
+            groups_to_check.iter().map(|(size, vec_file_entry)| size * (vec_file_entry.len() as u64 - 1)).sum::<u64>(),
+        );
+
+        let keep_single_files = self.reference_index.is_some();
+        let file_system = self.common_data.file_system.as_ref();
+        let verified_groups: Vec<(u64, Vec<Vec<DuplicateEntry>>, Vec<String>)> = groups_to_check
+            .into_par_iter()
+            .map(|(size, vec_file_entry)| {
+                let mut split_groups: Vec<Vec<DuplicateEntry>> = Vec::new();
+                let mut errors: Vec<String> = Vec::new();
+
+                THREAD_BUFFER.with_borrow_mut(|buffer| {
+                    'files: for file_entry in vec_file_entry {
+                        if check_if_stop_received(stop_flag) {
+                            return None;
+                        }
+                        if split_groups.is_empty() {
+                            split_groups.push(vec![file_entry]);
+                            continue;
+                        }
+
+                        let mut matching_group_idx = None;
+                        let mut idx = 0;
+                        while let Some(split_group) = split_groups.get_mut(idx) {
+                            let Some(pattern_entry) = split_group.first() else {
+                                split_groups.remove(idx);
+                                continue;
+                            };
+                            match compare_files_content_with_failed_file(file_system, buffer, pattern_entry, &file_entry, progress_handler.size_counter(), stop_flag) {
+                                Ok(Some(true)) => {
+                                    matching_group_idx = Some(idx);
+                                    break;
+                                }
+                                Ok(Some(false)) => idx += 1,
+                                Ok(None) => return None,
+                                // Unreadable pattern is dropped and next file from its group is used as pattern
+                                Err((ComparedFile::First, e)) => {
+                                    errors.push(e);
+                                    split_group.remove(0);
+                                    if split_group.is_empty() {
+                                        split_groups.remove(idx);
+                                    }
+                                }
+                                // File that cannot be compared, cannot be safely reported as duplicate
+                                Err((ComparedFile::Second, e)) => {
+                                    errors.push(e);
+                                    progress_handler.increase_items(1);
+                                    continue 'files;
+                                }
+                            }
+                        }
+                        progress_handler.increase_items(1);
+
+                        match matching_group_idx.and_then(|idx| split_groups.get_mut(idx)) {
+                            Some(split_group) => split_group.push(file_entry),
+                            None => split_groups.push(vec![file_entry]),
+                        }
+                    }
+                    Some(())
+                })?;
+
+                split_groups.retain(|split_group| split_group.len() > 1 || keep_single_files);
+                Some((size, split_groups, errors))
+            })
+            .while_some()
+            .collect();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        for (size, split_groups, mut errors) in verified_groups {
+            self.common_data.text_messages.warnings.append(&mut errors);
+            if !split_groups.is_empty() {
+                self.files_with_identical_hashes.entry(size).or_default().extend(split_groups);
+            }
+        }
+        debug!(
+            "Byte by byte verification changed number of groups from {groups_before_verification} to {}",
+            self.files_with_identical_hashes.values().map(Vec::len).sum::<usize>()
+        );
+
+        WorkContinueStatus::Continue
+    }
+
+    // Groups with files from reference index are moved to referenced groups, other groups are later checked normally
+    #[fun_time(message = "match_reference_index", level = "debug")]
+    fn match_reference_index(&mut self) {
+        let Some(reference_index) = &self.reference_index else {
+            return;
+        };
+        let indexed_files = reference_index.entries_by_size_and_hash();
+
+        for (size, vec_vec_file_entry) in mem::take(&mut self.files_with_identical_hashes) {
+            for vec_file_entry in vec_vec_file_entry {
+                let indexed_file = vec_file_entry.first().and_then(|file_entry| indexed_files.get(&(size, file_entry.hash.as_str())));
+                if let Some(indexed_file) = indexed_file {
+                    self.files_with_identical_hashes_referenced.entry(size).or_default().push((
+                        DuplicateEntry {
+                            from_reference_index: true,
+                            ..indexed_file.to_duplicate_entry()
+                        },
+                        vec_file_entry,
+                    ));
+                } else if vec_file_entry.len() > 1 {
+                    self.files_with_identical_hashes.entry(size).or_default().push(vec_file_entry);
+                }
+            }
+        }
+    }
+
     #[fun_time(message = "hash_reference_folders", level = "debug")]
     fn hash_reference_folders(&mut self) {
         // Reference - only use in size, because later hash will be counted differently
@@ -744,10 +1164,15 @@ impl DuplicateFinder {
                 .collect::<Vec<Vec<(DuplicateEntry, Vec<DuplicateEntry>)>>>();
             #[expect(clippy::indexing_slicing)] // Safe, because here, empty vectors cannot exist
             for vec_of_vec in vec {
-                self.files_with_identical_hashes_referenced.insert(vec_of_vec[0].0.size, vec_of_vec);
+                // Groups matched with reference index may already exist
+                self.files_with_identical_hashes_referenced.entry(vec_of_vec[0].0.size).or_default().extend(vec_of_vec);
             }
         }
 
+        self.calculate_hash_stats();
+    }
+
+    pub(crate) fn calculate_hash_stats(&mut self) {
         if self.common_data.use_reference_folders {
             for (size, vector_vectors) in &self.files_with_identical_hashes_referenced {
                 for (_fe, vector) in vector_vectors {
@@ -769,9 +1194,21 @@ impl DuplicateFinder {
 
     #[fun_time(message = "check_files_hash", level = "debug")]
     pub(crate) fn check_files_hash(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
-        assert_eq!(self.get_params().check_method, CheckingMethod::Hash);
+        assert!(matches!(self.get_params().check_method, CheckingMethod::Hash | CheckingMethod::PartialHash));
+
+        let (unchanged_groups, changed_groups) = self.take_unchanged_size_groups_from_snapshot();
+        let warnings_before_hashing = self.common_data.text_messages.warnings.len();
 
         let mut pre_checked_map: BTreeMap<u64, Vec<DuplicateEntry>> = Default::default();
+        if let Some(reference_index) = &self.reference_index {
+            // Prehash of files from reference index is unknown, so groups with size of any indexed file cannot be filtered out by it
+            let reference_index_sizes = reference_index.sizes();
+            let (indexed_sizes, other_sizes) = mem::take(&mut self.files_with_identical_size)
+                .into_iter()
+                .partition(|(size, _)| reference_index_sizes.contains(size));
+            pre_checked_map = indexed_sizes;
+            self.files_with_identical_size = other_sizes;
+        }
         if self.prehashing(stop_flag, progress_sender, &mut pre_checked_map) == WorkContinueStatus::Stop {
             return WorkContinueStatus::Stop;
         }
@@ -780,6 +1217,14 @@ impl DuplicateFinder {
             return WorkContinueStatus::Stop;
         }
 
+        if self.verify_hash_groups_byte_by_byte(stop_flag, progress_sender) == WorkContinueStatus::Stop {
+            return WorkContinueStatus::Stop;
+        }
+
+        let hashing_without_errors = self.common_data.text_messages.warnings.len() == warnings_before_hashing;
+        self.update_snapshot_hash_groups(unchanged_groups, changed_groups, hashing_without_errors);
+
+        self.match_reference_index();
         self.hash_reference_folders();
 
         // Clean unused data
@@ -790,7 +1235,37 @@ impl DuplicateFinder {
     }
 }
 
+// Returns groups without files which size or modification date changed, and paths of such files
+fn split_files_changed_since_scan(file_system: &dyn FileSystem, groups: Vec<Vec<DuplicateEntry>>) -> (Vec<Vec<DuplicateEntry>>, Vec<PathBuf>) {
+    let mut changed_files = Vec::new();
+    let groups = groups
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .filter(|entry| {
+                    // Files inside archives are never modified by app, so there is no reason to check them
+                    let unchanged = entry.is_read_only()
+                        || file_system
+                            .metadata(&entry.path)
+                            .is_ok_and(|metadata| metadata.len == entry.size && get_modified_time(&metadata, &mut Vec::new(), &entry.path, false) == entry.modified_date);
+                    if !unchanged {
+                        changed_files.push(entry.path.clone());
+                    }
+                    unchanged
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|group| group.len() > 1)
+        .collect();
+    (groups, changed_files)
+}
+
 pub fn get_duplicate_cache_file(type_of_hash: HashType, is_prehash: bool) -> String {
     let prehash_str = if is_prehash { "_prehash" } else { "" };
     format!("cache_duplicates_{type_of_hash:?}{prehash_str}_{CACHE_DUPLICATE_VERSION}.bin")
 }
+
+pub fn get_duplicate_partial_cache_file(type_of_hash: HashType, segments: u64) -> String {
+    format!("cache_duplicates_{type_of_hash:?}_partial_{segments}_{CACHE_DUPLICATE_VERSION}.bin")
+}
@@ -0,0 +1,505 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use humansize::{BINARY, format_size};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::common::dir_traversal::get_modified_time;
+use crate::common::file_system::{FileSystem, FsEntryType};
+use crate::common::model::{CheckingMethod, FileEntry, HashType, WorkContinueStatus};
+use crate::common::progress_data::ProgressData;
+use crate::common::progress_stop_handler::check_if_stop_received;
+use crate::common::tool_data::{CommonData, DeleteItemType, DeleteMethod, select_items_to_delete};
+use crate::common::traits::ResultEntry;
+use crate::flc;
+use crate::tools::duplicate::{DuplicateEntry, DuplicateFinder};
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct DuplicateFolderEntry {
+    pub path: PathBuf,
+    pub modified_date: u64,
+    // Sum of sizes of all files inside folder
+    pub size: u64,
+    pub files_number: usize,
+    // Paths of all files inside folder, relative to it, with their sizes - empty if not all files have duplicates
+    pub files: Vec<(PathBuf, u64)>,
+    // Hash of sorted relative paths of files with their hashes
+    pub hash: String,
+}
+
+impl ResultEntry for DuplicateFolderEntry {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+    fn get_modified_date(&self) -> u64 {
+        self.modified_date
+    }
+    fn get_size(&self) -> u64 {
+        self.size
+    }
+    fn get_folder_files(&self) -> &[(PathBuf, u64)] {
+        &self.files
+    }
+}
+
+// Counts all files inside folder and its subfolders, None when any of them cannot be read
+fn count_all_files_in_folder(file_system: &dyn FileSystem, folder: &Path, counted_folders: &HashMap<PathBuf, Option<usize>>) -> Option<usize> {
+    let mut files_number = 0;
+    for entry in file_system.read_dir(folder).ok()? {
+        let entry = entry.ok()?;
+        if entry.entry_type == FsEntryType::Dir {
+            files_number += match counted_folders.get(&entry.path) {
+                Some(subfolder_files_number) => (*subfolder_files_number)?,
+                None => count_all_files_in_folder(file_system, &entry.path, counted_folders)?,
+            };
+        } else {
+            files_number += 1;
+        }
+    }
+    Some(files_number)
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct DuplicateFolderGroup {
+    pub folders: Vec<DuplicateFolderEntry>,
+    // When false, folders contain same files, but some of them are named differently
+    pub identical_names: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct FolderSubset {
+    pub subset: DuplicateFolderEntry,
+    pub superset: DuplicateFolderEntry,
+}
+
+// Files inside folder(also in subfolders), with paths relative to this folder
+struct FolderContent<'a> {
+    files: Vec<(&'a Path, &'a DuplicateEntry)>,
+    exact_hash: String,
+    content_hash: String,
+    size: u64,
+}
+
+impl DuplicateFinder {
+    pub const fn get_duplicated_folders(&self) -> &Vec<DuplicateFolderGroup> {
+        &self.duplicated_folders
+    }
+
+    pub const fn get_folder_subsets(&self) -> &Vec<FolderSubset> {
+        &self.folder_subsets
+    }
+
+    pub(crate) fn check_duplicate_folders_settings(&mut self) {
+        if self.params.find_duplicate_folders && (self.params.check_method != CheckingMethod::Hash || self.common_data.use_reference_folders) {
+            self.common_data.text_messages.warnings.push(flc!("core_duplicate_folders_only_hash_mode"));
+            self.params.find_duplicate_folders = false;
+        }
+    }
+
+    // Folder may be duplicated only if all files inside it were checked, so all files from disk are counted, also the ones
+    // skipped e.g. by size or extension filters, because they would be removed together with folder
+    pub(crate) fn count_files_in_folders<'a>(&mut self, files: impl Iterator<Item = &'a FileEntry>) {
+        if !self.get_params().find_duplicate_folders {
+            return;
+        }
+        let included_directories = &self.common_data.directories.included_directories;
+        let mut checked_files_size: HashMap<PathBuf, u64> = HashMap::new();
+        for file in files {
+            let mut current_folder = file.path.parent();
+            while let Some(folder) = current_folder {
+                if !included_directories.iter().any(|dir| folder.starts_with(dir)) {
+                    break;
+                }
+                *checked_files_size.entry(folder.to_path_buf()).or_default() += file.size;
+                current_folder = folder.parent();
+            }
+        }
+
+        // Deeper folders are counted first, so their parents reuse results
+        let mut folders: Vec<PathBuf> = checked_files_size.keys().cloned().collect();
+        folders.sort_unstable_by_key(|folder| Reverse(folder.components().count()));
+        let mut counted_folders: HashMap<PathBuf, Option<usize>> = HashMap::new();
+        for folder in folders {
+            let files_number = count_all_files_in_folder(self.common_data.file_system.as_ref(), &folder, &counted_folders);
+            counted_folders.insert(folder, files_number);
+        }
+
+        // Folders which cannot be fully read are never reported as duplicated
+        self.folder_files = counted_folders
+            .into_iter()
+            .filter_map(|(folder, files_number)| {
+                let files_size = checked_files_size.get(&folder).copied().unwrap_or_default();
+                files_number.map(|files_number| (folder, (files_number, files_size)))
+            })
+            .collect();
+    }
+
+    #[fun_time(message = "find_duplicate_folders", level = "debug")]
+    pub(crate) fn find_duplicate_folders(&mut self, stop_flag: &Arc<AtomicBool>) -> WorkContinueStatus {
+        if !self.get_params().find_duplicate_folders {
+            return WorkContinueStatus::Continue;
+        }
+
+        let hashed_files: HashMap<&Path, &DuplicateEntry> = self
+            .files_with_identical_hashes
+            .values()
+            .flatten()
+            .flatten()
+            // Files inside archives never belong to any real folder
+            .filter(|e| !e.is_read_only())
+            .map(|e| (e.path.as_path(), e))
+            .collect();
+
+        let mut folders_content: HashMap<&Path, Vec<(&Path, &DuplicateEntry)>> = HashMap::new();
+        for (file_path, entry) in &hashed_files {
+            let mut current_folder = file_path.parent();
+            while let Some(folder) = current_folder {
+                if !self.folder_files.contains_key(folder) {
+                    break;
+                }
+                let relative_path = file_path.strip_prefix(folder).unwrap_or(file_path);
+                folders_content.entry(folder).or_default().push((relative_path, entry));
+                current_folder = folder.parent();
+            }
+        }
+
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        // Only folders in which all files have duplicates may be duplicated or be subset of other folder
+        let complete_folders: HashMap<&Path, FolderContent> = folders_content
+            .into_iter()
+            .filter(|(folder, files)| self.folder_files.get(*folder).is_some_and(|(files_number, _)| *files_number == files.len()))
+            .map(|(folder, mut files)| {
+                files.sort_unstable_by_key(|(relative_path, _)| *relative_path);
+                let (exact_hash, content_hash) = calculate_folder_hashes(&files);
+                let size = files.iter().map(|(_, entry)| entry.size).sum();
+                (
+                    folder,
+                    FolderContent {
+                        files,
+                        exact_hash,
+                        content_hash,
+                        size,
+                    },
+                )
+            })
+            .collect();
+
+        let mut warnings = Vec::new();
+        let folder_files = &self.folder_files;
+        let file_system = self.common_data.file_system.clone();
+        let mut folder_entry = |folder: &Path| {
+            let (files_number, size) = folder_files.get(folder).copied().unwrap_or_default();
+            DuplicateFolderEntry {
+                path: folder.to_path_buf(),
+                modified_date: file_system
+                    .metadata(folder)
+                    .map(|metadata| get_modified_time(&metadata, &mut warnings, folder, true))
+                    .unwrap_or_default(),
+                size,
+                files_number,
+                files: complete_folders
+                    .get(folder)
+                    .map(|content| content.files.iter().map(|(relative_path, entry)| (relative_path.to_path_buf(), entry.size)).collect())
+                    .unwrap_or_default(),
+                // Superset may contain files without duplicates, so its hash is not always known
+                hash: complete_folders.get(folder).map(|content| content.exact_hash.clone()).unwrap_or_default(),
+            }
+        };
+
+        let duplicated_folders = group_duplicated_folders(&complete_folders);
+        let folder_subsets = find_folder_subsets(&complete_folders, &hashed_files, folder_files);
+
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        self.duplicated_folders = duplicated_folders
+            .into_iter()
+            .map(|folders| {
+                let identical_names = folders
+                    .iter()
+                    .filter_map(|folder| complete_folders.get(folder))
+                    .map(|content| content.exact_hash.as_str())
+                    .collect::<HashSet<_>>()
+                    .len()
+                    == 1;
+                let folders = folders.into_iter().map(&mut folder_entry).collect();
+                DuplicateFolderGroup { folders, identical_names }
+            })
+            .collect();
+        self.folder_subsets = folder_subsets
+            .into_iter()
+            .map(|(subset, superset)| FolderSubset {
+                subset: folder_entry(subset),
+                superset: folder_entry(superset),
+            })
+            .collect();
+        self.common_data.text_messages.warnings.extend(warnings);
+
+        debug!(
+            "find_duplicate_folders - found {} groups of duplicated folders and {} subsets, checked {} complete folders",
+            self.duplicated_folders.len(),
+            self.folder_subsets.len(),
+            complete_folders.len()
+        );
+
+        self.calculate_folder_stats();
+
+        WorkContinueStatus::Continue
+    }
+
+    pub(crate) fn calculate_folder_stats(&mut self) {
+        self.information.number_of_groups_by_folders = self.duplicated_folders.len();
+        self.information.number_of_duplicated_folders = self.duplicated_folders.iter().map(|group| group.folders.len() - 1).sum();
+        self.information.lost_space_by_folders = self
+            .duplicated_folders
+            .iter()
+            .map(|group| group.folders.iter().skip(1).map(|folder| folder.size).sum::<u64>())
+            .sum();
+    }
+
+    pub(crate) fn delete_duplicate_folders(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        let delete_method = self.common_data.delete_method;
+        // Folders cannot be linked, so all files inside them are linked instead
+        let delete_item_type = match delete_method {
+            DeleteMethod::None => return WorkContinueStatus::Continue,
+            DeleteMethod::HardLink => DeleteItemType::HardlinkingFiles(self.get_folder_files_to_link()),
+            DeleteMethod::Reflink => DeleteItemType::ReflinkingFiles(self.get_folder_files_to_link()),
+            _ => {
+                if !self.are_keep_rules_set_if_needed() {
+                    return WorkContinueStatus::Continue;
+                }
+                let folders = self.duplicated_folders.iter().map(|group| group.folders.clone()).collect();
+                return self.delete_simple_elements_and_add_to_messages(
+                    stop_flag,
+                    progress_sender,
+                    DeleteItemType::DeletingFolders(select_items_to_delete(delete_method, &self.common_data.keep_rules, folders)),
+                );
+            }
+        };
+        self.delete_simple_elements_and_add_to_messages(stop_flag, progress_sender, delete_item_type)
+    }
+
+    pub(crate) fn write_duplicate_folders_results<T: Write>(&self, writer: &mut T) -> io::Result<()> {
+        if self.duplicated_folders.is_empty() && self.folder_subsets.is_empty() {
+            write!(writer, "Not found any duplicated folders.")?;
+            return Ok(());
+        }
+
+        if !self.duplicated_folders.is_empty() {
+            writeln!(
+                writer,
+                "-------------------------------------------------Folders with same content-------------------------------------------------"
+            )?;
+            writeln!(
+                writer,
+                "Found {} duplicated folders which in {} groups which takes {}.",
+                self.information.number_of_duplicated_folders,
+                self.information.number_of_groups_by_folders,
+                format_size(self.information.lost_space_by_folders, BINARY)
+            )?;
+            for group in &self.duplicated_folders {
+                let Some(first_folder) = group.folders.first() else {
+                    continue;
+                };
+                writeln!(
+                    writer,
+                    "\n---- Size {} ({}) - {} folders with {} files",
+                    format_size(first_folder.size, BINARY),
+                    first_folder.size,
+                    group.folders.len(),
+                    first_folder.files_number
+                )?;
+                if !group.identical_names {
+                    writeln!(writer, "Some files have different names")?;
+                }
+                for folder in &group.folders {
+                    writeln!(writer, "\"{}\"", folder.path.to_string_lossy())?;
+                }
+            }
+        }
+
+        if !self.folder_subsets.is_empty() {
+            writeln!(
+                writer,
+                "\n-------------------------------------------------Folders included in other folders-------------------------------------------------"
+            )?;
+            writeln!(writer, "Found {} folders which content is fully included in other folders.", self.folder_subsets.len())?;
+            for folder_subset in &self.folder_subsets {
+                writeln!(
+                    writer,
+                    "\"{}\" ({} files) is subset of \"{}\" ({} files)",
+                    folder_subset.subset.path.to_string_lossy(),
+                    folder_subset.subset.files_number,
+                    folder_subset.superset.path.to_string_lossy(),
+                    folder_subset.superset.files_number
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Files from duplicated folders, matched by relative path with files from first folder in group
+    pub(crate) fn get_folder_files_to_link(&self) -> Vec<(DuplicateEntry, Vec<DuplicateEntry>)> {
+        let hashed_files: HashMap<&Path, &DuplicateEntry> = self
+            .files_with_identical_hashes
+            .values()
+            .flatten()
+            .flatten()
+            // Files inside archives never belong to any real folder
+            .filter(|e| !e.is_read_only())
+            .map(|e| (e.path.as_path(), e))
+            .collect();
+
+        let mut files_to_link = Vec::new();
+        for group in &self.duplicated_folders {
+            let Some((original_folder, other_folders)) = group.folders.split_first() else {
+                continue;
+            };
+            for (file_path, original_file) in hashed_files.iter().filter(|(path, _)| path.starts_with(&original_folder.path)) {
+                let Ok(relative_path) = file_path.strip_prefix(&original_folder.path) else {
+                    continue;
+                };
+                let other_files: Vec<DuplicateEntry> = other_folders
+                    .iter()
+                    .filter_map(|folder| hashed_files.get(folder.path.join(relative_path).as_path()))
+                    .filter(|file| file.hash == original_file.hash)
+                    .map(|file| (*file).clone())
+                    .collect();
+                if !other_files.is_empty() {
+                    files_to_link.push(((*original_file).clone(), other_files));
+                }
+            }
+        }
+        files_to_link
+    }
+}
+
+fn calculate_folder_hashes(files: &[(&Path, &DuplicateEntry)]) -> (String, String) {
+    let mut exact_hasher = HashType::Blake3.hasher();
+    for (relative_path, entry) in files {
+        exact_hasher.update(relative_path.to_string_lossy().as_bytes());
+        exact_hasher.update(b"\0");
+        exact_hasher.update(entry.hash.as_bytes());
+        exact_hasher.update(b"\n");
+    }
+
+    // Names are ignored here, so folders with renamed files are also grouped
+    // Sorted list instead of set keeps number of copies of each file
+    let mut content: Vec<(u64, &str)> = files.iter().map(|(_, entry)| (entry.size, entry.hash.as_str())).collect();
+    content.sort_unstable();
+    let mut content_hasher = HashType::Blake3.hasher();
+    for (size, hash) in content {
+        content_hasher.update(&size.to_le_bytes());
+        content_hasher.update(hash.as_bytes());
+        content_hasher.update(b"\n");
+    }
+    (exact_hasher.finalize(), content_hasher.finalize())
+}
+
+fn group_duplicated_folders<'a>(complete_folders: &HashMap<&'a Path, FolderContent>) -> Vec<Vec<&'a Path>> {
+    let mut folders_by_content: HashMap<(&str, usize), Vec<&'a Path>> = HashMap::new();
+    for (folder, content) in complete_folders {
+        folders_by_content.entry((content.content_hash.as_str(), content.files.len())).or_default().push(folder);
+    }
+
+    let groups: Vec<Vec<&Path>> = folders_by_content
+        .into_values()
+        .filter_map(|folders| {
+            // Folder with only one subfolder has same content as this subfolder, so only the subfolder is kept
+            let mut deepest_folders: Vec<&Path> = folders
+                .iter()
+                .filter(|folder| !folders.iter().any(|other| other != *folder && other.starts_with(folder)))
+                .copied()
+                .collect();
+            deepest_folders.sort_unstable();
+            (deepest_folders.len() > 1).then_some(deepest_folders)
+        })
+        .collect();
+
+    // Subfolders of duplicated folders are also duplicated, but showing them is not useful
+    // Group is still shown, when at least 2 folders from it are not inside other duplicated folders
+    let duplicated_folders: HashSet<&Path> = groups.iter().flatten().copied().collect();
+    let mut groups: Vec<Vec<&Path>> = groups
+        .into_iter()
+        .filter(|folders| {
+            folders
+                .iter()
+                .filter(|folder| folder.parent().is_none_or(|parent| !duplicated_folders.contains(parent)))
+                .count()
+                > 1
+        })
+        .collect();
+
+    groups.sort_unstable_by_key(|folders| {
+        let first_folder = folders.first().copied();
+        (
+            Reverse(first_folder.and_then(|folder| complete_folders.get(folder)).map(|content| content.size)),
+            first_folder,
+        )
+    });
+    groups
+}
+
+fn find_folder_subsets<'a>(
+    complete_folders: &HashMap<&'a Path, FolderContent>,
+    hashed_files: &HashMap<&'a Path, &'a DuplicateEntry>,
+    folder_files: &'a HashMap<PathBuf, (usize, u64)>,
+) -> Vec<(&'a Path, &'a Path)> {
+    // Files with same hash, used to find folders which may contain files from checked folder
+    let mut files_by_hash: HashMap<(u64, &str), Vec<&Path>> = HashMap::new();
+    for (path, entry) in hashed_files {
+        files_by_hash.entry((entry.size, entry.hash.as_str())).or_default().push(path);
+    }
+
+    let mut subsets: BTreeSet<(&Path, &Path)> = BTreeSet::new();
+    for (folder, content) in complete_folders {
+        let Some((first_relative_path, first_entry)) = content.files.first() else {
+            continue;
+        };
+        let depth = first_relative_path.components().count();
+        let candidates: HashSet<&Path> = files_by_hash
+            .get(&(first_entry.size, first_entry.hash.as_str()))
+            .into_iter()
+            .flatten()
+            .filter(|path| path.ends_with(first_relative_path))
+            .filter_map(|path| path.ancestors().nth(depth))
+            .filter(|candidate| !candidate.starts_with(folder) && !folder.starts_with(candidate))
+            .collect();
+
+        for candidate in candidates {
+            let Some((candidate, (candidate_files_number, _))) = folder_files.get_key_value(candidate) else {
+                continue;
+            };
+            if *candidate_files_number <= content.files.len() {
+                continue;
+            }
+            let contains_all_files = content.files.iter().all(|(relative_path, entry)| {
+                hashed_files
+                    .get(candidate.join(relative_path).as_path())
+                    .is_some_and(|candidate_entry| candidate_entry.hash == entry.hash)
+            });
+            if contains_all_files {
+                subsets.insert((*folder, candidate.as_path()));
+            }
+        }
+    }
+
+    // When parent folder is already subset of other folder, then its subfolders are not interesting
+    let is_covered_by_parent = |subset: &Path, superset: &Path| match (subset.parent(), superset.parent()) {
+        (Some(subset_parent), Some(superset_parent)) => subsets.contains(&(subset_parent, superset_parent)),
+        _ => false,
+    };
+    subsets.iter().filter(|(subset, superset)| !is_covered_by_parent(subset, superset)).copied().collect()
+}
@@ -1,18 +1,19 @@
+pub mod archives;
 pub mod core;
+pub mod folders;
+pub mod reference_index;
+pub mod snapshot;
 #[cfg(test)]
 mod tests;
 pub mod traits;
+mod watch;
 
 use std::cell::RefCell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
-#[cfg(target_family = "unix")]
-use std::fs;
-use std::fs::File;
 use std::hash::Hasher;
 use std::io::prelude::*;
-#[cfg(target_family = "unix")]
-use std::os::unix::fs::MetadataExt;
+use std::io::{ErrorKind, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -23,25 +24,42 @@ use serde::{Deserialize, Serialize};
 use static_assertions::const_assert;
 use xxhash_rust::xxh3::Xxh3;
 
+use crate::common::file_system::FileSystem;
 use crate::common::model::{CheckingMethod, FileEntry, HashType};
+use crate::common::ndjson::NdjsonItem;
 use crate::common::progress_stop_handler::check_if_stop_received;
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 use crate::flc;
+use crate::tools::duplicate::archives::{ArchiveEntryHashes, with_entry_reader};
+use crate::tools::duplicate::folders::{DuplicateFolderGroup, FolderSubset};
+use crate::tools::duplicate::reference_index::ReferenceIndex;
+use crate::tools::duplicate::snapshot::DuplicateScanSnapshot;
+use crate::tools::duplicate::watch::DuplicateWatchState;
 
 pub const PREHASHING_BUFFER_SIZE: u64 = 4 * 1024;
 pub const THREAD_BUFFER_SIZE: usize = 2 * 1024 * 1024;
+pub const PARTIAL_HASH_BLOCK_SIZE: u64 = 1024 * 1024;
+pub const DEFAULT_PARTIAL_HASH_SEGMENTS: u64 = 8;
+pub const MAX_PARTIAL_HASH_SEGMENTS: u64 = 1024;
+pub const DEFAULT_MINIMAL_CACHE_SIZE: u64 = 257_144;
 
 thread_local! {
     static THREAD_BUFFER: RefCell<Vec<u8>> = RefCell::new(vec![0u8; THREAD_BUFFER_SIZE]);
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[non_exhaustive]
 pub struct DuplicateEntry {
     pub path: PathBuf,
     pub modified_date: u64,
     pub size: u64,
     pub hash: String,
+    // Archive which contains this file - such virtual entries are read-only and cannot be deleted or linked
+    pub archive_path: Option<PathBuf>,
+    // File known only from reference index, e.g. from archive on offline disk, so it cannot be deleted or linked
+    #[serde(default)]
+    pub from_reference_index: bool,
 }
 impl ResultEntry for DuplicateEntry {
     fn get_path(&self) -> &Path {
@@ -53,6 +71,26 @@ impl ResultEntry for DuplicateEntry {
     fn get_size(&self) -> u64 {
         self.size
     }
+    fn is_read_only(&self) -> bool {
+        self.archive_path.is_some() || self.from_reference_index
+    }
+    fn get_hash(&self) -> Option<&str> {
+        (!self.hash.is_empty()).then_some(self.hash.as_str())
+    }
+}
+
+// Used to compare content of items, which were sent to clients as results
+impl From<&NdjsonItem> for DuplicateEntry {
+    fn from(item: &NdjsonItem) -> Self {
+        Self {
+            path: item.path.clone(),
+            modified_date: item.modified_date,
+            size: item.size,
+            hash: item.get_hash().unwrap_or_default().to_string(),
+            archive_path: item.details.get("archive_path").and_then(|archive_path| serde_json::from_value(archive_path.clone()).ok()),
+            from_reference_index: item.details.get("from_reference_index").and_then(serde_json::Value::as_bool).unwrap_or_default(),
+        }
+    }
 }
 
 impl FileEntry {
@@ -62,6 +100,8 @@ impl FileEntry {
             path: self.path,
             modified_date: self.modified_date,
             hash: String::new(),
+            archive_path: None,
+            from_reference_index: false,
         }
     }
 }
@@ -78,6 +118,9 @@ pub struct Info {
     pub number_of_duplicated_files_by_size_name: usize,
     pub lost_space_by_size: u64,
     pub lost_space_by_hash: u64,
+    pub number_of_groups_by_folders: usize,
+    pub number_of_duplicated_folders: usize,
+    pub lost_space_by_folders: u64,
     pub scanning_time: Duration,
 }
 
@@ -89,6 +132,14 @@ pub struct DuplicateFinderParameters {
     pub minimal_cache_file_size: u64,
     pub minimal_prehash_cache_file_size: u64,
     pub case_sensitive_name_comparison: bool,
+    pub use_scan_snapshot: bool,
+    pub verify_byte_by_byte: bool,
+    pub reference_index_path: Option<PathBuf>,
+    pub find_duplicate_folders: bool,
+    // Number of evenly spaced blocks hashed in PartialHash mode, besides first and last block
+    pub partial_hash_segments: u64,
+    // Files inside zip/tar archives are also compared, but they are read-only
+    pub check_archives: bool,
 }
 
 impl DuplicateFinderParameters {
@@ -99,6 +150,12 @@ impl DuplicateFinderParameters {
         minimal_cache_file_size: u64,
         minimal_prehash_cache_file_size: u64,
         case_sensitive_name_comparison: bool,
+        use_scan_snapshot: bool,
+        verify_byte_by_byte: bool,
+        reference_index_path: Option<PathBuf>,
+        find_duplicate_folders: bool,
+        partial_hash_segments: u64,
+        check_archives: bool,
     ) -> Self {
         Self {
             check_method,
@@ -107,6 +164,12 @@ impl DuplicateFinderParameters {
             minimal_cache_file_size,
             minimal_prehash_cache_file_size,
             case_sensitive_name_comparison,
+            use_scan_snapshot,
+            verify_byte_by_byte,
+            reference_index_path,
+            find_duplicate_folders,
+            partial_hash_segments,
+            check_archives,
         }
     }
 }
@@ -131,14 +194,34 @@ pub struct DuplicateFinder {
     // File Size, next grouped by file size, next grouped by hash
     files_with_identical_hashes_referenced: BTreeMap<u64, Vec<(DuplicateEntry, Vec<DuplicateEntry>)>>,
     params: DuplicateFinderParameters,
+    // Snapshot of previous scan with same settings, updated during current scan
+    scan_snapshot: Option<DuplicateScanSnapshot>,
+    // Hashes of files from previous runs(e.g. offline archive), used as additional reference files
+    reference_index: Option<ReferenceIndex>,
+    // Folder path, number and size of all files inside it(also in subfolders)
+    folder_files: HashMap<PathBuf, (usize, u64)>,
+    // Folders with same content, next grouped by content
+    duplicated_folders: Vec<DuplicateFolderGroup>,
+    // Folders which contents are fully included in other folders
+    folder_subsets: Vec<FolderSubset>,
+    // Hashes of files inside tar archives, calculated during single read of each archive
+    archive_entry_hashes: HashMap<PathBuf, Result<ArchiveEntryHashes, String>>,
+    // Files and hashes used to update results while watching folders
+    watch_state: Option<DuplicateWatchState>,
+    // New exact duplicates noticed while watching folders, are replaced by hardlinks
+    hardlink_new_duplicates: bool,
 }
 
 #[cfg(target_family = "windows")]
-fn filter_hard_links(vec_file_entry: Vec<FileEntry>) -> Vec<FileEntry> {
+fn filter_hard_links<T: ResultEntry>(vec_file_entry: Vec<T>, file_system: &dyn FileSystem) -> Vec<T> {
+    // File ids are available only for local files
+    if !file_system.is_local() {
+        return vec_file_entry;
+    }
     let mut inodes: IndexSet<u128> = IndexSet::with_capacity(vec_file_entry.len());
-    let mut identical: Vec<FileEntry> = Vec::with_capacity(vec_file_entry.len());
+    let mut identical: Vec<T> = Vec::with_capacity(vec_file_entry.len());
     for f in vec_file_entry {
-        if let Ok(meta) = file_id::get_high_res_file_id(&f.path) {
+        if let Ok(meta) = file_id::get_high_res_file_id(f.get_path()) {
             if let file_id::FileId::HighRes { file_id, .. } = meta {
                 if !inodes.insert(file_id) {
                     continue;
@@ -151,12 +234,13 @@ fn filter_hard_links(vec_file_entry: Vec<FileEntry>) -> Vec<FileEntry> {
 }
 
 #[cfg(target_family = "unix")]
-fn filter_hard_links(vec_file_entry: Vec<FileEntry>) -> Vec<FileEntry> {
+fn filter_hard_links<T: ResultEntry>(vec_file_entry: Vec<T>, file_system: &dyn FileSystem) -> Vec<T> {
     let mut inodes: IndexSet<u64> = IndexSet::with_capacity(vec_file_entry.len());
-    let mut identical: Vec<FileEntry> = Vec::with_capacity(vec_file_entry.len());
+    let mut identical: Vec<T> = Vec::with_capacity(vec_file_entry.len());
     for f in vec_file_entry {
-        if let Ok(meta) = fs::metadata(&f.path)
-            && !inodes.insert(meta.ino())
+        if let Ok(meta) = file_system.metadata(f.get_path())
+            && let Some(inode) = meta.inode
+            && !inodes.insert(inode)
         {
             continue;
         }
@@ -199,6 +283,10 @@ impl DuplicateFinder {
         self.common_data.dry_run = dry_run;
     }
 
+    pub fn set_hardlink_new_duplicates(&mut self, hardlink_new_duplicates: bool) {
+        self.hardlink_new_duplicates = hardlink_new_duplicates;
+    }
+
     pub fn get_use_reference(&self) -> bool {
         self.common_data.use_reference_folders
     }
@@ -220,60 +308,120 @@ impl DuplicateFinder {
     }
 }
 
-pub(crate) fn hash_calculation_limit(buffer: &mut [u8], file_entry: &DuplicateEntry, hash_type: HashType, limit: u64, size_counter: &Arc<AtomicU64>) -> Result<String, String> {
+pub(crate) fn hash_calculation_limit(
+    file_system: &dyn FileSystem,
+    buffer: &mut [u8],
+    file_entry: &DuplicateEntry,
+    hash_type: HashType,
+    limit: u64,
+    size_counter: &Arc<AtomicU64>,
+) -> Result<String, String> {
     // This function is used only to calculate hash of file with limit
     // We must ensure that buffer is big enough to store all data
     // We don't need to check that each time
     const_assert!(PREHASHING_BUFFER_SIZE <= THREAD_BUFFER_SIZE as u64);
 
-    let mut file_handler = match File::open(&file_entry.path) {
-        Ok(t) => t,
-        Err(e) => {
-            size_counter.fetch_add(limit, Ordering::Relaxed);
-            return Err(flc!(
-                "core_unable_check_hash_of_file",
-                file = file_entry.path.to_string_lossy().to_string(),
-                reason = e.to_string()
-            ));
-        }
-    };
-    let hasher = &mut *hash_type.hasher();
-    #[expect(clippy::indexing_slicing)] // Safe, because limit is always <= buffer size
-    let n = match file_handler.read(&mut buffer[..limit as usize]) {
-        Ok(t) => t,
-        Err(e) => return Err(flc!("core_error_checking_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string())),
-    };
+    let hash_result = with_entry_reader(file_system, file_entry, |reader| {
+        let hasher = &mut *hash_type.hasher();
+        // Decompressed data from archives may be returned in smaller chunks, so whole buffer must be filled
+        #[expect(clippy::indexing_slicing)] // Safe, because limit is always <= buffer size
+        let n = match read_into_whole_buffer(reader, &mut buffer[..limit as usize]) {
+            Ok(t) => t,
+            Err(e) => return Err(flc!("core_error_checking_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string())),
+        };
 
-    #[expect(clippy::indexing_slicing)] // Safe, because we read only n bytes, which is always <= limit <= buffer size
-    hasher.update(&buffer[..n]);
-    size_counter.fetch_add(n as u64, Ordering::Relaxed);
-    Ok(hasher.finalize())
+        #[expect(clippy::indexing_slicing)] // Safe, because we read only n bytes, which is always <= limit <= buffer size
+        hasher.update(&buffer[..n]);
+        size_counter.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(hasher.finalize())
+    });
+
+    hash_result.unwrap_or_else(|e| {
+        size_counter.fetch_add(limit, Ordering::Relaxed);
+        Err(flc!(
+            "core_unable_check_hash_of_file",
+            file = file_entry.path.to_string_lossy().to_string(),
+            reason = e.to_string()
+        ))
+    })
 }
 
 pub fn hash_calculation(
+    file_system: &dyn FileSystem,
+    buffer: &mut [u8],
+    file_entry: &DuplicateEntry,
+    hash_type: HashType,
+    size_counter: &Arc<AtomicU64>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Result<Option<String>, String> {
+    let hash_result = with_entry_reader(file_system, file_entry, |reader| {
+        let hasher = &mut *hash_type.hasher();
+        loop {
+            let n = match reader.read(buffer) {
+                Ok(0) => break,
+                Ok(t) => t,
+                Err(e) => return Err(flc!("core_error_checking_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string())),
+            };
+
+            #[expect(clippy::indexing_slicing)] // Safe, because we read only n bytes, which is always <= buffer size
+            hasher.update(&buffer[..n]);
+            size_counter.fetch_add(n as u64, Ordering::Relaxed);
+            if check_if_stop_received(stop_flag) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(hasher.finalize()))
+    });
+
+    hash_result.unwrap_or_else(|e| {
+        size_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+        Err(flc!("core_unable_check_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string()))
+    })
+}
+
+// Bytes that will be read when hashing file, used to show progress
+pub(crate) fn partial_hash_bytes_to_read(size: u64, segments: u64) -> u64 {
+    size.min((segments + 2) * PARTIAL_HASH_BLOCK_SIZE)
+}
+
+// Hashes only first and last block and `segments` evenly spaced blocks between them
+// Smaller files are hashed entirely, because reading them in parts would not be faster
+pub fn partial_hash_calculation(
+    file_system: &dyn FileSystem,
     buffer: &mut [u8],
     file_entry: &DuplicateEntry,
     hash_type: HashType,
+    segments: u64,
     size_counter: &Arc<AtomicU64>,
     stop_flag: &Arc<AtomicBool>,
 ) -> Result<Option<String>, String> {
-    let mut file_handler = match File::open(&file_entry.path) {
+    const_assert!(PARTIAL_HASH_BLOCK_SIZE <= THREAD_BUFFER_SIZE as u64);
+
+    let blocks_number = segments + 2;
+    if file_entry.size <= blocks_number * PARTIAL_HASH_BLOCK_SIZE {
+        return hash_calculation(file_system, buffer, file_entry, hash_type, size_counter, stop_flag);
+    }
+
+    let mut file_handler = match file_system.open(&file_entry.path) {
         Ok(t) => t,
         Err(e) => {
-            size_counter.fetch_add(file_entry.size, Ordering::Relaxed);
+            size_counter.fetch_add(partial_hash_bytes_to_read(file_entry.size, segments), Ordering::Relaxed);
             return Err(flc!("core_unable_check_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string()));
         }
     };
     let hasher = &mut *hash_type.hasher();
-    loop {
-        let n = match file_handler.read(buffer) {
-            Ok(0) => break,
-            Ok(t) => t,
-            Err(e) => return Err(flc!("core_error_checking_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string())),
-        };
+    let last_block_offset = file_entry.size - PARTIAL_HASH_BLOCK_SIZE;
+    #[expect(clippy::indexing_slicing)] // Safe, because block size is always <= buffer size
+    let block_buffer = &mut buffer[..PARTIAL_HASH_BLOCK_SIZE as usize];
+    for block_idx in 0..blocks_number {
+        let offset = last_block_offset * block_idx / (blocks_number - 1);
+        let n = file_handler
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| read_into_whole_buffer(&mut *file_handler, block_buffer))
+            .map_err(|e| flc!("core_error_checking_hash_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string()))?;
 
         #[expect(clippy::indexing_slicing)] // Safe, because we read only n bytes, which is always <= buffer size
-        hasher.update(&buffer[..n]);
+        hasher.update(&block_buffer[..n]);
         size_counter.fetch_add(n as u64, Ordering::Relaxed);
         if check_if_stop_received(stop_flag) {
             return Ok(None);
@@ -282,6 +430,77 @@ pub fn hash_calculation(
     Ok(Some(hasher.finalize()))
 }
 
+// Unlike single read, fills whole buffer if file still have enough data, so chunks of two files can be compared directly
+fn read_into_whole_buffer(file_handler: &mut dyn Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while let Some(remaining) = buffer.get_mut(filled..)
+        && !remaining.is_empty()
+    {
+        match file_handler.read(remaining) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+// Compares content of second file with first one, only bytes of second file are counted in size_counter
+pub fn compare_files_content(
+    file_system: &dyn FileSystem,
+    buffer: &mut [u8],
+    first_entry: &DuplicateEntry,
+    second_entry: &DuplicateEntry,
+    size_counter: &Arc<AtomicU64>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Result<Option<bool>, String> {
+    compare_files_content_with_failed_file(file_system, buffer, first_entry, second_entry, size_counter, stop_flag).map_err(|(_failed_file, e)| e)
+}
+
+// Which of compared files could not be opened or read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ComparedFile {
+    First,
+    Second,
+}
+
+pub(crate) fn compare_files_content_with_failed_file(
+    file_system: &dyn FileSystem,
+    buffer: &mut [u8],
+    first_entry: &DuplicateEntry,
+    second_entry: &DuplicateEntry,
+    size_counter: &Arc<AtomicU64>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Result<Option<bool>, (ComparedFile, String)> {
+    let open_error = |file_entry: &DuplicateEntry, e: std::io::Error| flc!("core_unable_compare_content_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string());
+    let read_error =
+        |file_entry: &DuplicateEntry, e: std::io::Error| flc!("core_error_comparing_content_of_file", file = file_entry.path.to_string_lossy(), reason = e.to_string());
+
+    let (first_buffer, second_buffer) = buffer.split_at_mut(buffer.len() / 2);
+    with_entry_reader(file_system, first_entry, |first_file_handler| {
+        with_entry_reader(file_system, second_entry, |second_file_handler| {
+            loop {
+                let first_n = read_into_whole_buffer(first_file_handler, first_buffer).map_err(|e| (ComparedFile::First, read_error(first_entry, e)))?;
+                let second_n = read_into_whole_buffer(second_file_handler, second_buffer).map_err(|e| (ComparedFile::Second, read_error(second_entry, e)))?;
+                size_counter.fetch_add(second_n as u64, Ordering::Relaxed);
+
+                if first_buffer.get(..first_n) != second_buffer.get(..second_n) {
+                    return Ok(Some(false));
+                }
+                if first_n == 0 {
+                    return Ok(Some(true));
+                }
+                if check_if_stop_received(stop_flag) {
+                    return Ok(None);
+                }
+            }
+        })
+        .unwrap_or_else(|e| Err((ComparedFile::Second, open_error(second_entry, e))))
+    })
+    .unwrap_or_else(|e| Err((ComparedFile::First, open_error(first_entry, e))))
+}
+
 impl MyHasher for blake3::Hasher {
     fn update(&mut self, bytes: &[u8]) {
         self.update(bytes);
@@ -312,16 +531,17 @@ impl MyHasher for Xxh3 {
 #[cfg(test)]
 mod tests2 {
     use std::fs::File;
-    use std::io;
+    use std::{fs, io};
 
     use super::*;
+    use crate::common::file_system::LocalFileSystem;
     use crate::common::model::FileEntry;
     use crate::tools::duplicate::filter_hard_links;
 
     #[test]
     fn test_filter_hard_links_empty() {
         let expected: Vec<FileEntry> = Default::default();
-        assert_eq!(expected, filter_hard_links(Vec::new()));
+        assert_eq!(expected, filter_hard_links(Vec::new(), &LocalFileSystem));
     }
 
     #[cfg(target_family = "unix")]
@@ -333,7 +553,7 @@ mod tests2 {
         fs::hard_link(src.clone(), dst.clone())?;
         let e1 = FileEntry { path: src, ..Default::default() };
         let e2 = FileEntry { path: dst, ..Default::default() };
-        let actual = filter_hard_links(vec![e1.clone(), e2]);
+        let actual = filter_hard_links(vec![e1.clone(), e2], &LocalFileSystem);
         assert_eq!(vec![e1], actual);
         Ok(())
     }
@@ -346,7 +566,7 @@ mod tests2 {
         File::create(&dst)?;
         let e1 = FileEntry { path: src, ..Default::default() };
         let e2 = FileEntry { path: dst, ..Default::default() };
-        let actual = filter_hard_links(vec![e1.clone(), e2.clone()]);
+        let actual = filter_hard_links(vec![e1.clone(), e2.clone()], &LocalFileSystem);
         assert_eq!(vec![e1, e2], actual);
         Ok(())
     }
@@ -360,7 +580,7 @@ mod tests2 {
         file.write_all(b"aaAAAAAAAAAAAAAAFFFFFFFFFFFFFFFFFFFFGGGGGGGGG")?;
         let e = DuplicateEntry { path: src, ..Default::default() };
         let size_counter = Arc::new(AtomicU64::new(0));
-        let r = hash_calculation(&mut buf, &e, HashType::Blake3, &size_counter, &Arc::default())
+        let r = hash_calculation(&LocalFileSystem, &mut buf, &e, HashType::Blake3, &size_counter, &Arc::default())
             .expect("hash_calculation failed")
             .expect("hash_calculation returned None");
         assert!(!r.is_empty());
@@ -379,9 +599,9 @@ mod tests2 {
         let size_counter_1 = Arc::new(AtomicU64::new(0));
         let size_counter_2 = Arc::new(AtomicU64::new(0));
         let size_counter_3 = Arc::new(AtomicU64::new(0));
-        let r1 = hash_calculation_limit(&mut buf, &e, HashType::Blake3, 1, &size_counter_1).expect("hash_calculation failed");
-        let r2 = hash_calculation_limit(&mut buf, &e, HashType::Blake3, 2, &size_counter_2).expect("hash_calculation failed");
-        let r3 = hash_calculation_limit(&mut buf, &e, HashType::Blake3, 1000, &size_counter_3).expect("hash_calculation failed");
+        let r1 = hash_calculation_limit(&LocalFileSystem, &mut buf, &e, HashType::Blake3, 1, &size_counter_1).expect("hash_calculation failed");
+        let r2 = hash_calculation_limit(&LocalFileSystem, &mut buf, &e, HashType::Blake3, 2, &size_counter_2).expect("hash_calculation failed");
+        let r3 = hash_calculation_limit(&LocalFileSystem, &mut buf, &e, HashType::Blake3, 1000, &size_counter_3).expect("hash_calculation failed");
         assert_ne!(r1, r2);
         assert_eq!(r2, r3);
 
@@ -398,7 +618,7 @@ mod tests2 {
         let mut buf = [0u8; 1 << 10];
         let src = dir.path().join("a");
         let e = DuplicateEntry { path: src, ..Default::default() };
-        let r = hash_calculation(&mut buf, &e, HashType::Blake3, &Arc::default(), &Arc::default()).expect_err("hash_calculation succeeded");
+        let r = hash_calculation(&LocalFileSystem, &mut buf, &e, HashType::Blake3, &Arc::default(), &Arc::default()).expect_err("hash_calculation succeeded");
         assert!(!r.is_empty());
         Ok(())
     }
@@ -0,0 +1,88 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::flc;
+use crate::tools::duplicate::DuplicateEntry;
+
+pub const REFERENCE_INDEX_VERSION: u8 = 1;
+const REFERENCE_INDEX_MEMORY_LIMIT: u64 = 8 * 1024 * 1024 * 1024;
+
+// Hash is always calculated with blake3, to be able to compare it with files from any later scan
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceIndexEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub hash: String,
+}
+
+impl ReferenceIndexEntry {
+    pub(crate) fn to_duplicate_entry(&self) -> DuplicateEntry {
+        DuplicateEntry {
+            path: self.path.clone(),
+            modified_date: 0,
+            size: self.size,
+            hash: self.hash.clone(),
+            archive_path: None,
+            from_reference_index: false,
+        }
+    }
+}
+
+// Hashes of files from e.g. archive on offline disk, so later scans can be compared against it without mounting it
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReferenceIndex {
+    pub version: u8,
+    pub entries: Vec<ReferenceIndexEntry>,
+}
+
+impl ReferenceIndex {
+    pub fn new(entries: Vec<ReferenceIndexEntry>) -> Self {
+        Self {
+            version: REFERENCE_INDEX_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file_handler = File::open(path).map_err(|e| flc!("core_failed_to_load_reference_index", file = path.to_string_lossy(), reason = e.to_string()))?;
+        let options = bincode::DefaultOptions::new().with_limit(REFERENCE_INDEX_MEMORY_LIMIT);
+        let reference_index: Self = options
+            .deserialize_from(BufReader::new(file_handler))
+            .map_err(|e| flc!("core_failed_to_load_reference_index", file = path.to_string_lossy(), reason = e.to_string()))?;
+
+        if reference_index.version != REFERENCE_INDEX_VERSION {
+            return Err(flc!(
+                "core_failed_to_load_reference_index",
+                file = path.to_string_lossy(),
+                reason = format!("unsupported version {}, expected {REFERENCE_INDEX_VERSION}", reference_index.version)
+            ));
+        }
+        Ok(reference_index)
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let file_handler = File::create(path).map_err(|e| flc!("core_failed_to_save_reference_index", file = path.to_string_lossy(), reason = e.to_string()))?;
+        let options = bincode::DefaultOptions::new().with_limit(REFERENCE_INDEX_MEMORY_LIMIT);
+        options
+            .serialize_into(BufWriter::new(file_handler), self)
+            .map_err(|e| flc!("core_failed_to_save_reference_index", file = path.to_string_lossy(), reason = e.to_string()))
+    }
+
+    pub(crate) fn sizes(&self) -> BTreeSet<u64> {
+        self.entries.iter().map(|e| e.size).collect()
+    }
+
+    // When archive contains multiple identical files, only first of them is used
+    pub(crate) fn entries_by_size_and_hash(&self) -> HashMap<(u64, &str), &ReferenceIndexEntry> {
+        let mut entries = HashMap::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            entries.entry((entry.size, entry.hash.as_str())).or_insert(entry);
+        }
+        entries
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+use bincode::Options;
+use fun_time::fun_time;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::common::cache::CACHE_SCAN_SNAPSHOT_VERSION;
+use crate::common::config_cache_path::open_cache_folder;
+use crate::common::dir_traversal::FolderSnapshot;
+use crate::flc;
+use crate::helpers::messages::Messages;
+use crate::tools::duplicate::DuplicateEntry;
+
+const SNAPSHOT_MEMORY_LIMIT: u64 = 8 * 1024 * 1024 * 1024;
+
+// Files used to calculate groups of single size - path and modification date, sorted by path
+pub(crate) type SizeGroupMembers = Vec<(PathBuf, u64)>;
+
+// State of previous duplicate scan, used to only check folders and size groups that changed since then
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DuplicateScanSnapshot {
+    pub settings: String,
+    pub folders: FolderSnapshot,
+    // File size -> (files with this size used in hashing, groups of files with identical hashes)
+    pub hash_groups: BTreeMap<u64, (SizeGroupMembers, Vec<Vec<DuplicateEntry>>)>,
+}
+
+impl DuplicateScanSnapshot {
+    pub(crate) fn new(settings: String) -> Self {
+        Self { settings, ..Default::default() }
+    }
+}
+
+pub(crate) fn size_group_members(entries: &[DuplicateEntry]) -> SizeGroupMembers {
+    let mut members: SizeGroupMembers = entries.iter().map(|e| (e.path.clone(), e.modified_date)).collect();
+    members.sort_unstable();
+    members
+}
+
+// Snapshot depends on all settings that changes list of collected files, so every set of settings have its own file
+pub fn get_duplicate_scan_snapshot_file(settings: &str) -> String {
+    let settings_hash: String = blake3::hash(settings.as_bytes()).to_hex().chars().take(16).collect();
+    format!("scan_snapshot_duplicates_{settings_hash}_{CACHE_SCAN_SNAPSHOT_VERSION}.bin")
+}
+
+#[fun_time(message = "load_duplicate_scan_snapshot", level = "debug")]
+pub(crate) fn load_duplicate_scan_snapshot(settings: &str) -> (Messages, Option<DuplicateScanSnapshot>) {
+    let mut text_messages = Messages::new();
+    let snapshot_file_name = get_duplicate_scan_snapshot_file(settings);
+
+    let Some(((Some(file_handler), snapshot_file), _)) = open_cache_folder(&snapshot_file_name, false, false, &mut text_messages.warnings) else {
+        debug!("Scan snapshot \"{snapshot_file_name}\" not exists, full scan will be performed");
+        return (text_messages, None);
+    };
+
+    let options = bincode::DefaultOptions::new().with_limit(SNAPSHOT_MEMORY_LIMIT);
+    let snapshot: DuplicateScanSnapshot = match options.deserialize_from(BufReader::new(file_handler)) {
+        Ok(t) => t,
+        Err(e) => {
+            text_messages
+                .warnings
+                .push(flc!("core_failed_to_load_data_from_cache", file = snapshot_file.to_string_lossy(), reason = e.to_string()));
+            return (text_messages, None);
+        }
+    };
+
+    // Different settings may in very rare cases have same hash in file name
+    if snapshot.settings != settings {
+        debug!("Scan snapshot \"{snapshot_file_name}\" was created with different settings, ignoring it");
+        return (text_messages, None);
+    }
+
+    debug!(
+        "Loaded scan snapshot \"{snapshot_file_name}\" with {} folders and {} size groups",
+        snapshot.folders.folders.len(),
+        snapshot.hash_groups.len()
+    );
+    (text_messages, Some(snapshot))
+}
+
+#[fun_time(message = "save_duplicate_scan_snapshot", level = "debug")]
+pub(crate) fn save_duplicate_scan_snapshot(snapshot: &DuplicateScanSnapshot) -> Messages {
+    let mut text_messages = Messages::new();
+    let snapshot_file_name = get_duplicate_scan_snapshot_file(&snapshot.settings);
+
+    let Some(((Some(file_handler), snapshot_file), _)) = open_cache_folder(&snapshot_file_name, true, false, &mut text_messages.warnings) else {
+        return text_messages;
+    };
+
+    let options = bincode::DefaultOptions::new().with_limit(SNAPSHOT_MEMORY_LIMIT);
+    if let Err(e) = options.serialize_into(BufWriter::new(file_handler), snapshot) {
+        text_messages
+            .warnings
+            .push(flc!("core_failed_to_write_data_to_cache", file = snapshot_file.to_string_lossy(), reason = e.to_string()));
+        return text_messages;
+    }
+
+    debug!(
+        "Saved scan snapshot \"{snapshot_file_name}\" with {} folders and {} size groups",
+        snapshot.folders.folders.len(),
+        snapshot.hash_groups.len()
+    );
+    text_messages
+}
@@ -1,13 +1,44 @@
+use std::collections::BTreeSet;
 use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use tempfile::TempDir;
 
-use crate::common::model::{CheckingMethod, HashType};
-use crate::common::tool_data::CommonData;
-use crate::common::traits::Search;
-use crate::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters};
+use crate::common::cache::tests::setup_cache_path;
+use crate::common::config_cache_path::get_config_cache_path;
+use crate::common::file_system::{FileSystem, FsDirEntry, FsFile, FsMetadata, LocalFileSystem};
+use crate::common::import::import_results_instead_of_search;
+use crate::common::journal::{JournalAction, list_journal_sessions};
+use crate::common::model::{CheckingMethod, HashType, WorkContinueStatus};
+use crate::common::ndjson::NdjsonItem;
+use crate::common::progress_data::CurrentStage;
+use crate::common::tool_data::{CommonData, DeleteMethod};
+use crate::common::traits::{DeletingItems, ImportingResults, PrintResults, ResultEntry, Search, WatchingResults};
+use crate::common::watch::WatchChangeKind;
+use crate::tools::duplicate::core::{get_duplicate_cache_file, get_duplicate_partial_cache_file};
+use crate::tools::duplicate::reference_index::ReferenceIndex;
+use crate::tools::duplicate::snapshot::get_duplicate_scan_snapshot_file;
+use crate::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateEntry, DuplicateFinder, DuplicateFinderParameters};
+
+fn get_default_parameters() -> DuplicateFinderParameters {
+    DuplicateFinderParameters::new(
+        CheckingMethod::Hash,
+        HashType::Blake3,
+        false,
+        0,
+        0,
+        true,
+        false,
+        false,
+        None,
+        false,
+        DEFAULT_PARTIAL_HASH_SEGMENTS,
+        false,
+    )
+}
 
 #[test]
 fn test_find_duplicates_by_hash() {
@@ -19,7 +50,7 @@ fn test_find_duplicates_by_hash() {
     fs::write(path.join("file2.txt"), b"duplicate content").unwrap();
     fs::write(path.join("unique.txt"), b"unique content").unwrap();
 
-    let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, 0, 0, true);
+    let params = get_default_parameters();
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_included_paths(vec![path.to_path_buf()]);
@@ -45,7 +76,10 @@ fn test_find_duplicates_by_size() {
     fs::write(path.join("file2.txt"), b"abcde").unwrap();
     fs::write(path.join("unique.txt"), b"123").unwrap();
 
-    let params = DuplicateFinderParameters::new(CheckingMethod::Size, HashType::Blake3, false, 0, 0, true);
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::Size,
+        ..get_default_parameters()
+    };
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_included_paths(vec![path.to_path_buf()]);
@@ -76,7 +110,10 @@ fn test_find_duplicates_by_name() {
     fs::write(dir2.join("duplicate.txt"), b"content2").unwrap();
     fs::write(dir1.join("unique.txt"), b"unique").unwrap();
 
-    let params = DuplicateFinderParameters::new(CheckingMethod::Name, HashType::Blake3, false, 0, 0, true);
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::Name,
+        ..get_default_parameters()
+    };
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_recursive_search(true);
@@ -100,14 +137,11 @@ fn test_case_insensitive_name_comparison() {
     fs::write(path.join("TEST.txt"), b"content1").unwrap();
     fs::write(path.join("test.txt"), b"content2").unwrap();
 
-    let params = DuplicateFinderParameters::new(
-        CheckingMethod::Name,
-        HashType::Blake3,
-        false,
-        0,
-        0,
-        false, // case insensitive
-    );
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::Name,
+        case_sensitive_name_comparison: false,
+        ..get_default_parameters()
+    };
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_recursive_search(true);
@@ -130,7 +164,7 @@ fn test_no_duplicates_found() {
     fs::write(path.join("file1.txt"), b"content1").unwrap();
     fs::write(path.join("file2.txt"), b"content2").unwrap();
 
-    let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, 0, 0, true);
+    let params = get_default_parameters();
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_included_paths(vec![path.to_path_buf()]);
@@ -157,7 +191,7 @@ fn test_lost_space_calculation() {
     fs::write(path.join("file2.txt"), &content).unwrap();
     fs::write(path.join("file3.txt"), &content).unwrap();
 
-    let params = DuplicateFinderParameters::new(CheckingMethod::Hash, HashType::Blake3, false, 0, 0, true);
+    let params = get_default_parameters();
 
     let mut finder = DuplicateFinder::new(params);
     finder.set_minimal_file_size(0);
@@ -170,3 +204,1002 @@ fn test_lost_space_calculation() {
     let info = finder.get_information();
     assert_eq!(info.lost_space_by_hash, 200, "Should calculate 200 bytes lost space (2 duplicate files * 100 bytes)");
 }
+
+#[test]
+fn test_incremental_rescan_with_scan_snapshot() {
+    setup_cache_path();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    let sub_dir = path.join("sub");
+    fs::create_dir(&sub_dir).unwrap();
+
+    fs::write(path.join("file1.txt"), b"duplicate content").unwrap();
+    fs::write(sub_dir.join("file2.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("other1.txt"), b"other content 1").unwrap();
+
+    let run_scan = || {
+        let params = DuplicateFinderParameters {
+            use_scan_snapshot: true,
+            ..get_default_parameters()
+        };
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_paths(vec![path.to_path_buf()]);
+        finder.set_minimal_file_size(0);
+        finder.set_use_cache(false);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+        finder
+    };
+
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+    let cache_folder = get_config_cache_path().unwrap().cache_folder;
+    let snapshot_files = fs::read_dir(&cache_folder)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_name().to_string_lossy().starts_with("scan_snapshot_duplicates_"))
+        .count();
+    assert!(snapshot_files >= 1, "Scan snapshot should be saved in cache folder");
+
+    // Nothing changed, so groups are taken from snapshot
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+    assert_eq!(finder.get_information().number_of_duplicated_files_by_hash, 1);
+
+    // New file in subfolder changes only its size group
+    fs::write(sub_dir.join("other2.txt"), b"other content 1").unwrap();
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 2);
+    assert_eq!(finder.get_information().number_of_duplicated_files_by_hash, 2);
+
+    // Removed file is noticed, because its folder modification date changes
+    fs::remove_file(path.join("file1.txt")).unwrap();
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+}
+
+#[test]
+fn test_scan_snapshot_files_modified_in_place_are_hashed_again() {
+    setup_cache_path();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let set_modified_time = |name: &str, time: i64| filetime::set_file_mtime(path.join(name), filetime::FileTime::from_unix_time(time, 0)).unwrap();
+    fs::write(path.join("original.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("copy.txt"), b"duplicate content").unwrap();
+    set_modified_time("original.txt", 1_000_000);
+    set_modified_time("copy.txt", 2_000_000);
+
+    let run_scan = || {
+        let params = DuplicateFinderParameters {
+            use_scan_snapshot: true,
+            ..get_default_parameters()
+        };
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_paths(vec![path.to_path_buf()]);
+        finder.set_minimal_file_size(0);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+        finder
+    };
+    assert_eq!(run_scan().get_information().number_of_groups_by_hash, 1);
+
+    // Editing file in place doesn't change modification date of its folder, but file from snapshot is checked again and hashed with its new content
+    fs::write(path.join("copy.txt"), b"modified content!").unwrap();
+    set_modified_time("copy.txt", 3_000_000);
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 0);
+    assert!(finder.get_files_sorted_by_hash().is_empty());
+
+    // Group is found again after restoring content of file
+    fs::write(path.join("copy.txt"), b"duplicate content").unwrap();
+    set_modified_time("copy.txt", 4_000_000);
+    let finder = run_scan();
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+    assert!(finder.get_text_messages().warnings.is_empty());
+}
+
+#[test]
+fn test_scan_snapshot_size_groups_skip_files_changed_since_scan() {
+    setup_cache_path();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("original.txt"), b"same size 1").unwrap();
+    fs::write(path.join("copy.txt"), b"same size 2").unwrap();
+    filetime::set_file_mtime(path.join("original.txt"), filetime::FileTime::from_unix_time(1_000_000, 0)).unwrap();
+
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::Size,
+        use_scan_snapshot: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+    assert_eq!(finder.get_information().number_of_groups_by_size, 1);
+
+    fs::write(path.join("copy.txt"), b"different size").unwrap();
+    finder.set_delete_method(DeleteMethod::AllExceptOldest);
+    assert_eq!(finder.delete_files(&Arc::new(AtomicBool::new(false)), None), WorkContinueStatus::Continue);
+    assert!(path.join("copy.txt").exists());
+    assert!(finder.get_text_messages().warnings.iter().any(|warning| warning.contains("copy.txt")));
+}
+
+#[test]
+fn test_scan_snapshot_file_name_depends_on_settings() {
+    assert_ne!(get_duplicate_scan_snapshot_file("settings_1"), get_duplicate_scan_snapshot_file("settings_2"));
+    assert_eq!(get_duplicate_scan_snapshot_file("settings_1"), get_duplicate_scan_snapshot_file("settings_1"));
+}
+
+#[test]
+fn test_find_duplicates_by_hash_with_byte_verification() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    fs::write(path.join("file1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file2.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file3.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("unique.txt"), b"unique content").unwrap();
+
+    let params = DuplicateFinderParameters {
+        hash_type: HashType::Crc32,
+        verify_byte_by_byte: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    let info = finder.get_information();
+    assert_eq!(info.number_of_groups_by_hash, 1);
+    assert_eq!(info.number_of_duplicated_files_by_hash, 2);
+}
+
+#[test]
+fn test_byte_verification_splits_colliding_groups() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    // Same size, but different content - simulates hash collision
+    let contents: [&[u8]; 4] = [b"content A", b"content B", b"content A", b"content B"];
+    let entries: Vec<DuplicateEntry> = contents
+        .iter()
+        .enumerate()
+        .map(|(idx, content)| {
+            let file_path = path.join(format!("file{idx}.txt"));
+            fs::write(&file_path, content).unwrap();
+            DuplicateEntry {
+                path: file_path,
+                size: content.len() as u64,
+                hash: "colliding_hash".to_string(),
+                ..Default::default()
+            }
+        })
+        .collect();
+    let lonely_entries = vec![entries[0].clone(), entries[1].clone()];
+
+    let params = DuplicateFinderParameters {
+        hash_type: HashType::Crc32,
+        verify_byte_by_byte: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.files_with_identical_hashes.insert(9, vec![entries, lonely_entries]);
+
+    let status = finder.verify_hash_groups_byte_by_byte(&Arc::new(AtomicBool::new(false)), None);
+    assert_eq!(status, WorkContinueStatus::Continue);
+
+    let groups = &finder.get_files_sorted_by_hash()[&9];
+    assert_eq!(groups.len(), 2);
+    for group in groups {
+        assert_eq!(group.len(), 2);
+        assert_eq!(fs::read(&group[0].path).unwrap(), fs::read(&group[1].path).unwrap());
+    }
+}
+
+#[test]
+fn test_byte_verification_with_unreadable_first_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("file1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file2.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file3.txt"), b"duplicate content").unwrap();
+    // First file was removed after hashing, so it cannot be used as a pattern
+    let entries = ["missing.txt", "file1.txt", "file2.txt", "file3.txt"]
+        .iter()
+        .map(|name| DuplicateEntry {
+            path: path.join(name),
+            size: 17,
+            ..Default::default()
+        })
+        .collect();
+
+    let params = DuplicateFinderParameters {
+        hash_type: HashType::Crc32,
+        verify_byte_by_byte: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.files_with_identical_hashes.insert(17, vec![entries]);
+
+    let status = finder.verify_hash_groups_byte_by_byte(&Arc::new(AtomicBool::new(false)), None);
+    assert_eq!(status, WorkContinueStatus::Continue);
+
+    let groups = &finder.get_files_sorted_by_hash()[&17];
+    assert_eq!(groups.len(), 1);
+    let mut names: Vec<_> = groups[0].iter().map(|entry| entry.path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    names.sort();
+    assert_eq!(names, ["file1.txt", "file2.txt", "file3.txt"]);
+    assert_eq!(finder.get_text_messages().warnings.len(), 1);
+}
+
+#[test]
+fn test_byte_verification_respects_stop_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("file1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("file2.txt"), b"duplicate content").unwrap();
+    let entries = ["file1.txt", "file2.txt"]
+        .iter()
+        .map(|name| DuplicateEntry {
+            path: path.join(name),
+            size: 17,
+            ..Default::default()
+        })
+        .collect();
+
+    let params = DuplicateFinderParameters {
+        hash_type: HashType::Crc32,
+        verify_byte_by_byte: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.files_with_identical_hashes.insert(17, vec![entries]);
+
+    let status = finder.verify_hash_groups_byte_by_byte(&Arc::new(AtomicBool::new(true)), None);
+    assert_eq!(status, WorkContinueStatus::Stop);
+}
+
+#[test]
+fn test_reference_index_matches_files_from_archive() {
+    let archive_dir = TempDir::new().unwrap();
+    let scanned_dir = TempDir::new().unwrap();
+    let index_dir = TempDir::new().unwrap();
+    let index_file = index_dir.path().join("archive.bin");
+
+    fs::write(archive_dir.path().join("photo.jpg"), b"archived photo").unwrap();
+    fs::write(archive_dir.path().join("document.txt"), b"archived document").unwrap();
+    fs::write(archive_dir.path().join("other.txt"), b"archived other").unwrap();
+
+    let params = get_default_parameters();
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![archive_dir.path().to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    let reference_index = finder.create_reference_index(&Arc::new(AtomicBool::new(false)), None).unwrap();
+    assert_eq!(reference_index.entries.len(), 3);
+    reference_index.save_to_file(&index_file).unwrap();
+
+    let loaded_index = ReferenceIndex::load_from_file(&index_file).unwrap();
+    assert_eq!(loaded_index.entries.len(), 3);
+
+    // Single copy of archived file must be found too, even if it has no duplicate in scanned folder
+    fs::write(scanned_dir.path().join("photo_copy.jpg"), b"archived photo").unwrap();
+    fs::write(scanned_dir.path().join("document_1.txt"), b"archived document").unwrap();
+    fs::write(scanned_dir.path().join("document_2.txt"), b"archived document").unwrap();
+    fs::write(scanned_dir.path().join("new.txt"), b"not archived").unwrap();
+    fs::write(scanned_dir.path().join("new_copy.txt"), b"not archived").unwrap();
+
+    let params = DuplicateFinderParameters {
+        reference_index_path: Some(index_file),
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![scanned_dir.path().to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(finder.get_text_messages().critical.is_none());
+    assert!(finder.get_use_reference());
+    let groups: Vec<_> = finder.get_files_with_identical_hashes_referenced().values().flatten().collect();
+    assert_eq!(groups.len(), 2);
+    for (reference, files) in groups {
+        assert!(reference.from_reference_index);
+        assert!(files.iter().all(|e| !e.from_reference_index));
+        // Flag is saved in results, so files from index are recognized after loading them
+        let item = NdjsonItem::new(reference, true);
+        assert_eq!(item.details.get("from_reference_index"), Some(&serde_json::Value::Bool(true)));
+        assert!(item.is_read_only());
+        assert!(DuplicateEntry::from(&item).from_reference_index);
+        assert!(reference.path.starts_with(archive_dir.path()));
+        assert!(files.iter().all(|e| e.path.starts_with(scanned_dir.path())));
+        let expected_files = if reference.path.ends_with("photo.jpg") { 1 } else { 2 };
+        assert_eq!(files.len(), expected_files);
+    }
+}
+
+#[test]
+fn test_missing_reference_index_sets_critical_error() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("file1.txt"), b"content").unwrap();
+
+    let params = DuplicateFinderParameters {
+        reference_index_path: Some(temp_dir.path().join("not_existing_index.bin")),
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![temp_dir.path().to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(finder.get_text_messages().critical.is_some());
+    assert!(finder.get_files_with_identical_hashes_referenced().is_empty());
+}
+
+#[test]
+fn test_reference_index_prehashes_files_with_other_sizes() {
+    let archive_dir = TempDir::new().unwrap();
+    let scanned_dir = TempDir::new().unwrap();
+    let index_dir = TempDir::new().unwrap();
+    let index_file = index_dir.path().join("archive.bin");
+
+    fs::write(archive_dir.path().join("photo.jpg"), b"archived photo").unwrap();
+    let params = get_default_parameters();
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![archive_dir.path().to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    finder
+        .create_reference_index(&Arc::new(AtomicBool::new(false)), None)
+        .unwrap()
+        .save_to_file(&index_file)
+        .unwrap();
+
+    fs::write(scanned_dir.path().join("photo_copy.jpg"), b"archived photo").unwrap();
+    // Same size, but different beginning - prehash must still drop them before full hashing
+    fs::write(scanned_dir.path().join("first.txt"), b"first content").unwrap();
+    fs::write(scanned_dir.path().join("other.txt"), b"other content").unwrap();
+
+    let params = DuplicateFinderParameters {
+        reference_index_path: Some(index_file),
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![scanned_dir.path().to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    let (progress_sender, progress_receiver) = crossbeam_channel::unbounded();
+    finder.search(&Arc::new(AtomicBool::new(false)), Some(&progress_sender));
+
+    assert!(finder.get_text_messages().critical.is_none());
+    let stages: Vec<_> = progress_receiver.try_iter().collect();
+    let prehashed = stages.iter().find(|p| p.sstage == CurrentStage::DuplicatePreHashing).map(|p| p.entries_to_check);
+    assert_eq!(prehashed, Some(2));
+    let full_hashed = stages.iter().find(|p| p.sstage == CurrentStage::DuplicateFullHashing).map(|p| p.entries_to_check);
+    assert_eq!(full_hashed, Some(1));
+    assert_eq!(finder.get_files_with_identical_hashes_referenced().values().flatten().count(), 1);
+}
+
+fn create_folder_with_files(folder: &std::path::Path, files: &[(&str, &[u8])]) {
+    for (name, content) in files {
+        let file_path = folder.join(name);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(file_path, content).unwrap();
+    }
+}
+
+fn find_duplicate_folders_in(path: &std::path::Path, delete_method: DeleteMethod) -> DuplicateFinder {
+    setup_cache_path();
+    let params = DuplicateFinderParameters {
+        find_duplicate_folders: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    finder.set_delete_method(delete_method);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+    finder
+}
+
+#[test]
+fn test_find_duplicate_folders() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let photos: &[(&str, &[u8])] = &[("a.jpg", b"photo a"), ("b.jpg", b"photo b"), ("nested/c.jpg", b"photo c")];
+    create_folder_with_files(&path.join("photos"), photos);
+    create_folder_with_files(&path.join("backup/photos_copy"), photos);
+    create_folder_with_files(&path.join("partial"), &[("a.jpg", b"photo a"), ("nested/c.jpg", b"photo c")]);
+    create_folder_with_files(&path.join("other"), &[("unique.txt", b"unique content")]);
+
+    let finder = find_duplicate_folders_in(path, DeleteMethod::None);
+
+    let groups = finder.get_duplicated_folders();
+    assert_eq!(groups.len(), 1, "Only topmost duplicated folders should be reported - {groups:?}");
+    let group_folders: Vec<_> = groups[0].folders.iter().map(|e| e.path.clone()).collect();
+    assert_eq!(group_folders, vec![path.join("backup/photos_copy"), path.join("photos")]);
+    assert!(groups[0].identical_names);
+    assert_eq!(groups[0].folders[0].files_number, 3);
+    assert_eq!(groups[0].folders[0].size, 21);
+
+    let info = finder.get_information();
+    assert_eq!(info.number_of_groups_by_folders, 1);
+    assert_eq!(info.number_of_duplicated_folders, 1);
+    assert_eq!(info.lost_space_by_folders, 21);
+
+    let mut subsets: Vec<_> = finder.get_folder_subsets().iter().map(|e| (e.subset.path.clone(), e.superset.path.clone())).collect();
+    subsets.sort();
+    assert_eq!(
+        subsets,
+        vec![(path.join("partial"), path.join("backup/photos_copy")), (path.join("partial"), path.join("photos"))]
+    );
+}
+
+#[test]
+fn test_find_duplicate_folders_with_renamed_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    create_folder_with_files(&path.join("first"), &[("a.txt", b"content a"), ("b.txt", b"content b")]);
+    create_folder_with_files(&path.join("second"), &[("renamed_a.txt", b"content a"), ("b.txt", b"content b")]);
+
+    let finder = find_duplicate_folders_in(path, DeleteMethod::None);
+
+    let groups = finder.get_duplicated_folders();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].folders.len(), 2);
+    assert!(!groups[0].identical_names);
+}
+
+#[test]
+fn test_folders_with_different_number_of_copies_are_not_duplicated() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    create_folder_with_files(&path.join("first"), &[("a.txt", b"content a"), ("b.txt", b"content a"), ("c.txt", b"content b")]);
+    create_folder_with_files(&path.join("second"), &[("a.txt", b"content a"), ("b.txt", b"content b"), ("c.txt", b"content b")]);
+
+    let finder = find_duplicate_folders_in(path, DeleteMethod::None);
+
+    assert!(finder.get_duplicated_folders().is_empty(), "{:?}", finder.get_duplicated_folders());
+}
+
+#[test]
+fn test_folders_with_files_skipped_by_filters_are_not_duplicated() {
+    setup_cache_path();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let files: &[(&str, &[u8])] = &[("a.txt", b"content a"), ("b.txt", b"content b")];
+    create_folder_with_files(&path.join("first"), files);
+    create_folder_with_files(&path.join("second"), files);
+    // Not checked during scan, but would be removed together with folder
+    create_folder_with_files(&path.join("first"), &[("notes.log", b"unique notes"), ("sub/tiny.txt", b"t")]);
+
+    let params = DuplicateFinderParameters {
+        find_duplicate_folders: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_excluded_extensions(vec!["log".to_string()]);
+    finder.set_minimal_file_size(2);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(finder.get_duplicated_folders().is_empty(), "{:?}", finder.get_duplicated_folders());
+    let subsets: Vec<_> = finder.get_folder_subsets().iter().map(|e| (e.subset.path.clone(), e.superset.path.clone())).collect();
+    assert_eq!(subsets, vec![(path.join("second"), path.join("first"))]);
+    assert_eq!(finder.get_folder_subsets()[0].superset.files_number, 4);
+}
+
+#[test]
+fn test_delete_duplicate_folders() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let files: &[(&str, &[u8])] = &[("a.txt", b"content a"), ("sub/b.txt", b"content b")];
+    create_folder_with_files(&path.join("first"), files);
+    create_folder_with_files(&path.join("second"), files);
+    create_folder_with_files(&path.join("third"), files);
+
+    let finder = find_duplicate_folders_in(path, DeleteMethod::AllExceptNewest);
+
+    assert_eq!(finder.get_duplicated_folders().len(), 1);
+    assert!(finder.get_text_messages().errors.is_empty(), "{:?}", finder.get_text_messages().errors);
+    let remaining_folders = ["first", "second", "third"].iter().filter(|name| path.join(name).exists()).count();
+    assert_eq!(remaining_folders, 1);
+}
+
+#[test]
+fn test_delete_duplicate_folders_skips_changed_folders() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let files: &[(&str, &[u8])] = &[("a.txt", b"content a"), ("b.txt", b"content b")];
+    create_folder_with_files(&path.join("first"), files);
+    create_folder_with_files(&path.join("second"), files);
+
+    let mut finder = find_duplicate_folders_in(path, DeleteMethod::None);
+    assert_eq!(finder.get_duplicated_folders().len(), 1);
+
+    // Files added after scan, must not be removed
+    fs::write(path.join("first/new.txt"), b"new file").unwrap();
+    fs::write(path.join("second/new.txt"), b"new file").unwrap();
+
+    finder.set_delete_method(DeleteMethod::Delete);
+    assert!(finder.delete_files(&Arc::new(AtomicBool::new(false)), None) == WorkContinueStatus::Continue);
+
+    assert!(path.join("first/new.txt").exists());
+    assert!(path.join("second/new.txt").exists());
+    assert_eq!(finder.get_text_messages().errors.len(), 2);
+}
+
+#[test]
+fn test_delete_duplicate_folders_skips_folders_with_replaced_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let files: &[(&str, &[u8])] = &[("a.txt", b"content a"), ("b.txt", b"content b")];
+    create_folder_with_files(&path.join("first"), files);
+    create_folder_with_files(&path.join("second"), files);
+
+    let mut finder = find_duplicate_folders_in(path, DeleteMethod::None);
+    assert_eq!(finder.get_duplicated_folders().len(), 1);
+
+    // Number of files is the same, but one of them is different than in scan
+    for folder in ["first", "second"] {
+        fs::remove_file(path.join(folder).join("b.txt")).unwrap();
+        fs::write(path.join(folder).join("c.txt"), b"new file").unwrap();
+    }
+
+    finder.set_delete_method(DeleteMethod::Delete);
+    assert!(finder.delete_files(&Arc::new(AtomicBool::new(false)), None) == WorkContinueStatus::Continue);
+
+    assert!(path.join("first/c.txt").exists());
+    assert!(path.join("second/c.txt").exists());
+    assert_eq!(finder.get_text_messages().errors.len(), 2);
+}
+
+#[test]
+fn test_delete_duplicate_folders_skips_folders_with_resized_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let files: &[(&str, &[u8])] = &[("a.txt", b"content a"), ("b.txt", b"content b")];
+    create_folder_with_files(&path.join("first"), files);
+    create_folder_with_files(&path.join("second"), files);
+
+    let mut finder = find_duplicate_folders_in(path, DeleteMethod::None);
+    assert_eq!(finder.get_duplicated_folders().len(), 1);
+
+    // Names of files are the same as in scan, but content was changed
+    for folder in ["first", "second"] {
+        fs::write(path.join(folder).join("b.txt"), b"longer new content").unwrap();
+    }
+
+    finder.set_delete_method(DeleteMethod::Delete);
+    assert!(finder.delete_files(&Arc::new(AtomicBool::new(false)), None) == WorkContinueStatus::Continue);
+
+    assert!(path.join("first/b.txt").exists());
+    assert!(path.join("second/b.txt").exists());
+    assert_eq!(finder.get_text_messages().errors.len(), 2);
+}
+
+#[test]
+fn test_find_duplicates_by_partial_hash() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    // With 1 segment, blocks at start, middle and end of file are hashed
+    let content = vec![7u8; 4 * 1024 * 1024];
+    let mut changed_in_skipped_part = content.clone();
+    changed_in_skipped_part[1024 * 1024 + 1024 * 256] = 8;
+    let mut changed_in_hashed_part = content.clone();
+    changed_in_hashed_part[0] = 8;
+    fs::write(path.join("original.bin"), &content).unwrap();
+    fs::write(path.join("changed_in_skipped_part.bin"), &changed_in_skipped_part).unwrap();
+    fs::write(path.join("changed_in_hashed_part.bin"), &changed_in_hashed_part).unwrap();
+
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::PartialHash,
+        partial_hash_segments: 1,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    let info = finder.get_information();
+    assert_eq!(info.number_of_groups_by_hash, 1);
+    assert_eq!(info.number_of_duplicated_files_by_hash, 1);
+    let mut group: Vec<_> = finder.get_files_sorted_by_hash().values().flatten().flatten().map(|e| e.path.clone()).collect();
+    group.sort();
+    assert_eq!(group, vec![path.join("changed_in_skipped_part.bin"), path.join("original.bin")]);
+
+    let mut text_results = Vec::new();
+    finder.write_results(&mut text_results).unwrap();
+    assert!(String::from_utf8(text_results).unwrap().contains("only probable duplicates"));
+
+    let json_file = path.join("results.json");
+    finder.save_results_to_file_as_json(json_file.to_str().unwrap(), false).unwrap();
+    assert!(fs::read_to_string(json_file).unwrap().contains("\"probable\":true"));
+}
+
+#[test]
+fn test_partial_hash_does_not_delete_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("file1.bin"), b"content").unwrap();
+    fs::write(path.join("file2.bin"), b"content").unwrap();
+
+    for delete_method in [DeleteMethod::Delete, DeleteMethod::AllExceptNewest, DeleteMethod::HardLink, DeleteMethod::Reflink] {
+        let params = DuplicateFinderParameters {
+            check_method: CheckingMethod::PartialHash,
+            partial_hash_segments: 1,
+            ..get_default_parameters()
+        };
+        let mut finder = DuplicateFinder::new(params);
+        finder.set_included_paths(vec![path.to_path_buf()]);
+        finder.set_use_cache(false);
+        finder.set_delete_method(delete_method);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+        assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+        assert!(path.join("file1.bin").exists());
+        assert!(path.join("file2.bin").exists());
+        assert_eq!(finder.get_text_messages().warnings.len(), 1, "{delete_method:?}");
+
+        // Deleting after search must be refused too
+        assert_eq!(finder.delete_files(&Arc::new(AtomicBool::new(false)), None), WorkContinueStatus::Continue);
+        assert!(path.join("file1.bin").exists());
+        assert!(path.join("file2.bin").exists());
+        assert_eq!(finder.get_text_messages().warnings.len(), 2, "{delete_method:?}");
+    }
+}
+
+#[test]
+fn test_partial_hash_cache_file_is_separate() {
+    assert_ne!(get_duplicate_partial_cache_file(HashType::Blake3, 8), get_duplicate_cache_file(HashType::Blake3, false));
+    assert_ne!(
+        get_duplicate_partial_cache_file(HashType::Blake3, 8),
+        get_duplicate_partial_cache_file(HashType::Blake3, 16)
+    );
+}
+
+fn find_duplicates_with_archives(path: &Path, delete_method: DeleteMethod) -> DuplicateFinder {
+    let params = DuplicateFinderParameters {
+        check_archives: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.set_delete_method(delete_method);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+    finder
+}
+
+fn create_test_archives(path: &Path) {
+    let mut zip_writer = zip::ZipWriter::new(fs::File::create(path.join("archive.zip")).unwrap());
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip_writer.start_file("inner/zipped.txt", options).unwrap();
+    zip_writer.write_all(b"archived content").unwrap();
+    zip_writer.start_file("other.txt", options).unwrap();
+    zip_writer.write_all(b"other archived content").unwrap();
+    zip_writer.finish().unwrap();
+
+    let gz_encoder = flate2::write::GzEncoder::new(fs::File::create(path.join("archive.tar.gz")).unwrap(), flate2::Compression::default());
+    let mut tar_builder = tar::Builder::new(gz_encoder);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(b"archived content".len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar_builder.append_data(&mut header, "tarred.txt", &b"archived content"[..]).unwrap();
+    tar_builder.into_inner().unwrap().finish().unwrap();
+}
+
+#[test]
+fn test_find_duplicates_inside_archives() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    create_test_archives(path);
+    fs::write(path.join("loose.txt"), b"archived content").unwrap();
+
+    let finder = find_duplicates_with_archives(path, DeleteMethod::None);
+
+    assert!(finder.get_text_messages().warnings.is_empty(), "{:?}", finder.get_text_messages().warnings);
+    let groups: Vec<_> = finder.get_files_sorted_by_hash().values().flatten().collect();
+    assert_eq!(groups.len(), 1);
+    let group = groups[0];
+    assert_eq!(group.len(), 3);
+    assert_eq!(group.iter().filter(|e| e.archive_path.is_some()).count(), 2);
+    assert!(group.iter().any(|e| e.path == path.join("archive.zip!/inner/zipped.txt")));
+    assert!(group.iter().any(|e| e.path == path.join("archive.tar.gz!/tarred.txt")));
+}
+
+// Counts how many times tar archives were opened
+#[derive(Debug, Default)]
+struct TarOpenCounter {
+    opened: AtomicUsize,
+}
+
+impl FileSystem for TarOpenCounter {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<FsDirEntry>>> {
+        LocalFileSystem.read_dir(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        LocalFileSystem.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FsMetadata> {
+        LocalFileSystem.symlink_metadata(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn FsFile>> {
+        if path.extension().is_some_and(|extension| extension == "tar") {
+            self.opened.fetch_add(1, Ordering::Relaxed);
+        }
+        LocalFileSystem.open(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        LocalFileSystem.remove_file(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        LocalFileSystem.rename(from, to)
+    }
+
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        LocalFileSystem.hard_link(original, link)
+    }
+}
+
+#[test]
+fn test_files_inside_tar_archive_are_hashed_in_single_read() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    // Files are bigger than prehash, so only full hash can tell that last file is different
+    let content = vec![b'x'; 10_000];
+    let mut other_content = content.clone();
+    other_content[9_999] = b'y';
+    let mut tar_builder = tar::Builder::new(fs::File::create(path.join("archive.tar")).unwrap());
+    for (name, data) in [("a.txt", &content), ("b.txt", &content), ("c.txt", &other_content)] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar_builder.append_data(&mut header, name, data.as_slice()).unwrap();
+    }
+    tar_builder.into_inner().unwrap();
+    fs::write(path.join("loose.txt"), &content).unwrap();
+
+    let file_system = Arc::new(TarOpenCounter::default());
+    let mut finder = DuplicateFinder::new(DuplicateFinderParameters {
+        check_archives: true,
+        ..get_default_parameters()
+    });
+    finder.set_file_system(file_system.clone());
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(finder.get_text_messages().warnings.is_empty(), "{:?}", finder.get_text_messages().warnings);
+    let groups: Vec<_> = finder.get_files_sorted_by_hash().values().flatten().collect();
+    assert_eq!(groups.len(), 1);
+    let mut group_paths: Vec<_> = groups[0].iter().map(|e| e.path.clone()).collect();
+    group_paths.sort();
+    assert_eq!(group_paths, [path.join("archive.tar!/a.txt"), path.join("archive.tar!/b.txt"), path.join("loose.txt")]);
+    // Once to list files and once to hash all of them
+    assert_eq!(file_system.opened.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_files_inside_archives_are_not_deleted() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    create_test_archives(path);
+    fs::write(path.join("loose.txt"), b"archived content").unwrap();
+    fs::write(path.join("loose_copy.txt"), b"archived content").unwrap();
+    // Archives are newer than loose files, so files inside them are selected to delete
+    let old_time = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    fs::File::options().write(true).open(path.join("loose.txt")).unwrap().set_modified(old_time).unwrap();
+
+    let finder = find_duplicates_with_archives(path, DeleteMethod::AllExceptOldest);
+
+    assert!(finder.get_text_messages().errors.is_empty(), "{:?}", finder.get_text_messages().errors);
+    assert!(path.join("archive.zip").exists());
+    assert!(path.join("archive.tar.gz").exists());
+    assert!(path.join("loose.txt").exists());
+    assert!(!path.join("loose_copy.txt").exists());
+}
+
+#[test]
+fn test_check_archives_is_ignored_outside_hash_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    create_test_archives(path);
+
+    let params = DuplicateFinderParameters {
+        check_method: CheckingMethod::Size,
+        check_archives: true,
+        ..get_default_parameters()
+    };
+    let mut finder = DuplicateFinder::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(!finder.get_params().check_archives);
+    assert_eq!(finder.get_text_messages().warnings.len(), 1);
+}
+
+#[test]
+fn test_import_saved_results_and_delete() {
+    setup_cache_path();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    for name in ["a1.txt", "a2.txt", "a3.txt"] {
+        fs::write(path.join(name), b"duplicate content").unwrap();
+    }
+    fs::write(path.join("b1.txt"), b"other content").unwrap();
+    fs::write(path.join("b2.txt"), b"other content").unwrap();
+    let results_file = path.join("results.json");
+
+    let mut finder = DuplicateFinder::new(get_default_parameters());
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+    finder.save_results_to_file_as_json(&results_file.to_string_lossy(), false).unwrap();
+
+    // Modified file is dropped, so its group no longer contains any duplicates
+    fs::write(path.join("b2.txt"), b"changed content").unwrap();
+
+    let mut imported = DuplicateFinder::new(get_default_parameters());
+    let summary = imported.import_results(&results_file).unwrap();
+    assert_eq!(summary.loaded_entries, 3);
+    assert_eq!(summary.changed_entries, 1);
+    let info = imported.get_information();
+    assert_eq!(info.number_of_groups_by_hash, 1);
+    assert_eq!(info.number_of_duplicated_files_by_hash, 2);
+
+    let mut imported = DuplicateFinder::new(get_default_parameters());
+    imported.set_delete_method(DeleteMethod::AllExceptNewest);
+    import_results_instead_of_search(&mut imported, &results_file, &Arc::new(AtomicBool::new(false)), None);
+    assert!(imported.get_text_messages().critical.is_none());
+    let remaining = ["a1.txt", "a2.txt", "a3.txt"].iter().filter(|name| path.join(name).exists()).count();
+    assert_eq!(remaining, 1);
+}
+
+#[test]
+fn test_import_results_saved_with_other_method_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let results_file = temp_dir.path().join("results.json");
+    fs::write(&results_file, br#"[{"path":"/a","size":1,"modified_date":2}]"#).unwrap();
+
+    let mut imported = DuplicateFinder::new(get_default_parameters());
+    import_results_instead_of_search(&mut imported, &results_file, &Arc::new(AtomicBool::new(false)), None);
+    assert!(imported.get_text_messages().critical.is_some());
+}
+
+fn watching_finder(path: &Path) -> DuplicateFinder {
+    setup_cache_path();
+    let mut finder = DuplicateFinder::new(get_default_parameters());
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    finder.search(&stop_flag, None);
+    finder.prepare_watching(&stop_flag).unwrap();
+    finder
+}
+
+#[test]
+fn test_watch_changes_update_duplicate_groups() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("a1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("a2.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("b1.txt"), b"other content").unwrap();
+    let mut finder = watching_finder(path);
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+
+    fs::write(path.join("a3.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("b2.txt"), b"other content").unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("a3.txt"), path.join("b2.txt")]));
+    let mut added: Vec<_> = update
+        .events
+        .iter()
+        .filter(|event| event.change == WatchChangeKind::Added)
+        .map(|event| event.item.path.clone())
+        .collect();
+    added.sort();
+    assert_eq!(added, [path.join("a3.txt"), path.join("b1.txt"), path.join("b2.txt")]);
+    assert_eq!(update.events.len(), 3);
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 2);
+    assert_eq!(finder.get_information().number_of_duplicated_files_by_hash, 3);
+
+    // Removing file leaves single file in group, so whole group disappears
+    fs::remove_file(path.join("b1.txt")).unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("b1.txt")]));
+    assert!(update.events.iter().all(|event| event.change == WatchChangeKind::Removed));
+    let mut removed: Vec<_> = update.events.iter().map(|event| event.item.path.clone()).collect();
+    removed.sort();
+    assert_eq!(removed, [path.join("b1.txt"), path.join("b2.txt")]);
+    assert_eq!(finder.get_information().number_of_groups_by_hash, 1);
+}
+
+#[test]
+fn test_watch_changes_hardlink_new_duplicates() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("a1.txt"), b"duplicate content").unwrap();
+    fs::write(path.join("a2.txt"), b"duplicate content").unwrap();
+    let mut finder = watching_finder(path);
+    finder.set_hardlink_new_duplicates(true);
+
+    fs::write(path.join("a3.txt"), b"duplicate content").unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("a3.txt")]));
+    let events: Vec<_> = update.events.iter().map(|event| (event.change, event.item.path.clone())).collect();
+    assert_eq!(events, [(WatchChangeKind::Added, path.join("a3.txt")), (WatchChangeKind::Hardlinked, path.join("a3.txt"))]);
+    assert_eq!(fs::read(path.join("a3.txt")).unwrap(), b"duplicate content");
+
+    let journal_entries: Vec<_> = list_journal_sessions()
+        .unwrap()
+        .into_iter()
+        .flat_map(|session| session.entries)
+        .filter(|entry| entry.original_path == path.join("a3.txt"))
+        .collect();
+    assert_eq!(journal_entries.len(), 1);
+    assert_eq!(journal_entries[0].action, JournalAction::HardLink);
+    assert!(journal_entries[0].target.as_ref().is_some_and(|target| target.starts_with(path)));
+
+    // Hardlink replacing file is noticed as change, but file is still same duplicate
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("a3.txt")]));
+    assert!(update.events.is_empty());
+}
+
+#[test]
+fn test_watch_changes_does_not_hardlink_files_with_colliding_hashes() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    setup_cache_path();
+    let mut finder = DuplicateFinder::new(DuplicateFinderParameters {
+        hash_type: HashType::Crc32,
+        ..get_default_parameters()
+    });
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_minimal_file_size(0);
+    finder.set_use_cache(false);
+    // Both texts have same CRC32 checksum
+    fs::write(path.join("a1.txt"), b"plumless").unwrap();
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    finder.search(&stop_flag, None);
+    finder.prepare_watching(&stop_flag).unwrap();
+    finder.set_hardlink_new_duplicates(true);
+
+    fs::write(path.join("a2.txt"), b"buckeroo").unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("a2.txt")]));
+    assert!(update.events.iter().all(|event| event.change != WatchChangeKind::Hardlinked), "{:?}", update.events);
+    assert_eq!(update.warnings.len(), 1, "{:?}", update.warnings);
+    assert_eq!(fs::read(path.join("a1.txt")).unwrap(), b"plumless");
+    assert_eq!(fs::read(path.join("a2.txt")).unwrap(), b"buckeroo");
+}
+
+#[test]
+fn test_watch_requires_hash_checking_method() {
+    let mut finder = DuplicateFinder::new(DuplicateFinderParameters {
+        check_method: CheckingMethod::Size,
+        ..get_default_parameters()
+    });
+    finder.prepare_watching(&Arc::new(AtomicBool::new(false))).unwrap_err();
+}
@@ -1,5 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::prelude::*;
 use std::io::{self};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -7,27 +10,187 @@ use std::time::Instant;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use humansize::{BINARY, format_size};
+use serde::{Deserialize, Serialize};
 
-use crate::common::model::{CheckingMethod, WorkContinueStatus};
+use crate::common::import::{ImportSummary, SavedResults, load_json_results};
+use crate::common::model::{CheckingMethod, HashType, WorkContinueStatus};
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters, Info};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search, WatchingResults};
+use crate::common::watch::WatchUpdate;
+use crate::flc;
+use crate::tools::duplicate::folders::DuplicateFolderGroup;
+use crate::tools::duplicate::{DuplicateEntry, DuplicateFinder, DuplicateFinderParameters, Info};
+
+impl ImportingResults for DuplicateFinder {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.information = Info::default();
+
+        if self.get_params().find_duplicate_folders {
+            let groups: Vec<DuplicateFolderGroup> = load_json_results(file_name)?;
+            self.duplicated_folders = groups
+                .into_iter()
+                .filter_map(|group| {
+                    let identical_names = group.identical_names;
+                    summary.filter_group(group.folders).map(|folders| DuplicateFolderGroup { folders, identical_names })
+                })
+                .collect();
+            self.calculate_folder_stats();
+            return Ok(summary);
+        }
+
+        match self.get_params().check_method {
+            CheckingMethod::Name => match load_json_results(file_name)? {
+                SavedResults::Referenced(groups) => {
+                    self.set_use_reference_folders(true);
+                    self.files_with_identical_names_referenced = filter_map_values(groups, |group| summary.filter_referenced_group(group))?;
+                }
+                SavedResults::Normal(groups) => {
+                    self.set_use_reference_folders(false);
+                    self.files_with_identical_names = filter_map_values(groups, |group| summary.filter_group(group))?;
+                }
+            },
+            // Keys are not saved, so they are recreated from first file of group, because they only need to be unique
+            CheckingMethod::SizeName => match load_json_results::<SavedGroups>(file_name)? {
+                SavedResults::Referenced(groups) => {
+                    self.set_use_reference_folders(true);
+                    self.files_with_identical_size_names_referenced = summary
+                        .filter_referenced_groups(groups)
+                        .into_iter()
+                        .map(|(reference, group)| ((reference.size, reference.path.to_string_lossy().to_string()), (reference, group)))
+                        .collect();
+                }
+                SavedResults::Normal(groups) => {
+                    self.set_use_reference_folders(false);
+                    #[expect(clippy::indexing_slicing)] // Safe, because filtered groups have at least 2 items
+                    let files_with_identical_size_names = summary
+                        .filter_groups(groups)
+                        .into_iter()
+                        .map(|group| ((group[0].size, group[0].path.to_string_lossy().to_string()), group))
+                        .collect();
+                    self.files_with_identical_size_names = files_with_identical_size_names;
+                }
+            },
+            CheckingMethod::Size => match load_json_results(file_name)? {
+                SavedResults::Referenced(groups) => {
+                    self.set_use_reference_folders(true);
+                    self.files_with_identical_size_referenced = filter_map_values(groups, |group| summary.filter_referenced_group(group))?;
+                }
+                SavedResults::Normal(groups) => {
+                    self.set_use_reference_folders(false);
+                    self.files_with_identical_size = filter_map_values(groups, |group| summary.filter_group(group))?;
+                }
+            },
+            CheckingMethod::Hash | CheckingMethod::PartialHash => {
+                let saved_groups: SavedHashGroups = if self.get_params().check_method == CheckingMethod::PartialHash {
+                    load_json_results::<SavedProbableDuplicates<SavedHashGroups>>(file_name)?.groups
+                } else {
+                    load_json_results(file_name)?
+                };
+                match saved_groups {
+                    SavedResults::Referenced(groups) => {
+                        self.set_use_reference_folders(true);
+                        self.files_with_identical_hashes_referenced =
+                            filter_map_values(groups, |groups| Some(summary.filter_referenced_groups(groups)).filter(|groups| !groups.is_empty()))?;
+                    }
+                    SavedResults::Normal(groups) => {
+                        self.set_use_reference_folders(false);
+                        self.files_with_identical_hashes = filter_map_values(groups, |groups| Some(summary.filter_groups(groups)).filter(|groups| !groups.is_empty()))?;
+                    }
+                }
+            }
+            _ => panic!(),
+        }
+
+        match self.get_params().check_method {
+            CheckingMethod::Name => self.calculate_name_stats(),
+            CheckingMethod::SizeName => self.calculate_size_name_stats(),
+            CheckingMethod::Size => self.calculate_size_stats(),
+            CheckingMethod::Hash | CheckingMethod::PartialHash => self.calculate_hash_stats(),
+            _ => panic!(),
+        }
+        Ok(summary)
+    }
+}
+
+// Untagged enums cannot deserialize numeric map keys directly, so all keys are loaded as strings
+fn filter_map_values<K: FromStr + Ord, V>(map: BTreeMap<String, V>, mut filter: impl FnMut(V) -> Option<V>) -> Result<BTreeMap<K, V>, String> {
+    let mut result = BTreeMap::new();
+    for (key, value) in map {
+        let parsed_key = key.parse().map_err(|_| format!("Invalid group key \"{key}\" in results file"))?;
+        if let Some(value) = filter(value) {
+            result.insert(parsed_key, value);
+        }
+    }
+    Ok(result)
+}
 
 impl AllTraits for DuplicateFinder {}
 
+impl WatchingResults for DuplicateFinder {
+    fn prepare_watching(&mut self, stop_flag: &Arc<AtomicBool>) -> Result<(), String> {
+        self.prepare_watching_duplicates(stop_flag)
+    }
+
+    fn apply_watch_changes(&mut self, changed_paths: &BTreeSet<PathBuf>) -> WatchUpdate {
+        self.update_changed_duplicates(changed_paths)
+    }
+
+    fn finish_watching(&mut self) {
+        self.finish_watching_duplicates();
+    }
+}
+
+// Partial hashes are calculated only from some parts of files, so groups may contain files with different content
+#[derive(Serialize, Debug)]
+struct ProbableDuplicates<'a, T> {
+    probable: bool,
+    groups: &'a T,
+}
+
+impl<'a, T: Serialize> ProbableDuplicates<'a, T> {
+    fn new(groups: &'a T) -> Self {
+        Self { probable: true, groups }
+    }
+}
+
+#[derive(Deserialize)]
+struct SavedProbableDuplicates<T> {
+    groups: T,
+}
+
+type SavedGroups = SavedResults<Vec<Vec<DuplicateEntry>>, Vec<(DuplicateEntry, Vec<DuplicateEntry>)>>;
+type SavedHashGroups = SavedResults<BTreeMap<String, Vec<Vec<DuplicateEntry>>>, BTreeMap<String, Vec<(DuplicateEntry, Vec<DuplicateEntry>)>>>;
+
 impl DeletingItems for DuplicateFinder {
     #[fun_time(message = "delete_files", level = "debug")]
     fn delete_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
         if self.common_data.delete_method == DeleteMethod::None {
             return WorkContinueStatus::Continue;
         }
+        // Probable duplicates may differ in parts that were not hashed, so removing or linking them could lose data
+        if self.get_params().check_method == CheckingMethod::PartialHash {
+            self.common_data.text_messages.warnings.push(flc!("core_partial_hash_cannot_delete"));
+            return WorkContinueStatus::Continue;
+        }
+
+        if self.get_params().find_duplicate_folders {
+            return self.delete_duplicate_folders(stop_flag, progress_sender);
+        }
 
         let files_to_delete = match self.get_params().check_method {
             CheckingMethod::Name => self.files_with_identical_names.values().cloned().collect::<Vec<_>>(),
             CheckingMethod::SizeName => self.files_with_identical_size_names.values().cloned().collect::<Vec<_>>(),
-            CheckingMethod::Hash => self.files_with_identical_hashes.values().flatten().cloned().collect::<Vec<_>>(),
-            CheckingMethod::Size => self.files_with_identical_size.values().cloned().collect::<Vec<_>>(),
+            CheckingMethod::Hash | CheckingMethod::PartialHash => {
+                let groups = self.files_with_identical_hashes.values().flatten().cloned().collect::<Vec<_>>();
+                self.remove_files_changed_since_scan(groups)
+            }
+            CheckingMethod::Size => {
+                let groups = self.files_with_identical_size.values().cloned().collect::<Vec<_>>();
+                self.remove_files_changed_since_scan(groups)
+            }
             _ => panic!(),
         };
         self.delete_advanced_elements_and_add_to_messages(stop_flag, progress_sender, files_to_delete)
@@ -44,6 +207,13 @@ impl Search for DuplicateFinder {
                 return;
             }
             self.common_data.use_reference_folders = !self.common_data.directories.reference_directories.is_empty() || !self.common_data.directories.reference_files.is_empty();
+            if let Err(e) = self.load_reference_index() {
+                self.common_data.text_messages.critical = Some(e);
+                return;
+            }
+            self.check_duplicate_folders_settings();
+            self.check_archives_settings();
+            self.load_scan_snapshot();
 
             match self.get_params().check_method {
                 CheckingMethod::Name => {
@@ -64,7 +234,7 @@ impl Search for DuplicateFinder {
                         return;
                     }
                 }
-                CheckingMethod::Hash => {
+                CheckingMethod::Hash | CheckingMethod::PartialHash => {
                     self.common_data.stopped_search = self.check_files_size(stop_flag, progress_sender) == WorkContinueStatus::Stop;
                     if self.common_data.stopped_search {
                         return;
@@ -73,9 +243,14 @@ impl Search for DuplicateFinder {
                     if self.common_data.stopped_search {
                         return;
                     }
+                    self.common_data.stopped_search = self.find_duplicate_folders(stop_flag) == WorkContinueStatus::Stop;
+                    if self.common_data.stopped_search {
+                        return;
+                    }
                 }
                 _ => panic!(),
             }
+            self.save_scan_snapshot();
             if self.delete_files(stop_flag, progress_sender) == WorkContinueStatus::Stop {
                 self.common_data.stopped_search = true;
             }
@@ -106,6 +281,10 @@ impl DebugPrint for DuplicateFinder {
             "Number of duplicated files by name(in groups) - {} ({})",
             self.information.number_of_duplicated_files_by_name, self.information.number_of_groups_by_name
         );
+        println!(
+            "Number of duplicated folders(in groups) - {} ({})",
+            self.information.number_of_duplicated_folders, self.information.number_of_groups_by_folders
+        );
         println!(
             "Lost space by size - {} ({} bytes)",
             format_size(self.information.lost_space_by_size, BINARY),
@@ -127,6 +306,8 @@ impl DebugPrint for DuplicateFinder {
         println!("Files with identical size names referenced - {}", self.files_with_identical_size_names_referenced.len());
         println!("Files with identical size referenced - {}", self.files_with_identical_size_referenced.len());
         println!("Files with identical hashes referenced - {}", self.files_with_identical_hashes_referenced.len());
+        println!("Folders with counted files - {}", self.folder_files.len());
+        println!("Folder subsets - {}", self.folder_subsets.len());
         println!("Checking Method - {:?}", self.get_params().check_method);
         self.debug_print_common();
         println!("-----------------------------------------");
@@ -260,22 +441,42 @@ impl PrintResults for DuplicateFinder {
                     write!(writer, "Not found any duplicates.")?;
                 }
             }
-            CheckingMethod::Hash => {
+            CheckingMethod::Hash if self.get_params().find_duplicate_folders => {
+                self.write_duplicate_folders_results(writer)?;
+            }
+            CheckingMethod::Hash | CheckingMethod::PartialHash => {
+                let is_partial_hash = self.get_params().check_method == CheckingMethod::PartialHash;
+                if is_partial_hash && (!self.files_with_identical_hashes.is_empty() || !self.files_with_identical_hashes_referenced.is_empty()) {
+                    writeln!(
+                        writer,
+                        "Files were compared only by hashes of {} sampled parts, so found groups are only probable duplicates.",
+                        self.get_params().partial_hash_segments + 2
+                    )?;
+                }
                 if !self.files_with_identical_hashes.is_empty() {
                     writeln!(
                         writer,
-                        "-------------------------------------------------Files with same hashes-------------------------------------------------"
+                        "-------------------------------------------------Files with same {}hashes-------------------------------------------------",
+                        if is_partial_hash { "partial " } else { "" }
                     )?;
                     writeln!(
                         writer,
-                        "Found {} duplicated files which in {} groups which takes {}.",
+                        "Found {} {}duplicated files which in {} groups which takes {}.",
                         self.information.number_of_duplicated_files_by_hash,
+                        if is_partial_hash { "probably " } else { "" },
                         self.information.number_of_groups_by_hash,
                         format_size(self.information.lost_space_by_hash, BINARY)
                     )?;
                     for (size, vectors_vector) in self.files_with_identical_hashes.iter().rev() {
                         for vector in vectors_vector {
-                            writeln!(writer, "\n---- Size {} ({}) - {} files", format_size(*size, BINARY), size, vector.len())?;
+                            writeln!(
+                                writer,
+                                "\n---- Size {} ({}) - {} {}files",
+                                format_size(*size, BINARY),
+                                size,
+                                vector.len(),
+                                if is_partial_hash { "probably duplicated " } else { "" }
+                            )?;
                             for file_entry in vector {
                                 writeln!(writer, "\"{}\"", file_entry.path.to_string_lossy())?;
                             }
@@ -284,19 +485,32 @@ impl PrintResults for DuplicateFinder {
                 } else if !self.files_with_identical_hashes_referenced.is_empty() {
                     writeln!(
                         writer,
-                        "-------------------------------------------------Files with same hashes in referenced folders-------------------------------------------------"
+                        "-------------------------------------------------Files with same {}hashes in referenced folders-------------------------------------------------",
+                        if is_partial_hash { "partial " } else { "" }
                     )?;
                     writeln!(
                         writer,
-                        "Found {} duplicated files which in {} groups which takes {}.",
+                        "Found {} {}duplicated files which in {} groups which takes {}.",
                         self.information.number_of_duplicated_files_by_hash,
+                        if is_partial_hash { "probably " } else { "" },
                         self.information.number_of_groups_by_hash,
                         format_size(self.information.lost_space_by_hash, BINARY)
                     )?;
                     for (size, vectors_vector) in self.files_with_identical_hashes_referenced.iter().rev() {
                         for (file_entry, vector) in vectors_vector {
-                            writeln!(writer, "\n---- Size {} ({}) - {} files", format_size(*size, BINARY), size, vector.len())?;
-                            writeln!(writer, "Reference file - \"{}\"", file_entry.path.to_string_lossy())?;
+                            writeln!(
+                                writer,
+                                "\n---- Size {} ({}) - {} {}files",
+                                format_size(*size, BINARY),
+                                size,
+                                vector.len(),
+                                if is_partial_hash { "probably duplicated " } else { "" }
+                            )?;
+                            if file_entry.from_reference_index {
+                                writeln!(writer, "Already archived at - \"{}\"", file_entry.path.to_string_lossy())?;
+                            } else {
+                                writeln!(writer, "Reference file - \"{}\"", file_entry.path.to_string_lossy())?;
+                            }
                             for file_entry in vector {
                                 writeln!(writer, "\"{}\"", file_entry.path.to_string_lossy())?;
                             }
@@ -322,14 +536,42 @@ impl PrintResults for DuplicateFinder {
                 }
                 CheckingMethod::Size => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size_referenced, pretty_print),
                 CheckingMethod::Hash => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes_referenced, pretty_print),
+                CheckingMethod::PartialHash => {
+                    self.save_results_to_file_as_json_internal(file_name, &ProbableDuplicates::new(&self.files_with_identical_hashes_referenced), pretty_print)
+                }
                 _ => panic!(),
             }
+        } else if self.get_params().find_duplicate_folders {
+            self.save_results_to_file_as_json_internal(file_name, &self.duplicated_folders, pretty_print)
         } else {
             match self.get_params().check_method {
                 CheckingMethod::Name => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_names, pretty_print),
                 CheckingMethod::SizeName => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size_names.values().collect::<Vec<_>>(), pretty_print),
                 CheckingMethod::Size => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_size, pretty_print),
                 CheckingMethod::Hash => self.save_results_to_file_as_json_internal(file_name, &self.files_with_identical_hashes, pretty_print),
+                CheckingMethod::PartialHash => self.save_results_to_file_as_json_internal(file_name, &ProbableDuplicates::new(&self.files_with_identical_hashes), pretty_print),
+                _ => panic!(),
+            }
+        }
+    }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        if self.get_use_reference() {
+            match self.get_params().check_method {
+                CheckingMethod::Name => writer.write_referenced_groups(self.files_with_identical_names_referenced.values()),
+                CheckingMethod::SizeName => writer.write_referenced_groups(self.files_with_identical_size_names_referenced.values()),
+                CheckingMethod::Size => writer.write_referenced_groups(self.files_with_identical_size_referenced.values()),
+                CheckingMethod::Hash | CheckingMethod::PartialHash => writer.write_referenced_groups(self.files_with_identical_hashes_referenced.values().flatten()),
+                _ => panic!(),
+            }
+        } else if self.get_params().find_duplicate_folders {
+            writer.write_groups(self.duplicated_folders.iter().map(|group| &group.folders))
+        } else {
+            match self.get_params().check_method {
+                CheckingMethod::Name => writer.write_groups(self.files_with_identical_names.values()),
+                CheckingMethod::SizeName => writer.write_groups(self.files_with_identical_size_names.values()),
+                CheckingMethod::Size => writer.write_groups(self.files_with_identical_size.values()),
+                CheckingMethod::Hash | CheckingMethod::PartialHash => writer.write_groups(self.files_with_identical_hashes.values().flatten()),
                 _ => panic!(),
             }
         }
@@ -355,10 +597,15 @@ impl CommonData for DuplicateFinder {
     fn get_check_method(&self) -> CheckingMethod {
         self.get_params().check_method
     }
+    fn has_blake3_hashes_of_whole_files(&self) -> bool {
+        self.params.check_method == CheckingMethod::Hash && self.params.hash_type == HashType::Blake3
+    }
     fn found_any_items(&self) -> bool {
         self.get_information().number_of_duplicated_files_by_hash > 0
             || self.get_information().number_of_duplicated_files_by_name > 0
             || self.get_information().number_of_duplicated_files_by_size > 0
             || self.get_information().number_of_duplicated_files_by_size_name > 0
+            || self.get_information().number_of_duplicated_folders > 0
+            || !self.folder_subsets.is_empty()
     }
 }
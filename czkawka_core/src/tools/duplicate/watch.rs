@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use log::debug;
+
+use crate::common::cache::{load_cache_from_file_generalized_by_path, save_cache_to_file_generalized};
+use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
+use crate::common::journal::{Journal, JournalAction};
+use crate::common::make_hard_link_with_file_system;
+use crate::common::model::{CheckingMethod, FileEntry, HashType};
+use crate::common::ndjson::NdjsonItem;
+use crate::common::tool_data::CommonData;
+use crate::common::watch::{WatchChangeKind, WatchEvent, WatchUpdate, collect_changed_files};
+use crate::tools::duplicate::{DuplicateEntry, DuplicateFinder, THREAD_BUFFER, compare_files_content, filter_hard_links, hash_calculation};
+
+// Data needed to update groups of duplicates, after changes of single files
+#[derive(Debug, Default)]
+pub(crate) struct DuplicateWatchState {
+    // All files inside included folders, because new file may be duplicate of any of them
+    files_by_size: BTreeMap<u64, Vec<DuplicateEntry>>,
+    // Hashes of files by path, valid only if size and modification date are still same
+    hashes: BTreeMap<String, DuplicateEntry>,
+    new_hashes_calculated: bool,
+    // Automatic hardlinks are destructive operations, so must be possible to revert like any other
+    journal: Journal,
+}
+
+impl DuplicateFinder {
+    pub(crate) fn prepare_watching_duplicates(&mut self, stop_flag: &Arc<AtomicBool>) -> Result<(), String> {
+        let params = self.get_params();
+        if params.check_method != CheckingMethod::Hash {
+            return Err("Watching folders is supported only when checking duplicates by hash".to_string());
+        }
+        if self.common_data.use_reference_folders || params.reference_index_path.is_some() || params.check_archives || params.find_duplicate_folders {
+            return Err("Watching folders is not supported with reference folders, reference index, archives or duplicated folders".to_string());
+        }
+
+        let result = DirTraversalBuilder::new()
+            .common_data(&self.common_data)
+            .group_by(|fe| fe.size)
+            .stop_flag(stop_flag)
+            .checking_method(CheckingMethod::Hash)
+            .build()
+            .run();
+        let (grouped_file_entries, warnings) = match result {
+            DirTraversalResult::SuccessFiles { grouped_file_entries, warnings } => (grouped_file_entries, warnings),
+            DirTraversalResult::Stopped => return Err("Preparing to watch folders was stopped".to_string()),
+        };
+        self.common_data.text_messages.warnings.extend(warnings);
+
+        let mut state = DuplicateWatchState {
+            files_by_size: grouped_file_entries
+                .into_iter()
+                .map(|(size, files)| (size, files.into_iter().map(FileEntry::into_duplicate_entry).collect()))
+                .collect(),
+            // Journal can verify and revert only local files
+            journal: if self.common_data.file_system.is_local() {
+                Journal::new(&format!("{:?}", self.common_data.tool_type))
+            } else {
+                Journal::default()
+            },
+            ..Default::default()
+        };
+
+        if self.get_use_cache() {
+            let used_files: BTreeMap<String, DuplicateEntry> = state
+                .files_by_size
+                .values()
+                .flatten()
+                .map(|entry| (entry.path.to_string_lossy().to_string(), entry.clone()))
+                .collect();
+            let (messages, loaded_items) = load_cache_from_file_generalized_by_path::<DuplicateEntry>(&self.get_full_hash_cache_file(), false, &used_files);
+            self.get_text_messages_mut().extend_with_another_messages(messages);
+            state.hashes = loaded_items.unwrap_or_default();
+        }
+        for entry in self.files_with_identical_hashes.values().flatten().flatten() {
+            state.hashes.insert(entry.path.to_string_lossy().to_string(), entry.clone());
+        }
+
+        debug!(
+            "prepare_watching_duplicates - {} files, {} known hashes",
+            state.files_by_size.values().map(Vec::len).sum::<usize>(),
+            state.hashes.len()
+        );
+        self.watch_state = Some(state);
+        Ok(())
+    }
+
+    pub(crate) fn update_changed_duplicates(&mut self, changed_paths: &BTreeSet<PathBuf>) -> WatchUpdate {
+        let Some(mut state) = self.watch_state.take() else {
+            return WatchUpdate::default();
+        };
+        let changed_files = collect_changed_files(&self.common_data, changed_paths);
+        let mut update = WatchUpdate {
+            warnings: changed_files.warnings.clone(),
+            ..Default::default()
+        };
+
+        let mut affected_sizes = BTreeSet::new();
+        for (size, entries) in &mut state.files_by_size {
+            let files_before = entries.len();
+            entries.retain(|entry| !changed_files.is_changed(&entry.path));
+            if entries.len() != files_before {
+                affected_sizes.insert(*size);
+            }
+        }
+        for file in &changed_files.files {
+            if (self.common_data.minimal_file_size..=self.common_data.maximal_file_size).contains(&file.size) {
+                affected_sizes.insert(file.size);
+                state.files_by_size.entry(file.size).or_default().push(file.clone().into_duplicate_entry());
+            }
+        }
+        state.files_by_size.retain(|_size, entries| !entries.is_empty());
+
+        for size in affected_sizes {
+            let old_groups = self.files_with_identical_hashes.remove(&size).unwrap_or_default();
+            let entries = state.files_by_size.get(&size).cloned().unwrap_or_default();
+            let new_groups = self.group_changed_size(&mut state, entries, &mut update.warnings);
+            self.emit_group_changes(&state.journal, &old_groups, &new_groups, &mut update);
+
+            if !new_groups.is_empty() {
+                self.files_with_identical_hashes.insert(size, new_groups);
+            }
+        }
+
+        self.watch_state = Some(state);
+        // Stats are only increased when calculated, so must be cleared before
+        self.information.number_of_duplicated_files_by_hash = 0;
+        self.information.number_of_groups_by_hash = 0;
+        self.information.lost_space_by_hash = 0;
+        self.calculate_hash_stats();
+        update
+    }
+
+    pub(crate) fn finish_watching_duplicates(&mut self) {
+        let Some(state) = self.watch_state.take() else {
+            return;
+        };
+        if !self.get_use_cache() || !state.new_hashes_calculated {
+            return;
+        }
+        let messages = save_cache_to_file_generalized(
+            &self.get_full_hash_cache_file(),
+            &state.hashes,
+            self.common_data.save_also_as_json,
+            self.get_params().minimal_cache_file_size,
+        );
+        self.get_text_messages_mut().extend_with_another_messages(messages);
+    }
+
+    // Only files with same size may be duplicates, so only changed sizes needs to be grouped again
+    fn group_changed_size(&self, state: &mut DuplicateWatchState, entries: Vec<DuplicateEntry>, warnings: &mut Vec<String>) -> Vec<Vec<DuplicateEntry>> {
+        let entries = if self.get_hide_hard_links() {
+            filter_hard_links(entries, self.common_data.file_system.as_ref())
+        } else {
+            entries
+        };
+        if entries.len() < 2 {
+            return Vec::new();
+        }
+
+        let hash_type = self.get_params().hash_type;
+        let size_counter = Arc::default();
+        let stop_flag = Arc::default();
+        let mut hashmap_with_hash: BTreeMap<String, Vec<DuplicateEntry>> = Default::default();
+        for mut entry in entries {
+            let key = entry.path.to_string_lossy().to_string();
+            match state.hashes.get(&key) {
+                Some(cached) if cached.size == entry.size && cached.modified_date == entry.modified_date => entry.hash.clone_from(&cached.hash),
+                _ => {
+                    let hash_result =
+                        THREAD_BUFFER.with_borrow_mut(|buffer| hash_calculation(self.common_data.file_system.as_ref(), buffer, &entry, hash_type, &size_counter, &stop_flag));
+                    match hash_result {
+                        Ok(Some(hash)) => {
+                            entry.hash = hash;
+                            state.hashes.insert(key, entry.clone());
+                            state.new_hashes_calculated = true;
+                        }
+                        Ok(None) => continue,
+                        Err(e) => {
+                            warnings.push(e);
+                            continue;
+                        }
+                    }
+                }
+            }
+            hashmap_with_hash.entry(entry.hash.clone()).or_default().push(entry);
+        }
+
+        hashmap_with_hash.into_values().filter(|group| group.len() > 1).collect()
+    }
+
+    // Compares old and new groups of same size
+    // Modification date is not compared, so file replaced by hardlink to other file from its group, is not treated as new duplicate
+    fn emit_group_changes(&self, journal: &Journal, old_groups: &[Vec<DuplicateEntry>], new_groups: &[Vec<DuplicateEntry>], update: &mut WatchUpdate) {
+        let is_in_groups = |groups: &[Vec<DuplicateEntry>], entry: &DuplicateEntry| groups.iter().flatten().any(|other| other.path == entry.path && other.hash == entry.hash);
+
+        for entry in old_groups.iter().flatten().filter(|entry| !is_in_groups(new_groups, entry)) {
+            update.events.push(WatchEvent {
+                change: WatchChangeKind::Removed,
+                item: NdjsonItem::new(entry, false),
+            });
+        }
+
+        for group in new_groups {
+            let (added, existing): (Vec<&DuplicateEntry>, Vec<&DuplicateEntry>) = group.iter().partition(|entry| !is_in_groups(old_groups, entry));
+            for entry in &added {
+                update.events.push(WatchEvent {
+                    change: WatchChangeKind::Added,
+                    item: NdjsonItem::new(*entry, false),
+                });
+            }
+
+            if !self.hardlink_new_duplicates || self.common_data.dry_run {
+                continue;
+            }
+            // New files are linked to file which was already in group, or to first of them if whole group is new
+            let (original, added) = match existing.first() {
+                Some(original) => (*original, added.as_slice()),
+                None => match added.split_first() {
+                    Some((original, added)) => (*original, added),
+                    None => continue,
+                },
+            };
+            for entry in added {
+                // Hash may collide, so content is compared before replacing file, which cannot be undone by scanning again
+                let stop_flag = Arc::default();
+                let size_counter = Arc::default();
+                let compare_result =
+                    THREAD_BUFFER.with_borrow_mut(|buffer| compare_files_content(self.common_data.file_system.as_ref(), buffer, original, entry, &size_counter, &stop_flag));
+                match compare_result {
+                    Ok(Some(true)) => {}
+                    Ok(_) => {
+                        update.warnings.push(format!(
+                            "Content of \"{}\" is different than \"{}\" despite same hash, so it is not hardlinked",
+                            entry.path.to_string_lossy(),
+                            original.path.to_string_lossy()
+                        ));
+                        continue;
+                    }
+                    Err(e) => {
+                        update.warnings.push(e);
+                        continue;
+                    }
+                }
+                let known_hash = (self.params.hash_type == HashType::Blake3).then_some(entry.hash.as_str());
+                let hardlink_result = journal.record_operation_with_hash(JournalAction::HardLink, &entry.path, Some(&original.path), known_hash, || {
+                    make_hard_link_with_file_system(self.common_data.file_system.as_ref(), &original.path, &entry.path)
+                });
+                match hardlink_result {
+                    Ok(()) => update.events.push(WatchEvent {
+                        change: WatchChangeKind::Hardlinked,
+                        item: NdjsonItem::new(*entry, false),
+                    }),
+                    Err(e) => update.warnings.push(format!(
+                        "Cannot hardlink \"{}\" to \"{}\" - {e}",
+                        entry.path.to_string_lossy(),
+                        original.path.to_string_lossy()
+                    )),
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
@@ -6,9 +8,11 @@ use fun_time::fun_time;
 use log::debug;
 
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
-use crate::common::model::{ToolType, WorkContinueStatus};
+use crate::common::model::{FileEntry, ToolType, WorkContinueStatus};
+use crate::common::ndjson::NdjsonItem;
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::CommonToolData;
+use crate::common::watch::{WatchChangeKind, WatchEvent, WatchUpdate, collect_changed_files};
 use crate::tools::empty_files::{EmptyFiles, Info};
 
 impl EmptyFiles {
@@ -46,4 +50,42 @@ impl EmptyFiles {
             DirTraversalResult::Stopped => WorkContinueStatus::Stop,
         }
     }
+
+    // Every file is checked separately, so changed files only need to be added to or removed from results
+    pub(crate) fn update_changed_files(&mut self, changed_paths: &BTreeSet<PathBuf>) -> WatchUpdate {
+        let changed_files = collect_changed_files(&self.common_data, changed_paths);
+        let mut new_empty_files: Vec<FileEntry> = changed_files.files.iter().filter(|file| file.size == 0).cloned().collect();
+        let mut events = Vec::new();
+
+        for file in &mut self.empty_files {
+            if !changed_files.is_changed(&file.path) {
+                continue;
+            }
+            // File which is still empty, only gets new modification date
+            if let Some(idx) = new_empty_files.iter().position(|new_file| new_file.path == file.path) {
+                *file = new_empty_files.swap_remove(idx);
+            } else {
+                events.push(WatchEvent {
+                    change: WatchChangeKind::Removed,
+                    item: NdjsonItem::new(&*file, false),
+                });
+            }
+        }
+        self.empty_files
+            .retain(|file| !changed_files.is_changed(&file.path) || changed_files.files.iter().any(|new_file| new_file.path == file.path && new_file.size == 0));
+
+        for file in new_empty_files {
+            events.push(WatchEvent {
+                change: WatchChangeKind::Added,
+                item: NdjsonItem::new(&file, false),
+            });
+            self.empty_files.push(file);
+        }
+        self.information.number_of_empty_files = self.empty_files.len();
+
+        WatchUpdate {
+            events,
+            warnings: changed_files.warnings,
+        }
+    }
 }
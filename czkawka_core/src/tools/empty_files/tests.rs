@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fs;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -5,7 +6,8 @@ use std::sync::atomic::AtomicBool;
 use tempfile::TempDir;
 
 use crate::common::tool_data::CommonData;
-use crate::common::traits::Search;
+use crate::common::traits::{Search, WatchingResults};
+use crate::common::watch::WatchChangeKind;
 use crate::tools::empty_files::EmptyFiles;
 
 #[test]
@@ -72,3 +74,40 @@ fn test_recursive_search_empty_files() {
     let info = finder.get_information();
     assert_eq!(info.number_of_empty_files, 2, "Should find empty files in subdirectories");
 }
+
+#[test]
+fn test_watch_changes_update_empty_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    fs::write(path.join("empty1.txt"), b"").unwrap();
+    fs::write(path.join("not_empty.txt"), b"content").unwrap();
+
+    let mut finder = EmptyFiles::new();
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_recursive_search(true);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    finder.search(&stop_flag, None);
+    finder.prepare_watching(&stop_flag).unwrap();
+    assert_eq!(finder.get_empty_files().len(), 1);
+
+    fs::write(path.join("empty1.txt"), b"now with content").unwrap();
+    fs::write(path.join("empty2.txt"), b"").unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("empty1.txt"), path.join("empty2.txt")]));
+    let events: Vec<_> = update.events.iter().map(|event| (event.change, event.item.path.clone())).collect();
+    assert_eq!(
+        events,
+        [(WatchChangeKind::Removed, path.join("empty1.txt")), (WatchChangeKind::Added, path.join("empty2.txt"))]
+    );
+    assert_eq!(finder.get_information().number_of_empty_files, 1);
+
+    // Touching file which is still empty, doesn't change results
+    fs::write(path.join("empty2.txt"), b"").unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("empty2.txt")]));
+    assert!(update.events.is_empty());
+
+    fs::remove_file(path.join("empty2.txt")).unwrap();
+    let update = finder.apply_watch_changes(&BTreeSet::from([path.join("empty2.txt")]));
+    assert_eq!(update.events.len(), 1);
+    assert_eq!(update.events[0].change, WatchChangeKind::Removed);
+    assert!(finder.get_empty_files().is_empty());
+}
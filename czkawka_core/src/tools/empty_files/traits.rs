@@ -1,4 +1,6 @@
+use std::collections::BTreeSet;
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -6,14 +8,36 @@ use std::time::Instant;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search, WatchingResults};
+use crate::common::watch::WatchUpdate;
 use crate::tools::empty_files::{EmptyFiles, Info};
 
+impl ImportingResults for EmptyFiles {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.empty_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_empty_files = self.empty_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for EmptyFiles {}
 
+impl WatchingResults for EmptyFiles {
+    fn prepare_watching(&mut self, _stop_flag: &Arc<AtomicBool>) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn apply_watch_changes(&mut self, changed_paths: &BTreeSet<PathBuf>) -> WatchUpdate {
+        self.update_changed_files(changed_paths)
+    }
+}
+
 impl Search for EmptyFiles {
     #[fun_time(message = "find_empty_files", level = "info")]
     fn search(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) {
@@ -72,6 +96,10 @@ impl PrintResults for EmptyFiles {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.empty_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.empty_files)
+    }
 }
 impl CommonData for EmptyFiles {
     type Info = Info;
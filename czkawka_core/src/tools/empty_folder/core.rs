@@ -1,4 +1,3 @@
-use std::fs::DirEntry;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -11,10 +10,12 @@ use rayon::prelude::*;
 
 use crate::common::dir_traversal::{common_get_entry_data, common_get_metadata_dir, common_read_dir, get_modified_time};
 use crate::common::directories::Directories;
+use crate::common::file_system::{FileSystem, FsDirEntry, FsEntryType};
 use crate::common::items::ExcludedItems;
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::shared_traversal::SharedTraversal;
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::tools::empty_folder::{EmptyFolder, FolderEmptiness, FolderEntry, Info};
 
@@ -56,12 +57,18 @@ impl EmptyFolder {
 
     #[fun_time(message = "check_for_empty_folders", level = "debug")]
     pub(crate) fn check_for_empty_folders(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        if let Some(shared_traversal) = self.common_data.shared_traversal.clone() {
+            self.check_for_empty_folders_in_shared_traversal(&shared_traversal);
+            return WorkContinueStatus::Continue;
+        }
+
         let mut folders_to_check: Vec<PathBuf> = self.common_data.directories.included_directories.clone();
 
         let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::CollectingFiles, 0, self.get_test_type(), 0);
 
         let excluded_items = self.common_data.excluded_items.clone();
         let directories = self.common_data.directories.clone();
+        let file_system = self.common_data.file_system.clone();
 
         let mut non_empty_folders: Vec<String> = Vec::new();
 
@@ -92,7 +99,7 @@ impl EmptyFolder {
 
                     let current_folder_as_string = current_folder.to_string_lossy().to_string();
 
-                    let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
+                    let Some(read_dir) = common_read_dir(file_system.as_ref(), &current_folder, &mut warnings) else {
                         return (dir_result, warnings, Some(current_folder_as_string), folder_entries_list);
                     };
 
@@ -102,11 +109,11 @@ impl EmptyFolder {
                         let Some(entry_data) = common_get_entry_data(&entry, &mut warnings, &current_folder) else {
                             continue;
                         };
-                        let Ok(file_type) = entry_data.file_type() else { continue };
 
-                        if file_type.is_dir() {
+                        if entry_data.entry_type == FsEntryType::Dir {
                             counter += 1;
                             Self::process_dir_in_dir_mode(
+                                file_system.as_ref(),
                                 &current_folder,
                                 &current_folder_as_string,
                                 entry_data,
@@ -156,6 +163,35 @@ impl EmptyFolder {
             }
         }
 
+        self.save_empty_folders(folder_entries, non_empty_folders);
+        progress_handler.join_thread();
+        WorkContinueStatus::Continue
+    }
+
+    // Folders were already visited by traversal shared with other tools, so only their content needs to be checked
+    fn check_for_empty_folders_in_shared_traversal(&mut self, shared_traversal: &SharedTraversal) {
+        let mut folder_entries: IndexMap<String, FolderEntry> = IndexMap::with_capacity(shared_traversal.folders.len());
+        let mut non_empty_folders: Vec<String> = Vec::new();
+        for folder in &shared_traversal.folders {
+            let name = folder.path.to_string_lossy().to_string();
+            if folder.has_content {
+                non_empty_folders.push(name.clone());
+            }
+            folder_entries.insert(
+                name,
+                FolderEntry {
+                    path: folder.path.clone(),
+                    parent_path: folder.parent_path.as_ref().map(|parent_path| parent_path.to_string_lossy().to_string()),
+                    is_empty: FolderEmptiness::Maybe,
+                    modified_date: folder.modified_date,
+                },
+            );
+        }
+
+        self.save_empty_folders(folder_entries, non_empty_folders);
+    }
+
+    fn save_empty_folders(&mut self, mut folder_entries: IndexMap<String, FolderEntry>, non_empty_folders: Vec<String>) {
         for current_folder in non_empty_folders.into_iter().rev() {
             Self::set_as_not_empty_folder(&mut folder_entries, &current_folder);
         }
@@ -167,8 +203,6 @@ impl EmptyFolder {
         }
 
         debug!("Found {} empty folders.", self.empty_folder_list.len());
-        progress_handler.join_thread();
-        WorkContinueStatus::Continue
     }
 
     pub(crate) fn set_as_not_empty_folder(folder_entries: &mut IndexMap<String, FolderEntry>, current_folder: &str) {
@@ -198,9 +232,10 @@ impl EmptyFolder {
     }
 
     fn process_dir_in_dir_mode(
+        file_system: &dyn FileSystem,
         current_folder: &Path,
         current_folder_as_str: &str,
-        entry_data: &DirEntry,
+        entry_data: &FsDirEntry,
         directories: &Directories,
         dir_result: &mut Vec<PathBuf>,
         warnings: &mut Vec<String>,
@@ -208,7 +243,7 @@ impl EmptyFolder {
         non_empty_folder: &mut Option<String>,
         folder_entries_list: &mut Vec<FolderEntry>,
     ) {
-        let next_folder = entry_data.path();
+        let next_folder = entry_data.path.clone();
         if excluded_items.is_excluded(&next_folder) || directories.is_excluded(&next_folder) {
             if non_empty_folder.is_none() {
                 *non_empty_folder = Some(current_folder_as_str.to_string());
@@ -225,7 +260,7 @@ impl EmptyFolder {
             }
         }
 
-        let Some(metadata) = common_get_metadata_dir(entry_data, warnings, &next_folder) else {
+        let Some(metadata) = common_get_metadata_dir(file_system, entry_data, warnings, &next_folder) else {
             if non_empty_folder.is_none() {
                 *non_empty_folder = Some(current_folder_as_str.to_string());
             }
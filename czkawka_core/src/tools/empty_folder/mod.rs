@@ -7,15 +7,19 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use indexmap::IndexMap;
+use serde::Serialize;
 
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
 pub struct FolderEntry {
     pub path: PathBuf,
+    #[serde(skip)]
     pub(crate) parent_path: Option<String>,
     // Usable only when finding
+    #[serde(skip)]
     pub(crate) is_empty: FolderEmptiness,
     pub modified_date: u64,
 }
@@ -1,4 +1,6 @@
+use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -7,11 +9,43 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 use rayon::prelude::*;
 
+use crate::common::dir_traversal::get_modified_time;
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
-use crate::tools::empty_folder::{EmptyFolder, Info};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
+use crate::tools::empty_folder::{EmptyFolder, FolderEmptiness, FolderEntry, Info};
+
+impl ImportingResults for EmptyFolder {
+    // Only paths of folders are saved, so modification date is read again from disk
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let folders: Vec<PathBuf> = load_json_results(file_name)?;
+        let mut summary = ImportSummary::default();
+        self.empty_folder_list.clear();
+        for path in folders {
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                summary.changed_entries += 1;
+                continue;
+            };
+            if !metadata.is_dir() {
+                summary.changed_entries += 1;
+                continue;
+            }
+            let folder_entry = FolderEntry {
+                modified_date: get_modified_time(&metadata.into(), &mut self.common_data.text_messages.warnings, &path, true),
+                path,
+                parent_path: None,
+                is_empty: FolderEmptiness::Maybe,
+            };
+            self.empty_folder_list.insert(folder_entry.path.to_string_lossy().to_string(), folder_entry);
+        }
+        summary.loaded_entries = self.empty_folder_list.len();
+        self.information.number_of_empty_folders = self.empty_folder_list.len();
+        Ok(summary)
+    }
+}
 
 impl AllTraits for EmptyFolder {}
 
@@ -78,6 +112,10 @@ impl PrintResults for EmptyFolder {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.empty_folder_list.keys().collect::<Vec<_>>(), pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(self.empty_folder_list.values())
+    }
 }
 
 impl CommonData for EmptyFolder {
@@ -14,6 +14,7 @@ use rayon::prelude::*;
 
 use crate::common::cache::{CACHE_VERSION, load_and_split_cache_generalized_by_path, save_and_connect_cache_generalized_by_path};
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
+use crate::common::journal::{Journal, JournalAction};
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
@@ -198,6 +199,7 @@ impl ExifRemover {
 
     #[fun_time(message = "fix_files", level = "debug")]
     pub(crate) fn fix_files(&mut self, stop_flag: &Arc<AtomicBool>, _progress_sender: Option<&Sender<ProgressData>>, fix_params: ExifTagsFixerParams) {
+        let journal = Journal::new("ExifRemover");
         let warnings: Vec<_> = mem::take(&mut self.exif_files)
             .into_par_iter()
             .map(|entry| {
@@ -206,7 +208,15 @@ impl ExifRemover {
                 }
 
                 let exif_data_to_remove: Vec<(u16, String)> = entry.exif_tags.iter().map(|item_tag| (item_tag.code, item_tag.group.clone())).collect();
-                match clean_exif_tags(&entry.path.to_string_lossy(), &exif_data_to_remove, fix_params.override_file) {
+                let clean_result = if fix_params.override_file {
+                    journal.record_operation(JournalAction::CleanExif, &entry.path, None, || {
+                        clean_exif_tags(&entry.path.to_string_lossy(), &exif_data_to_remove, true)
+                    })
+                } else {
+                    // Cleaned data is saved to new file, so original stays untouched
+                    clean_exif_tags(&entry.path.to_string_lossy(), &exif_data_to_remove, false)
+                };
+                match clean_result {
                     Ok(_number_removed_tags) => Some(None),
                     Err(e) => Some(Some(format!("Failed to clean EXIF tags for file \"{}\": {}", entry.path.to_string_lossy(), e))),
                 }
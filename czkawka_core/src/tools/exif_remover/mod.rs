@@ -30,6 +30,7 @@ impl ExifRemoverParameters {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ExifEntry {
     pub path: PathBuf,
     pub size: u64,
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -8,12 +9,23 @@ use fun_time::fun_time;
 use humansize::BINARY;
 
 use crate::common::consts::EXIF_FILES_EXTENSIONS;
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, ImportingResults, PrintResults, Search};
 use crate::tools::exif_remover::{ExifEntry, ExifRemover, ExifRemoverParameters, ExifTagsFixerParams, Info};
 
+impl ImportingResults for ExifRemover {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.exif_files = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_files_with_exif = self.exif_files.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for ExifRemover {}
 
 impl DeletingItems for ExifRemover {
@@ -80,6 +92,10 @@ impl PrintResults for ExifRemover {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.exif_files, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.exif_files)
+    }
 }
 
 impl Search for ExifRemover {
@@ -53,6 +53,7 @@ pub struct SymlinkInfo {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct SymlinksFileEntry {
     pub path: PathBuf,
     pub size: u64,
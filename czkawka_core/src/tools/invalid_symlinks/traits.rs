@@ -1,4 +1,5 @@
 use std::io::prelude::*;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -6,12 +7,23 @@ use std::time::Instant;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteItemType, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::tools::invalid_symlinks::{ErrorType, Info, InvalidSymlinks};
 
+impl ImportingResults for InvalidSymlinks {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        self.invalid_symlinks = summary.filter_entries(load_json_results(file_name)?);
+        self.information.number_of_invalid_symlinks = self.invalid_symlinks.len();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for InvalidSymlinks {}
 
 impl Search for InvalidSymlinks {
@@ -80,6 +92,10 @@ impl PrintResults for InvalidSymlinks {
     fn save_results_to_file_as_json(&self, file_name: &str, pretty_print: bool) -> std::io::Result<()> {
         self.save_results_to_file_as_json_internal(file_name, &self.invalid_symlinks, pretty_print)
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        writer.write_entries(&self.invalid_symlinks)
+    }
 }
 
 impl CommonData for InvalidSymlinks {
@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod bad_extensions;
 pub mod bad_names;
 pub mod big_file;
@@ -13,7 +13,7 @@ use lofty::prelude::*;
 use lofty::read_from;
 use log::{debug, error};
 use rayon::prelude::*;
-use rusty_chromaprint::{Configuration, Fingerprinter, match_fingerprints};
+use rusty_chromaprint::{Configuration, Fingerprinter, Segment, match_fingerprints};
 use symphonia::core::audio::SampleBuffer;
 use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
 use symphonia::core::formats::FormatOptions;
@@ -21,7 +21,7 @@ use symphonia::core::io::MediaSourceStream;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
-use crate::common::cache::{CACHE_VERSION, load_and_split_cache_generalized_by_path, save_and_connect_cache_generalized_by_path};
+use crate::common::cache::{CACHE_SAME_MUSIC_VERSION, load_and_split_cache_generalized_by_path, save_and_connect_cache_generalized_by_path};
 use crate::common::create_crash_message;
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult};
 use crate::common::model::{ToolType, WorkContinueStatus};
@@ -30,7 +30,7 @@ use crate::common::progress_stop_handler::{check_if_stop_received, prepare_threa
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
 use crate::flc;
-use crate::tools::same_music::{GroupedFilesToCheck, Info, MusicEntry, MusicSimilarity, SameMusic, SameMusicParameters};
+use crate::tools::same_music::{GroupedFilesToCheck, Info, MatchedSegment, MusicEntry, MusicSimilarity, SameMusic, SameMusicParameters};
 
 impl SameMusic {
     pub fn new(params: SameMusicParameters) -> Self {
@@ -225,6 +225,22 @@ impl SameMusic {
         WorkContinueStatus::Continue
     }
 
+    pub(crate) fn calculate_duplicates_stats(&mut self) {
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
+        if self.common_data.use_reference_folders {
+            for (_fe, vector) in &self.duplicated_music_entries_referenced {
+                self.information.number_of_duplicates += vector.len();
+                self.information.number_of_groups += 1;
+            }
+        } else {
+            for vector in &self.duplicated_music_entries {
+                self.information.number_of_duplicates += vector.len() - 1;
+                self.information.number_of_groups += 1;
+            }
+        }
+    }
+
     #[fun_time(message = "check_for_duplicate_tags", level = "debug")]
     pub(crate) fn check_for_duplicate_tags(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
         if self.music_entries.is_empty() {
@@ -318,17 +334,7 @@ impl SameMusic {
             self.duplicated_music_entries_referenced = self.common_data.directories.filter_reference_folders(mem::take(&mut self.duplicated_music_entries));
         }
 
-        if self.common_data.use_reference_folders {
-            for (_fe, vector) in &self.duplicated_music_entries_referenced {
-                self.information.number_of_duplicates += vector.len();
-                self.information.number_of_groups += 1;
-            }
-        } else {
-            for vector in &self.duplicated_music_entries {
-                self.information.number_of_duplicates += vector.len() - 1;
-                self.information.number_of_groups += 1;
-            }
-        }
+        self.calculate_duplicates_stats();
 
         // Clear unused data
         self.music_entries.clear();
@@ -400,6 +406,7 @@ impl SameMusic {
         let configuration = &self.hash_preset_config;
         let minimum_segment_duration = self.params.minimum_segment_duration;
         let maximum_difference = self.params.maximum_difference;
+        let minimum_match_coverage = self.params.minimum_match_coverage;
 
         let mut duplicated_music_entries = Vec::new();
 
@@ -426,7 +433,11 @@ impl SameMusic {
                         Err(e) => return Some(Err(flc!("core_error_comparing_fingerprints", reason = e.to_string()))),
                     };
                     segments.retain(|s| s.duration(configuration) > minimum_segment_duration && s.score < maximum_difference);
-                    if segments.is_empty() { None } else { Some(Ok((e_string, e_entry))) }
+                    let matched_segment = calculate_matched_segment(&segments, &f_entry.fingerprint, &e_entry.fingerprint, configuration)?;
+                    if matched_segment.coverage < minimum_match_coverage {
+                        return None;
+                    }
+                    Some(Ok((e_string, e_entry, matched_segment)))
                 })
                 .flatten()
                 .partition_map(|res| match res {
@@ -436,12 +447,14 @@ impl SameMusic {
 
             self.common_data.text_messages.errors.extend(errors);
 
-            collected_similar_items.retain(|(path, _entry)| !used_paths.contains(path));
+            collected_similar_items.retain(|(path, _entry, _matched_segment)| !used_paths.contains(path));
             if !collected_similar_items.is_empty() {
                 let mut music_entries = Vec::new();
-                for (path, entry) in collected_similar_items {
+                for (path, entry, matched_segment) in collected_similar_items {
                     used_paths.insert(path);
-                    music_entries.push(entry.clone());
+                    let mut entry = entry.clone();
+                    entry.matched_segment = Some(matched_segment);
+                    music_entries.push(entry);
                 }
                 used_paths.insert(f_string);
                 music_entries.push(f_entry);
@@ -480,17 +493,7 @@ impl SameMusic {
             self.duplicated_music_entries_referenced = self.common_data.directories.filter_reference_folders(mem::take(&mut self.duplicated_music_entries));
         }
 
-        if self.common_data.use_reference_folders {
-            for (_fe, vector) in &self.duplicated_music_entries_referenced {
-                self.information.number_of_duplicates += vector.len();
-                self.information.number_of_groups += 1;
-            }
-        } else {
-            for vector in &self.duplicated_music_entries {
-                self.information.number_of_duplicates += vector.len() - 1;
-                self.information.number_of_groups += 1;
-            }
-        }
+        self.calculate_duplicates_stats();
 
         // Clear unused data
         self.music_entries.clear();
@@ -682,6 +685,36 @@ fn read_single_file_tags(path: &str, mut music_entry: MusicEntry) -> Option<Musi
     Some(music_entry)
 }
 
+// Segments are computed by comparing other file(first fingerprint) with checked file(second fingerprint)
+fn calculate_matched_segment(segments: &[Segment], other_fingerprint: &[u32], fingerprint: &[u32], configuration: &Configuration) -> Option<MatchedSegment> {
+    let longest_segment = segments.iter().max_by(|a, b| a.duration(configuration).total_cmp(&b.duration(configuration)))?;
+
+    let longer_file_duration = other_fingerprint.len().max(fingerprint.len()) as f32 * configuration.item_duration_in_seconds();
+    let matched_duration: f32 = segments.iter().map(|s| s.duration(configuration)).sum();
+    let coverage = if longer_file_duration > 0.0 {
+        (matched_duration / longer_file_duration * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+
+    Some(MatchedSegment {
+        start: longest_segment.start2(configuration),
+        other_start: longest_segment.start1(configuration),
+        duration: longest_segment.duration(configuration),
+        coverage,
+    })
+}
+
+pub fn format_matched_segment(matched_segment: &MatchedSegment) -> String {
+    format!(
+        "{:.0}% ({} - {}, {})",
+        matched_segment.coverage,
+        format_audio_duration(matched_segment.start as u32),
+        format_audio_duration(matched_segment.other_start as u32),
+        format_audio_duration(matched_segment.duration as u32)
+    )
+}
+
 pub fn format_audio_duration(duration: u32) -> String {
     let hours = duration / 3600;
     let minutes = (duration % 3600) / 60;
@@ -765,9 +798,9 @@ fn get_simplified_name(what: &str) -> String {
 
 pub fn get_similar_music_cache_file(checking_tags: bool) -> String {
     if checking_tags {
-        format!("cache_same_music_tags_{CACHE_VERSION}.bin")
+        format!("cache_same_music_tags_{CACHE_SAME_MUSIC_VERSION}.bin")
     } else {
-        format!("cache_same_music_fingerprints_{CACHE_VERSION}.bin")
+        format!("cache_same_music_fingerprints_{CACHE_SAME_MUSIC_VERSION}.bin")
     }
 }
 
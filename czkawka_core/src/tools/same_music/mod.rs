@@ -31,6 +31,7 @@ bitflags! {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MusicEntry {
     pub size: u64,
 
@@ -44,6 +45,21 @@ pub struct MusicEntry {
     pub length: u32,
     pub genre: String,
     pub bitrate: u32,
+
+    // Set only when comparing by content, for files which were matched with first file of group
+    pub matched_segment: Option<MatchedSegment>,
+}
+
+// Part of track, which is similar to part of other file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MatchedSegment {
+    // Start of longest matched part in this file, in seconds
+    pub start: f32,
+    // Start of same part in file with which this file was compared, in seconds
+    pub other_start: f32,
+    pub duration: f32,
+    // Percent of longer file, covered by all matched parts - allows to distinguish full duplicates from tracks which only contain sample of other
+    pub coverage: f32,
 }
 
 impl ResultEntry for MusicEntry {
@@ -56,6 +72,9 @@ impl ResultEntry for MusicEntry {
     fn get_size(&self) -> u64 {
         self.size
     }
+    fn get_bitrate(&self) -> Option<u64> {
+        Some(u64::from(self.bitrate))
+    }
 }
 
 impl FileEntry {
@@ -72,6 +91,7 @@ impl FileEntry {
             length: 0,
             genre: String::new(),
             bitrate: 0,
+            matched_segment: None,
         }
     }
 }
@@ -96,6 +116,8 @@ pub struct SameMusicParameters {
     pub minimum_segment_duration: f32,
     pub maximum_difference: f64,
     pub compare_fingerprints_only_with_similar_titles: bool,
+    // Percent 0-100, files with smaller coverage of matched parts are not treated as similar
+    pub minimum_match_coverage: f32,
 }
 
 impl SameMusicParameters {
@@ -106,6 +128,7 @@ impl SameMusicParameters {
         minimum_segment_duration: f32,
         maximum_difference: f64,
         compare_fingerprints_only_with_similar_titles: bool,
+        minimum_match_coverage: f32,
     ) -> Self {
         assert!(!music_similarity.is_empty());
         assert!([CheckingMethod::AudioTags, CheckingMethod::AudioContent].contains(&check_type));
@@ -116,6 +139,7 @@ impl SameMusicParameters {
             minimum_segment_duration,
             maximum_difference,
             compare_fingerprints_only_with_similar_titles,
+            minimum_match_coverage,
         }
     }
 }
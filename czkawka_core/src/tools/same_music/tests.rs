@@ -19,7 +19,7 @@ fn get_test_resources_path() -> PathBuf {
 fn test_same_music_by_content_high_similarity() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.2, false);
+    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.2, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -42,7 +42,7 @@ fn test_same_music_by_content_high_similarity() {
 fn test_same_music_by_content_medium_similarity() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.5, false);
+    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.5, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -65,7 +65,7 @@ fn test_same_music_by_content_medium_similarity() {
 fn test_same_music_by_content_low_similarity() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.8, false);
+    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.8, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -95,6 +95,7 @@ fn test_same_music_by_tags_title_artist() {
         10.0,
         0.2,
         false,
+        0.0,
     );
 
     let mut finder = SameMusic::new(params);
@@ -118,7 +119,7 @@ fn test_same_music_by_tags_title_artist() {
 fn test_same_music_by_tags_year() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::YEAR, false, CheckingMethod::AudioTags, 10.0, 0.2, false);
+    let params = SameMusicParameters::new(MusicSimilarity::YEAR, false, CheckingMethod::AudioTags, 10.0, 0.2, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -140,7 +141,7 @@ fn test_same_music_by_tags_year() {
 fn test_same_music_by_tags_genre() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::GENRE, false, CheckingMethod::AudioTags, 10.0, 0.2, false);
+    let params = SameMusicParameters::new(MusicSimilarity::GENRE, false, CheckingMethod::AudioTags, 10.0, 0.2, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -163,7 +164,7 @@ fn test_same_music_by_tags_genre() {
 fn test_same_music_by_tags_bitrate() {
     let test_path = get_test_resources_path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::BITRATE, false, CheckingMethod::AudioTags, 10.0, 0.2, false);
+    let params = SameMusicParameters::new(MusicSimilarity::BITRATE, false, CheckingMethod::AudioTags, 10.0, 0.2, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -193,6 +194,7 @@ fn test_same_music_by_tags_all_criteria() {
         10.0,
         0.2,
         false,
+        0.0,
     );
 
     let mut finder = SameMusic::new(params);
@@ -222,6 +224,7 @@ fn test_same_music_approximate_comparison() {
         10.0,
         0.2,
         false,
+        0.0,
     );
 
     let mut finder = SameMusic::new(params);
@@ -248,7 +251,7 @@ fn test_same_music_empty_directory() {
     let temp_dir = TempDir::new().unwrap();
     let path = temp_dir.path();
 
-    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioTags, 10.0, 0.2, false);
+    let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioTags, 10.0, 0.2, false, 0.0);
 
     let mut finder = SameMusic::new(params);
     finder.set_included_paths(vec![path.to_path_buf()]);
@@ -265,3 +268,35 @@ fn test_same_music_empty_directory() {
     assert_eq!(info.number_of_groups, 0);
     assert_eq!(duplicates.len(), 0);
 }
+
+#[test]
+fn test_same_music_by_content_matched_segments() {
+    let find_similar_music = |minimum_match_coverage: f32| {
+        let params = SameMusicParameters::new(MusicSimilarity::TRACK_TITLE, false, CheckingMethod::AudioContent, 10.0, 0.8, false, minimum_match_coverage);
+        let mut finder = SameMusic::new(params);
+        finder.set_included_paths(vec![get_test_resources_path()]);
+        finder.set_recursive_search(true);
+        finder.set_use_cache(false);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+        finder
+    };
+
+    let finder = find_similar_music(0.0);
+    let duplicates = finder.get_duplicated_music_entries();
+    assert_eq!(duplicates.len(), 1);
+
+    // Only file with which others were compared, has no matched segment
+    let group = &duplicates[0];
+    assert_eq!(group.iter().filter(|e| e.matched_segment.is_none()).count(), 1);
+    let matched_segments: Vec<_> = group.iter().filter_map(|e| e.matched_segment).collect();
+    assert_eq!(matched_segments.len(), 3);
+    for matched_segment in &matched_segments {
+        assert!(matched_segment.duration > 10.0);
+        assert!(matched_segment.coverage > 0.0 && matched_segment.coverage <= 100.0);
+    }
+
+    // Files with the smallest coverage(e.g. containing only part of track) are skipped with higher threshold
+    let minimal_coverage = matched_segments.iter().map(|e| e.coverage).fold(f32::MAX, f32::min);
+    let finder = find_similar_music(minimal_coverage + 0.01);
+    assert!(finder.get_information().number_of_duplicates < 3);
+}
@@ -1,4 +1,5 @@
 use std::io::prelude::*;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -7,14 +8,34 @@ use crossbeam_channel::Sender;
 use fun_time::fun_time;
 
 use crate::common::consts::AUDIO_FILES_EXTENSIONS;
+use crate::common::import::{ImportSummary, SavedResults, load_json_results};
 use crate::common::model::{CheckingMethod, WorkContinueStatus};
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::flc;
-use crate::tools::same_music::core::format_audio_duration;
+use crate::tools::same_music::core::{format_audio_duration, format_matched_segment};
 use crate::tools::same_music::{Info, MusicEntry, MusicSimilarity, SameMusic, SameMusicParameters};
 
+impl ImportingResults for SameMusic {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        match load_json_results(file_name)? {
+            SavedResults::Referenced(groups) => {
+                self.set_use_reference_folders(true);
+                self.duplicated_music_entries_referenced = summary.filter_referenced_groups(groups);
+            }
+            SavedResults::Normal(groups) => {
+                self.set_use_reference_folders(false);
+                self.duplicated_music_entries = summary.filter_groups(groups);
+            }
+        }
+        self.calculate_duplicates_stats();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for SameMusic {}
 
 impl Search for SameMusic {
@@ -128,10 +149,18 @@ impl PrintResults for SameMusic {
             self.save_results_to_file_as_json_internal(file_name, &self.duplicated_music_entries, pretty_print)
         }
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        if self.get_use_reference() {
+            writer.write_referenced_groups(&self.duplicated_music_entries_referenced)
+        } else {
+            writer.write_groups(&self.duplicated_music_entries)
+        }
+    }
 }
 
 fn write_music_entry<T: Write>(writer: &mut T, file_entry: &MusicEntry) -> std::io::Result<()> {
-    writeln!(
+    write!(
         writer,
         "TT: {}  -  TA: {}  -  Y: {}  -  L: {}  -  G: {}  -  B: {}  -  P: \"{}\"",
         file_entry.track_title,
@@ -141,7 +170,11 @@ fn write_music_entry<T: Write>(writer: &mut T, file_entry: &MusicEntry) -> std::
         file_entry.genre,
         file_entry.bitrate,
         file_entry.path.to_string_lossy()
-    )
+    )?;
+    if let Some(matched_segment) = &file_entry.matched_segment {
+        write!(writer, "  -  M: {}", format_matched_segment(matched_segment))?;
+    }
+    writeln!(writer)
 }
 
 impl CommonData for SameMusic {
@@ -2,11 +2,12 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
-use std::{mem, panic};
+use std::{iter, mem, panic};
 
 use bk_tree::BKTree;
 use crossbeam_channel::Sender;
 use fun_time::fun_time;
+use hamming_bitwise_fast::hamming_bitwise_fast;
 use image::GenericImageView;
 use image_hasher::{FilterType, HashAlg, HasherConfig};
 use indexmap::{IndexMap, IndexSet};
@@ -16,13 +17,15 @@ use rayon::prelude::*;
 use crate::common::cache::{CACHE_IMAGE_VERSION, load_and_split_cache_generalized_by_path, save_and_connect_cache_generalized_by_path};
 use crate::common::dir_traversal::{DirTraversalBuilder, DirTraversalResult, inode, take_1_per_inode};
 use crate::common::image::get_dynamic_image_from_path;
+use crate::common::keep_rules::KeepRule;
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
 use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
 use crate::common::tool_data::{CommonData, CommonToolData};
 use crate::common::traits::ResultEntry;
 use crate::flc;
-use crate::tools::similar_images::{Hamming, ImHash, ImagesEntry, SIMILAR_VALUES, SimilarImages, SimilarImagesParameters, SimilarityPreset};
+use crate::tools::similar_images::quality::calculate_quality_metrics;
+use crate::tools::similar_images::{Hamming, ImHash, ImageTransform, ImagesEntry, SIMILAR_VALUES, SimilarImages, SimilarImagesParameters, SimilarityPreset};
 
 impl SimilarImages {
     pub fn new(params: SimilarImagesParameters) -> Self {
@@ -35,6 +38,7 @@ impl SimilarImages {
             params,
             images_to_check: Default::default(),
             image_hashes: Default::default(),
+            query_images: Vec::new(),
         }
     }
 
@@ -45,6 +49,7 @@ impl SimilarImages {
             .stop_flag(stop_flag)
             .progress_sender(progress_sender)
             .common_data(&self.common_data)
+            .checking_method(self.get_check_method())
             .build()
             .run();
 
@@ -75,7 +80,13 @@ impl SimilarImages {
     #[fun_time(message = "hash_images_load_cache", level = "debug")]
     fn hash_images_load_cache(&mut self) -> (BTreeMap<String, ImagesEntry>, BTreeMap<String, ImagesEntry>, BTreeMap<String, ImagesEntry>) {
         load_and_split_cache_generalized_by_path(
-            &get_similar_images_cache_file(self.get_params().hash_size, self.get_params().hash_alg, self.get_params().image_filter),
+            &get_similar_images_cache_file(
+                self.get_params().hash_size,
+                self.get_params().hash_alg,
+                self.get_params().image_filter,
+                self.get_params().check_transforms,
+                self.uses_quality_metrics(),
+            ),
             mem::take(&mut self.images_to_check),
             self,
         )
@@ -84,7 +95,13 @@ impl SimilarImages {
     #[fun_time(message = "save_to_cache", level = "debug")]
     fn save_to_cache(&mut self, vec_file_entry: &[ImagesEntry], loaded_hash_map: BTreeMap<String, ImagesEntry>) {
         save_and_connect_cache_generalized_by_path(
-            &get_similar_images_cache_file(self.get_params().hash_size, self.get_params().hash_alg, self.get_params().image_filter),
+            &get_similar_images_cache_file(
+                self.get_params().hash_size,
+                self.get_params().hash_alg,
+                self.get_params().image_filter,
+                self.get_params().check_transforms,
+                self.uses_quality_metrics(),
+            ),
             vec_file_entry,
             loaded_hash_map,
             self,
@@ -152,7 +169,7 @@ impl SimilarImages {
         WorkContinueStatus::Continue
     }
 
-    fn collect_image_file_entry(&self, mut file_entry: ImagesEntry) -> Result<ImagesEntry, String> {
+    pub(crate) fn collect_image_file_entry(&self, mut file_entry: ImagesEntry) -> Result<ImagesEntry, String> {
         let img = get_dynamic_image_from_path(&file_entry.path.to_string_lossy())?;
 
         let dimensions = img.dimensions();
@@ -168,9 +185,29 @@ impl SimilarImages {
         let hash = hasher.hash_image(&img);
         file_entry.hash = hash.as_bytes().to_vec();
 
+        // Images rotated or mirrored by editors, without EXIF orientation, have completely different hashes
+        if self.get_params().check_transforms {
+            file_entry.transformed_hashes = ImageTransform::VARIANTS
+                .iter()
+                .map(|transform| hasher.hash_image(&transform.apply(&img)).as_bytes().to_vec())
+                .collect();
+        }
+
+        if self.uses_quality_metrics() {
+            calculate_quality_metrics(&mut file_entry, &img);
+        }
+
         Ok(file_entry)
     }
 
+    // Metrics need additional full resolution pass over every image, so they are calculated only when something uses them
+    pub(crate) fn uses_quality_metrics(&self) -> bool {
+        self.get_params().calculate_quality
+            || self.get_params().series_max_interval > 0
+            || self.get_delete_method().works_only_with_similar_images()
+            || self.get_keep_rules().get_rules().iter().any(KeepRule::works_only_with_similar_images)
+    }
+
     // Split hashes at 2 parts, base hashes and hashes to compare, 3 argument is set of hashes with multiple images
     #[fun_time(message = "split_hashes", level = "debug")]
     fn split_hashes(&mut self, all_hashed_images: &IndexMap<ImHash, Vec<ImagesEntry>>) -> (Vec<ImHash>, IndexSet<ImHash>) {
@@ -233,9 +270,11 @@ impl SimilarImages {
         }
 
         for (child_hash, (parent_hash, similarity)) in hashes_similarity {
+            let transform = find_matching_transform(&parent_hash, get_transformed_hashes(all_hashed_images, &parent_hash), &child_hash);
             let mut vec_fe = all_hashed_images[&child_hash].clone();
             for fe in &mut vec_fe {
                 fe.difference = similarity;
+                fe.transform = transform;
             }
             collected_similar_images
                 .get_mut(&parent_hash)
@@ -276,11 +315,9 @@ impl SimilarImages {
                         return None;
                     }
                     let mut found_items = self
-                        .bktree
-                        .find(hash_to_check, tolerance)
-                        .filter(|(similarity, compared_hash)| {
-                            *similarity != 0 && !hashes_parents.contains_key(*compared_hash) && !hashes_with_multiple_images.contains(*compared_hash)
-                        })
+                        .find_in_bktree(hash_to_check, get_transformed_hashes(all_hashed_images, hash_to_check), tolerance)
+                        .into_iter()
+                        .filter(|(_similarity, compared_hash)| !hashes_parents.contains_key(*compared_hash) && !hashes_with_multiple_images.contains(*compared_hash))
                         .filter(|(similarity, compared_hash)| {
                             if let Some((_, other_similarity_with_parent)) = hashes_similarity.get(*compared_hash) {
                                 // If current hash is more similar to other hash than to current parent hash, then skip check earlier
@@ -324,6 +361,22 @@ impl SimilarImages {
         WorkContinueStatus::Continue
     }
 
+    // Without transforms, only checked hash is used, so similarity 0 means the same hash
+    // With them, the smallest distance of all transformed hashes is used, because rotated image may be identical to other one
+    fn find_in_bktree<'a>(&'a self, hash_to_check: &'a ImHash, transformed_hashes: &'a [ImHash], tolerance: u32) -> Vec<(u32, &'a ImHash)> {
+        let mut found_items: IndexMap<&ImHash, u32> = IndexMap::new();
+        for hash in iter::once(hash_to_check).chain(transformed_hashes) {
+            for (similarity, compared_hash) in self.bktree.find(hash, tolerance) {
+                if compared_hash == hash_to_check {
+                    continue;
+                }
+                let current_similarity = found_items.entry(compared_hash).or_insert(similarity);
+                *current_similarity = (*current_similarity).min(similarity);
+            }
+        }
+        found_items.into_iter().map(|(compared_hash, similarity)| (similarity, compared_hash)).collect()
+    }
+
     fn connect_results_simplified<'a>(
         partial_results: Vec<(&'a ImHash, Vec<(u32, &'a ImHash)>)>,
         hashes_parents: &mut IndexMap<ImHash, u32>,
@@ -406,6 +459,22 @@ impl SimilarImages {
         }
     }
 
+    pub(crate) fn calculate_duplicates_stats(&mut self) {
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
+        if self.common_data.use_reference_folders {
+            for (_fe, vector) in &self.similar_referenced_vectors {
+                self.information.number_of_duplicates += vector.len();
+                self.information.number_of_groups += 1;
+            }
+        } else {
+            for vector in &self.similar_vectors {
+                self.information.number_of_duplicates += vector.len() - 1;
+                self.information.number_of_groups += 1;
+            }
+        }
+    }
+
     #[fun_time(message = "find_similar_hashes", level = "debug")]
     pub(crate) fn find_similar_hashes(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
         if self.image_hashes.is_empty() {
@@ -418,9 +487,15 @@ impl SimilarImages {
         let mut collected_similar_images: IndexMap<ImHash, Vec<ImagesEntry>> = Default::default();
 
         let all_hashed_images = mem::take(&mut self.image_hashes);
+        let images_to_match_crops: Vec<ImagesEntry> = if self.get_params().match_crops {
+            all_hashed_images.values().flatten().cloned().collect()
+        } else {
+            Vec::new()
+        };
 
         // Checking entries with tolerance 0 is really easy and fast, because only entries with same hashes needs to be checked
-        if tolerance == 0 {
+        // Transformed hashes are not keys of map, so they always need to be checked with bktree
+        if tolerance == 0 && !self.get_params().check_transforms {
             for (hash, vec_file_entry) in all_hashed_images {
                 if vec_file_entry.len() >= 2 {
                     collected_similar_images.insert(hash, vec_file_entry);
@@ -436,21 +511,15 @@ impl SimilarImages {
         // Info about hashes is not needed anymore, so we drop this info
         self.similar_vectors = collected_similar_images.into_values().collect();
 
+        if self.find_cropped_images(images_to_match_crops, stop_flag, progress_sender) == WorkContinueStatus::Stop {
+            return WorkContinueStatus::Stop;
+        }
+
         self.exclude_items_with_same_size();
 
         self.remove_multiple_records_from_reference_folders();
 
-        if self.common_data.use_reference_folders {
-            for (_fe, vector) in &self.similar_referenced_vectors {
-                self.information.number_of_duplicates += vector.len();
-                self.information.number_of_groups += 1;
-            }
-        } else {
-            for vector in &self.similar_vectors {
-                self.information.number_of_duplicates += vector.len() - 1;
-                self.information.number_of_groups += 1;
-            }
-        }
+        self.calculate_duplicates_stats();
 
         // Clean unused data to save ram
         self.image_hashes = Default::default();
@@ -461,7 +530,7 @@ impl SimilarImages {
     }
 
     #[fun_time(message = "exclude_items_with_same_size", level = "debug")]
-    fn exclude_items_with_same_size(&mut self) {
+    pub(crate) fn exclude_items_with_same_size(&mut self) {
         if self.get_params().exclude_images_with_same_size {
             for vec_file_entry in mem::take(&mut self.similar_vectors) {
                 let mut bt_sizes: BTreeSet<u64> = Default::default();
@@ -479,7 +548,7 @@ impl SimilarImages {
     }
 
     #[fun_time(message = "remove_multiple_records_from_reference_folders", level = "debug")]
-    fn remove_multiple_records_from_reference_folders(&mut self) {
+    pub(crate) fn remove_multiple_records_from_reference_folders(&mut self) {
         if self.common_data.use_reference_folders {
             self.similar_referenced_vectors = mem::take(&mut self.similar_vectors)
                 .into_iter()
@@ -673,14 +742,33 @@ fn debug_check_for_duplicated_things(
     assert!(!found_broken_thing);
 }
 
-pub fn get_similar_images_cache_file(hash_size: u8, hash_alg: HashAlg, image_filter: FilterType) -> String {
+// Entries with transformed hashes or quality metrics are saved in separate files, so enabling them doesn't require rehashing images without them
+pub fn get_similar_images_cache_file(hash_size: u8, hash_alg: HashAlg, image_filter: FilterType, check_transforms: bool, calculate_quality: bool) -> String {
     format!(
-        "cache_similar_images_{hash_size}_{}_{}_{CACHE_IMAGE_VERSION}.bin",
+        "cache_similar_images_{hash_size}_{}_{}{}{}_{CACHE_IMAGE_VERSION}.bin",
         convert_algorithm_to_string(hash_alg),
         convert_filters_to_string(image_filter),
+        if check_transforms { "_transforms" } else { "" },
+        if calculate_quality { "_quality" } else { "" },
     )
 }
 
+// All images with same hash have also same transformed hashes
+fn get_transformed_hashes<'a>(all_hashed_images: &'a IndexMap<ImHash, Vec<ImagesEntry>>, hash: &ImHash) -> &'a [ImHash] {
+    all_hashed_images
+        .get(hash)
+        .and_then(|vec_file_entry| vec_file_entry.first())
+        .map_or(&[], |file_entry| &file_entry.transformed_hashes)
+}
+
+// Transform of parent image with the smallest distance to child hash - non transformed hash is preferred when distances are equal
+pub(crate) fn find_matching_transform(parent_hash: &ImHash, parent_transformed_hashes: &[ImHash], child_hash: &ImHash) -> ImageTransform {
+    iter::once((ImageTransform::None, parent_hash))
+        .chain(ImageTransform::VARIANTS.into_iter().zip(parent_transformed_hashes))
+        .min_by_key(|(_transform, hash)| hamming_bitwise_fast(hash, child_hash))
+        .map_or(ImageTransform::None, |(transform, _hash)| transform)
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -692,7 +780,7 @@ mod tests {
 
     use super::*;
     use crate::common::tool_data::CommonData;
-    use crate::tools::similar_images::{Hamming, ImHash, ImagesEntry, SimilarImages, SimilarImagesParameters};
+    use crate::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, Hamming, ImHash, ImagesEntry, SimilarImages, SimilarImagesParameters};
 
     fn get_default_parameters() -> SimilarImagesParameters {
         SimilarImagesParameters {
@@ -701,6 +789,11 @@ mod tests {
             max_difference: 0,
             image_filter: FilterType::Lanczos3,
             exclude_images_with_same_size: false,
+            check_transforms: false,
+            match_crops: false,
+            match_crops_max_images: DEFAULT_MATCH_CROPS_MAX_IMAGES,
+            series_max_interval: 0,
+            calculate_quality: false,
         }
     }
 
@@ -1133,6 +1226,12 @@ mod tests {
             modified_date: 0,
             hash,
             difference: 0,
+            transformed_hashes: Vec::new(),
+            transform: ImageTransform::None,
+            overlap_ratio: None,
+            sharpness: None,
+            jpeg_quality: None,
+            upscaled: false,
         }
     }
 }
@@ -1143,10 +1242,11 @@ mod connect_results_tests {
     use indexmap::{IndexMap, IndexSet};
 
     use super::*;
+    use crate::tools::similar_images::DEFAULT_MATCH_CROPS_MAX_IMAGES;
 
     #[test]
     fn test_connect_results_real_case() {
-        let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false);
+        let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
         let _finder = SimilarImages::new(params);
 
         let hash1: ImHash = vec![59, 41, 53, 27, 19, 143, 228, 228];
@@ -0,0 +1,317 @@
+// Second stage of similar images, which finds images containing large part of other image - crops, screenshots with borders, images with watermarks
+// Perceptual hashes are calculated from whole image, so such images have completely different hashes
+// Instead, FAST keypoints with BRIEF descriptors are compared and matches are verified by voting for the same scale and offset of images
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use image::GrayImage;
+use image::imageops::FilterType;
+use imageproc::binary_descriptors::brief::{TestPair, brief};
+use imageproc::binary_descriptors::{BinaryDescriptor, match_binary_descriptors};
+use imageproc::corners::oriented_fast;
+use imageproc::point::Point;
+use log::debug;
+use rayon::prelude::*;
+
+use crate::common::image::get_dynamic_image_from_path;
+use crate::common::model::WorkContinueStatus;
+use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::tool_data::CommonData;
+use crate::flc;
+use crate::tools::similar_images::{ImagesEntry, SimilarImages};
+
+// Longer side of image, before building pyramid
+const NORMALIZED_SIZE: u32 = 512;
+// Every level of pyramid is smaller by this factor, so images scaled from ~0.33 to ~3 times can be matched
+const LEVEL_SCALE_STEP: f32 = 0.8;
+const LEVELS: i32 = 6;
+const KEYPOINTS_PER_LEVEL: usize = 250;
+// BRIEF requires at least 17 pixels from edge
+const EDGE_RADIUS: u32 = 20;
+const MIN_LEVEL_SIZE: u32 = 2 * EDGE_RADIUS + 32;
+const DESCRIPTOR_BITS: usize = 256;
+const BRIEF_PATCH_DIAMETER: u32 = 31;
+const MAX_DESCRIPTOR_DISTANCE: u32 = 40;
+// Size of bin(in normalized pixels) used to vote for offset between images
+const OFFSET_BIN_SIZE: f32 = 16.0;
+const MIN_MATCHED_KEYPOINTS: usize = 15;
+const MIN_OVERLAP_RATIO: f32 = 0.5;
+// Fixed seed, so results are the same in every scan
+const SEED: u64 = 0x0063_7a6b_6177_6b61;
+
+pub(crate) struct Keypoint {
+    bits: Vec<u128>,
+    // Position in normalized image, not in pyramid level
+    x: f32,
+    y: f32,
+    level: i32,
+}
+
+impl BinaryDescriptor for Keypoint {
+    fn get_size(&self) -> u32 {
+        (self.bits.len() * 128) as u32
+    }
+
+    fn hamming_distance(&self, other: &Self) -> u32 {
+        self.bits.iter().zip(&other.bits).map(|(a, b)| (a ^ b).count_ones()).sum()
+    }
+
+    fn get_bit_subset(&self, bits: &[u32]) -> u128 {
+        bits.iter().fold(0, |subset, bit| {
+            let chunk = self.bits.get((bit / 128) as usize).copied().unwrap_or_default();
+            (subset << 1) | ((chunk >> (bit % 128)) & 1)
+        })
+    }
+
+    fn position(&self) -> Point<u32> {
+        Point::new(self.x as u32, self.y as u32)
+    }
+}
+
+pub(crate) struct ImageFeatures {
+    keypoints: Vec<Keypoint>,
+    width: f32,
+    height: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FeatureMatch {
+    // Part of smaller image, which is visible in bigger one
+    pub(crate) overlap_ratio: f32,
+    pub(crate) first_contains_second: bool,
+}
+
+pub(crate) struct FeatureMatcher {
+    // All descriptors must be computed with the same test pairs to be comparable
+    test_pairs: Vec<TestPair>,
+}
+
+impl FeatureMatcher {
+    pub(crate) fn new() -> Self {
+        // Simple splitmix64, because random generator from imageproc is not deterministic
+        let mut state = SEED;
+        let mut next_coordinate = || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            ((z ^ (z >> 31)) % BRIEF_PATCH_DIAMETER as u64) as u32
+        };
+        let test_pairs = (0..DESCRIPTOR_BITS)
+            .map(|_| TestPair {
+                p0: Point::new(next_coordinate(), next_coordinate()),
+                p1: Point::new(next_coordinate(), next_coordinate()),
+            })
+            .collect();
+        Self { test_pairs }
+    }
+
+    pub(crate) fn compute_features(&self, path: &Path) -> Result<ImageFeatures, String> {
+        let image = get_dynamic_image_from_path(&path.to_string_lossy())?;
+        if image.width() == 0 || image.height() == 0 {
+            return Err(format!("Image \"{}\" is empty", path.to_string_lossy()));
+        }
+        let normalized = image.resize(NORMALIZED_SIZE, NORMALIZED_SIZE, FilterType::Triangle).to_luma8();
+        let (width, height) = normalized.dimensions();
+
+        let mut keypoints = Vec::new();
+        for level in 0..LEVELS {
+            let level_scale = LEVEL_SCALE_STEP.powi(level);
+            let level_width = (width as f32 * level_scale).round() as u32;
+            let level_height = (height as f32 * level_scale).round() as u32;
+            if level_width.min(level_height) < MIN_LEVEL_SIZE {
+                break;
+            }
+            let level_image: GrayImage = if level == 0 {
+                normalized.clone()
+            } else {
+                image::imageops::resize(&normalized, level_width, level_height, FilterType::Triangle)
+            };
+
+            let corners = oriented_fast(&level_image, None, KEYPOINTS_PER_LEVEL, EDGE_RADIUS, Some(SEED));
+            let points: Vec<Point<u32>> = corners.iter().map(|corner| Point::new(corner.corner.x, corner.corner.y)).collect();
+            let (descriptors, _test_pairs) = brief(&level_image, &points, DESCRIPTOR_BITS, Some(&self.test_pairs))?;
+
+            keypoints.extend(descriptors.into_iter().map(|descriptor| Keypoint {
+                x: descriptor.corner.x as f32 / level_scale,
+                y: descriptor.corner.y as f32 / level_scale,
+                bits: descriptor.bits,
+                level,
+            }));
+        }
+
+        Ok(ImageFeatures {
+            keypoints,
+            width: width as f32,
+            height: height as f32,
+        })
+    }
+}
+
+// Matched keypoints vote for scale and offset of second image inside first one - random matches are spread over many bins, so only the most popular one is used
+pub(crate) fn match_features(first: &ImageFeatures, second: &ImageFeatures) -> Option<FeatureMatch> {
+    let matches = match_binary_descriptors(&first.keypoints, &second.keypoints, MAX_DESCRIPTOR_DISTANCE, Some(SEED));
+
+    let mut votes: BTreeMap<(i32, i32, i32), Vec<(f32, f32)>> = BTreeMap::new();
+    for (first_keypoint, second_keypoint) in matches {
+        let level_difference = second_keypoint.level - first_keypoint.level;
+        let scale = LEVEL_SCALE_STEP.powi(level_difference);
+        let offset_x = first_keypoint.x - scale * second_keypoint.x;
+        let offset_y = first_keypoint.y - scale * second_keypoint.y;
+        let bin = (level_difference, (offset_x / OFFSET_BIN_SIZE).round() as i32, (offset_y / OFFSET_BIN_SIZE).round() as i32);
+        votes.entry(bin).or_default().push((offset_x, offset_y));
+    }
+
+    let ((level_difference, _, _), offsets) = votes.into_iter().max_by_key(|(_bin, offsets)| offsets.len())?;
+    if offsets.len() < MIN_MATCHED_KEYPOINTS {
+        return None;
+    }
+
+    let scale = LEVEL_SCALE_STEP.powi(level_difference);
+    let offset_x = offsets.iter().map(|(x, _)| x).sum::<f32>() / offsets.len() as f32;
+    let offset_y = offsets.iter().map(|(_, y)| y).sum::<f32>() / offsets.len() as f32;
+
+    // Second image, placed in coordinates of first one
+    let second_width = second.width * scale;
+    let second_height = second.height * scale;
+    let intersection_width = (first.width.min(offset_x + second_width) - offset_x.max(0.0)).max(0.0);
+    let intersection_height = (first.height.min(offset_y + second_height) - offset_y.max(0.0)).max(0.0);
+
+    let first_area = first.width * first.height;
+    let second_area = second_width * second_height;
+    let overlap_ratio = (intersection_width * intersection_height / first_area.min(second_area)).min(1.0);
+    if overlap_ratio < MIN_OVERLAP_RATIO {
+        return None;
+    }
+
+    Some(FeatureMatch {
+        overlap_ratio,
+        first_contains_second: second_area <= first_area,
+    })
+}
+
+impl SimilarImages {
+    // Only images that are not already in groups may be added as contained ones, so results are never duplicated
+    #[fun_time(message = "find_cropped_images", level = "debug")]
+    pub(crate) fn find_cropped_images(&mut self, images: Vec<ImagesEntry>, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        if !self.get_params().match_crops || images.len() < 2 {
+            return WorkContinueStatus::Continue;
+        }
+        // Every image is compared with every other one, so this is really slow with many images
+        let max_images = self.get_params().match_crops_max_images;
+        if images.len() > max_images {
+            self.common_data
+                .text_messages
+                .warnings
+                .push(flc!("core_too_many_images_to_match_crops", images = images.len(), limit = max_images));
+            return WorkContinueStatus::Continue;
+        }
+
+        let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesMatchingFeatures, images.len() * 2, self.get_test_type(), 0);
+
+        let matcher = FeatureMatcher::new();
+        let features: Vec<Option<ImageFeatures>> = images
+            .par_iter()
+            .map(|entry| {
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
+                let features = matcher
+                    .compute_features(&entry.path)
+                    .inspect_err(|e| debug!("Cannot compute features of \"{}\" - {e}", entry.path.to_string_lossy()))
+                    .ok();
+                progress_handler.increase_items(1);
+                Some(features)
+            })
+            .while_some()
+            .collect();
+
+        if check_if_stop_received(stop_flag) {
+            progress_handler.join_thread();
+            return WorkContinueStatus::Stop;
+        }
+
+        let mut group_of_image: HashMap<usize, usize> = HashMap::new();
+        let path_to_group: HashMap<&Path, usize> = self
+            .similar_vectors
+            .iter()
+            .enumerate()
+            .flat_map(|(group_idx, group)| group.iter().map(move |entry| (entry.path.as_path(), group_idx)))
+            .collect();
+        for (image_idx, entry) in images.iter().enumerate() {
+            if let Some(group_idx) = path_to_group.get(entry.path.as_path()) {
+                group_of_image.insert(image_idx, *group_idx);
+            }
+        }
+
+        // (container, contained, overlap ratio)
+        let mut found_pairs: Vec<(usize, usize, f32)> = features
+            .par_iter()
+            .enumerate()
+            .map(|(first_idx, first_features)| {
+                let mut pairs = Vec::new();
+                if let Some(first_features) = first_features {
+                    for (second_idx, second_features) in features.iter().enumerate().skip(first_idx + 1) {
+                        if check_if_stop_received(stop_flag) {
+                            break;
+                        }
+                        if group_of_image.contains_key(&first_idx) && group_of_image.contains_key(&second_idx) {
+                            continue;
+                        }
+                        let Some(second_features) = second_features else {
+                            continue;
+                        };
+                        if let Some(feature_match) = match_features(first_features, second_features) {
+                            let (container, contained) = if feature_match.first_contains_second {
+                                (first_idx, second_idx)
+                            } else {
+                                (second_idx, first_idx)
+                            };
+                            if !group_of_image.contains_key(&contained) {
+                                pairs.push((container, contained, feature_match.overlap_ratio));
+                            }
+                        }
+                    }
+                }
+                progress_handler.increase_items(1);
+                pairs
+            })
+            .flatten()
+            .collect();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        // Best matches are used first, and image used as contained one, cannot be container of other images
+        found_pairs.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+        let mut contained_images: HashSet<usize> = HashSet::new();
+        for (container, contained, overlap_ratio) in found_pairs {
+            if contained_images.contains(&container) || contained_images.contains(&contained) || group_of_image.contains_key(&contained) {
+                continue;
+            }
+            let (Some(container_entry), Some(contained_entry)) = (images.get(container), images.get(contained)) else {
+                continue;
+            };
+
+            let group_idx = *group_of_image.entry(container).or_insert_with(|| {
+                self.similar_vectors.push(vec![container_entry.clone()]);
+                self.similar_vectors.len() - 1
+            });
+            let mut contained_entry = contained_entry.clone();
+            contained_entry.overlap_ratio = Some(overlap_ratio);
+            if let Some(group) = self.similar_vectors.get_mut(group_idx) {
+                group.push(contained_entry);
+            }
+            contained_images.insert(contained);
+        }
+
+        WorkContinueStatus::Continue
+    }
+}
@@ -1,4 +1,8 @@
 pub mod core;
+mod features;
+mod quality;
+mod query;
+mod series;
 pub mod traits;
 
 #[cfg(test)]
@@ -10,6 +14,7 @@ use std::time::Duration;
 
 use bk_tree::BKTree;
 use hamming_bitwise_fast::hamming_bitwise_fast;
+use image::DynamicImage;
 use image_hasher::{FilterType, HashAlg};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -17,9 +22,15 @@ use serde::{Deserialize, Serialize};
 use crate::common::model::FileEntry;
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
+use crate::flc;
+use crate::tools::similar_images::quality::calculate_quality_score;
 
 type ImHash = Vec<u8>;
 
+pub const DEFAULT_MATCH_CROPS_MAX_IMAGES: usize = 1000;
+// Bursts and bracketing usually take a few seconds, longer intervals would group unrelated photos of the same scene
+pub const MAX_SERIES_INTERVAL: u32 = 60;
+
 // 40 is a little useless in 8 similarity - but this value is kept to simplify harder Krokiet max value calculations
 pub const SIMILAR_VALUES: [[u32; 6]; 4] = [
     [1, 2, 5, 7, 14, 40],    // 8
@@ -29,6 +40,8 @@ pub const SIMILAR_VALUES: [[u32; 6]; 4] = [
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+// New fields are added by optional modes, so entries are built only inside czkawka_core
+#[non_exhaustive]
 pub struct ImagesEntry {
     pub path: PathBuf,
     pub size: u64,
@@ -37,6 +50,25 @@ pub struct ImagesEntry {
     pub modified_date: u64,
     pub hash: ImHash,
     pub difference: u32,
+    // Hashes of rotated and mirrored image, in order of ImageTransform::VARIANTS - empty when transforms are not checked
+    #[serde(default)]
+    pub transformed_hashes: Vec<ImHash>,
+    // How image in group must be transformed to look like this one
+    #[serde(default)]
+    pub transform: ImageTransform,
+    // Part of this image visible in image containing it, set only for images found by matching crops
+    // Containing image is in the same group, but it is not always first, because it may be added to group of images with similar hashes
+    #[serde(default)]
+    pub overlap_ratio: Option<f32>,
+    // Variance of laplacian, bigger is sharper
+    #[serde(default)]
+    pub sharpness: Option<f32>,
+    // Estimated from quantization tables, set only for JPEG images
+    #[serde(default)]
+    pub jpeg_quality: Option<u8>,
+    // Image doesn't contain more details than its 2 times smaller version
+    #[serde(default)]
+    pub upscaled: bool,
 }
 
 impl ResultEntry for ImagesEntry {
@@ -49,6 +81,13 @@ impl ResultEntry for ImagesEntry {
     fn get_size(&self) -> u64 {
         self.size
     }
+    fn get_sharpness(&self) -> Option<f32> {
+        self.sharpness
+    }
+    // Without metrics score would depend only on resolution
+    fn get_quality_score(&self) -> Option<f64> {
+        self.sharpness.is_some().then(|| calculate_quality_score(self))
+    }
 }
 impl FileEntry {
     fn into_images_entry(self) -> ImagesEntry {
@@ -61,6 +100,65 @@ impl FileEntry {
             height: 0,
             hash: Vec::new(),
             difference: 0,
+            transformed_hashes: Vec::new(),
+            transform: ImageTransform::None,
+            overlap_ratio: None,
+            sharpness: None,
+            jpeg_quality: None,
+            upscaled: false,
+        }
+    }
+}
+
+// Rotations are clockwise, mirroring is horizontal and is done before rotation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageTransform {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Mirror,
+    MirrorRotate90,
+    MirrorRotate180,
+    MirrorRotate270,
+}
+
+impl ImageTransform {
+    // All transforms except None, for which additional hashes are calculated
+    pub const VARIANTS: [Self; 7] = [
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::Mirror,
+        Self::MirrorRotate90,
+        Self::MirrorRotate180,
+        Self::MirrorRotate270,
+    ];
+
+    pub fn apply(self, image: &DynamicImage) -> DynamicImage {
+        match self {
+            Self::None => image.clone(),
+            Self::Rotate90 => image.rotate90(),
+            Self::Rotate180 => image.rotate180(),
+            Self::Rotate270 => image.rotate270(),
+            Self::Mirror => image.fliph(),
+            Self::MirrorRotate90 => image.fliph().rotate90(),
+            Self::MirrorRotate180 => image.flipv(),
+            Self::MirrorRotate270 => image.fliph().rotate270(),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Rotate90 => "rotated 90°",
+            Self::Rotate180 => "rotated 180°",
+            Self::Rotate270 => "rotated 270°",
+            Self::Mirror => "mirrored",
+            Self::MirrorRotate90 => "mirrored and rotated 90°",
+            Self::MirrorRotate180 => "mirrored and rotated 180°",
+            Self::MirrorRotate270 => "mirrored and rotated 270°",
         }
     }
 }
@@ -96,10 +194,29 @@ pub struct SimilarImagesParameters {
     pub hash_alg: HashAlg,
     pub image_filter: FilterType,
     pub exclude_images_with_same_size: bool,
+    // Also finds rotated and mirrored images, at cost of calculating 8 hashes per image
+    pub check_transforms: bool,
+    // Also finds images containing large part of other image, by comparing keypoints of every pair of images
+    pub match_crops: bool,
+    pub match_crops_max_images: usize,
+    // When not 0, photos taken within this number of seconds are grouped into series, instead of grouping similar images
+    pub series_max_interval: u32,
+    // Also calculates sharpness, JPEG quality and upscaling of images, used to choose the best image in group
+    pub calculate_quality: bool,
 }
 
 impl SimilarImagesParameters {
-    pub fn new(max_difference: u32, hash_size: u8, hash_alg: HashAlg, image_filter: FilterType, exclude_images_with_same_size: bool) -> Self {
+    pub fn new(
+        max_difference: u32,
+        hash_size: u8,
+        hash_alg: HashAlg,
+        image_filter: FilterType,
+        exclude_images_with_same_size: bool,
+        check_transforms: bool,
+        match_crops: bool,
+        match_crops_max_images: usize,
+        series_max_interval: u32,
+    ) -> Self {
         assert!([8, 16, 32, 64].contains(&hash_size));
         Self {
             max_difference,
@@ -107,6 +224,11 @@ impl SimilarImagesParameters {
             hash_alg,
             image_filter,
             exclude_images_with_same_size,
+            check_transforms,
+            match_crops,
+            match_crops_max_images,
+            series_max_interval,
+            calculate_quality: false,
         }
     }
 }
@@ -121,6 +243,8 @@ pub struct SimilarImages {
     image_hashes: IndexMap<ImHash, Vec<ImagesEntry>>,
     images_to_check: BTreeMap<String, ImagesEntry>,
     params: SimilarImagesParameters,
+    // When not empty, only images similar to these are searched
+    query_images: Vec<PathBuf>,
 }
 
 #[derive(Default, Clone, Copy)]
@@ -144,6 +268,29 @@ impl SimilarImages {
         &self.similar_referenced_vectors
     }
 
+    pub fn set_query_images(&mut self, query_images: Vec<PathBuf>) {
+        self.query_images = query_images;
+    }
+
+    pub fn get_query_images(&self) -> &[PathBuf] {
+        &self.query_images
+    }
+
+    // Query images are used as reference items and are compared only by hash, so other reference paths and crop matching cannot be used with them
+    pub(crate) fn check_query_images_settings(&self) -> Result<(), String> {
+        if self.query_images.is_empty() {
+            return Ok(());
+        }
+        let directories = &self.common_data.directories;
+        if !directories.reference_directories.is_empty() || !directories.reference_files.is_empty() {
+            return Err(flc!("core_query_images_with_reference_paths"));
+        }
+        if self.get_params().match_crops {
+            return Err(flc!("core_query_images_with_match_crops"));
+        }
+        Ok(())
+    }
+
     pub fn get_use_reference(&self) -> bool {
         self.common_data.use_reference_folders
     }
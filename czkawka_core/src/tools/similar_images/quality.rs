@@ -0,0 +1,255 @@
+// Quality metrics calculated together with hashes, used to choose the best copy of image in group
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, GrayImage};
+use imageproc::filter::laplacian_filter;
+
+use crate::tools::similar_images::ImagesEntry;
+
+// Sharpness depends on resolution, so images are resized to the same size before calculating it
+const SHARPNESS_IMAGE_SIZE: u32 = 1024;
+// Upscaling is checked only on part of image at original resolution, because resizing would hide it
+const UPSCALE_CHECK_SIZE: u32 = 512;
+const UPSCALE_MIN_SIZE: u32 = 64;
+// In interpolated images, differences between neighbour pixels are about half of differences between pixels 2 apart
+// In sharp images they are much closer, because most details are only 1 pixel wide
+const UPSCALE_MAX_DIFFERENCE_RATIO: f64 = 0.6;
+// Flat images like solid backgrounds have no details at all, so cannot be classified
+const UPSCALE_MIN_STANDARD_DEVIATION: f64 = 4.0;
+
+// Luminance quantization table from JPEG standard(Annex K), used by libjpeg for quality 50, in zigzag order
+#[rustfmt::skip]
+const STANDARD_LUMINANCE_TABLE: [u16; 64] = [
+    16, 11, 12, 14, 12, 10, 16, 14,
+    13, 14, 18, 17, 16, 19, 24, 40,
+    26, 24, 22, 22, 24, 49, 35, 37,
+    29, 40, 58, 51, 61, 60, 57, 51,
+    56, 55, 64, 72, 92, 78, 64, 68,
+    87, 69, 55, 56, 80, 109, 81, 87,
+    95, 98, 103, 104, 103, 62, 77, 113,
+    121, 112, 100, 120, 92, 101, 103, 99,
+];
+
+const JPEG_MARKER_SOI: u8 = 0xD8;
+const JPEG_MARKER_EOI: u8 = 0xD9;
+const JPEG_MARKER_SOS: u8 = 0xDA;
+const JPEG_MARKER_DQT: u8 = 0xDB;
+
+pub(crate) fn calculate_quality_metrics(file_entry: &mut ImagesEntry, image: &DynamicImage) {
+    file_entry.sharpness = Some(calculate_sharpness(image));
+    file_entry.upscaled = is_upscaled(image);
+    file_entry.jpeg_quality = estimate_jpeg_quality(&file_entry.path);
+}
+
+// Combines all metrics into one value, bigger is better - only makes sense to compare images with same content
+// Upscaled images are treated like their original, 2 times smaller version and lossless formats like 100% quality JPEG
+pub(crate) fn calculate_quality_score(file_entry: &ImagesEntry) -> f64 {
+    let pixels = f64::from(file_entry.width) * f64::from(file_entry.height);
+    let effective_pixels = if file_entry.upscaled { pixels / 4.0 } else { pixels };
+    let compression_factor = f64::from(file_entry.jpeg_quality.unwrap_or(100)) / 100.0;
+    let sharpness_factor = (2.0 + f64::from(file_entry.sharpness.unwrap_or_default())).log2();
+
+    effective_pixels.sqrt() * compression_factor * sharpness_factor
+}
+
+// Variance of laplacian - blurred images have less edges, so values of laplacian are closer to 0
+pub(crate) fn calculate_sharpness(image: &DynamicImage) -> f32 {
+    let gray_image = if image.width().max(image.height()) > SHARPNESS_IMAGE_SIZE {
+        image.resize(SHARPNESS_IMAGE_SIZE, SHARPNESS_IMAGE_SIZE, FilterType::Triangle).to_luma8()
+    } else {
+        image.to_luma8()
+    };
+    let (_mean, variance) = mean_and_variance(laplacian_filter(&gray_image).pixels().map(|pixel| f64::from(pixel.0[0])));
+    variance as f32
+}
+
+// Upscaled images are smooth at pixel level, so their brightness changes almost linearly between neighbour pixels
+// Blurry photos are also detected as upscaled, because they also don't contain any fine details
+pub(crate) fn is_upscaled(image: &DynamicImage) -> bool {
+    let (width, height) = (image.width(), image.height());
+    if width < UPSCALE_MIN_SIZE || height < UPSCALE_MIN_SIZE {
+        return false;
+    }
+    let crop_width = width.min(UPSCALE_CHECK_SIZE);
+    let crop_height = height.min(UPSCALE_CHECK_SIZE);
+    let crop = image.crop_imm((width - crop_width) / 2, (height - crop_height) / 2, crop_width, crop_height).to_luma8();
+
+    let (_mean, variance) = mean_and_variance(crop.pixels().map(|pixel| f64::from(pixel.0[0])));
+    let standard_deviation = variance.sqrt();
+    if standard_deviation < UPSCALE_MIN_STANDARD_DEVIATION {
+        return false;
+    }
+
+    let neighbour_difference = mean_difference(&crop, 1);
+    let distant_difference = mean_difference(&crop, 2);
+    distant_difference > 0.0 && neighbour_difference / distant_difference < UPSCALE_MAX_DIFFERENCE_RATIO
+}
+
+// Mean absolute difference between pixels in given horizontal and vertical distance
+fn mean_difference(image: &GrayImage, distance: u32) -> f64 {
+    let mut sum = 0.0;
+    let mut count = 0u64;
+    for (x, y, pixel) in image.enumerate_pixels() {
+        for (other_x, other_y) in [(x + distance, y), (x, y + distance)] {
+            if let Some(other) = image.get_pixel_checked(other_x, other_y) {
+                sum += f64::from(pixel.0[0].abs_diff(other.0[0]));
+                count += 1;
+            }
+        }
+    }
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+fn mean_and_variance(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let count = values.clone().count();
+    if count == 0 {
+        return (0.0, 0.0);
+    }
+    let mean = values.clone().sum::<f64>() / count as f64;
+    let variance = values.map(|value| (value - mean).powi(2)).sum::<f64>() / count as f64;
+    (mean, variance)
+}
+
+// Quality is estimated from luminance quantization table, the same way as libjpeg scales it from standard table
+// Encoders with custom tables(e.g. some cameras) get approximate value
+pub(crate) fn estimate_jpeg_quality(path: &Path) -> Option<u8> {
+    let table = read_jpeg_luminance_table(path)?;
+    let scaling: f64 = table
+        .iter()
+        .zip(STANDARD_LUMINANCE_TABLE)
+        .map(|(&value, standard_value)| f64::from(value) * 100.0 / f64::from(standard_value))
+        .sum::<f64>()
+        / table.len() as f64;
+
+    let quality = if scaling <= 100.0 { (200.0 - scaling) / 2.0 } else { 5000.0 / scaling };
+    Some(quality.round().clamp(1.0, 100.0) as u8)
+}
+
+// Only headers are read, until the start of compressed data
+fn read_jpeg_luminance_table(path: &Path) -> Option<[u16; 64]> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    let mut marker = [0u8; 2];
+    reader.read_exact(&mut marker).ok()?;
+    if marker != [0xFF, JPEG_MARKER_SOI] {
+        return None;
+    }
+
+    loop {
+        reader.read_exact(&mut marker).ok()?;
+        if marker[0] != 0xFF {
+            return None;
+        }
+        // Markers may be preceded by any number of fill bytes
+        while marker[1] == 0xFF {
+            reader.read_exact(&mut marker[1..]).ok()?;
+        }
+        if marker[1] == JPEG_MARKER_SOS || marker[1] == JPEG_MARKER_EOI {
+            return None;
+        }
+
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length).ok()?;
+        let length = u16::from_be_bytes(length).checked_sub(2)?;
+        if marker[1] != JPEG_MARKER_DQT {
+            reader.seek_relative(i64::from(length)).ok()?;
+            continue;
+        }
+
+        let mut segment = vec![0u8; length as usize];
+        reader.read_exact(&mut segment).ok()?;
+        if let Some(table) = parse_luminance_table(&segment) {
+            return Some(table);
+        }
+    }
+}
+
+// Single DQT segment may contain multiple tables, luminance table has id 0
+fn parse_luminance_table(mut segment: &[u8]) -> Option<[u16; 64]> {
+    while let Some((&info, rest)) = segment.split_first() {
+        let is_16_bit = info >> 4 != 0;
+        let table_size = if is_16_bit { 128 } else { 64 };
+        let table_data = rest.get(..table_size)?;
+
+        if info & 0x0F == 0 {
+            let mut table = [0u16; 64];
+            for (idx, value) in table.iter_mut().enumerate() {
+                *value = if is_16_bit {
+                    u16::from_be_bytes([*table_data.get(idx * 2)?, *table_data.get(idx * 2 + 1)?])
+                } else {
+                    u16::from(*table_data.get(idx)?)
+                };
+            }
+            // Zeroes are invalid, and would break quality calculation
+            return table.iter().all(|&value| value > 0).then_some(table);
+        }
+        segment = rest.get(table_size..)?;
+    }
+    None
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use image::{ImageBuffer, Luma, RgbImage};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    // Deterministic xorshift noise of given amplitude added to base pattern, which adds fine details lost after blurring, compression or upscaling
+    pub(crate) fn noise_image(width: u32, height: u32, amplitude: u32, base: impl Fn(u32, u32) -> u8) -> DynamicImage {
+        let mut state = 0x1234_5678_u32;
+        DynamicImage::ImageLuma8(ImageBuffer::from_fn(width, height, |x, y| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let value = i64::from(base(x, y)) + i64::from(state % amplitude) - i64::from(amplitude / 2);
+            Luma([value.clamp(0, 255) as u8])
+        }))
+    }
+
+    fn full_noise_image(width: u32, height: u32) -> DynamicImage {
+        noise_image(width, height, 256, |_, _| 128)
+    }
+
+    #[test]
+    fn test_estimate_jpeg_quality() {
+        let temp_dir = TempDir::new().unwrap();
+        let image = DynamicImage::ImageRgb8(RgbImage::from_fn(64, 64, |x, y| image::Rgb([(x * 4) as u8, (y * 4) as u8, 128])));
+
+        for quality in [30, 50, 75, 90, 100] {
+            let path = temp_dir.path().join(format!("image_{quality}.jpg"));
+            let mut file = File::create(&path).unwrap();
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, quality).encode_image(&image).unwrap();
+            drop(file);
+
+            let estimated = estimate_jpeg_quality(&path).unwrap();
+            assert!(estimated.abs_diff(quality) <= 1, "Quality {quality} estimated as {estimated}");
+        }
+
+        let png_path = temp_dir.path().join("image.png");
+        image.save(&png_path).unwrap();
+        assert_eq!(estimate_jpeg_quality(&png_path), None);
+    }
+
+    #[test]
+    fn test_is_upscaled() {
+        let image = full_noise_image(300, 200);
+        assert!(!is_upscaled(&image));
+        assert!(is_upscaled(&image.resize_exact(600, 400, FilterType::Triangle)));
+        assert!(is_upscaled(&image.resize_exact(600, 400, FilterType::Lanczos3)));
+        assert!(is_upscaled(&image.resize_exact(900, 600, FilterType::CatmullRom)));
+        assert!(is_upscaled(&image.resize_exact(600, 400, FilterType::Nearest)));
+        // Too small or without any details
+        assert!(!is_upscaled(&full_noise_image(40, 40)));
+        assert!(!is_upscaled(&DynamicImage::new_luma8(300, 300)));
+    }
+
+    #[test]
+    fn test_calculate_sharpness() {
+        let image = full_noise_image(200, 200);
+        assert!(calculate_sharpness(&image) > calculate_sharpness(&image.blur(2.0)));
+        assert!(calculate_sharpness(&DynamicImage::new_luma8(200, 200)) < f32::EPSILON);
+    }
+}
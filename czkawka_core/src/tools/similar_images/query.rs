@@ -0,0 +1,123 @@
+// Query mode - instead of grouping all scanned images, only images similar to given query images are found
+// Results are saved as referenced groups, with query image as reference, so they are shown and deleted like results of reference folders
+use std::iter;
+use std::mem;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use bk_tree::BKTree;
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use indexmap::IndexMap;
+use rayon::prelude::*;
+
+use crate::common::dir_traversal::{common_get_metadata_from_path, get_modified_time};
+use crate::common::file_system::LocalFileSystem;
+use crate::common::model::{FileEntry, WorkContinueStatus};
+use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::tool_data::CommonData;
+use crate::tools::similar_images::core::find_matching_transform;
+use crate::tools::similar_images::{Hamming, ImHash, ImagesEntry, SimilarImages};
+
+impl SimilarImages {
+    #[fun_time(message = "find_similar_to_query_images", level = "debug")]
+    pub(crate) fn find_similar_to_query_images(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        self.common_data.use_reference_folders = true;
+
+        let query_images = self.hash_query_images();
+        let all_hashed_images = mem::take(&mut self.image_hashes);
+        for hash in all_hashed_images.keys() {
+            self.bktree.add(hash.clone());
+        }
+
+        let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesComparingHashes, query_images.len(), self.get_test_type(), 0);
+
+        let tolerance = self.get_params().max_difference;
+        let results: Vec<(ImagesEntry, Vec<ImagesEntry>)> = query_images
+            .into_par_iter()
+            .map(|query_image| {
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
+                let similar_images = self.find_similar_to_query_image(&query_image, &all_hashed_images, tolerance);
+                progress_handler.increase_items(1);
+                Some((query_image, similar_images))
+            })
+            .while_some()
+            .filter(|(_query_image, similar_images)| !similar_images.is_empty())
+            .collect();
+
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        self.similar_referenced_vectors = results;
+        self.calculate_duplicates_stats();
+
+        self.images_to_check = Default::default();
+        self.bktree = BKTree::new(Hamming);
+
+        WorkContinueStatus::Continue
+    }
+
+    // Query images are not cached, because usually there is only few of them and they may be outside scanned folders
+    fn hash_query_images(&mut self) -> Vec<ImagesEntry> {
+        let mut warnings = Vec::new();
+        let entries: Vec<ImagesEntry> = self
+            .query_images
+            .iter()
+            .filter_map(|path| {
+                let metadata = common_get_metadata_from_path(&LocalFileSystem, path, &mut warnings)?;
+                let file_entry = FileEntry {
+                    size: metadata.len,
+                    modified_date: get_modified_time(&metadata, &mut warnings, path, false),
+                    path: path.clone(),
+                };
+                Some(file_entry.into_images_entry())
+            })
+            .collect();
+        self.common_data.text_messages.warnings.extend(warnings);
+
+        let (hashed_entries, errors): (Vec<ImagesEntry>, Vec<String>) = entries.into_par_iter().map(|entry| self.collect_image_file_entry(entry)).partition_map(|res| match res {
+            Ok(entry) => itertools::Either::Left(entry),
+            Err(err) => itertools::Either::Right(err),
+        });
+        self.common_data.text_messages.errors.extend(errors);
+
+        hashed_entries
+    }
+
+    // Most similar images are first, query image itself is never returned, even if it is in scanned folders
+    fn find_similar_to_query_image(&self, query_image: &ImagesEntry, all_hashed_images: &IndexMap<ImHash, Vec<ImagesEntry>>, tolerance: u32) -> Vec<ImagesEntry> {
+        let mut found_hashes: IndexMap<&ImHash, u32> = IndexMap::new();
+        for hash in iter::once(&query_image.hash).chain(&query_image.transformed_hashes) {
+            for (similarity, found_hash) in self.bktree.find(hash, tolerance) {
+                let current_similarity = found_hashes.entry(found_hash).or_insert(similarity);
+                *current_similarity = (*current_similarity).min(similarity);
+            }
+        }
+
+        let mut similar_images: Vec<ImagesEntry> = found_hashes
+            .into_iter()
+            .filter_map(|(found_hash, similarity)| all_hashed_images.get(found_hash).map(|entries| (found_hash, similarity, entries)))
+            .flat_map(|(found_hash, similarity, entries)| {
+                let transform = find_matching_transform(&query_image.hash, &query_image.transformed_hashes, found_hash);
+                entries.iter().filter(|entry| !is_same_file(&entry.path, &query_image.path)).map(move |entry| {
+                    let mut entry = entry.clone();
+                    entry.difference = similarity;
+                    entry.transform = transform;
+                    entry
+                })
+            })
+            .collect();
+        similar_images.sort_by(|a, b| a.difference.cmp(&b.difference).then_with(|| a.path.cmp(&b.path)));
+        similar_images
+    }
+}
+
+fn is_same_file(first: &Path, second: &Path) -> bool {
+    first == second || matches!((first.canonicalize(), second.canonicalize()), (Ok(first), Ok(second)) if first == second)
+}
@@ -0,0 +1,190 @@
+// Series mode - groups photos taken one after another(bursts, bracketing), instead of images similar to each other
+// Camera moves slightly between shots, so hash threshold is relaxed, but only photos taken within short time are compared
+// Photos in group are sorted by sharpness calculated when hashing, so the sharpest one is first
+use std::mem;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use bk_tree::BKTree;
+use crossbeam_channel::Sender;
+use fun_time::fun_time;
+use hamming_bitwise_fast::hamming_bitwise_fast;
+use rayon::prelude::*;
+
+use crate::common::image::get_capture_time_from_exif;
+use crate::common::model::WorkContinueStatus;
+use crate::common::progress_data::{CurrentStage, ProgressData};
+use crate::common::progress_stop_handler::{check_if_stop_received, prepare_thread_handler_common};
+use crate::common::tool_data::CommonData;
+use crate::tools::similar_images::{Hamming, ImagesEntry, SimilarImages};
+
+// Photos in series may differ more than similar images, because of camera or subject movement
+const SERIES_DIFFERENCE_MULTIPLIER: u32 = 2;
+
+// EXIF time is usually local time of camera, while modification date is UTC, so photos with different time sources are never in the same series
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum CaptureTime {
+    Exif(i64),
+    Modified(i64),
+}
+
+impl CaptureTime {
+    fn seconds_after(self, previous: Self) -> Option<i64> {
+        match (self, previous) {
+            (Self::Exif(time), Self::Exif(previous_time)) | (Self::Modified(time), Self::Modified(previous_time)) => Some(time - previous_time),
+            _ => None,
+        }
+    }
+}
+
+impl SimilarImages {
+    #[fun_time(message = "find_photo_series", level = "debug")]
+    pub(crate) fn find_photo_series(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        let images: Vec<ImagesEntry> = mem::take(&mut self.image_hashes).into_values().flatten().collect();
+
+        let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::SimilarImagesComparingHashes, images.len(), self.get_test_type(), 0);
+        // Photos without EXIF(e.g. from some phones or after editing) usually still have modification date close to capture time
+        let mut images_with_time: Vec<(CaptureTime, ImagesEntry)> = images
+            .into_par_iter()
+            .map(|entry| {
+                if check_if_stop_received(stop_flag) {
+                    return None;
+                }
+                let capture_time = get_capture_time_from_exif(&entry.path).map_or(CaptureTime::Modified(entry.modified_date as i64), CaptureTime::Exif);
+                progress_handler.increase_items(1);
+                Some((capture_time, entry))
+            })
+            .while_some()
+            .collect();
+        progress_handler.join_thread();
+        if check_if_stop_received(stop_flag) {
+            return WorkContinueStatus::Stop;
+        }
+
+        images_with_time.sort_unstable_by(|(time_a, entry_a), (time_b, entry_b)| time_a.cmp(time_b).then_with(|| entry_a.path.cmp(&entry_b.path)));
+        let series = split_into_series(
+            images_with_time,
+            i64::from(self.get_params().series_max_interval),
+            self.get_params().max_difference * SERIES_DIFFERENCE_MULTIPLIER,
+        );
+        self.similar_vectors = series.into_iter().map(sort_by_sharpness).collect();
+
+        self.exclude_items_with_same_size();
+
+        self.remove_multiple_records_from_reference_folders();
+
+        self.calculate_duplicates_stats();
+
+        self.images_to_check = Default::default();
+        self.bktree = BKTree::new(Hamming);
+
+        WorkContinueStatus::Continue
+    }
+}
+
+// Photo joins series when it is similar to any of its photos taken within interval, so unrelated shots taken in between(e.g. by second camera) don't split it
+// Only photos close in time are compared, so series may slowly change e.g. when camera pans
+fn split_into_series(images_with_time: Vec<(CaptureTime, ImagesEntry)>, max_interval: i64, max_difference: u32) -> Vec<Vec<ImagesEntry>> {
+    let is_within_interval = |capture_time: CaptureTime, other_time: CaptureTime| capture_time.seconds_after(other_time).is_some_and(|interval| interval <= max_interval);
+
+    let mut all_series = Vec::new();
+    let mut open_series: Vec<Vec<(CaptureTime, ImagesEntry)>> = Vec::new();
+    let mut close_series = |series: Vec<(CaptureTime, ImagesEntry)>| {
+        if series.len() > 1 {
+            all_series.push(series.into_iter().map(|(_, entry)| entry).collect());
+        }
+    };
+    for (capture_time, entry) in images_with_time {
+        // Photos are sorted by time, so series with last photo taken before interval will never be continued
+        let (still_open, finished): (Vec<_>, Vec<_>) = mem::take(&mut open_series)
+            .into_iter()
+            .partition(|series| series.last().is_some_and(|(last_time, _)| is_within_interval(capture_time, *last_time)));
+        finished.into_iter().for_each(&mut close_series);
+        open_series = still_open;
+
+        let matching_series = open_series.iter_mut().find(|series| {
+            series
+                .iter()
+                .any(|(time, photo)| is_within_interval(capture_time, *time) && hamming_bitwise_fast(&photo.hash, &entry.hash) <= max_difference)
+        });
+        match matching_series {
+            Some(series) => series.push((capture_time, entry)),
+            None => open_series.push(vec![(capture_time, entry)]),
+        }
+    }
+    open_series.into_iter().for_each(close_series);
+    all_series
+}
+
+// Difference is shown relative to the sharpest photo, which is the first in group
+fn sort_by_sharpness(mut photos: Vec<ImagesEntry>) -> Vec<ImagesEntry> {
+    photos.sort_by(|a, b| b.sharpness.unwrap_or(-1.0).total_cmp(&a.sharpness.unwrap_or(-1.0)));
+    let sharpest_hash = photos.first().map(|photo| photo.hash.clone()).unwrap_or_default();
+    for photo in &mut photos {
+        photo.difference = hamming_bitwise_fast(&sharpest_hash, &photo.hash);
+    }
+    photos
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::common::model::FileEntry;
+
+    fn photo(name: &str) -> ImagesEntry {
+        photo_with_hash(name, vec![0; 8])
+    }
+
+    fn photo_with_hash(name: &str, hash: Vec<u8>) -> ImagesEntry {
+        let mut entry = FileEntry {
+            path: PathBuf::from(name),
+            ..Default::default()
+        }
+        .into_images_entry();
+        entry.hash = hash;
+        entry
+    }
+
+    fn series_names(series: Vec<Vec<ImagesEntry>>) -> Vec<Vec<String>> {
+        series
+            .into_iter()
+            .map(|photos| photos.into_iter().map(|entry| entry.path.to_string_lossy().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_into_series_does_not_mix_time_sources() {
+        // Local EXIF time may be hours away from UTC modification date, so only photos with the same time source are compared
+        let mut images_with_time = vec![
+            (CaptureTime::Exif(1000), photo("exif_1")),
+            (CaptureTime::Exif(1002), photo("exif_2")),
+            (CaptureTime::Modified(1001), photo("modified_1")),
+            (CaptureTime::Modified(1003), photo("modified_2")),
+            (CaptureTime::Modified(1100), photo("modified_later")),
+        ];
+        images_with_time.sort_unstable_by_key(|(capture_time, _)| *capture_time);
+
+        let series = series_names(split_into_series(images_with_time, 10, 0));
+        assert_eq!(series, [["exif_1", "exif_2"], ["modified_1", "modified_2"]]);
+    }
+
+    #[test]
+    fn test_split_into_series_with_interleaved_photos() {
+        // Two cameras take photos of different subjects at the same time
+        let images_with_time = vec![
+            (CaptureTime::Exif(1000), photo_with_hash("first_1", vec![0; 8])),
+            (CaptureTime::Exif(1001), photo_with_hash("second_1", vec![255; 8])),
+            (CaptureTime::Exif(1002), photo_with_hash("first_2", vec![0; 8])),
+            (CaptureTime::Exif(1003), photo_with_hash("second_2", vec![255; 8])),
+            (CaptureTime::Exif(1004), photo_with_hash("unrelated", vec![15; 8])),
+            (CaptureTime::Exif(1005), photo_with_hash("first_3", vec![0; 8])),
+            // Too late to be compared with any photo of first series
+            (CaptureTime::Exif(1020), photo_with_hash("first_later", vec![0; 8])),
+        ];
+
+        let series = series_names(split_into_series(images_with_time, 10, 2));
+        assert_eq!(series, [vec!["first_1", "first_2", "first_3"], vec!["second_1", "second_2"]]);
+    }
+}
@@ -1,12 +1,15 @@
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use image_hasher::{FilterType, HashAlg};
 
-use crate::common::tool_data::CommonData;
-use crate::common::traits::Search;
-use crate::tools::similar_images::{SimilarImages, SimilarImagesParameters};
+use crate::common::keep_rules::KeepRules;
+use crate::common::tool_data::{CommonData, DeleteMethod, select_items_to_delete};
+use crate::common::traits::{ResultEntry, Search};
+use crate::tools::similar_images::quality::tests::noise_image;
+use crate::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, ImageTransform, SimilarImages, SimilarImagesParameters};
 
 fn get_test_resources_path() -> PathBuf {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_resources").join("images");
@@ -42,7 +45,7 @@ fn test_similar_images() {
     ];
 
     for (idx, (hash_alg, filter_type, hash_size, similarity, duplicates, groups, all_in_similar)) in algo_filter_hash_sim_found.into_iter().enumerate() {
-        let params = SimilarImagesParameters::new(similarity, hash_size, hash_alg, filter_type, false);
+        let params = SimilarImagesParameters::new(similarity, hash_size, hash_alg, filter_type, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
 
         let mut finder = SimilarImages::new(params);
         finder.set_included_paths(vec![test_path.clone()]);
@@ -69,7 +72,7 @@ fn test_similar_images() {
 fn test_similar_images_exclude_same_size() {
     let test_path = get_test_resources_path();
 
-    let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, true);
+    let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, true, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
 
     let mut finder = SimilarImages::new(params);
     finder.set_included_paths(vec![test_path]);
@@ -99,7 +102,7 @@ fn test_similar_images_empty_directory() {
     let temp_dir = TempDir::new().unwrap();
     let path = temp_dir.path();
 
-    let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false);
+    let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
 
     let mut finder = SimilarImages::new(params);
     finder.set_included_paths(vec![path.to_path_buf()]);
@@ -116,3 +119,238 @@ fn test_similar_images_empty_directory() {
     assert_eq!(info.number_of_groups, 0);
     assert_eq!(similar_images.len(), 0);
 }
+
+#[test]
+fn test_similar_images_check_transforms() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let image = image::open(get_test_resources_path().join("normal.jpg")).unwrap();
+    image.save(path.join("original.png")).unwrap();
+    image.rotate90().save(path.join("rotated.png")).unwrap();
+
+    for check_transforms in [false, true] {
+        let params = SimilarImagesParameters::new(
+            2,
+            16,
+            HashAlg::Gradient,
+            FilterType::Lanczos3,
+            false,
+            check_transforms,
+            false,
+            DEFAULT_MATCH_CROPS_MAX_IMAGES,
+            0,
+        );
+
+        let mut finder = SimilarImages::new(params);
+        finder.set_included_paths(vec![path.to_path_buf()]);
+        finder.set_use_cache(false);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+        let similar_images = finder.get_similar_images();
+        if !check_transforms {
+            assert!(similar_images.is_empty());
+            continue;
+        }
+
+        assert_eq!(similar_images.len(), 1);
+        let rotated = similar_images[0].iter().find(|entry| entry.path.ends_with("rotated.png")).unwrap();
+        let original = similar_images[0].iter().find(|entry| entry.path.ends_with("original.png")).unwrap();
+        // Transform is reported for image matched to first one in group
+        let expected = if similar_images[0][0].path == original.path {
+            (ImageTransform::None, ImageTransform::Rotate90)
+        } else {
+            (ImageTransform::Rotate270, ImageTransform::None)
+        };
+        assert_eq!((original.transform, rotated.transform), expected);
+        assert_eq!(rotated.transformed_hashes.len(), ImageTransform::VARIANTS.len());
+    }
+}
+
+#[test]
+fn test_similar_images_match_crops() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let image = image::open(get_test_resources_path().join("normal.jpg")).unwrap();
+    let (width, height) = (image.width(), image.height());
+    image.save(path.join("original.png")).unwrap();
+    image.crop_imm(width / 5, height / 5, width * 3 / 5, height * 3 / 5).save(path.join("cropped.png")).unwrap();
+
+    for match_crops in [false, true] {
+        let params = SimilarImagesParameters::new(0, 16, HashAlg::Gradient, FilterType::Lanczos3, false, false, match_crops, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
+
+        let mut finder = SimilarImages::new(params);
+        finder.set_included_paths(vec![path.to_path_buf()]);
+        finder.set_use_cache(false);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+        let similar_images = finder.get_similar_images();
+        if !match_crops {
+            assert!(similar_images.is_empty());
+            continue;
+        }
+
+        assert_eq!(similar_images.len(), 1);
+        // Bigger image is always first in group
+        assert!(similar_images[0][0].path.ends_with("original.png"));
+        assert_eq!(similar_images[0][0].overlap_ratio, None);
+        let cropped = &similar_images[0][1];
+        assert!(cropped.path.ends_with("cropped.png"));
+        assert!(cropped.overlap_ratio.unwrap() > 0.8, "Overlap ratio {:?}", cropped.overlap_ratio);
+    }
+}
+
+#[test]
+fn test_similar_images_match_crops_limit() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let image = image::open(get_test_resources_path().join("normal.jpg")).unwrap();
+    image.save(path.join("original.png")).unwrap();
+    image.crop_imm(0, 0, image.width() / 2, image.height() / 2).save(path.join("cropped.png")).unwrap();
+
+    let params = SimilarImagesParameters::new(0, 16, HashAlg::Gradient, FilterType::Lanczos3, false, false, true, 1, 0);
+    let mut finder = SimilarImages::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    assert!(finder.get_similar_images().is_empty());
+    assert_eq!(finder.get_text_messages().warnings.len(), 1);
+}
+
+#[test]
+fn test_similar_images_query() {
+    let test_path = get_test_resources_path();
+
+    for (max_difference, expected_found) in [(64, 2), (15, 1), (0, 0)] {
+        let params = SimilarImagesParameters::new(
+            max_difference,
+            8,
+            HashAlg::Gradient,
+            FilterType::Lanczos3,
+            false,
+            false,
+            false,
+            DEFAULT_MATCH_CROPS_MAX_IMAGES,
+            0,
+        );
+
+        let mut finder = SimilarImages::new(params);
+        finder.set_included_paths(vec![test_path.clone()]);
+        finder.set_use_cache(false);
+        finder.set_query_images(vec![test_path.join("normal.jpg")]);
+        finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+        assert!(finder.get_use_reference());
+        assert!(finder.get_similar_images().is_empty());
+        let results = finder.get_similar_images_referenced();
+        if expected_found == 0 {
+            assert!(results.is_empty());
+            continue;
+        }
+
+        assert_eq!(results.len(), 1);
+        let (query_image, found) = &results[0];
+        assert_eq!(query_image.path, test_path.join("normal.jpg"));
+        assert_eq!(found.len(), expected_found, "Max difference {max_difference}");
+        // Query image is never returned as similar to itself
+        assert!(found.iter().all(|entry| entry.path != query_image.path));
+        assert!(found.windows(2).all(|pair| pair[0].difference <= pair[1].difference));
+        assert_eq!(finder.get_information().number_of_duplicates, expected_found);
+    }
+}
+
+#[test]
+fn test_similar_images_photo_series() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    let image = image::open(get_test_resources_path().join("normal.jpg"))
+        .unwrap()
+        .resize(240, 200, image::imageops::FilterType::Lanczos3);
+    // Burst of 3 photos, one of them blurred and the same photo taken much later, which is not part of series
+    let photos = [("burst_1.png", 1000, 0.0), ("burst_2.png", 1001, 2.0), ("burst_3.png", 1003, 0.5), ("later.png", 5000, 0.0)];
+    for (name, modified_time, blur) in photos {
+        let photo = if blur > 0.0 { image.blur(blur) } else { image.clone() };
+        photo.save(path.join(name)).unwrap();
+        filetime::set_file_mtime(path.join(name), filetime::FileTime::from_unix_time(modified_time, 0)).unwrap();
+    }
+
+    let params = SimilarImagesParameters::new(5, 8, HashAlg::Gradient, FilterType::Lanczos3, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 10);
+    let mut finder = SimilarImages::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    let similar_images = finder.get_similar_images();
+    assert_eq!(similar_images.len(), 1);
+    let names: Vec<_> = similar_images[0]
+        .iter()
+        .map(|entry| entry.path.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    // Photos are sorted from the sharpest
+    assert_eq!(names, ["burst_1.png", "burst_3.png", "burst_2.png"]);
+    assert!(similar_images[0].iter().all(|entry| entry.sharpness.is_some()));
+
+    let kept = KeepRules::from_str("sharpest").unwrap().rank(similar_images[0].iter().rev().cloned().collect());
+    assert!(kept[0].path.ends_with("burst_1.png"));
+}
+
+#[test]
+fn test_similar_images_keep_highest_quality() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    // Smooth shapes are used for hashing, while noise adds fine details, which are lost after compression or upscaling
+    let image = noise_image(300, 200, 50, |x, y| if (x / 60 + y / 50) % 2 == 0 { 60 } else { 190 });
+    let mut file = std::fs::File::create(path.join("original.jpg")).unwrap();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, 95).encode_image(&image).unwrap();
+    let mut file = std::fs::File::create(path.join("compressed.jpg")).unwrap();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, 20).encode_image(&image).unwrap();
+    image
+        .resize_exact(600, 400, image::imageops::FilterType::CatmullRom)
+        .save(path.join("upscaled.png"))
+        .unwrap();
+
+    // Metrics are not calculated, when nothing uses them
+    let params = SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0);
+    let mut finder = SimilarImages::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+    assert_eq!(finder.get_similar_images().len(), 1);
+    assert!(
+        finder.get_similar_images()[0]
+            .iter()
+            .all(|entry| entry.sharpness.is_none() && entry.jpeg_quality.is_none() && entry.get_quality_score().is_none())
+    );
+
+    let params = SimilarImagesParameters {
+        calculate_quality: true,
+        ..SimilarImagesParameters::new(10, 8, HashAlg::Gradient, FilterType::Lanczos3, false, false, false, DEFAULT_MATCH_CROPS_MAX_IMAGES, 0)
+    };
+    let mut finder = SimilarImages::new(params);
+    finder.set_included_paths(vec![path.to_path_buf()]);
+    finder.set_use_cache(false);
+    finder.search(&Arc::new(AtomicBool::new(false)), None);
+
+    let similar_images = finder.get_similar_images();
+    assert_eq!(similar_images.len(), 1);
+    assert_eq!(similar_images[0].len(), 3);
+    for entry in &similar_images[0] {
+        let name = entry.path.file_name().unwrap().to_string_lossy();
+        match name.as_ref() {
+            "original.jpg" => assert!(entry.jpeg_quality.is_some_and(|quality| quality >= 90) && !entry.upscaled),
+            "compressed.jpg" => assert!(entry.jpeg_quality.is_some_and(|quality| quality <= 30) && !entry.upscaled),
+            "upscaled.png" => assert!(entry.jpeg_quality.is_none() && entry.upscaled),
+            _ => panic!("Unexpected file {name}"),
+        }
+    }
+
+    let to_delete = select_items_to_delete(DeleteMethod::AllExceptHighestQuality, &KeepRules::default(), similar_images.clone());
+    let mut deleted_names: Vec<_> = to_delete.iter().map(|entry| entry.path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    deleted_names.sort();
+    assert_eq!(deleted_names, ["compressed.jpg", "upscaled.png"]);
+}
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -8,12 +9,32 @@ use fun_time::fun_time;
 use humansize::{BINARY, format_size};
 
 use crate::common::consts::{HEIC_EXTENSIONS, IMAGE_RS_SIMILAR_IMAGES_EXTENSIONS, RAW_IMAGE_EXTENSIONS};
-use crate::common::model::WorkContinueStatus;
+use crate::common::import::{ImportSummary, SavedResults, load_json_results};
+use crate::common::model::{CheckingMethod, WorkContinueStatus};
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::tools::similar_images::core::get_string_from_similarity;
-use crate::tools::similar_images::{Info, SimilarImages, SimilarImagesParameters};
+use crate::tools::similar_images::{ImageTransform, ImagesEntry, Info, SimilarImages, SimilarImagesParameters};
+
+impl ImportingResults for SimilarImages {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        match load_json_results(file_name)? {
+            SavedResults::Referenced(groups) => {
+                self.set_use_reference_folders(true);
+                self.similar_referenced_vectors = summary.filter_referenced_groups(groups);
+            }
+            SavedResults::Normal(groups) => {
+                self.set_use_reference_folders(false);
+                self.similar_vectors = summary.filter_groups(groups);
+            }
+        }
+        self.calculate_duplicates_stats();
+        Ok(summary)
+    }
+}
 
 impl AllTraits for SimilarImages {}
 
@@ -32,6 +53,10 @@ impl Search for SimilarImages {
             if self.prepare_items(Some(&extensions)).is_err() {
                 return;
             }
+            if let Err(e) = self.check_query_images_settings() {
+                self.common_data.text_messages.critical = Some(e);
+                return;
+            }
             self.common_data.use_reference_folders = !self.common_data.directories.reference_directories.is_empty() || !self.common_data.directories.reference_files.is_empty();
             if self.check_for_similar_images(stop_flag, progress_sender) == WorkContinueStatus::Stop {
                 self.common_data.stopped_search = true;
@@ -41,7 +66,14 @@ impl Search for SimilarImages {
                 self.common_data.stopped_search = true;
                 return;
             }
-            if self.find_similar_hashes(stop_flag, progress_sender) == WorkContinueStatus::Stop {
+            let found_status = if !self.query_images.is_empty() {
+                self.find_similar_to_query_images(stop_flag, progress_sender)
+            } else if self.get_params().series_max_interval > 0 {
+                self.find_photo_series(stop_flag, progress_sender)
+            } else {
+                self.find_similar_hashes(stop_flag, progress_sender)
+            };
+            if found_status == WorkContinueStatus::Stop {
                 self.common_data.stopped_search = true;
                 return;
             }
@@ -82,12 +114,13 @@ impl PrintResults for SimilarImages {
                 for file_entry in struct_similar {
                     writeln!(
                         writer,
-                        "\"{}\" - {}x{} - {} - {}",
+                        "\"{}\" - {}x{} - {} - {}{}",
                         file_entry.path.to_string_lossy(),
                         file_entry.width,
                         file_entry.height,
                         format_size(file_entry.size, BINARY),
-                        get_string_from_similarity(file_entry.difference, self.get_params().hash_size)
+                        get_string_from_similarity(file_entry.difference, self.get_params().hash_size),
+                        additional_info_text(file_entry)
                     )?;
                 }
                 writeln!(writer)?;
@@ -100,22 +133,24 @@ impl PrintResults for SimilarImages {
                 writeln!(writer)?;
                 writeln!(
                     writer,
-                    "\"{}\" - {}x{} - {} - {}",
+                    "\"{}\" - {}x{} - {} - {}{}",
                     file_entry.path.to_string_lossy(),
                     file_entry.width,
                     file_entry.height,
                     format_size(file_entry.size, BINARY),
-                    get_string_from_similarity(file_entry.difference, self.get_params().hash_size)
+                    get_string_from_similarity(file_entry.difference, self.get_params().hash_size),
+                    additional_info_text(file_entry)
                 )?;
                 for file_entry in vec_file_entry {
                     writeln!(
                         writer,
-                        "\"{}\" - {}x{} - {} - {}",
+                        "\"{}\" - {}x{} - {} - {}{}",
                         file_entry.path.to_string_lossy(),
                         file_entry.width,
                         file_entry.height,
                         format_size(file_entry.size, BINARY),
-                        get_string_from_similarity(file_entry.difference, self.get_params().hash_size)
+                        get_string_from_similarity(file_entry.difference, self.get_params().hash_size),
+                        additional_info_text(file_entry)
                     )?;
                 }
                 writeln!(writer)?;
@@ -134,7 +169,35 @@ impl PrintResults for SimilarImages {
             self.save_results_to_file_as_json_internal(file_name, &self.similar_vectors, pretty_print)
         }
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        if self.get_use_reference() {
+            writer.write_referenced_groups(&self.similar_referenced_vectors)
+        } else {
+            writer.write_groups(&self.similar_vectors)
+        }
+    }
+}
+fn additional_info_text(file_entry: &ImagesEntry) -> String {
+    let mut parts = Vec::new();
+    if file_entry.transform != ImageTransform::None {
+        parts.push(file_entry.transform.as_str().to_string());
+    }
+    if let Some(overlap_ratio) = file_entry.overlap_ratio {
+        parts.push(format!("{:.0}% overlap", overlap_ratio * 100.0));
+    }
+    if let Some(sharpness) = file_entry.sharpness {
+        parts.push(format!("sharpness {sharpness:.1}"));
+    }
+    if let Some(jpeg_quality) = file_entry.jpeg_quality {
+        parts.push(format!("JPEG quality {jpeg_quality}"));
+    }
+    if file_entry.upscaled {
+        parts.push("upscaled".to_string());
+    }
+    parts.iter().map(|part| format!(" - {part}")).collect()
 }
+
 impl CommonData for SimilarImages {
     type Info = Info;
     type Parameters = SimilarImagesParameters;
@@ -151,6 +214,13 @@ impl CommonData for SimilarImages {
     fn get_cd_mut(&mut self) -> &mut CommonToolData {
         &mut self.common_data
     }
+    fn get_check_method(&self) -> CheckingMethod {
+        if self.get_params().match_crops {
+            CheckingMethod::ImageFeatures
+        } else {
+            CheckingMethod::None
+        }
+    }
     fn found_any_items(&self) -> bool {
         self.information.number_of_duplicates > 0
     }
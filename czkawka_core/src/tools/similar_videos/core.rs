@@ -99,6 +99,22 @@ impl SimilarVideos {
         file_entry
     }
 
+    pub(crate) fn calculate_duplicates_stats(&mut self) {
+        self.information.number_of_duplicates = 0;
+        self.information.number_of_groups = 0;
+        if self.common_data.use_reference_folders {
+            for (_fe, vector) in &self.similar_referenced_vectors {
+                self.information.number_of_duplicates += vector.len();
+                self.information.number_of_groups += 1;
+            }
+        } else {
+            for vector in &self.similar_vectors {
+                self.information.number_of_duplicates += vector.len() - 1;
+                self.information.number_of_groups += 1;
+            }
+        }
+    }
+
     #[fun_time(message = "sort_videos", level = "debug")]
     pub(crate) fn sort_videos(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
         if self.videos_to_check.is_empty() {
@@ -168,17 +184,7 @@ impl SimilarVideos {
 
         self.remove_from_reference_folders();
 
-        if self.common_data.use_reference_folders {
-            for (_fe, vector) in &self.similar_referenced_vectors {
-                self.information.number_of_duplicates += vector.len();
-                self.information.number_of_groups += 1;
-            }
-        } else {
-            for vector in &self.similar_vectors {
-                self.information.number_of_duplicates += vector.len() - 1;
-                self.information.number_of_groups += 1;
-            }
-        }
+        self.calculate_duplicates_stats();
 
         // Clean unused data
         self.videos_hashes = Default::default();
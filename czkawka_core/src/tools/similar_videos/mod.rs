@@ -29,6 +29,7 @@ pub const DEFAULT_VID_HASH_DURATION: u32 = 10;
 pub const DEFAULT_VIDEO_PERCENTAGE_FOR_THUMBNAIL: u8 = 10;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct VideosEntry {
     pub path: PathBuf,
     pub size: u64,
@@ -58,6 +59,9 @@ impl ResultEntry for VideosEntry {
     fn get_size(&self) -> u64 {
         self.size
     }
+    fn get_bitrate(&self) -> Option<u64> {
+        self.bitrate
+    }
 }
 
 impl FileEntry {
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -9,14 +10,34 @@ use humansize::{BINARY, format_size};
 
 use crate::common::consts::VIDEO_FILES_EXTENSIONS;
 use crate::common::ffmpeg_utils::check_if_ffprobe_ffmpeg_exists;
+use crate::common::import::{ImportSummary, SavedResults, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData, DeleteMethod};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, ImportingResults, PrintResults, Search};
 use crate::flc;
 use crate::tools::similar_videos::core::{format_bitrate_opt, format_duration_opt};
 use crate::tools::similar_videos::{Info, SimilarVideos, SimilarVideosParameters};
 
+impl ImportingResults for SimilarVideos {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        match load_json_results(file_name)? {
+            SavedResults::Referenced(groups) => {
+                self.set_use_reference_folders(true);
+                self.similar_referenced_vectors = summary.filter_referenced_groups(groups);
+            }
+            SavedResults::Normal(groups) => {
+                self.set_use_reference_folders(false);
+                self.similar_vectors = summary.filter_groups(groups);
+            }
+        }
+        self.calculate_duplicates_stats();
+        Ok(summary)
+    }
+}
+
 impl AllTraits for SimilarVideos {}
 
 impl Search for SimilarVideos {
@@ -153,6 +174,14 @@ impl PrintResults for SimilarVideos {
             self.save_results_to_file_as_json_internal(file_name, &self.similar_vectors, pretty_print)
         }
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        if self.get_use_reference() {
+            writer.write_referenced_groups(&self.similar_referenced_vectors)
+        } else {
+            writer.write_groups(&self.similar_vectors)
+        }
+    }
 }
 
 impl CommonData for SimilarVideos {
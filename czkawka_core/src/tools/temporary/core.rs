@@ -1,4 +1,4 @@
-use std::fs::DirEntry;
+use std::ffi::OsStr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
@@ -9,6 +9,7 @@ use rayon::prelude::*;
 
 use crate::common::dir_traversal::{common_read_dir, get_modified_time};
 use crate::common::directories::Directories;
+use crate::common::file_system::{FileSystem, FsDirEntry, FsEntryType};
 use crate::common::items::ExcludedItems;
 use crate::common::model::{ToolType, WorkContinueStatus};
 use crate::common::progress_data::{CurrentStage, ProgressData};
@@ -27,7 +28,22 @@ impl Temporary {
 
     #[fun_time(message = "check_files", level = "debug")]
     pub(crate) fn check_files(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: Option<&Sender<ProgressData>>) -> WorkContinueStatus {
+        if let Some(shared_traversal) = &self.common_data.shared_traversal {
+            self.temporary_files = shared_traversal
+                .files()
+                .filter(|fe| fe.path.file_name().is_some_and(is_temporary_file_name))
+                .map(|fe| TemporaryFileEntry {
+                    path: fe.path.clone(),
+                    modified_date: fe.modified_date,
+                    size: fe.size,
+                })
+                .collect();
+            self.information.number_of_temporary_files = self.temporary_files.len();
+            return WorkContinueStatus::Continue;
+        }
+
         let mut folders_to_check: Vec<PathBuf> = self.common_data.directories.included_directories.clone();
+        let file_system = self.common_data.file_system.as_ref();
 
         let progress_handler = prepare_thread_handler_common(progress_sender, CurrentStage::CollectingFiles, 0, self.get_test_type(), 0);
 
@@ -44,7 +60,7 @@ impl Temporary {
                     let mut warnings = Vec::new();
                     let mut fe_result = Vec::new();
 
-                    let Some(read_dir) = common_read_dir(&current_folder, &mut warnings) else {
+                    let Some(read_dir) = common_read_dir(file_system, &current_folder, &mut warnings) else {
                         return (dir_result, warnings, fe_result);
                     };
 
@@ -53,11 +69,8 @@ impl Temporary {
                         let Ok(entry_data) = entry else {
                             continue;
                         };
-                        let Ok(file_type) = entry_data.file_type() else {
-                            continue;
-                        };
 
-                        if file_type.is_dir() {
+                        if entry_data.entry_type == FsEntryType::Dir {
                             check_folder_children(
                                 &mut dir_result,
                                 &mut warnings,
@@ -66,8 +79,8 @@ impl Temporary {
                                 &self.common_data.directories,
                                 &self.common_data.excluded_items,
                             );
-                        } else if file_type.is_file()
-                            && let Some(file_entry) = self.get_file_entry(progress_handler.items_counter(), &entry_data, &mut warnings)
+                        } else if entry_data.entry_type == FsEntryType::File
+                            && let Some(file_entry) = self.get_file_entry(file_system, progress_handler.items_counter(), &entry_data, &mut warnings)
                         {
                             fe_result.push(file_entry);
                         }
@@ -95,38 +108,47 @@ impl Temporary {
         WorkContinueStatus::Continue
     }
 
-    pub(crate) fn get_file_entry(&self, items_counter: &Arc<AtomicUsize>, entry_data: &DirEntry, warnings: &mut Vec<String>) -> Option<TemporaryFileEntry> {
+    pub(crate) fn get_file_entry(
+        &self,
+        file_system: &dyn FileSystem,
+        items_counter: &Arc<AtomicUsize>,
+        entry_data: &FsDirEntry,
+        warnings: &mut Vec<String>,
+    ) -> Option<TemporaryFileEntry> {
         items_counter.fetch_add(1, Ordering::Relaxed);
 
-        let current_file_name = entry_data.path();
+        let current_file_name = entry_data.path.clone();
         if self.common_data.excluded_items.is_excluded(&current_file_name) {
             return None;
         }
 
-        let file_name = entry_data.file_name();
-        let file_name_ascii_lowercase = file_name.to_ascii_lowercase();
-        let file_name_lowercase = file_name_ascii_lowercase.to_string_lossy();
-        if !TEMP_EXTENSIONS.iter().any(|f| file_name_lowercase.ends_with(f)) {
+        if !is_temporary_file_name(entry_data.file_name()) {
             return None;
         }
 
-        let Ok(metadata) = entry_data.metadata() else {
+        let Ok(metadata) = file_system.symlink_metadata(&current_file_name) else {
             return None;
         };
 
         // Creating new file entry
         Some(TemporaryFileEntry {
             modified_date: get_modified_time(&metadata, warnings, &current_file_name, false),
-            size: metadata.len(),
+            size: metadata.len,
             path: current_file_name,
         })
     }
 }
 
+fn is_temporary_file_name(file_name: &OsStr) -> bool {
+    let file_name_ascii_lowercase = file_name.to_ascii_lowercase();
+    let file_name_lowercase = file_name_ascii_lowercase.to_string_lossy();
+    TEMP_EXTENSIONS.iter().any(|f| file_name_lowercase.ends_with(f))
+}
+
 pub(crate) fn check_folder_children(
     dir_result: &mut Vec<PathBuf>,
     warnings: &mut Vec<String>,
-    entry_data: &DirEntry,
+    entry_data: &FsDirEntry,
     recursive_search: bool,
     directories: &Directories,
     excluded_items: &ExcludedItems,
@@ -135,7 +157,7 @@ pub(crate) fn check_folder_children(
         return;
     }
 
-    let next_item = entry_data.path();
+    let next_item = entry_data.path.clone();
     if directories.is_excluded(&next_item) {
         return;
     }
@@ -4,7 +4,7 @@ pub mod traits;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::common::tool_data::CommonToolData;
 use crate::common::traits::ResultEntry;
@@ -25,7 +25,8 @@ const TEMP_EXTENSIONS: &[&str] = &[
     ".partial",
 ];
 
-#[derive(Clone, Serialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[non_exhaustive]
 pub struct TemporaryFileEntry {
     pub path: PathBuf,
     pub modified_date: u64,
@@ -1,4 +1,5 @@
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::time::Instant;
@@ -9,13 +10,32 @@ use humansize::{BINARY, format_size};
 
 use crate::common::consts::VIDEO_FILES_EXTENSIONS;
 use crate::common::ffmpeg_utils::check_if_ffprobe_ffmpeg_exists;
+use crate::common::import::{ImportSummary, load_json_results};
 use crate::common::model::WorkContinueStatus;
+use crate::common::ndjson::{NdjsonOutput, NdjsonWriter};
 use crate::common::progress_data::ProgressData;
 use crate::common::tool_data::{CommonData, CommonToolData};
-use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, PrintResults, Search};
+use crate::common::traits::{AllTraits, DebugPrint, DeletingItems, FixingItems, ImportingResults, PrintResults, Search};
 use crate::flc;
 use crate::tools::video_optimizer::{Info, VideoOptimizer, VideoOptimizerFixParams, VideoOptimizerParameters};
 
+impl ImportingResults for VideoOptimizer {
+    fn import_results(&mut self, file_name: &Path) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+        match &self.params {
+            VideoOptimizerParameters::VideoTranscode(_) => {
+                self.video_transcode_result_entries = summary.filter_entries(load_json_results(file_name)?);
+                self.information.number_of_videos_to_transcode = self.video_transcode_result_entries.len();
+            }
+            VideoOptimizerParameters::VideoCrop(_) => {
+                self.video_crop_result_entries = summary.filter_entries(load_json_results(file_name)?);
+                self.information.number_of_videos_to_crop = self.video_crop_result_entries.len();
+            }
+        }
+        Ok(summary)
+    }
+}
+
 impl AllTraits for VideoOptimizer {}
 
 impl DeletingItems for VideoOptimizer {
@@ -120,6 +140,13 @@ impl PrintResults for VideoOptimizer {
             VideoOptimizerParameters::VideoCrop(_) => self.save_results_to_file_as_json_internal(file_name, &self.video_crop_result_entries, pretty_print),
         }
     }
+
+    fn write_results_as_ndjson<T: NdjsonOutput>(&self, writer: &mut NdjsonWriter<T>) -> std::io::Result<()> {
+        match &self.params {
+            VideoOptimizerParameters::VideoTranscode(_) => writer.write_entries(&self.video_transcode_result_entries),
+            VideoOptimizerParameters::VideoCrop(_) => writer.write_entries(&self.video_crop_result_entries),
+        }
+    }
 }
 
 impl Search for VideoOptimizer {
@@ -76,7 +76,7 @@ duplicate_hash_type_tooltip =
 
         Blake3 - cryptographic hash function. This is the default because it is very fast.
 
-        CRC32 - simple hash function. This should be faster than Blake3, but may very rarely have some collisions.
+        CRC32 - simple hash function. This should be faster than Blake3, but may very rarely have some collisions. Enable byte by byte verification in settings, to be sure that found files are identical.
 
         XXH3 - very similar in performance and hash quality to Blake3 (but non-cryptographic). So, such modes can be easily interchanged.
 
@@ -438,6 +438,10 @@ settings_duplicates_minimal_size_entry_tooltip =
 
         Choosing a smaller value will generate more records. This will speedup search, but slowdown cache loading/saving.
 
+settings_duplicates_verify_byte_by_byte_checkbutton_tooltip =
+        Compares content of files in each group found by hash, before showing them.
+
+        Groups with files that only have the same hash are split. Slower, but recommended with CRC32 hash, where collisions are possible.
 settings_duplicates_prehash_checkbutton_tooltip = 
         Enables caching of prehash (a hash computed from a small part of the file) which allows earlier dismissal of non-duplicated results.
 
@@ -449,6 +453,7 @@ settings_duplicates_prehash_minimal_entry_tooltip = Minimal size of cached entry
 
 settings_duplicates_hide_hard_link_button = Hide hard links
 settings_duplicates_prehash_checkbutton = Use prehash cache
+settings_duplicates_verify_byte_by_byte_checkbutton = Verify hash groups byte by byte
 
 settings_duplicates_minimal_size_cache_label = Minimal size of files (in bytes) saved to cache
 settings_duplicates_minimal_size_cache_prehash_label = Minimal size of files (in bytes) saved to prehash cache
@@ -508,6 +513,7 @@ progress_scanning_video = Hashed of {$file_checked}/{$all_files} video
 progress_creating_video_thumbnails = Created thumbnails of {$file_checked}/{$all_files} video
 progress_scanning_image = Hashed of {$file_checked}/{$all_files} image ({$data_checked}/{$all_data})
 progress_comparing_image_hashes = Compared {$file_checked}/{$all_files} image hash
+progress_matching_image_features = Matched features of {$file_checked}/{$all_files} image
 progress_scanning_music_tags_end = Compared tags of {$file_checked}/{$all_files} music file
 progress_scanning_music_tags = Read tags of {$file_checked}/{$all_files} music file
 progress_scanning_music_content_end = Compared fingerprint of {$file_checked}/{$all_files} music file
@@ -521,6 +527,7 @@ progress_scanning_size_name = Scanned name and size of {$file_number} file
 progress_scanning_name = Scanned name of {$file_number} file
 progress_analyzed_partial_hash = Analyzed partial hash of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
 progress_analyzed_full_hash = Analyzed full hash of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
+progress_compared_bytes = Compared content of {$file_checked}/{$all_files} files ({$data_checked}/{$all_data})
 progress_prehash_cache_loading = Loading prehash cache
 progress_prehash_cache_saving = Saving prehash cache
 progress_hash_cache_loading = Loading hash cache
@@ -579,7 +586,7 @@ move_files_title_dialog = Choose folder to which you want to move duplicated fil
 move_files_choose_more_than_1_path = Only one path may be selected to be able to copy their duplicated files, selected {$path_number}.
 move_stats = Properly moved {$num_files}/{$all_files} items
 
-save_results_to_file = Saved results both to txt and json files into "{$name}" folder.
+save_results_to_file = Saved results to txt, json, csv and sqlite files into "{$name}" folder.
 
 search_not_choosing_any_music = ERROR: You must select at least one checkbox with music searching types.
 search_not_choosing_any_broken_files = ERROR: You must select at least one checkbox with type of checked broken files.
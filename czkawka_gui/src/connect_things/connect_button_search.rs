@@ -12,12 +12,12 @@ use czkawka_core::common::traits::Search;
 use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsParameters};
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters};
 use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
-use czkawka_core::tools::duplicate::{DuplicateFinder, DuplicateFinderParameters};
+use czkawka_core::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinder, DuplicateFinderParameters};
 use czkawka_core::tools::empty_files::EmptyFiles;
 use czkawka_core::tools::empty_folder::EmptyFolder;
 use czkawka_core::tools::invalid_symlinks::InvalidSymlinks;
 use czkawka_core::tools::same_music::{MusicSimilarity, SameMusic, SameMusicParameters};
-use czkawka_core::tools::similar_images::{SimilarImages, SimilarImagesParameters};
+use czkawka_core::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, SimilarImages, SimilarImagesParameters};
 use czkawka_core::tools::similar_videos::{DEFAULT_CROP_DETECT, DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION, SimilarVideos, SimilarVideosParameters};
 use czkawka_core::tools::temporary::Temporary;
 use fun_time::fun_time;
@@ -234,6 +234,7 @@ fn duplicate_search(
     let combo_box_duplicate_check_method = gui_data.main_notebook.combo_box_duplicate_check_method.clone();
     let combo_box_duplicate_hash_type = gui_data.main_notebook.combo_box_duplicate_hash_type.clone();
     let check_button_duplicates_use_prehash_cache = gui_data.settings.check_button_duplicates_use_prehash_cache.clone();
+    let check_button_duplicates_verify_byte_by_byte = gui_data.settings.check_button_duplicates_verify_byte_by_byte.clone();
     let check_button_duplicate_case_sensitive_name: gtk4::CheckButton = gui_data.main_notebook.check_button_duplicate_case_sensitive_name.clone();
     let check_button_settings_duplicates_delete_outdated_cache = gui_data.settings.check_button_settings_duplicates_delete_outdated_cache.clone();
     let entry_settings_prehash_cache_file_minimal_size = gui_data.settings.entry_settings_prehash_cache_file_minimal_size.clone();
@@ -249,6 +250,7 @@ fn duplicate_search(
     let hash_type = DUPLICATES_HASH_TYPE_COMBO_BOX[hash_type_index].hash_type;
 
     let use_prehash_cache = check_button_duplicates_use_prehash_cache.is_active();
+    let verify_byte_by_byte = check_button_duplicates_verify_byte_by_byte.is_active();
     let minimal_prehash_cache_file_size = entry_settings_prehash_cache_file_minimal_size.text().as_str().parse::<u64>().unwrap_or(0);
 
     let case_sensitive_name_comparison = check_button_duplicate_case_sensitive_name.is_active();
@@ -266,6 +268,12 @@ fn duplicate_search(
                 loaded_commons.minimal_cache_file_size,
                 minimal_prehash_cache_file_size,
                 case_sensitive_name_comparison,
+                false, // Scan snapshots are mostly useful for repeated CLI scans
+                verify_byte_by_byte,
+                None,
+                false,
+                DEFAULT_PARTIAL_HASH_SEGMENTS,
+                false,
             );
             let mut tool = DuplicateFinder::new(params);
 
@@ -447,6 +455,7 @@ fn same_music_search(
                     minimum_segment_duration,
                     maximum_difference,
                     comparison_only_in_title_group,
+                    0.0,
                 );
                 let mut tool = SameMusic::new(params);
 
@@ -596,7 +605,17 @@ fn similar_image_search(
     thread::Builder::new()
         .stack_size(DEFAULT_THREAD_SIZE)
         .spawn(move || {
-            let params = SimilarImagesParameters::new(similarity, hash_size, hash_alg, image_filter, ignore_same_size);
+            let params = SimilarImagesParameters::new(
+                similarity,
+                hash_size,
+                hash_alg,
+                image_filter,
+                ignore_same_size,
+                false,
+                false,
+                DEFAULT_MATCH_CROPS_MAX_IMAGES,
+                0,
+            );
             let mut tool = SimilarImages::new(params);
 
             set_common_settings(&mut tool, &loaded_commons);
@@ -139,6 +139,9 @@ fn progress_default(gui_data: &GuiData, item: &ProgressData) {
         CurrentStage::SimilarImagesComparingHashes => {
             label_stage.set_text(&flg!("progress_comparing_image_hashes", progress_ratio_tm(item)));
         }
+        CurrentStage::SimilarImagesMatchingFeatures => {
+            label_stage.set_text(&flg!("progress_matching_image_features", progress_ratio_tm(item)));
+        }
         CurrentStage::SimilarVideosCalculatingHashes => {
             label_stage.set_text(&flg!("progress_scanning_video", progress_ratio_tm(item)));
         }
@@ -157,6 +160,9 @@ fn progress_default(gui_data: &GuiData, item: &ProgressData) {
         CurrentStage::DuplicateFullHashing => {
             label_stage.set_text(&flg!("progress_analyzed_full_hash", progress_ratio_tm(item)));
         }
+        CurrentStage::DuplicateByteComparing => {
+            label_stage.set_text(&flg!("progress_compared_bytes", progress_ratio_tm(item)));
+        }
         _ => unreachable!("Invalid stage {:?}", item.sstage),
     }
 }
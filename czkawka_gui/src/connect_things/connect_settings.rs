@@ -183,13 +183,15 @@ pub(crate) fn connect_settings(gui_data: &GuiData) {
                                     HashAlg::Mean,
                                     HashAlg::Median,
                                 ] {
-                                    let file_name = get_similar_images_cache_file(hash_size, hash_alg, image_filter);
-                                    let (mut messages, loaded_items) =
-                                        load_cache_from_file_generalized_by_path::<czkawka_core::tools::similar_images::ImagesEntry>(&file_name, true, &Default::default());
-
-                                    if let Some(cache_entries) = loaded_items {
-                                        let save_messages = save_cache_to_file_generalized(&file_name, &cache_entries, false, 0);
-                                        messages.extend_with_another_messages(save_messages);
+                                    for (check_transforms, calculate_quality) in [(false, false), (false, true), (true, false), (true, true)] {
+                                        let file_name = get_similar_images_cache_file(hash_size, hash_alg, image_filter, check_transforms, calculate_quality);
+                                        let (mut messages, loaded_items) =
+                                            load_cache_from_file_generalized_by_path::<czkawka_core::tools::similar_images::ImagesEntry>(&file_name, true, &Default::default());
+
+                                        if let Some(cache_entries) = loaded_items {
+                                            let save_messages = save_cache_to_file_generalized(&file_name, &cache_entries, false, 0);
+                                            messages.extend_with_another_messages(save_messages);
+                                        }
                                     }
                                 }
                             }
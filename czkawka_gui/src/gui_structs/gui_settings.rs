@@ -33,6 +33,7 @@ pub struct GuiSettings {
     pub entry_settings_cache_file_minimal_size: gtk4::Entry,
     pub entry_settings_prehash_cache_file_minimal_size: gtk4::Entry,
     pub check_button_duplicates_use_prehash_cache: gtk4::CheckButton,
+    pub check_button_duplicates_verify_byte_by_byte: gtk4::CheckButton,
     pub check_button_settings_show_preview_duplicates: gtk4::CheckButton,
     pub check_button_settings_duplicates_delete_outdated_cache: gtk4::CheckButton,
     pub button_settings_duplicates_clear_cache: gtk4::Button,
@@ -95,6 +96,7 @@ impl GuiSettings {
             builder.object("check_button_settings_duplicates_delete_outdated_cache").expect("Cambalache");
         let button_settings_duplicates_clear_cache: gtk4::Button = builder.object("button_settings_duplicates_clear_cache").expect("Cambalache");
         let check_button_duplicates_use_prehash_cache: gtk4::CheckButton = builder.object("check_button_duplicates_use_prehash_cache").expect("Cambalache");
+        let check_button_duplicates_verify_byte_by_byte: gtk4::CheckButton = builder.object("check_button_duplicates_verify_byte_by_byte").expect("Cambalache");
         let entry_settings_prehash_cache_file_minimal_size: gtk4::Entry = builder.object("entry_settings_prehash_cache_file_minimal_size").expect("Cambalache");
         let label_settings_duplicate_minimal_size_cache: gtk4::Label = builder.object("label_settings_duplicate_minimal_size_cache").expect("Cambalache");
         let label_settings_duplicate_minimal_size_cache_prehash: gtk4::Label = builder.object("label_settings_duplicate_minimal_size_cache_prehash").expect("Cambalache");
@@ -141,6 +143,7 @@ impl GuiSettings {
             entry_settings_cache_file_minimal_size,
             entry_settings_prehash_cache_file_minimal_size,
             check_button_duplicates_use_prehash_cache,
+            check_button_duplicates_verify_byte_by_byte,
             check_button_settings_show_preview_duplicates,
             check_button_settings_duplicates_delete_outdated_cache,
             button_settings_duplicates_clear_cache,
@@ -213,6 +216,8 @@ impl GuiSettings {
         self.button_settings_duplicates_clear_cache.set_label(&flg!("settings_multiple_clear_cache_button"));
         self.check_button_duplicates_use_prehash_cache
             .set_label(Some(&flg!("settings_duplicates_prehash_checkbutton")));
+        self.check_button_duplicates_verify_byte_by_byte
+            .set_label(Some(&flg!("settings_duplicates_verify_byte_by_byte_checkbutton")));
         self.label_settings_duplicate_minimal_size_cache
             .set_label(&flg!("settings_duplicates_minimal_size_cache_label"));
         self.label_settings_duplicate_minimal_size_cache_prehash
@@ -230,6 +235,8 @@ impl GuiSettings {
             .set_tooltip_text(Some(&flg!("settings_multiple_clear_cache_button_tooltip")));
         self.check_button_duplicates_use_prehash_cache
             .set_tooltip_text(Some(&flg!("settings_duplicates_prehash_checkbutton_tooltip")));
+        self.check_button_duplicates_verify_byte_by_byte
+            .set_tooltip_text(Some(&flg!("settings_duplicates_verify_byte_by_byte_checkbutton_tooltip")));
         self.entry_settings_prehash_cache_file_minimal_size
             .set_tooltip_text(Some(&flg!("settings_duplicates_prehash_minimal_entry_tooltip")));
 
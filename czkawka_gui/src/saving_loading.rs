@@ -37,6 +37,7 @@ const DEFAULT_USE_CACHE: bool = true;
 const DEFAULT_SAVE_ALSO_AS_JSON: bool = false;
 const DEFAULT_HIDE_HARD_LINKS: bool = true;
 const DEFAULT_USE_PRECACHE: bool = false;
+const DEFAULT_VERIFY_BYTE_BY_BYTE: bool = false;
 const DEFAULT_USE_TRASH: bool = false;
 pub const DEFAULT_MINIMAL_CACHE_SIZE: &str = "257144";
 const DEFAULT_PREHASH_MINIMAL_CACHE_SIZE: &str = "0";
@@ -259,6 +260,9 @@ pub struct SettingsJson {
     #[serde(default = "default_use_prehash_cache")]
     pub use_prehash_cache: bool,
 
+    #[serde(default = "default_verify_byte_by_byte")]
+    pub verify_byte_by_byte: bool,
+
     #[serde(default = "default_minimal_prehash_cache_size")]
     pub minimal_prehash_cache_size: String,
 
@@ -406,6 +410,9 @@ fn default_video_delete_outdated_cache_entries() -> bool {
 fn default_use_prehash_cache() -> bool {
     DEFAULT_USE_PRECACHE
 }
+fn default_verify_byte_by_byte() -> bool {
+    DEFAULT_VERIFY_BYTE_BY_BYTE
+}
 fn default_minimal_prehash_cache_size() -> String {
     DEFAULT_PREHASH_MINIMAL_CACHE_SIZE.to_string()
 }
@@ -533,6 +540,7 @@ fn set_configuration_to_gui_internal(upper_notebook: &GuiUpperNotebook, main_not
             .check_button_settings_duplicates_delete_outdated_cache
             .set_active(default_config.duplicate_delete_outdated_cache_entries);
         settings.check_button_duplicates_use_prehash_cache.set_active(default_config.use_prehash_cache);
+        settings.check_button_duplicates_verify_byte_by_byte.set_active(default_config.verify_byte_by_byte);
         settings.entry_settings_prehash_cache_file_minimal_size.set_text(&default_config.minimal_prehash_cache_size);
 
         let lang_idx = LANGUAGES_ALL.iter().position(|l| l.short_text == default_config.language).unwrap_or(0);
@@ -658,6 +666,7 @@ fn gui_to_settings(upper_notebook: &GuiUpperNotebook, main_notebook: &GuiMainNot
         image_delete_outdated_cache_entries: settings.check_button_settings_similar_images_delete_outdated_cache.is_active(),
         video_delete_outdated_cache_entries: settings.check_button_settings_similar_videos_delete_outdated_cache.is_active(),
         use_prehash_cache: settings.check_button_duplicates_use_prehash_cache.is_active(),
+        verify_byte_by_byte: settings.check_button_duplicates_verify_byte_by_byte.is_active(),
         minimal_prehash_cache_size: settings.entry_settings_prehash_cache_file_minimal_size.text().to_string(),
         language: language_text,
         combo_box_duplicate_hash_type: main_notebook.combo_box_duplicate_hash_type.active().unwrap_or(0),
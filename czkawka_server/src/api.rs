@@ -0,0 +1,404 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crossbeam_channel::Receiver;
+use czkawka_core::common::file_system::LocalFileSystem;
+use czkawka_core::common::import::is_entry_unchanged;
+use czkawka_core::common::journal::{Journal, JournalAction};
+use czkawka_core::common::model::{CheckingMethod, ToolType};
+use czkawka_core::common::ndjson::{NdjsonItem, NdjsonRecord};
+use czkawka_core::common::profile::ScanProfile;
+use czkawka_core::common::{check_if_folder_contains_expected_files, make_hard_link, remove_folder_if_contains_expected_files, remove_single_file};
+use czkawka_core::tools::duplicate::{DuplicateEntry, THREAD_BUFFER_SIZE, compare_files_content};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::scans::{ScanData, ScanEvent, ScanState, Scans};
+use crate::tools::{prepare_tool, scan_check_method};
+
+pub(crate) const DEFAULT_RESULTS_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Method {
+    Get,
+    Post,
+    Delete,
+    Other,
+}
+
+// Request independent of HTTP library, so routing can be tested without opening sockets
+#[derive(Debug)]
+pub(crate) struct ApiRequest {
+    pub(crate) method: Method,
+    // Path with optional query, e.g. "/scans/1/results?offset=10"
+    pub(crate) url: String,
+    // Value of "Authorization: Bearer" header
+    pub(crate) token: Option<String>,
+    pub(crate) body: Vec<u8>,
+}
+
+pub(crate) enum ApiBody {
+    Json(Vec<u8>),
+    // Server-Sent Events, stream ends when channel is closed
+    Events(Receiver<ScanEvent>),
+}
+
+pub(crate) struct ApiResponse {
+    pub(crate) status: u16,
+    pub(crate) body: ApiBody,
+}
+
+impl ApiResponse {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self {
+                status,
+                body: ApiBody::Json(body),
+            },
+            Err(e) => Self::error(500, &format!("Cannot serialize response - {e}")),
+        }
+    }
+
+    pub(crate) fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: ApiBody::Json(json!({ "error": message }).to_string().into_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StartScanRequest {
+    tool: ToolType,
+    // Same format as scan profiles and Krokiet presets
+    #[serde(default)]
+    settings: ScanProfile,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ActionRequest {
+    Delete {
+        paths: Vec<PathBuf>,
+        #[serde(default)]
+        move_to_trash: bool,
+    },
+    Move {
+        paths: Vec<PathBuf>,
+        destination: PathBuf,
+    },
+    // Every path is replaced by hardlink to source, which must be in same group
+    Hardlink {
+        paths: Vec<PathBuf>,
+        source: PathBuf,
+    },
+}
+
+impl ActionRequest {
+    fn paths(&self) -> &[PathBuf] {
+        match self {
+            Self::Delete { paths, .. } | Self::Move { paths, .. } | Self::Hardlink { paths, .. } => paths,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ActionResult {
+    processed: Vec<PathBuf>,
+    errors: Vec<String>,
+}
+
+pub(crate) struct ApiServer {
+    // Only hash is kept, comparing fixed length hashes takes same time for every token
+    token_hash: blake3::Hash,
+    // Operations are saved to journal, so they can be reverted later by CLI or GUI
+    use_journal: bool,
+    scans: Scans,
+}
+
+impl ApiServer {
+    pub(crate) fn new(token: &str, use_journal: bool) -> Self {
+        Self {
+            token_hash: blake3::hash(token.as_bytes()),
+            use_journal,
+            scans: Scans::default(),
+        }
+    }
+
+    // Needs only url and headers, so HTTP server can reject request before reading its body
+    pub(crate) fn is_authorized(&self, url: &str, token: Option<&str>) -> bool {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let query = parse_query(query);
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        // Browsers cannot set headers of EventSource, so only this endpoint accepts token in query
+        let is_events_endpoint = matches!(segments.as_slice(), ["scans", _, "events"]);
+        let token = token.or_else(|| if is_events_endpoint { query_value(&query, "token") } else { None });
+        // blake3::Hash implements constant time equality
+        token.is_some_and(|token| blake3::hash(token.as_bytes()) == self.token_hash)
+    }
+
+    pub(crate) fn handle(&self, request: &ApiRequest) -> ApiResponse {
+        if !self.is_authorized(&request.url, request.token.as_deref()) {
+            return ApiResponse::error(401, "Missing or invalid token");
+        }
+        let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+        let query = parse_query(query);
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        match (request.method, segments.as_slice()) {
+            (Method::Get, ["scans"]) => {
+                let statuses: Vec<_> = self.scans.all().iter().map(|scan| scan.status()).collect();
+                ApiResponse::json(200, &statuses)
+            }
+            (Method::Post, ["scans"]) => self.start_scan(&request.body),
+            (method, ["scans", id, rest @ ..]) => {
+                let Ok(id) = id.parse::<u64>() else {
+                    return ApiResponse::error(404, &format!("Invalid scan id \"{id}\""));
+                };
+                self.handle_scan(method, id, rest, &query, &request.body)
+            }
+            _ => ApiResponse::error(404, &format!("Not found - {path}")),
+        }
+    }
+
+    fn handle_scan(&self, method: Method, id: u64, rest: &[&str], query: &[(&str, &str)], body: &[u8]) -> ApiResponse {
+        let Some(scan) = self.scans.get(id) else {
+            return ApiResponse::error(404, &format!("Scan {id} not found"));
+        };
+
+        match (method, rest) {
+            (Method::Get, []) => ApiResponse::json(200, &scan.status()),
+            (Method::Delete, []) => {
+                scan.stop();
+                self.scans.remove(id);
+                ApiResponse::json(200, &json!({ "removed": id }))
+            }
+            (Method::Get, ["events"]) => ApiResponse {
+                status: 200,
+                body: ApiBody::Events(scan.subscribe()),
+            },
+            (Method::Post, ["stop"]) => {
+                scan.stop();
+                ApiResponse::json(200, &scan.status())
+            }
+            (Method::Get, ["results"]) => {
+                let (offset, limit) = match (parse_number(query, "offset", 0), parse_number(query, "limit", DEFAULT_RESULTS_LIMIT)) {
+                    (Ok(offset), Ok(limit)) => (offset, limit),
+                    (Err(e), _) | (_, Err(e)) => return ApiResponse::error(400, &e),
+                };
+                let data = scan.lock();
+                let items: Vec<&NdjsonRecord> = data.results.iter().skip(offset).take(limit).collect();
+                ApiResponse::json(
+                    200,
+                    &json!({
+                        "state": data.state,
+                        "total": data.results.len(),
+                        "offset": offset,
+                        "limit": limit,
+                        "items": items,
+                    }),
+                )
+            }
+            (Method::Post, ["actions"]) => {
+                let action: ActionRequest = match serde_json::from_slice(body) {
+                    Ok(action) => action,
+                    Err(e) => return ApiResponse::error(400, &format!("Invalid action - {e}")),
+                };
+                let mut data = scan.lock();
+                if data.state == ScanState::Running {
+                    return ApiResponse::error(409, "Actions can be run only after scan ends");
+                }
+                if let Err(e) = validate_action(&data, scan.check_method, &action) {
+                    return ApiResponse::error(400, &e);
+                }
+                let journal = if self.use_journal { Journal::new("Server") } else { Journal::default() };
+                let result = run_action(&journal, &data, &action);
+                let processed: Vec<&Path> = result.processed.iter().map(PathBuf::as_path).collect();
+                data.remove_items(&processed);
+                ApiResponse::json(200, &result)
+            }
+            _ => ApiResponse::error(404, "Not found"),
+        }
+    }
+
+    fn start_scan(&self, body: &[u8]) -> ApiResponse {
+        let request: StartScanRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid scan request - {e}")),
+        };
+        let tool = match prepare_tool(request.tool, &request.settings) {
+            Ok(tool) => tool,
+            Err(e) => return ApiResponse::error(400, &e),
+        };
+        match self.scans.start(request.tool, scan_check_method(request.tool, &request.settings), tool) {
+            Ok(scan) => ApiResponse::json(201, &scan.status()),
+            Err(e) => ApiResponse::error(500, &e),
+        }
+    }
+}
+
+// Only items from results can be changed, so client cannot use server to remove any file
+fn validate_action(data: &ScanData, check_method: Option<CheckingMethod>, action: &ActionRequest) -> Result<(), String> {
+    if action.paths().is_empty() {
+        return Err("No paths selected".to_string());
+    }
+    for path in action.paths() {
+        let Some((_, item)) = data.items().find(|(_, item)| &item.path == path) else {
+            return Err(format!("Path \"{}\" is not part of results", path.to_string_lossy()));
+        };
+        if item.reference {
+            return Err(format!("Path \"{}\" is in reference folder and cannot be changed", path.to_string_lossy()));
+        }
+    }
+
+    // Files found by partial hash may differ in not hashed parts
+    if matches!(action, ActionRequest::Delete { .. }) && check_method == Some(CheckingMethod::PartialHash) {
+        return Err("Files found by partial hash are only probable duplicates and cannot be deleted".to_string());
+    }
+    // Only files with same hash are identical, items of other groups would lose their content after hardlinking
+    if let ActionRequest::Hardlink { paths, source } = action {
+        if check_method != Some(CheckingMethod::Hash) {
+            return Err("Hardlinks can be created only between duplicates found by hash".to_string());
+        }
+        let Some((source_group, _)) = data.items().find(|(_, item)| &item.path == source) else {
+            return Err(format!("Source \"{}\" is not part of results", source.to_string_lossy()));
+        };
+        if paths.contains(source) {
+            return Err("Source cannot be hardlinked to itself".to_string());
+        }
+        if !matches!(data.results.get(source_group), Some(NdjsonRecord::Group { .. })) {
+            return Err("Hardlinks can be created only between items of same group".to_string());
+        }
+        for path in paths {
+            if !data.items().any(|(group, item)| group == source_group && &item.path == path) {
+                return Err(format!("Path \"{}\" is not in same group as source", path.to_string_lossy()));
+            }
+        }
+    }
+    if let ActionRequest::Move { destination, .. } = action
+        && !destination.is_dir()
+    {
+        return Err(format!("Destination \"{}\" is not existing folder", destination.to_string_lossy()));
+    }
+    Ok(())
+}
+
+// Files changed since scan may be no longer duplicates and folders may contain new files, so they are skipped
+fn run_action(journal: &Journal, data: &ScanData, action: &ActionRequest) -> ActionResult {
+    let find_item = |path: &Path| data.items().find(|(_, item)| item.path == path).map(|(_, item)| item);
+    let mut result = ActionResult::default();
+    // Allocated only when content of files needs to be compared
+    let mut buffer = Vec::new();
+    for path in action.paths() {
+        // Validation ensures that all paths are part of results
+        let Some(item) = find_item(path) else {
+            continue;
+        };
+        let is_dir = path.is_dir();
+        let action_result = if !is_dir && !is_entry_unchanged(item) {
+            Err(changed_since_scan_error(path))
+        } else {
+            match action {
+                ActionRequest::Delete { move_to_trash, .. } => {
+                    let journal_action = if *move_to_trash { JournalAction::MoveToTrash } else { JournalAction::Delete };
+                    journal.record_operation(journal_action, path, None, || {
+                        if is_dir {
+                            remove_folder_if_contains_expected_files(path, &item.get_folder_files(), *move_to_trash)
+                        } else {
+                            remove_single_file(path, *move_to_trash)
+                        }
+                    })
+                }
+                ActionRequest::Move { destination, .. } => {
+                    if is_dir {
+                        check_if_folder_contains_expected_files(path, &item.get_folder_files()).and_then(|()| move_file(journal, path, destination))
+                    } else {
+                        move_file(journal, path, destination)
+                    }
+                }
+                ActionRequest::Hardlink { source, .. } => match find_item(source) {
+                    Some(source_item) => hardlink_file(journal, &mut buffer, source_item, item),
+                    None => Err(format!("Source \"{}\" is not part of results", source.to_string_lossy())),
+                },
+            }
+        };
+        match action_result {
+            Ok(()) => result.processed.push(path.clone()),
+            Err(e) => result.errors.push(e),
+        }
+    }
+    result
+}
+
+fn changed_since_scan_error(path: &Path) -> String {
+    format!("\"{}\" was changed or removed since scan, so it is skipped", path.to_string_lossy())
+}
+
+// Hash may collide, so content is compared before replacing file, which cannot be restored from journal
+fn hardlink_file(journal: &Journal, buffer: &mut Vec<u8>, source: &NdjsonItem, item: &NdjsonItem) -> Result<(), String> {
+    if !is_entry_unchanged(source) {
+        return Err(changed_since_scan_error(&source.path));
+    }
+    if buffer.is_empty() {
+        buffer.resize(THREAD_BUFFER_SIZE, 0);
+    }
+    let (source_entry, entry) = (DuplicateEntry::from(source), DuplicateEntry::from(item));
+    match compare_files_content(&LocalFileSystem, buffer, &source_entry, &entry, &Arc::default(), &Arc::default()) {
+        Ok(Some(true)) => {}
+        Ok(_) => {
+            return Err(format!(
+                "Content of \"{}\" is different than \"{}\", so it is not hardlinked",
+                item.path.to_string_lossy(),
+                source.path.to_string_lossy()
+            ));
+        }
+        Err(e) => return Err(e),
+    }
+    journal.record_operation(JournalAction::HardLink, &item.path, Some(&source.path), || {
+        make_hard_link(&source.path, &item.path).map_err(|e| format!("Cannot hardlink \"{}\" to \"{}\" - {e}", item.path.to_string_lossy(), source.path.to_string_lossy()))
+    })
+}
+
+fn move_file(journal: &Journal, path: &Path, destination: &Path) -> Result<(), String> {
+    let Some(file_name) = path.file_name() else {
+        return Err(format!("Cannot get name of \"{}\"", path.to_string_lossy()));
+    };
+    let target = destination.join(file_name);
+    if target.exists() {
+        return Err(format!(
+            "Cannot move \"{}\", because \"{}\" already exists",
+            path.to_string_lossy(),
+            target.to_string_lossy()
+        ));
+    }
+    journal.record_operation(JournalAction::Move, path, Some(&target), || {
+        // Rename fails between different filesystems
+        if fs::rename(path, &target).is_ok() {
+            return Ok(());
+        }
+        fs::copy(path, &target)
+            .and_then(|_| fs::remove_file(path))
+            .map_err(|e| format!("Cannot move \"{}\" to \"{}\" - {e}", path.to_string_lossy(), target.to_string_lossy()))
+    })
+}
+
+fn parse_query(query: &str) -> Vec<(&str, &str)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .collect()
+}
+
+fn query_value<'a>(query: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    query.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+}
+
+fn parse_number(query: &[(&str, &str)], name: &str, default: usize) -> Result<usize, String> {
+    match query_value(query, name) {
+        Some(value) => value.parse().map_err(|_| format!("Invalid value \"{value}\" of \"{name}\"")),
+        None => Ok(default),
+    }
+}
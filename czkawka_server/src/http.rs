@@ -0,0 +1,139 @@
+use std::io::{self, Read};
+use std::thread;
+
+use crossbeam_channel::Receiver;
+use log::{error, warn};
+use tiny_http::{Header, Request, Response, Server, StatusCode};
+
+use crate::api::{ApiBody, ApiRequest, ApiResponse, ApiServer, Method};
+use crate::scans::ScanEvent;
+
+// Event streams may be open for whole scan and each one occupies worker, so pool is bigger than number of cores
+pub(crate) const REQUEST_WORKERS: usize = 32;
+// Scan settings and actions are small, so bigger bodies are rejected without reading them whole
+pub(crate) const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+pub(crate) fn run_server(server: &Server, api: &ApiServer) {
+    thread::scope(|scope| {
+        for _ in 0..REQUEST_WORKERS {
+            let worker = thread::Builder::new().name("request".to_string()).spawn_scoped(scope, || {
+                for request in server.incoming_requests() {
+                    handle_request(request, api);
+                }
+            });
+            if let Err(e) = worker {
+                error!("Cannot start request thread - {e}");
+            }
+        }
+    });
+}
+
+fn handle_request(mut request: Request, api: &ApiServer) {
+    let token = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "))
+        .map(str::to_string);
+    if !api.is_authorized(request.url(), token.as_deref()) {
+        respond_error(request, 401, "Missing or invalid token");
+        return;
+    }
+    if request.body_length().is_some_and(|length| length > MAX_BODY_SIZE) {
+        respond_error(request, 413, &format!("Request body is bigger than {MAX_BODY_SIZE} bytes"));
+        return;
+    }
+
+    let mut body = Vec::new();
+    // Content-Length is missing in chunked requests, so reading is limited too
+    if let Err(e) = request.as_reader().take(MAX_BODY_SIZE as u64 + 1).read_to_end(&mut body) {
+        warn!("Cannot read body of request - {e}");
+        return;
+    }
+    if body.len() > MAX_BODY_SIZE {
+        respond_error(request, 413, &format!("Request body is bigger than {MAX_BODY_SIZE} bytes"));
+        return;
+    }
+    let method = match request.method() {
+        tiny_http::Method::Get => Method::Get,
+        tiny_http::Method::Post => Method::Post,
+        tiny_http::Method::Delete => Method::Delete,
+        _ => Method::Other,
+    };
+
+    let api_response = api.handle(&ApiRequest {
+        method,
+        url: request.url().to_string(),
+        token,
+        body,
+    });
+
+    respond(request, api_response);
+}
+
+fn respond_error(request: Request, status: u16, message: &str) {
+    respond(request, ApiResponse::error(status, message));
+}
+
+fn respond(request: Request, api_response: ApiResponse) {
+    let status = StatusCode(api_response.status);
+    let result = match api_response.body {
+        ApiBody::Json(body) => request.respond(Response::from_data(body).with_status_code(status).with_header(header("Content-Type", "application/json"))),
+        ApiBody::Events(receiver) => {
+            // Unknown length makes response chunked, so every event is sent when it arrives
+            let response = Response::new(
+                status,
+                vec![header("Content-Type", "text/event-stream"), header("Cache-Control", "no-cache")],
+                EventReader::new(receiver),
+                None,
+                None,
+            );
+            request.respond(response)
+        }
+    };
+    if let Err(e) = result {
+        warn!("Cannot send response - {e}");
+    }
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("Header is valid")
+}
+
+pub(crate) fn format_event(event: &ScanEvent) -> String {
+    let data = serde_json::to_string(event).unwrap_or_default();
+    format!("event: {}\ndata: {data}\n\n", event.name())
+}
+
+// Blocks until next event, ends when scan finishes
+struct EventReader {
+    receiver: Receiver<ScanEvent>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl EventReader {
+    fn new(receiver: Receiver<ScanEvent>) -> Self {
+        Self {
+            receiver,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl Read for EventReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.buffer.len() {
+            let Ok(event) = self.receiver.recv() else {
+                return Ok(0);
+            };
+            self.buffer = format_event(&event).into_bytes();
+            self.position = 0;
+        }
+        let mut remaining = self.buffer.get(self.position..).unwrap_or_default();
+        let length = remaining.read(buf)?;
+        self.position += length;
+        Ok(length)
+    }
+}
@@ -0,0 +1,70 @@
+use std::net::SocketAddr;
+use std::process;
+
+use clap::Parser;
+use czkawka_core::common::config_cache_path::{print_infos_and_warnings, set_config_cache_path};
+use czkawka_core::common::logger::{filtering_messages, print_version_mode, setup_logger};
+use czkawka_core::common::set_number_of_threads;
+use log::{error, info, warn};
+use tiny_http::Server;
+
+use crate::api::ApiServer;
+use crate::http::run_server;
+
+mod api;
+mod http;
+mod scans;
+#[cfg(test)]
+mod tests;
+mod tools;
+
+#[derive(Parser, Debug)]
+#[clap(name = "czkawka_server", version, about = "HTTP/JSON API to run Czkawka scans and act on their results")]
+struct Args {
+    #[clap(
+        short,
+        long,
+        default_value = "127.0.0.1:8123",
+        help = "Address to listen on",
+        long_help = "Address to listen on. By default only connections from this computer are accepted - use other address only in trusted network."
+    )]
+    address: SocketAddr,
+    #[clap(
+        long,
+        env = "CZKAWKA_SERVER_TOKEN",
+        hide_env_values = true,
+        help = "Token required in every request",
+        long_help = "Token required in every request as \"Authorization: Bearer <token>\" header, or as \"token\" query parameter of event stream."
+    )]
+    token: String,
+    #[clap(short = 'T', long, default_value = "0", help = "Number of threads to use (0 = all available)")]
+    thread_number: usize,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let config_cache_path_set_result = set_config_cache_path("Czkawka", "Czkawka");
+    setup_logger(false, "czkawka_server", filtering_messages);
+    print_version_mode("Czkawka server");
+    print_infos_and_warnings(config_cache_path_set_result.infos, config_cache_path_set_result.warnings);
+    set_number_of_threads(args.thread_number);
+
+    if args.token.is_empty() {
+        error!("Token cannot be empty");
+        process::exit(1);
+    }
+    if !args.address.ip().is_loopback() {
+        warn!("Server listens on non local address {}, so it is available to other computers", args.address);
+    }
+
+    let server = match Server::http(args.address) {
+        Ok(server) => server,
+        Err(e) => {
+            error!("Cannot listen on {} - {e}", args.address);
+            process::exit(1);
+        }
+    };
+    info!("Listening on http://{}", args.address);
+    run_server(&server, &ApiServer::new(&args.token, true));
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
+use czkawka_core::common::model::{CheckingMethod, ToolType};
+use czkawka_core::common::ndjson::{NdjsonItem, NdjsonProgress, NdjsonRecord};
+use czkawka_core::common::progress_data::ProgressData;
+use log::{error, info};
+use serde::Serialize;
+
+use crate::tools::ScanTool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ScanState {
+    Running,
+    Finished,
+    Stopped,
+    Failed,
+}
+
+// Events sent to clients listening for progress of scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum ScanEvent {
+    Progress(NdjsonProgress),
+    // Always last event
+    Finished { state: ScanState },
+}
+
+impl ScanEvent {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Progress(_) => "progress",
+            Self::Finished { .. } => "finished",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ScanStatus {
+    pub(crate) id: u64,
+    pub(crate) tool: ToolType,
+    pub(crate) state: ScanState,
+    pub(crate) progress: Option<NdjsonProgress>,
+    // Number of groups or entries, depending on tool
+    pub(crate) results: usize,
+    pub(crate) messages: Vec<NdjsonRecord>,
+}
+
+pub(crate) struct ScanData {
+    pub(crate) state: ScanState,
+    progress: Option<NdjsonProgress>,
+    // Only group and entry records
+    pub(crate) results: Vec<NdjsonRecord>,
+    messages: Vec<NdjsonRecord>,
+    subscribers: Vec<Sender<ScanEvent>>,
+}
+
+impl ScanData {
+    // Items of all results, with index of group or entry containing them
+    pub(crate) fn items(&self) -> impl Iterator<Item = (usize, &NdjsonItem)> {
+        self.results.iter().enumerate().flat_map(|(record_idx, record)| {
+            let items: Vec<&NdjsonItem> = match record {
+                NdjsonRecord::Group { items, .. } => items.iter().collect(),
+                NdjsonRecord::Entry { item } => vec![item],
+                _ => Vec::new(),
+            };
+            items.into_iter().map(move |item| (record_idx, item))
+        })
+    }
+
+    // Items that were deleted, moved or hardlinked, are no longer interesting, so they are removed with groups that lost all their duplicates
+    pub(crate) fn remove_items(&mut self, paths: &[&Path]) {
+        for record in &mut self.results {
+            if let NdjsonRecord::Group { items, .. } = record {
+                items.retain(|item| !paths.contains(&item.path.as_path()));
+            }
+        }
+        self.results.retain(|record| match record {
+            NdjsonRecord::Group { items, .. } => items.len() > 1 && items.iter().any(|item| !item.reference),
+            NdjsonRecord::Entry { item } => !paths.contains(&item.path.as_path()),
+            _ => false,
+        });
+        for (new_index, record) in self.results.iter_mut().enumerate() {
+            if let NdjsonRecord::Group { index, .. } = record {
+                *index = new_index;
+            }
+        }
+    }
+}
+
+pub(crate) struct Scan {
+    pub(crate) id: u64,
+    pub(crate) tool: ToolType,
+    // Set only for duplicates, to know if found files are identical
+    pub(crate) check_method: Option<CheckingMethod>,
+    stop_flag: Arc<AtomicBool>,
+    data: Mutex<ScanData>,
+}
+
+impl Scan {
+    pub(crate) fn lock(&self) -> MutexGuard<'_, ScanData> {
+        self.data.lock().expect("Scan data mutex is poisoned")
+    }
+
+    pub(crate) fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn status(&self) -> ScanStatus {
+        let data = self.lock();
+        ScanStatus {
+            id: self.id,
+            tool: self.tool,
+            state: data.state,
+            progress: data.progress.clone(),
+            results: data.results.len(),
+            messages: data.messages.clone(),
+        }
+    }
+
+    // Already finished scan sends only its final state
+    pub(crate) fn subscribe(&self) -> Receiver<ScanEvent> {
+        let (sender, receiver) = unbounded();
+        let mut data = self.lock();
+        if data.state == ScanState::Running {
+            if let Some(progress) = &data.progress {
+                let _ = sender.send(ScanEvent::Progress(progress.clone()));
+            }
+            data.subscribers.push(sender);
+        } else {
+            let _ = sender.send(ScanEvent::Finished { state: data.state });
+        }
+        receiver
+    }
+
+    fn update_progress(&self, progress_data: &ProgressData) {
+        let progress = NdjsonProgress::from(progress_data);
+        let mut data = self.lock();
+        data.subscribers.retain(|subscriber| subscriber.send(ScanEvent::Progress(progress.clone())).is_ok());
+        data.progress = Some(progress);
+    }
+
+    fn finish(&self, state: ScanState, records: Vec<NdjsonRecord>) {
+        let mut data = self.lock();
+        for record in records {
+            match record {
+                NdjsonRecord::Group { .. } | NdjsonRecord::Entry { .. } => data.results.push(record),
+                NdjsonRecord::Message { .. } => data.messages.push(record),
+                NdjsonRecord::Progress(_) | NdjsonRecord::Change { .. } | NdjsonRecord::Summary { .. } => {}
+            }
+        }
+        data.state = state;
+        // Dropping senders closes event streams
+        for subscriber in data.subscribers.drain(..) {
+            let _ = subscriber.send(ScanEvent::Finished { state });
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Scans {
+    next_id: AtomicU64,
+    scans: Mutex<BTreeMap<u64, Arc<Scan>>>,
+}
+
+impl Scans {
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, Arc<Scan>>> {
+        self.scans.lock().expect("Scans mutex is poisoned")
+    }
+
+    pub(crate) fn get(&self, id: u64) -> Option<Arc<Scan>> {
+        self.lock().get(&id).cloned()
+    }
+
+    pub(crate) fn all(&self) -> Vec<Arc<Scan>> {
+        self.lock().values().cloned().collect()
+    }
+
+    pub(crate) fn remove(&self, id: u64) -> Option<Arc<Scan>> {
+        self.lock().remove(&id)
+    }
+
+    // Tool runs in separate thread, so request returns immediately with id of scan
+    pub(crate) fn start(&self, tool_type: ToolType, check_method: Option<CheckingMethod>, mut tool: Box<dyn ScanTool>) -> Result<Arc<Scan>, String> {
+        let scan = Arc::new(Scan {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+            tool: tool_type,
+            check_method,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            data: Mutex::new(ScanData {
+                state: ScanState::Running,
+                progress: None,
+                results: Vec::new(),
+                messages: Vec::new(),
+                subscribers: Vec::new(),
+            }),
+        });
+        self.lock().insert(scan.id, scan.clone());
+
+        let (progress_sender, progress_receiver): (Sender<ProgressData>, Receiver<ProgressData>) = unbounded();
+        let progress_scan = scan.clone();
+        thread::Builder::new()
+            .name(format!("scan_{}_progress", scan.id))
+            .spawn(move || {
+                for progress_data in progress_receiver {
+                    progress_scan.update_progress(&progress_data);
+                }
+            })
+            .map_err(|e| format!("Cannot start progress thread - {e}"))?;
+
+        let search_scan = scan.clone();
+        thread::Builder::new()
+            .name(format!("scan_{}", scan.id))
+            .stack_size(DEFAULT_THREAD_SIZE)
+            .spawn(move || {
+                info!("Starting scan {} with tool {:?}", search_scan.id, search_scan.tool);
+                // Scan must always end, otherwise clients would wait for its events forever
+                let result = panic::catch_unwind(AssertUnwindSafe(|| tool.search_and_collect(&search_scan.stop_flag, &progress_sender)));
+                // Progress thread ends when all senders are dropped
+                drop(progress_sender);
+                match result {
+                    Ok(Ok(collector)) => {
+                        let state = if search_scan.stop_flag.load(Ordering::Relaxed) {
+                            ScanState::Stopped
+                        } else {
+                            ScanState::Finished
+                        };
+                        let records = collector.lines.into_iter().map(|line| line.record).collect();
+                        search_scan.finish(state, records);
+                    }
+                    Ok(Err(e)) => {
+                        error!("Cannot collect results of scan {} - {e}", search_scan.id);
+                        search_scan.finish(ScanState::Failed, Vec::new());
+                    }
+                    Err(_) => {
+                        error!("Scan {} panicked", search_scan.id);
+                        search_scan.finish(ScanState::Failed, Vec::new());
+                    }
+                }
+                info!("Scan {} ended", search_scan.id);
+            })
+            .map_err(|e| format!("Cannot start scan thread - {e}"))?;
+
+        Ok(scan)
+    }
+}
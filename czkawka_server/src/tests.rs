@@ -0,0 +1,322 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::thread;
+
+use image::{Rgb, RgbImage};
+use serde_json::{Value, json};
+use tempfile::tempdir;
+use tiny_http::Server;
+
+use crate::api::{ApiBody, ApiRequest, ApiResponse, ApiServer, Method};
+use crate::http::{MAX_BODY_SIZE, format_event, run_server};
+use crate::scans::{ScanEvent, ScanState};
+
+const TOKEN: &str = "secret";
+
+fn request(api: &ApiServer, method: Method, url: &str, body: &Value) -> ApiResponse {
+    api.handle(&ApiRequest {
+        method,
+        url: url.to_string(),
+        token: Some(TOKEN.to_string()),
+        body: body.to_string().into_bytes(),
+    })
+}
+
+fn json_body(response: ApiResponse) -> (u16, Value) {
+    let ApiBody::Json(body) = response.body else {
+        panic!("Expected json response");
+    };
+    (response.status, serde_json::from_slice(&body).unwrap())
+}
+
+fn wait_for_scan(api: &ApiServer, id: u64) -> Vec<ScanEvent> {
+    let response = request(api, Method::Get, &format!("/scans/{id}/events"), &Value::Null);
+    let ApiBody::Events(receiver) = response.body else {
+        panic!("Expected event stream");
+    };
+    receiver.iter().collect()
+}
+
+fn start_duplicate_scan(api: &ApiServer, folder: &Path) -> u64 {
+    let settings = json!({
+        "included_paths": [folder],
+        "minimum_file_size": 1,
+        "use_cache": false,
+    });
+    let (status, scan) = json_body(request(api, Method::Post, "/scans", &json!({ "tool": "duplicate", "settings": settings })));
+    assert_eq!(status, 201, "{scan}");
+    assert_eq!(scan["tool"], "duplicate");
+    scan["id"].as_u64().unwrap()
+}
+
+fn group_paths(results: &Value) -> Vec<String> {
+    let mut paths: Vec<String> = results["items"][0]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["path"].as_str().unwrap().to_string())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn test_requests_require_token() {
+    let api = ApiServer::new(TOKEN, false);
+    for token in [None, Some("wrong".to_string())] {
+        let response = api.handle(&ApiRequest {
+            method: Method::Get,
+            url: "/scans".to_string(),
+            token,
+            body: Vec::new(),
+        });
+        assert_eq!(response.status, 401);
+    }
+
+    // Token in query is accepted only by event stream
+    let (status, _) = json_body(api.handle(&ApiRequest {
+        method: Method::Get,
+        url: format!("/scans?token={TOKEN}"),
+        token: None,
+        body: Vec::new(),
+    }));
+    assert_eq!(status, 401);
+    let (status, _) = json_body(api.handle(&ApiRequest {
+        method: Method::Get,
+        url: format!("/scans/1/events?token={TOKEN}"),
+        token: None,
+        body: Vec::new(),
+    }));
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_invalid_scan_requests() {
+    let api = ApiServer::new(TOKEN, false);
+    let (status, body) = json_body(request(&api, Method::Post, "/scans", &json!({ "tool": "duplicate" })));
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("included_paths"));
+
+    let (status, _) = json_body(request(&api, Method::Post, "/scans", &json!({ "tool": "unknown_tool", "settings": {} })));
+    assert_eq!(status, 400);
+
+    let settings = json!({ "included_paths": ["/"], "duplicates_sub_available_hash_type": "md5" });
+    let (status, body) = json_body(request(&api, Method::Post, "/scans", &json!({ "tool": "duplicate", "settings": settings })));
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("md5"));
+
+    let (status, _) = json_body(request(&api, Method::Get, "/scans/5/results", &Value::Null));
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn test_scan_results_and_actions() {
+    let temp_dir = tempdir().unwrap();
+    let folder = temp_dir.path().join("scanned");
+    let destination = temp_dir.path().join("moved");
+    fs::create_dir_all(&folder).unwrap();
+    fs::create_dir_all(&destination).unwrap();
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        fs::write(folder.join(name), vec![7u8; 4096]).unwrap();
+    }
+    fs::write(folder.join("unique.txt"), vec![8u8; 4096]).unwrap();
+
+    let api = ApiServer::new(TOKEN, false);
+    let id = start_duplicate_scan(&api, &folder);
+    let events = wait_for_scan(&api, id);
+    assert_eq!(events.last(), Some(&ScanEvent::Finished { state: ScanState::Finished }));
+
+    let (status, scan) = json_body(request(&api, Method::Get, &format!("/scans/{id}"), &Value::Null));
+    assert_eq!(status, 200);
+    assert_eq!(scan["state"], "finished");
+    assert_eq!(scan["results"], 1);
+
+    let (_, results) = json_body(request(&api, Method::Get, &format!("/scans/{id}/results?offset=0&limit=10"), &Value::Null));
+    assert_eq!(results["total"], 1);
+    let path = |name: &str| folder.join(name).to_string_lossy().to_string();
+    assert_eq!(group_paths(&results), [path("a.txt"), path("b.txt"), path("c.txt"), path("d.txt")]);
+
+    // Files outside results cannot be changed
+    let (status, _) = json_body(request(
+        &api,
+        Method::Post,
+        &format!("/scans/{id}/actions"),
+        &json!({ "action": "delete", "paths": [path("unique.txt")] }),
+    ));
+    assert_eq!(status, 400);
+    assert!(folder.join("unique.txt").exists());
+
+    let (status, result) = json_body(request(
+        &api,
+        Method::Post,
+        &format!("/scans/{id}/actions"),
+        &json!({ "action": "delete", "paths": [path("d.txt")] }),
+    ));
+    assert_eq!(status, 200);
+    assert_eq!(result["processed"], json!([path("d.txt")]));
+    assert!(!folder.join("d.txt").exists());
+
+    let action = json!({ "action": "move", "paths": [path("c.txt")], "destination": destination });
+    let (status, result) = json_body(request(&api, Method::Post, &format!("/scans/{id}/actions"), &action));
+    assert_eq!(status, 200, "{result}");
+    assert!(destination.join("c.txt").exists());
+
+    let (_, results) = json_body(request(&api, Method::Get, &format!("/scans/{id}/results"), &Value::Null));
+    assert_eq!(group_paths(&results), [path("a.txt"), path("b.txt")]);
+
+    let action = json!({ "action": "hardlink", "paths": [path("b.txt")], "source": path("a.txt") });
+    let (status, result) = json_body(request(&api, Method::Post, &format!("/scans/{id}/actions"), &action));
+    assert_eq!(status, 200, "{result}");
+    assert_eq!(result["errors"], json!([]));
+
+    // Group with single file is no longer shown
+    let (_, results) = json_body(request(&api, Method::Get, &format!("/scans/{id}/results"), &Value::Null));
+    assert_eq!(results["total"], 0);
+
+    let (status, _) = json_body(request(&api, Method::Delete, &format!("/scans/{id}"), &Value::Null));
+    assert_eq!(status, 200);
+    let (_, scans) = json_body(request(&api, Method::Get, "/scans", &Value::Null));
+    assert_eq!(scans, json!([]));
+}
+
+#[test]
+fn test_actions_skip_files_changed_since_scan() {
+    let temp_dir = tempdir().unwrap();
+    let folder = temp_dir.path();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(folder.join(name), vec![7u8; 4096]).unwrap();
+    }
+
+    let api = ApiServer::new(TOKEN, false);
+    let id = start_duplicate_scan(&api, folder);
+    wait_for_scan(&api, id);
+    let path = |name: &str| folder.join(name).to_string_lossy().to_string();
+
+    fs::write(folder.join("c.txt"), vec![7u8; 5000]).unwrap();
+    let (status, result) = json_body(request(
+        &api,
+        Method::Post,
+        &format!("/scans/{id}/actions"),
+        &json!({ "action": "delete", "paths": [path("c.txt")] }),
+    ));
+    assert_eq!(status, 200);
+    assert_eq!(result["processed"], json!([]));
+    assert_eq!(result["errors"].as_array().unwrap().len(), 1);
+    assert!(folder.join("c.txt").exists());
+
+    // Same size and maybe same modification date, but different content
+    fs::write(folder.join("b.txt"), vec![8u8; 4096]).unwrap();
+    let action = json!({ "action": "hardlink", "paths": [path("b.txt")], "source": path("a.txt") });
+    let (status, result) = json_body(request(&api, Method::Post, &format!("/scans/{id}/actions"), &action));
+    assert_eq!(status, 200);
+    assert_eq!(result["processed"], json!([]));
+    assert_eq!(result["errors"].as_array().unwrap().len(), 1);
+    assert_eq!(fs::read(folder.join("b.txt")).unwrap(), vec![8u8; 4096]);
+}
+
+#[test]
+fn test_hardlink_rejected_for_similar_images() {
+    let temp_dir = tempdir().unwrap();
+    let folder = temp_dir.path();
+    // Image hashes of uniform or smooth images are ignored, so irregular blocks are used
+    let mut image = RgbImage::from_fn(64, 64, |x, y| if ((x / 8) * 7 + (y / 8) * 3) % 5 < 2 { Rgb([255, 255, 255]) } else { Rgb([0, 0, 0]) });
+    image.save(folder.join("a.png")).unwrap();
+    image.put_pixel(10, 10, Rgb([128, 128, 128]));
+    image.save(folder.join("b.png")).unwrap();
+
+    let api = ApiServer::new(TOKEN, false);
+    let settings = json!({ "included_paths": [folder], "minimum_file_size": 0, "use_cache": false });
+    let (status, scan) = json_body(request(&api, Method::Post, "/scans", &json!({ "tool": "similar_images", "settings": settings })));
+    assert_eq!(status, 201, "{scan}");
+    let id = scan["id"].as_u64().unwrap();
+    wait_for_scan(&api, id);
+
+    let (_, results) = json_body(request(&api, Method::Get, &format!("/scans/{id}/results"), &Value::Null));
+    let path = |name: &str| folder.join(name).to_string_lossy().to_string();
+    assert_eq!(group_paths(&results), [path("a.png"), path("b.png")]);
+
+    let action = json!({ "action": "hardlink", "paths": [path("b.png")], "source": path("a.png") });
+    let (status, body) = json_body(request(&api, Method::Post, &format!("/scans/{id}/actions"), &action));
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("hash"));
+    assert_ne!(fs::read(folder.join("a.png")).unwrap(), fs::read(folder.join("b.png")).unwrap());
+}
+
+#[test]
+fn test_delete_rejected_for_partial_hash_duplicates() {
+    let temp_dir = tempdir().unwrap();
+    let folder = temp_dir.path();
+    fs::write(folder.join("a.txt"), vec![1u8; 4096]).unwrap();
+    fs::write(folder.join("b.txt"), vec![1u8; 4096]).unwrap();
+
+    let api = ApiServer::new(TOKEN, false);
+    let settings = json!({ "included_paths": [folder], "minimum_file_size": 1, "use_cache": false, "duplicates_sub_check_method": "partial_hash" });
+    let (status, scan) = json_body(request(&api, Method::Post, "/scans", &json!({ "tool": "duplicate", "settings": settings })));
+    assert_eq!(status, 201, "{scan}");
+    let id = scan["id"].as_u64().unwrap();
+    wait_for_scan(&api, id);
+
+    let (_, results) = json_body(request(&api, Method::Get, &format!("/scans/{id}/results"), &Value::Null));
+    let path = |name: &str| folder.join(name).to_string_lossy().to_string();
+    assert_eq!(group_paths(&results), [path("a.txt"), path("b.txt")]);
+
+    for action in [
+        json!({ "action": "delete", "paths": [path("b.txt")] }),
+        json!({ "action": "hardlink", "paths": [path("b.txt")], "source": path("a.txt") }),
+    ] {
+        let (status, body) = json_body(request(&api, Method::Post, &format!("/scans/{id}/actions"), &action));
+        assert_eq!(status, 400, "{body}");
+    }
+    assert!(folder.join("b.txt").exists());
+}
+
+#[test]
+fn test_event_format() {
+    let event = ScanEvent::Finished { state: ScanState::Stopped };
+    assert_eq!(format_event(&event), "event: finished\ndata: {\"event\":\"finished\",\"state\":\"stopped\"}\n\n");
+}
+
+#[test]
+fn test_http_server() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = server.server_addr().to_ip().unwrap();
+    thread::spawn(move || run_server(&server, &ApiServer::new(TOKEN, false)));
+
+    let send = |authorization: &str| {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET /scans HTTP/1.1\r\nHost: localhost\r\n{authorization}Connection: close\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let response = send("");
+    assert!(response.starts_with("HTTP/1.1 401"), "{response}");
+    let response = send(&format!("Authorization: Bearer {TOKEN}\r\n"));
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.ends_with("[]"), "{response}");
+}
+
+#[test]
+fn test_http_server_rejects_request_before_reading_body() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = server.server_addr().to_ip().unwrap();
+    thread::spawn(move || run_server(&server, &ApiServer::new(TOKEN, false)));
+
+    // Body is never sent, so response can only arrive if server doesn't wait for it
+    // tiny_http itself reads bodies up to 1024 bytes before returning request, so bigger length is used
+    let send_headers = |headers: &str| {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "POST /scans HTTP/1.1\r\nHost: localhost\r\n{headers}\r\n").unwrap();
+        let mut response = [0; 12];
+        stream.read_exact(&mut response).unwrap();
+        String::from_utf8_lossy(&response).to_string()
+    };
+
+    let response = send_headers("Content-Length: 2048\r\n");
+    assert_eq!(response, "HTTP/1.1 401");
+    let response = send_headers(&format!("Authorization: Bearer {TOKEN}\r\nContent-Length: {}\r\n", MAX_BODY_SIZE + 1));
+    assert_eq!(response, "HTTP/1.1 413");
+}
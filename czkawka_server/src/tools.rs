@@ -0,0 +1,318 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crossbeam_channel::Sender;
+use czkawka_core::common::model::{CheckingMethod, HashType, ToolType};
+use czkawka_core::common::ndjson::NdjsonCollector;
+use czkawka_core::common::profile::ScanProfile;
+use czkawka_core::common::progress_data::ProgressData;
+use czkawka_core::common::tool_data::CommonData;
+use czkawka_core::common::traits::AllTraits;
+use czkawka_core::re_exported::{FilterType, HashAlg};
+use czkawka_core::tools::bad_extensions::{BadExtensions, BadExtensionsParameters};
+use czkawka_core::tools::bad_names::{BadNames, BadNamesParameters, NameIssues};
+use czkawka_core::tools::big_file::{BigFile, BigFileParameters, SearchMode};
+use czkawka_core::tools::broken_files::{BrokenFiles, BrokenFilesParameters, CheckedTypes};
+use czkawka_core::tools::duplicate::{DEFAULT_MINIMAL_CACHE_SIZE, DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateFinder, DuplicateFinderParameters};
+use czkawka_core::tools::empty_files::EmptyFiles;
+use czkawka_core::tools::empty_folder::EmptyFolder;
+use czkawka_core::tools::exif_remover::{ExifRemover, ExifRemoverParameters};
+use czkawka_core::tools::invalid_symlinks::InvalidSymlinks;
+use czkawka_core::tools::same_music::{MusicSimilarity, SameMusic, SameMusicParameters};
+use czkawka_core::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, SimilarImages, SimilarImagesParameters};
+use czkawka_core::tools::similar_videos::{
+    DEFAULT_CROP_DETECT, DEFAULT_SKIP_FORWARD_AMOUNT, DEFAULT_VID_HASH_DURATION, SimilarVideos, SimilarVideosParameters, crop_detect_from_str_opt,
+};
+use czkawka_core::tools::temporary::Temporary;
+
+// Tool configured from request, which runs in scan thread
+pub(crate) trait ScanTool: Send {
+    fn search_and_collect(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> io::Result<NdjsonCollector>;
+}
+
+impl<T: AllTraits + Send> ScanTool for T {
+    fn search_and_collect(&mut self, stop_flag: &Arc<AtomicBool>, progress_sender: &Sender<ProgressData>) -> io::Result<NdjsonCollector> {
+        self.search(stop_flag, Some(progress_sender));
+        self.print_results_as_ndjson(NdjsonCollector::default())
+    }
+}
+
+// Settings use the same format as Krokiet presets and CLI profiles, missing fields use CLI defaults
+pub(crate) fn prepare_tool(tool_type: ToolType, settings: &ScanProfile) -> Result<Box<dyn ScanTool>, String> {
+    if settings.included_paths.as_ref().is_none_or(Vec::is_empty) {
+        return Err("At least one path must be set in \"included_paths\"".to_string());
+    }
+
+    let tool: Box<dyn ScanTool> = match tool_type {
+        ToolType::Duplicate => Box::new(prepare_duplicates(settings)?),
+        ToolType::EmptyFolders => Box::new(with_common_settings(EmptyFolder::new(), settings)),
+        ToolType::EmptyFiles => Box::new(with_common_settings(EmptyFiles::new(), settings)),
+        ToolType::InvalidSymlinks => Box::new(with_common_settings(InvalidSymlinks::new(), settings)),
+        ToolType::TemporaryFiles => Box::new(with_common_settings(Temporary::new(), settings)),
+        ToolType::BadExtensions => Box::new(with_common_settings(BadExtensions::new(BadExtensionsParameters::new()), settings)),
+        ToolType::BigFile => {
+            let search_mode = match settings.biggest_files_sub_method.as_deref() {
+                Some("smallest") => SearchMode::SmallestFiles,
+                _ => SearchMode::BiggestFiles,
+            };
+            let number_of_files = settings.biggest_files_sub_number_of_files.map_or(50, |number| number.max(1) as usize);
+            Box::new(with_common_settings(BigFile::new(BigFileParameters::new(number_of_files, search_mode)), settings))
+        }
+        ToolType::SimilarImages => Box::new(prepare_similar_images(settings)?),
+        ToolType::SimilarVideos => Box::new(prepare_similar_videos(settings)?),
+        ToolType::SameMusic => Box::new(prepare_same_music(settings)?),
+        ToolType::BrokenFiles => Box::new(with_common_settings(BrokenFiles::new(BrokenFilesParameters::new(checked_types(settings))), settings)),
+        ToolType::BadNames => Box::new(with_common_settings(BadNames::new(BadNamesParameters::new(name_issues(settings))), settings)),
+        ToolType::ExifRemover => {
+            let ignored_tags = split_comma_list(settings.ignored_exif_tags.as_ref()).unwrap_or_default();
+            Box::new(with_common_settings(ExifRemover::new(ExifRemoverParameters::new(ignored_tags)), settings))
+        }
+        ToolType::VideoOptimizer | ToolType::None => return Err(format!("Tool {tool_type:?} cannot be run by server")),
+    };
+    Ok(tool)
+}
+
+fn split_comma_list(value: Option<&String>) -> Option<Vec<String>> {
+    value.map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect())
+}
+
+fn kb_to_bytes(value: Option<i32>) -> Option<u64> {
+    value.map(|value| value.max(0) as u64 * 1024)
+}
+
+fn with_common_settings<T: CommonData>(mut tool: T, settings: &ScanProfile) -> T {
+    let mut included_paths = settings.included_paths.clone().unwrap_or_default();
+    if tool.get_tool_type().may_use_reference_paths()
+        && let Some(reference_paths) = settings.included_paths_referenced.clone().filter(|paths| !paths.is_empty())
+    {
+        included_paths.extend_from_slice(&reference_paths);
+        tool.set_reference_paths(reference_paths);
+    }
+    tool.set_included_paths(included_paths);
+    tool.set_excluded_paths(settings.excluded_paths.clone().unwrap_or_default());
+    tool.set_excluded_items(split_comma_list(settings.excluded_items.as_ref()).unwrap_or_default());
+    tool.set_allowed_extensions(split_comma_list(settings.allowed_extensions.as_ref()).unwrap_or_default());
+    tool.set_excluded_extensions(split_comma_list(settings.excluded_extensions.as_ref()).unwrap_or_default());
+    tool.set_recursive_search(settings.recursive_search.unwrap_or(true));
+    tool.set_use_cache(settings.use_cache.unwrap_or(true));
+    tool.set_exclude_other_filesystems(settings.ignore_other_file_systems.unwrap_or(false));
+    tool
+}
+
+fn with_size_settings<T: CommonData>(mut tool: T, settings: &ScanProfile, default_minimal_file_size: u64) -> T {
+    tool.set_minimal_file_size(kb_to_bytes(settings.minimum_file_size).unwrap_or(default_minimal_file_size));
+    tool.set_maximal_file_size(kb_to_bytes(settings.maximum_file_size).unwrap_or(u64::MAX));
+    tool.set_hide_hard_links(settings.hide_hard_links.unwrap_or(true));
+    tool
+}
+
+fn parse_setting<T>(name: &str, value: Option<&String>, default: T, parse: impl Fn(&str) -> Option<T>) -> Result<T, String> {
+    match value {
+        Some(value) => parse(value).ok_or_else(|| format!("Invalid value \"{value}\" of \"{name}\"")),
+        None => Ok(default),
+    }
+}
+
+// Actions allowed on results depend on how duplicates were compared
+pub(crate) fn scan_check_method(tool_type: ToolType, settings: &ScanProfile) -> Option<CheckingMethod> {
+    (tool_type == ToolType::Duplicate).then(|| duplicates_check_method(settings).ok()).flatten()
+}
+
+fn duplicates_check_method(settings: &ScanProfile) -> Result<CheckingMethod, String> {
+    parse_setting(
+        "duplicates_sub_check_method",
+        settings.duplicates_sub_check_method.as_ref(),
+        CheckingMethod::Hash,
+        |value| match value.to_ascii_lowercase().as_str() {
+            "name" => Some(CheckingMethod::Name),
+            "size" => Some(CheckingMethod::Size),
+            "size_and_name" | "size_name" => Some(CheckingMethod::SizeName),
+            "partial_hash" => Some(CheckingMethod::PartialHash),
+            "hash" => Some(CheckingMethod::Hash),
+            _ => None,
+        },
+    )
+}
+
+fn prepare_duplicates(settings: &ScanProfile) -> Result<DuplicateFinder, String> {
+    let check_method = duplicates_check_method(settings)?;
+    let hash_type = parse_setting(
+        "duplicates_sub_available_hash_type",
+        settings.duplicates_sub_available_hash_type.as_ref(),
+        HashType::Blake3,
+        |value| match value.to_ascii_lowercase().as_str() {
+            "blake3" => Some(HashType::Blake3),
+            "crc32" => Some(HashType::Crc32),
+            "xxh3" => Some(HashType::Xxh3),
+            _ => None,
+        },
+    )?;
+
+    let params = DuplicateFinderParameters::new(
+        check_method,
+        hash_type,
+        settings.duplicate_use_prehash.unwrap_or(false),
+        settings.duplicate_minimal_hash_cache_size.map_or(DEFAULT_MINIMAL_CACHE_SIZE, |size| size.max(0) as u64),
+        settings.duplicate_minimal_prehash_cache_size.map_or(DEFAULT_MINIMAL_CACHE_SIZE, |size| size.max(0) as u64),
+        settings.duplicates_sub_name_case_sensitive.unwrap_or(false),
+        false,
+        settings.duplicates_sub_verify_byte_by_byte.unwrap_or(false),
+        None,
+        false,
+        DEFAULT_PARTIAL_HASH_SEGMENTS,
+        false,
+    );
+    let tool = with_common_settings(DuplicateFinder::new(params), settings);
+    Ok(with_size_settings(tool, settings, 8192))
+}
+
+fn prepare_similar_images(settings: &ScanProfile) -> Result<SimilarImages, String> {
+    let hash_size = parse_setting("similar_images_sub_hash_size", settings.similar_images_sub_hash_size.as_ref(), 16, |value| {
+        value.parse::<u8>().ok().filter(|hash_size| [8, 16, 32, 64].contains(hash_size))
+    })?;
+    let hash_alg = parse_setting(
+        "similar_images_sub_hash_alg",
+        settings.similar_images_sub_hash_alg.as_ref(),
+        HashAlg::Gradient,
+        |value| match value.to_lowercase().as_str() {
+            "mean" => Some(HashAlg::Mean),
+            "gradient" => Some(HashAlg::Gradient),
+            "blockhash" => Some(HashAlg::Blockhash),
+            "vertgradient" => Some(HashAlg::VertGradient),
+            "doublegradient" => Some(HashAlg::DoubleGradient),
+            "median" => Some(HashAlg::Median),
+            _ => None,
+        },
+    )?;
+    let image_filter = parse_setting(
+        "similar_images_sub_resize_algorithm",
+        settings.similar_images_sub_resize_algorithm.as_ref(),
+        FilterType::Nearest,
+        |value| match value.to_lowercase().as_str() {
+            "lanczos3" => Some(FilterType::Lanczos3),
+            "nearest" => Some(FilterType::Nearest),
+            "triangle" => Some(FilterType::Triangle),
+            "gaussian" => Some(FilterType::Gaussian),
+            "catmullrom" => Some(FilterType::CatmullRom),
+            _ => None,
+        },
+    )?;
+
+    let params = SimilarImagesParameters {
+        calculate_quality: settings.similar_images_sub_calculate_quality.unwrap_or(false),
+        ..SimilarImagesParameters::new(
+            settings.similar_images_sub_similarity.map_or(5, |similarity| similarity.max(0) as u32),
+            hash_size,
+            hash_alg,
+            image_filter,
+            settings.similar_images_sub_ignore_same_size.unwrap_or(false),
+            settings.similar_images_sub_check_transforms.unwrap_or(false),
+            settings.similar_images_sub_match_crops.unwrap_or(false),
+            settings
+                .similar_images_sub_match_crops_max_images
+                .filter(|max_images| *max_images > 0)
+                .unwrap_or(DEFAULT_MATCH_CROPS_MAX_IMAGES),
+            settings.similar_images_sub_series_interval.unwrap_or(0),
+        )
+    };
+    let tool = with_common_settings(SimilarImages::new(params), settings);
+    Ok(with_size_settings(tool, settings, 16384))
+}
+
+fn prepare_similar_videos(settings: &ScanProfile) -> Result<SimilarVideos, String> {
+    let crop_detect = parse_setting(
+        "similar_videos_crop_detect",
+        settings.similar_videos_crop_detect.as_ref(),
+        DEFAULT_CROP_DETECT,
+        crop_detect_from_str_opt,
+    )?;
+
+    let params = SimilarVideosParameters::new(
+        settings.similar_videos_sub_similarity.unwrap_or(10),
+        settings.similar_videos_sub_ignore_same_size.unwrap_or(false),
+        settings.similar_videos_skip_forward_amount.unwrap_or(DEFAULT_SKIP_FORWARD_AMOUNT),
+        settings.similar_videos_vid_hash_duration.unwrap_or(DEFAULT_VID_HASH_DURATION),
+        crop_detect,
+        false,
+        10,
+        false,
+        2,
+    );
+    let tool = with_common_settings(SimilarVideos::new(params), settings);
+    Ok(with_size_settings(tool, settings, 8192))
+}
+
+fn prepare_same_music(settings: &ScanProfile) -> Result<SameMusic, String> {
+    let check_method = parse_setting(
+        "similar_music_sub_audio_check_type",
+        settings.similar_music_sub_audio_check_type.as_ref(),
+        CheckingMethod::AudioTags,
+        |value| match value.to_ascii_lowercase().as_str() {
+            "tags" => Some(CheckingMethod::AudioTags),
+            "fingerprint" | "content" => Some(CheckingMethod::AudioContent),
+            _ => None,
+        },
+    )?;
+
+    let mut music_similarity = MusicSimilarity::TRACK_TITLE | MusicSimilarity::TRACK_ARTIST;
+    for (enabled, flag) in [
+        (settings.similar_music_sub_title, MusicSimilarity::TRACK_TITLE),
+        (settings.similar_music_sub_artist, MusicSimilarity::TRACK_ARTIST),
+        (settings.similar_music_sub_year, MusicSimilarity::YEAR),
+        (settings.similar_music_sub_bitrate, MusicSimilarity::BITRATE),
+        (settings.similar_music_sub_genre, MusicSimilarity::GENRE),
+        (settings.similar_music_sub_length, MusicSimilarity::LENGTH),
+    ] {
+        if let Some(enabled) = enabled {
+            music_similarity.set(flag, enabled);
+        }
+    }
+
+    let params = SameMusicParameters::new(
+        music_similarity,
+        settings.similar_music_sub_approximate_comparison.unwrap_or(false),
+        check_method,
+        settings.similar_music_sub_minimal_fragment_duration_value.unwrap_or(10.0),
+        settings.similar_music_sub_maximum_difference_value.map_or(2.0, f64::from),
+        settings.similar_music_compare_fingerprints_only_with_similar_titles.unwrap_or(false),
+        0.0,
+    );
+    let mut tool = with_common_settings(SameMusic::new(params), settings);
+    tool.set_minimal_file_size(kb_to_bytes(settings.minimum_file_size).unwrap_or(8192));
+    tool.set_maximal_file_size(kb_to_bytes(settings.maximum_file_size).unwrap_or(u64::MAX));
+    Ok(tool)
+}
+
+fn checked_types(settings: &ScanProfile) -> CheckedTypes {
+    let mut checked_types = CheckedTypes::PDF;
+    for (enabled, checked_type) in [
+        (settings.broken_files_sub_audio, CheckedTypes::AUDIO),
+        (settings.broken_files_sub_pdf, CheckedTypes::PDF),
+        (settings.broken_files_sub_archive, CheckedTypes::ARCHIVE),
+        (settings.broken_files_sub_image, CheckedTypes::IMAGE),
+        (settings.broken_files_sub_video, CheckedTypes::VIDEO),
+    ] {
+        if let Some(enabled) = enabled {
+            checked_types.set(checked_type, enabled);
+        }
+    }
+    checked_types
+}
+
+fn name_issues(settings: &ScanProfile) -> NameIssues {
+    let all = NameIssues::all();
+    let restricted_charset_allowed = match (settings.bad_names_sub_restricted_charset_enabled, &settings.bad_names_sub_restricted_charset) {
+        (Some(true), Some(charset)) => Some(charset.clone()),
+        (Some(false), _) => None,
+        _ => all.restricted_charset_allowed,
+    };
+    NameIssues {
+        uppercase_extension: settings.bad_names_sub_uppercase_extension.unwrap_or(all.uppercase_extension),
+        emoji_used: settings.bad_names_sub_emoji_used.unwrap_or(all.emoji_used),
+        space_at_start_or_end: settings.bad_names_sub_space_at_start_end.unwrap_or(all.space_at_start_or_end),
+        non_ascii_graphical: settings.bad_names_sub_non_ascii.unwrap_or(all.non_ascii_graphical),
+        restricted_charset_allowed,
+        remove_duplicated_non_alphanumeric: settings.bad_names_sub_remove_duplicated.unwrap_or(all.remove_duplicated_non_alphanumeric),
+    }
+}
@@ -10,6 +10,11 @@ rust_init_error_message =
     You can try running different builds (skia_opengl, skia_vulkan, femtovg_opengl - the default) or with software renderer to see if that resolves the issue.
 
 rust_loaded_preset = Loaded preset { $preset_idx }
+rust_keep_rules_empty = Keep rules are not set in settings
+rust_keep_rules_invalid = Invalid keep rules: { $reason }
+rust_keep_rule_not_supported = Keep rule { $rule } cannot be used in this tab, because its results do not contain needed values
+rust_image_upscaled = Yes
+rust_image_quality_not_calculated = Image quality was not calculated, enable calculating sharpness and JPEG quality and scan again
 rust_file_already_exists = File "{ $file }" already exists, and will not be overridden
 rust_error_removing_file_after_copy = Error while removing file "{ $file }" (after copying into different partition), reason: { $reason }
 rust_error_copying_file = Error while copying "{ $input }" to "{ $output }", reason: { $reason }
@@ -34,6 +39,7 @@ rust_compared_tags = Compared tags of { $items_stats }
 rust_compared_content = Compared content of { $items_stats }
 rust_hashed_images = Hashed { $items_stats } images ({ $size_stats })
 rust_compared_image_hashes = Compared image hashes of { $items_stats }
+rust_matched_image_features = Matched image features of { $items_stats }
 rust_hashed_videos = Hashed { $items_stats } videos
 rust_created_thumbnails = Created thumbnails for { $items_stats } videos
 rust_checked_files = Checked { $items_stats } file ({ $size_stats })
@@ -42,6 +48,7 @@ rust_checked_files_bad_names = Checked { $items_stats } file
 rust_checked_videos = Checked { $items_stats } videos ({ $size_stats })
 rust_analyzed_partial_hash = Analyzed partial hash of { $items_stats } files ({ $size_stats })
 rust_analyzed_full_hash = Analyzed full hash of { $items_stats } files ({ $size_stats })
+rust_compared_bytes = Compared content of { $items_stats } files ({ $size_stats })
 rust_failed_to_rename_file = Failed to rename file { $old_path } to { $new_path }, error: { $error }
 rust_no_included_paths = Cannot start scan when no included paths are set.
 rust_all_paths_referenced = Cannot start scan when all included paths are set as referenced paths, you need to disable reference checkbox next to input path.
@@ -59,12 +66,20 @@ rust_found_bad_names = Found { $items_found } files with bad names in { $time }
 rust_found_video_optimizer = Found { $items_found } files to optimize in { $time }
 rust_found_duplicate_files = Found { $items_found } duplicate files in { $groups } groups taking { $size } in { $time }
 rust_found_duplicate_files_no_lost_space = Found { $items_found } duplicate files in { $groups } groups in { $time }
+rust_already_archived = Already archived
+rust_found_probable_duplicate_files = Found { $items_found } probable duplicate files(compared only by partial hash) in { $groups } groups taking { $size } in { $time }
+rust_probable_duplicates_cannot_be_changed = Files found by partial hash are only probable duplicates, so they cannot be deleted or linked - use full hash to remove them
 rust_found_big_files = Found { $items_found } big files with size { $size } in { $time }
 rust_found_exif_files = Found { $items_found } files with exif data in { $time }
 rust_cannot_load_preset = Cannot change and load preset { $preset_idx } - reason { $reason }, using default settings instead
 rust_saved_preset = Saved preset { $preset_idx }
+rust_saved_results = Saved results to txt, json, csv and sqlite files into "{ $name }" folder.
 rust_cannot_save_preset = Cannot save preset { $preset_idx } - reason { $reason }
 rust_reset_preset = Reset preset { $preset_idx }
+rust_exported_preset = Exported preset { $preset_idx } to { $file }
+rust_cannot_export_preset = Cannot export preset { $preset_idx } - reason { $reason }
+rust_imported_preset = Imported preset { $preset_idx } from { $file }
+rust_cannot_import_preset = Cannot import preset { $preset_idx } - reason { $reason }
 rust_cannot_create_output_folder = Cannot create output folder { $output_folder }, reason: { $error }
 
 rust_delete_summary = Deleted { $deleted } items, failed to remove { $failed } items, out of { $total } items
@@ -128,6 +143,8 @@ rust_cache_time_elapsed = Time elapsed: { $time }
 
 rust_symlink_failed = Failed to symlink {$name} to {$target}, reason {$reason}
 rust_hardlink_failed = Failed to hardlink { $name } to { $target }, reason { $reason }
+rust_journal_session = { $date } - { $source } - { $operations } operations
+rust_journal_reverted = Reverted { $reverted } out of { $all } operations
 
 # Slint translations, but in arrays
 
@@ -156,6 +173,10 @@ column_codec = Codec
 column_duration = Duration
 column_exif_tags = EXIF Tags
 column_new_name = New Name
+column_matched_segment = Matched Part
+column_sharpness = Sharpness
+column_jpeg_quality = JPEG Quality
+column_upscaled = Upscaled
 
 # Slint translations
 ok_button = Ok
@@ -164,6 +185,7 @@ do_you_want_to_continue = Do you want to continue?
 main_window_title = Krokiet - Data Cleaner
 scan_button = Scan
 stop_button = Stop
+open_results_button = Open
 stop_text = Stop
 select_button = Select
 move_button = Move
@@ -209,16 +231,26 @@ selection_newest = Select newest
 selection_oldest = Select oldest
 selection_shortest_path = Select the shortest path
 selection_longest_path = Select the longest path
+selection_all_except_kept_by_rules = Select all except files kept by rules
+selection_all_except_highest_quality = Select all except the highest quality
 stage_current = Current Stage:
 stage_all = All Stages:
 subsettings = Subsettings
 subsettings_images_hash_size = Hash Size
 subsettings_images_resize_algorithm = Resize Algorithm
 subsettings_images_ignore_same_size = Ignore images with same size
+subsettings_images_check_transforms = Find rotated and mirrored images
+subsettings_images_match_crops = Find cropped images (slow)
+subsettings_images_calculate_quality = Calculate sharpness and JPEG quality (slower)
+subsettings_images_query_images = Query images
+subsettings_images_query_images_hint = When images are added, only images similar to them are found, sorted from the most similar. List is not saved between sessions
 subsettings_images_max_difference = Max difference
+subsettings_images_series_interval = Photo series interval
+subsettings_images_series_interval_hint = When not 0, finds series of photos taken within given number of seconds one after another, sorted from the sharpest
 subsettings_images_duplicates_hash_type = Hash Type
 subsettings_duplicates_check_method = Check method
 subsettings_duplicates_name_case_sensitive = Case Sensitive(only name modes)
+subsettings_duplicates_verify_byte_by_byte = Verify hash groups byte by byte (recommended with CRC32)
 subsettings_biggest_files_sub_method = Method
 subsettings_biggest_files_sub_number_of_files = Number of files
 subsettings_videos_max_difference = Max difference
@@ -260,6 +292,7 @@ settings_global_settings = Global Settings
 settings_dark_theme = Dark theme
 settings_show_only_icons = Show only icons
 settings_excluded_items = Excluded item:
+settings_keep_rules = Keep rules:
 settings_allowed_extensions = Allowed extensions:
 settings_excluded_extensions = Excluded extensions:
 settings_file_size = File Size(Kilobytes)
@@ -296,6 +329,8 @@ settings_choose_name_for_prefix = Choose name for prefix
 settings_save = Save
 settings_load = Load
 settings_reset = Reset
+settings_export = Export
+settings_import = Import
 settings_similar_videos_tool = Similar Videos tool
 settings_video_thumbnails_clear_unused_thumbnails = Delete unused video thumbnails older than 7 days at app startup
 settings_video_thumbnails_header = Video Thumbnails
@@ -309,6 +344,10 @@ settings_similar_images_tool = Similar Images tool
 settings_general_settings = General Settings
 settings_cache_header_text = Cache Settings
 settings_clean_cache_button_text = Clean outdated cache
+settings_journal_header_text = Operations Journal
+settings_journal_refresh_button_text = Refresh
+settings_journal_revert_button_text = Revert session
+settings_journal_hint_text = Deleted, moved, renamed and linked files are saved in journal. Reverting restores files from trash, moves them back and replaces links with copies. Permanently deleted files cannot be restored.
 settings_settings = Settings
 settings_load_tabs_sizes_at_startup = Load tabs sizes at startup
 settings_load_windows_size_at_startup = Load windows size at startup
@@ -119,8 +119,11 @@ pub enum IntDataSimilarImages {
     Width,
     Height,
     PixelCount,
+    Sharpness,
+    JpegQuality,
+    QualityScore,
 }
-pub const MAX_INT_DATA_SIMILAR_IMAGES: usize = IntDataSimilarImages::PixelCount as usize + 1;
+pub const MAX_INT_DATA_SIMILAR_IMAGES: usize = IntDataSimilarImages::QualityScore as usize + 1;
 
 #[repr(u8)]
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -131,8 +134,11 @@ pub enum StrDataSimilarImages {
     Name,
     Path,
     ModificationDate,
+    Sharpness,
+    JpegQuality,
+    Upscaled,
 }
-pub const MAX_STR_DATA_SIMILAR_IMAGES: usize = StrDataSimilarImages::ModificationDate as usize + 1;
+pub const MAX_STR_DATA_SIMILAR_IMAGES: usize = StrDataSimilarImages::Upscaled as usize + 1;
 
 // Similar Videos
 #[repr(u8)]
@@ -176,8 +182,9 @@ pub enum IntDataSimilarMusic {
     SizePart2,
     Bitrate,
     Length,
+    MatchCoverage,
 }
-pub const MAX_INT_DATA_SIMILAR_MUSIC: usize = IntDataSimilarMusic::Length as usize + 1;
+pub const MAX_INT_DATA_SIMILAR_MUSIC: usize = IntDataSimilarMusic::MatchCoverage as usize + 1;
 
 #[repr(u8)]
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -192,8 +199,9 @@ pub enum StrDataSimilarMusic {
     Genre,
     Path,
     ModificationDate,
+    MatchedSegment,
 }
-pub const MAX_STR_DATA_SIMILAR_MUSIC: usize = StrDataSimilarMusic::ModificationDate as usize + 1;
+pub const MAX_STR_DATA_SIMILAR_MUSIC: usize = StrDataSimilarMusic::MatchedSegment as usize + 1;
 
 // Invalid Symlinks
 #[repr(u8)]
@@ -361,12 +369,14 @@ impl ActiveTab {
                 StrDataEmptyFiles::ModificationDate => SortIdx::IntIdxPair(IntDataEmptyFiles::ModificationDatePart1 as i32, IntDataEmptyFiles::ModificationDatePart2 as i32),
             },
             Self::SimilarImages => match StrDataSimilarImages::try_from(str_idx as u8).unwrap_or_else(|_| panic!("Invalid str idx {str_idx} for SimilarImages")) {
-                StrDataSimilarImages::Similarity | StrDataSimilarImages::Name | StrDataSimilarImages::Path => SortIdx::StrIdx(str_idx),
+                StrDataSimilarImages::Similarity | StrDataSimilarImages::Name | StrDataSimilarImages::Path | StrDataSimilarImages::Upscaled => SortIdx::StrIdx(str_idx),
                 StrDataSimilarImages::ModificationDate => {
                     SortIdx::IntIdxPair(IntDataSimilarImages::ModificationDatePart1 as i32, IntDataSimilarImages::ModificationDatePart2 as i32)
                 }
                 StrDataSimilarImages::Size => SortIdx::IntIdxPair(IntDataSimilarImages::SizePart1 as i32, IntDataSimilarImages::SizePart2 as i32),
                 StrDataSimilarImages::Resolution => SortIdx::IntIdx(IntDataSimilarImages::PixelCount as i32),
+                StrDataSimilarImages::Sharpness => SortIdx::IntIdx(IntDataSimilarImages::Sharpness as i32),
+                StrDataSimilarImages::JpegQuality => SortIdx::IntIdx(IntDataSimilarImages::JpegQuality as i32),
             },
             Self::DuplicateFiles => match StrDataDuplicateFiles::try_from(str_idx as u8).unwrap_or_else(|_| panic!("Invalid str idx {str_idx} for DuplicateFiles")) {
                 StrDataDuplicateFiles::Name | StrDataDuplicateFiles::Path => SortIdx::StrIdx(str_idx),
@@ -408,6 +418,7 @@ impl ActiveTab {
                 | StrDataSimilarMusic::Genre => SortIdx::StrIdx(str_idx),
                 StrDataSimilarMusic::ModificationDate => SortIdx::IntIdxPair(IntDataSimilarMusic::ModificationDatePart1 as i32, IntDataSimilarMusic::ModificationDatePart2 as i32),
                 StrDataSimilarMusic::Size => SortIdx::IntIdxPair(IntDataSimilarMusic::SizePart1 as i32, IntDataSimilarMusic::SizePart2 as i32),
+                StrDataSimilarMusic::MatchedSegment => SortIdx::IntIdx(IntDataSimilarMusic::MatchCoverage as i32),
             },
             Self::InvalidSymlinks => match StrDataInvalidSymlinks::try_from(str_idx as u8).unwrap_or_else(|_| panic!("Invalid str idx {str_idx} for InvalidSymlinks")) {
                 StrDataInvalidSymlinks::SymlinkName | StrDataInvalidSymlinks::SymlinkFolder | StrDataInvalidSymlinks::DestinationPath | StrDataInvalidSymlinks::TypeOfError => {
@@ -563,6 +574,30 @@ impl ActiveTab {
         }
     }
 
+    // Only similar images have quality metrics
+    pub(crate) fn get_int_sharpness_opt_idx(self) -> Option<usize> {
+        match self {
+            Self::SimilarImages => Some(IntDataSimilarImages::Sharpness as usize),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn get_int_quality_score_opt_idx(self) -> Option<usize> {
+        match self {
+            Self::SimilarImages => Some(IntDataSimilarImages::QualityScore as usize),
+            _ => None,
+        }
+    }
+
+    // Bitrate of videos is split into two parts, like size
+    pub(crate) fn get_int_bitrate_opt_idx(self) -> Option<usize> {
+        match self {
+            Self::SimilarVideos => Some(IntDataSimilarVideos::BitratePart1 as usize),
+            Self::SimilarMusic => Some(IntDataSimilarMusic::Bitrate as usize),
+            _ => None,
+        }
+    }
+
     pub(crate) fn get_str_video_codec_idx(self) -> usize {
         match self {
             Self::SimilarVideos => StrDataSimilarVideos::Codec as usize,
@@ -8,6 +8,39 @@ pub(crate) fn connect_add_remove_directories(app: &MainWindow) {
     connect_add_files(app);
     connect_remove_directories(app);
     connect_add_manual_directories(app);
+    connect_query_images(app);
+}
+
+fn connect_query_images(app: &MainWindow) {
+    let a = app.as_weak();
+    app.global::<Callabler>().on_add_query_images(move || {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+
+        let directory = std::env::current_dir().unwrap_or(std::path::PathBuf::from("/"));
+
+        let file_dialog = FileDialog::new().set_directory(directory);
+
+        let Some(files) = file_dialog.pick_files() else {
+            return;
+        };
+        let files = files.iter().map(|x| x.to_string_lossy().to_string()).collect::<Vec<_>>();
+
+        let settings = app.global::<Settings>();
+        let new_model = merge_paths_into_model(&settings.get_similar_images_sub_query_images_model(), &files);
+        settings.set_similar_images_sub_query_images_model(new_model);
+    });
+
+    let a = app.as_weak();
+    app.global::<Callabler>().on_remove_query_image(move |index_to_remove| {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+        let settings = app.global::<Settings>();
+
+        let query_model = settings.get_similar_images_sub_query_images_model();
+        let new_model = filter_model(&query_model, index_to_remove);
+
+        assert_eq!(query_model.iter().count(), new_model.len() + 1, "Removing item should reduce model size by 1");
+        settings.set_similar_images_sub_query_images_model(ModelRc::new(VecModel::from(new_model)));
+    });
 }
 
 fn connect_add_manual_directories(app: &MainWindow) {
@@ -124,7 +157,11 @@ fn add_included_paths(settings: &Settings, folders: &[String]) {
 }
 
 fn add_excluded_paths(settings: &Settings, folders: &[String]) {
-    let old_folders = settings.get_excluded_paths_model();
+    let new_folders_model = merge_paths_into_model(&settings.get_excluded_paths_model(), folders);
+    settings.set_excluded_paths_model(new_folders_model);
+}
+
+fn merge_paths_into_model(old_folders: &ModelRc<ExcludedPathsModel>, folders: &[String]) -> ModelRc<ExcludedPathsModel> {
     let old_folders_path = old_folders.iter().map(|x| x.path.to_string()).collect::<Vec<_>>();
     let mut new_folders = old_folders.iter().collect::<Vec<_>>();
 
@@ -141,6 +178,5 @@ fn add_excluded_paths(settings: &Settings, folders: &[String]) {
 
     new_folders.sort_by_key(|x| x.path.clone());
 
-    let new_folders_model = ModelRc::new(VecModel::from(new_folders));
-    settings.set_excluded_paths_model(new_folders_model);
+    ModelRc::new(VecModel::from(new_folders))
 }
@@ -0,0 +1,73 @@
+use std::thread;
+
+use czkawka_core::common::journal::{find_journal_session, list_journal_sessions, revert_journal_session};
+use czkawka_core::helpers::messages::{MessageLimit, Messages};
+use log::error;
+use slint::{ComponentHandle, ModelRc, SharedString, VecModel};
+
+use crate::connect_scan::get_dt_timestamp_string;
+use crate::{Callabler, GuiState, MainWindow, flk};
+
+pub(crate) fn connect_journal(app: &MainWindow) {
+    refresh_journal_sessions(app);
+
+    let a = app.as_weak();
+    app.global::<Callabler>().on_refresh_journal_sessions(move || {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+        refresh_journal_sessions(&app);
+    });
+
+    let a = app.as_weak();
+    app.global::<Callabler>().on_revert_journal_session(move |session_id| {
+        let weak_app = a.clone();
+        let session_id = session_id.to_string();
+        // Reverting may copy big files, so it cannot block GUI thread
+        thread::spawn(move || {
+            let info_text = match find_journal_session(&session_id) {
+                Ok(session) => {
+                    let result = revert_journal_session(&session);
+                    let mut info_text = flk!("rust_journal_reverted", reverted = result.reverted, all = session.entries.len());
+                    if !result.errors.is_empty() {
+                        info_text.push('\n');
+                        info_text.push_str(&Messages::new_from_errors(result.errors).create_messages_text(MessageLimit::NoLimit));
+                    }
+                    info_text
+                }
+                Err(e) => e,
+            };
+
+            weak_app
+                .upgrade_in_event_loop(move |app| {
+                    app.global::<GuiState>().set_info_text(info_text.into());
+                    refresh_journal_sessions(&app);
+                })
+                .expect("Failed to update journal sessions in event loop");
+        });
+    });
+}
+
+fn refresh_journal_sessions(app: &MainWindow) {
+    let sessions = list_journal_sessions().unwrap_or_else(|e| {
+        error!("{e}");
+        Vec::new()
+    });
+
+    let names: Vec<SharedString> = sessions
+        .iter()
+        .map(|session| {
+            flk!(
+                "rust_journal_session",
+                date = get_dt_timestamp_string(session.started),
+                source = session.source.clone(),
+                operations = session.entries.len()
+            )
+            .into()
+        })
+        .collect();
+    let ids: Vec<SharedString> = sessions.iter().map(|session| session.id.clone().into()).collect();
+
+    let gui_state = app.global::<GuiState>();
+    gui_state.set_journal_sessions(ModelRc::new(VecModel::from(names)));
+    gui_state.set_journal_session_ids(ModelRc::new(VecModel::from(ids)));
+    gui_state.set_journal_session_index(0);
+}
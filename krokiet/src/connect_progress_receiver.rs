@@ -60,12 +60,14 @@ fn progress_save_load_cache(item: &ProgressData) -> ProgressToSend {
         | CurrentStage::DuplicateScanningSize
         | CurrentStage::DuplicatePreHashing
         | CurrentStage::DuplicateFullHashing
+        | CurrentStage::DuplicateByteComparing
         | CurrentStage::SameMusicReadingTags
         | CurrentStage::SameMusicCalculatingFingerprints
         | CurrentStage::SameMusicComparingTags
         | CurrentStage::SameMusicComparingFingerprints
         | CurrentStage::SimilarImagesCalculatingHashes
         | CurrentStage::SimilarImagesComparingHashes
+        | CurrentStage::SimilarImagesMatchingFeatures
         | CurrentStage::SimilarVideosCalculatingHashes
         | CurrentStage::SimilarVideosCreatingThumbnails
         | CurrentStage::BrokenFilesChecking
@@ -116,6 +118,7 @@ fn progress_default(item: &ProgressData) -> ProgressToSend {
         CurrentStage::SameMusicComparingFingerprints => flk!("rust_compared_content", items_stats = items_stats),
         CurrentStage::SimilarImagesCalculatingHashes => flk!("rust_hashed_images", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::SimilarImagesComparingHashes => flk!("rust_compared_image_hashes", items_stats = items_stats),
+        CurrentStage::SimilarImagesMatchingFeatures => flk!("rust_matched_image_features", items_stats = items_stats),
         CurrentStage::SimilarVideosCalculatingHashes => flk!("rust_hashed_videos", items_stats = items_stats),
         CurrentStage::BrokenFilesChecking => flk!("rust_checked_files", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::BadExtensionsChecking => flk!("rust_checked_files_bad_extensions", items_stats = items_stats),
@@ -124,6 +127,7 @@ fn progress_default(item: &ProgressData) -> ProgressToSend {
         CurrentStage::VideoOptimizerProcessingVideos => flk!("rust_checked_videos", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::DuplicatePreHashing => flk!("rust_analyzed_partial_hash", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::DuplicateFullHashing => flk!("rust_analyzed_full_hash", items_stats = items_stats, size_stats = size_stats),
+        CurrentStage::DuplicateByteComparing => flk!("rust_compared_bytes", items_stats = items_stats, size_stats = size_stats),
 
         CurrentStage::DeletingFiles if item.bytes_to_check != 0 => flk!("rust_deleting_files", items_stats = items_stats, size_stats = size_stats),
         CurrentStage::DeletingFiles => flk!("rust_deleting_no_size_files", items_stats = items_stats),
@@ -4,7 +4,7 @@ use rfd::FileDialog;
 use slint::ComponentHandle;
 
 use crate::shared_models::SharedModels;
-use crate::{Callabler, GuiState, MainWindow};
+use crate::{Callabler, GuiState, MainWindow, flk};
 
 pub(crate) fn connect_save(app: &MainWindow, shared_models: Arc<Mutex<SharedModels>>) {
     let a = app.as_weak();
@@ -17,8 +17,9 @@ pub(crate) fn connect_save(app: &MainWindow, shared_models: Arc<Mutex<SharedMode
             return;
         };
         let folder_str = folder.to_string_lossy();
-        if let Err(e) = shared_models.lock().unwrap().save_results(active_tab, &folder_str) {
-            app.global::<GuiState>().set_info_text(e.into());
+        match shared_models.lock().unwrap().save_results(active_tab, &folder_str) {
+            Ok(()) => app.global::<GuiState>().set_info_text(flk!("rust_saved_results", name = folder_str.to_string()).into()),
+            Err(e) => app.global::<GuiState>().set_info_text(e.into()),
         }
     });
 }
@@ -15,15 +15,19 @@ mod similar_videos;
 mod temporary_files;
 mod video_optimizer;
 
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 use chrono::{Local, TimeZone, Utc};
 use crossbeam_channel::Sender;
+use czkawka_core::common::import::import_results_instead_of_search;
 use czkawka_core::common::progress_data::ProgressData;
 use czkawka_core::common::tool_data::CommonData;
+use czkawka_core::common::traits::AllTraits;
 use czkawka_core::helpers::messages::MessageLimit;
+use rfd::FileDialog;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel};
 
 use crate::audio_player::AudioPlayer;
@@ -45,7 +49,7 @@ use crate::connect_scan::similar_videos::scan_similar_videos;
 use crate::connect_scan::temporary_files::scan_temporary_files;
 use crate::connect_scan::video_optimizer::scan_video_optimizer;
 use crate::settings::model::{BasicSettings, ComboBoxItems, SettingsCustom};
-use crate::settings::{collect_base_settings, collect_combo_box_settings, collect_settings};
+use crate::settings::{collect_base_settings, collect_combo_box_settings, collect_query_images, collect_settings};
 use crate::shared_models::SharedModels;
 use crate::{ActiveTab, GuiState, MainWindow, ProgressToSend, SingleMainListModel, flk};
 
@@ -57,6 +61,10 @@ pub struct ScanData {
     pub combo_box_items: ComboBoxItems,
     pub shared_models: Arc<Mutex<SharedModels>>,
     pub audio_player: Arc<AudioPlayer>,
+    // Set when results are opened from file instead of being searched
+    pub import_results_file: Option<PathBuf>,
+    // Images chosen in similar images settings, they are not part of saved settings
+    pub query_images: Vec<PathBuf>,
 }
 
 pub struct MessagesData {
@@ -72,6 +80,10 @@ pub(crate) fn connect_scan_button(
     audio_player: Arc<AudioPlayer>,
 ) {
     let a = app.as_weak();
+    let progress_sender_clone = progress_sender.clone();
+    let stop_flag_clone = Arc::clone(&stop_flag);
+    let shared_models_clone = Arc::clone(&shared_models);
+    let audio_player_clone = Arc::clone(&audio_player);
     app.on_scan_starting(move |active_tab| {
         let app = a.upgrade().expect("Failed to upgrade app :(");
 
@@ -85,58 +97,81 @@ pub(crate) fn connect_scan_button(
             return;
         }
 
-        let progress_sender = progress_sender.clone();
-        let stop_flag = stop_flag.clone();
+        start_scan(&app, active_tab, &progress_sender_clone, &stop_flag_clone, &shared_models_clone, &audio_player_clone, None);
+    });
 
-        app.set_progress_datas(ProgressToSend {
-            all_progress: 0,
-            current_progress: -1,
-            current_progress_size: -1,
-            step_name: "".into(),
-        });
+    let a = app.as_weak();
+    app.on_open_results_starting(move |active_tab| {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
 
-        let custom_settings = collect_settings(&app);
-        let basic_settings = collect_base_settings(&app);
-        let combo_box_items = collect_combo_box_settings(&app);
+        let file_dialog = FileDialog::new().add_filter("JSON", &["json"]);
+        let Some(file_name) = file_dialog.pick_file() else {
+            app.invoke_scan_ended("".into());
+            return;
+        };
 
-        let cloned_model = Arc::clone(&shared_models);
+        start_scan(&app, active_tab, &progress_sender, &stop_flag, &shared_models, &audio_player, Some(file_name));
+    });
+}
 
-        app.global::<GuiState>().set_info_text("".into());
+// Results are loaded from file instead of searching when import_results_file is set, the rest of the flow is the same
+fn start_scan(
+    app: &MainWindow,
+    active_tab: ActiveTab,
+    progress_sender: &Sender<ProgressData>,
+    stop_flag: &Arc<AtomicBool>,
+    shared_models: &Arc<Mutex<SharedModels>>,
+    audio_player: &Arc<AudioPlayer>,
+    import_results_file: Option<PathBuf>,
+) {
+    app.set_progress_datas(ProgressToSend {
+        all_progress: 0,
+        current_progress: -1,
+        current_progress_size: -1,
+        step_name: "".into(),
+    });
 
-        let a = app.as_weak();
-        let audio_player_clone = Arc::clone(&audio_player);
+    let custom_settings = collect_settings(app);
+    let basic_settings = collect_base_settings(app);
+    let combo_box_items = collect_combo_box_settings(app);
+    let query_images = collect_query_images(app);
 
-        let scan_data = ScanData {
-            progress_sender,
-            stop_flag,
-            custom_settings,
-            basic_settings,
-            combo_box_items,
-            shared_models: cloned_model,
-            audio_player: audio_player_clone,
-        };
+    app.global::<GuiState>().set_info_text("".into());
 
-        match active_tab {
-            ActiveTab::DuplicateFiles => scan_duplicates(a, scan_data),
-            ActiveTab::EmptyFolders => scan_empty_folders(a, scan_data),
-            ActiveTab::BigFiles => scan_big_files(a, scan_data),
-            ActiveTab::EmptyFiles => scan_empty_files(a, scan_data),
-            ActiveTab::SimilarImages => scan_similar_images(a, scan_data),
-            ActiveTab::SimilarVideos => scan_similar_videos(a, scan_data),
-            ActiveTab::SimilarMusic => scan_similar_music(a, scan_data),
-            ActiveTab::InvalidSymlinks => scan_invalid_symlinks(a, scan_data),
-            ActiveTab::BadExtensions => scan_bad_extensions(a, scan_data),
-            ActiveTab::BadNames => scan_bad_names(a, scan_data),
-            ActiveTab::BrokenFiles => scan_broken_files(a, scan_data),
-            ActiveTab::TemporaryFiles => scan_temporary_files(a, scan_data),
-            ActiveTab::ExifRemover => scan_exif_remover(a, scan_data),
-            ActiveTab::VideoOptimizer => scan_video_optimizer(a, scan_data),
-            ActiveTab::Settings | ActiveTab::About => panic!("Button should be disabled"),
-        }
-    });
+    let a = app.as_weak();
+
+    let scan_data = ScanData {
+        progress_sender: progress_sender.clone(),
+        stop_flag: Arc::clone(stop_flag),
+        custom_settings,
+        basic_settings,
+        combo_box_items,
+        shared_models: Arc::clone(shared_models),
+        audio_player: Arc::clone(audio_player),
+        import_results_file,
+        query_images,
+    };
+
+    match active_tab {
+        ActiveTab::DuplicateFiles => scan_duplicates(a, scan_data),
+        ActiveTab::EmptyFolders => scan_empty_folders(a, scan_data),
+        ActiveTab::BigFiles => scan_big_files(a, scan_data),
+        ActiveTab::EmptyFiles => scan_empty_files(a, scan_data),
+        ActiveTab::SimilarImages => scan_similar_images(a, scan_data),
+        ActiveTab::SimilarVideos => scan_similar_videos(a, scan_data),
+        ActiveTab::SimilarMusic => scan_similar_music(a, scan_data),
+        ActiveTab::InvalidSymlinks => scan_invalid_symlinks(a, scan_data),
+        ActiveTab::BadExtensions => scan_bad_extensions(a, scan_data),
+        ActiveTab::BadNames => scan_bad_names(a, scan_data),
+        ActiveTab::BrokenFiles => scan_broken_files(a, scan_data),
+        ActiveTab::TemporaryFiles => scan_temporary_files(a, scan_data),
+        ActiveTab::ExifRemover => scan_exif_remover(a, scan_data),
+        ActiveTab::VideoOptimizer => scan_video_optimizer(a, scan_data),
+        ActiveTab::Settings | ActiveTab::About => panic!("Button should be disabled"),
+    }
 }
 
-fn get_dt_timestamp_string(timestamp: u64) -> String {
+pub(crate) fn get_dt_timestamp_string(timestamp: u64) -> String {
     let dt_local = Utc.timestamp_opt(timestamp as i64, 0).single().unwrap_or_default().with_timezone(&Local);
     dt_local.format("%Y-%m-%d %H:%M:%S").to_string()
 }
@@ -174,6 +209,16 @@ where
     (text_messages.critical.clone(), text_messages.create_messages_text(limit))
 }
 
+fn search_or_import<T>(component: &mut T, sd: &ScanData)
+where
+    T: AllTraits,
+{
+    match &sd.import_results_file {
+        Some(file_name) => import_results_instead_of_search(component, file_name, &sd.stop_flag, Some(&sd.progress_sender)),
+        None => component.search(&sd.stop_flag, Some(&sd.progress_sender)),
+    }
+}
+
 fn set_common_settings<T>(component: &mut T, custom_settings: &SettingsCustom, stop_flag: &Arc<AtomicBool>)
 where
     T: CommonData,
@@ -4,7 +4,7 @@ use std::thread;
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::model::FileEntry;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path};
 use czkawka_core::tools::big_file;
 use czkawka_core::tools::big_file::{BigFile, BigFileParameters, SearchMode};
@@ -13,7 +13,9 @@ use rayon::prelude::*;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_BIG_FILES, MAX_STR_DATA_BIG_FILES, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_big_files(a: Weak<MainWindow>, sd: ScanData) {
@@ -25,7 +27,7 @@ pub(crate) fn scan_big_files(a: Weak<MainWindow>, sd: ScanData) {
             let mut tool = BigFile::new(params);
 
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let mut vector = tool.get_big_files().clone();
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
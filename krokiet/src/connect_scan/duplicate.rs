@@ -4,16 +4,18 @@ use std::thread;
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::model::CheckingMethod;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path, split_path_compare};
 use czkawka_core::tools::duplicate;
-use czkawka_core::tools::duplicate::{DuplicateEntry, DuplicateFinder, DuplicateFinderParameters};
+use czkawka_core::tools::duplicate::{DEFAULT_PARTIAL_HASH_SEGMENTS, DuplicateEntry, DuplicateFinder, DuplicateFinderParameters};
 use humansize::{BINARY, format_size};
 use rayon::prelude::*;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_DUPLICATE_FILES, MAX_STR_DATA_DUPLICATE_FILES, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_duplicates(a: Weak<MainWindow>, sd: ScanData) {
@@ -30,17 +32,23 @@ pub(crate) fn scan_duplicates(a: Weak<MainWindow>, sd: ScanData) {
                 sd.custom_settings.duplicate_minimal_hash_cache_size as u64,
                 sd.custom_settings.duplicate_minimal_prehash_cache_size as u64,
                 sd.custom_settings.duplicates_sub_name_case_sensitive,
+                false, // Scan snapshots are mostly useful for repeated CLI scans
+                sd.custom_settings.duplicates_sub_verify_byte_by_byte,
+                None,
+                false,
+                DEFAULT_PARTIAL_HASH_SEGMENTS,
+                false,
             );
             let mut tool = DuplicateFinder::new(params);
 
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
 
             let mut vector;
             if tool.get_use_reference() {
                 match tool.get_params().check_method {
-                    CheckingMethod::Hash => {
+                    CheckingMethod::Hash | CheckingMethod::PartialHash => {
                         vector = tool
                             .get_files_with_identical_hashes_referenced()
                             .values()
@@ -62,7 +70,7 @@ pub(crate) fn scan_duplicates(a: Weak<MainWindow>, sd: ScanData) {
                 }
             } else {
                 match tool.get_params().check_method {
-                    CheckingMethod::Hash => {
+                    CheckingMethod::Hash | CheckingMethod::PartialHash => {
                         vector = tool.get_files_sorted_by_hash().values().flatten().cloned().map(|items| (None, items)).collect::<Vec<_>>();
                     }
                     CheckingMethod::Name | CheckingMethod::Size | CheckingMethod::SizeName => {
@@ -84,8 +92,9 @@ pub(crate) fn scan_duplicates(a: Weak<MainWindow>, sd: ScanData) {
 
             let info = tool.get_information();
             let stopped_search = tool.get_stopped_search();
+            let probable_duplicates = tool.get_check_method() == CheckingMethod::PartialHash;
             let (duplicates_number, groups_number, lost_space) = match tool.get_check_method() {
-                CheckingMethod::Hash => (info.number_of_duplicated_files_by_hash, info.number_of_groups_by_hash, info.lost_space_by_hash),
+                CheckingMethod::Hash | CheckingMethod::PartialHash => (info.number_of_duplicated_files_by_hash, info.number_of_groups_by_hash, info.lost_space_by_hash),
                 CheckingMethod::Name => (info.number_of_duplicated_files_by_name, info.number_of_groups_by_name, 0),
                 CheckingMethod::Size => (info.number_of_duplicated_files_by_size, info.number_of_groups_by_size, info.lost_space_by_size),
                 CheckingMethod::SizeName => (info.number_of_duplicated_files_by_size_name, info.number_of_groups_by_size_name, info.lost_space_by_size),
@@ -96,7 +105,18 @@ pub(crate) fn scan_duplicates(a: Weak<MainWindow>, sd: ScanData) {
             let messages_data = MessagesData { critical, messages };
 
             a.upgrade_in_event_loop(move |app| {
-                write_duplicate_results(&app, vector, messages_data, info, sd, stopped_search, duplicates_number, groups_number, lost_space);
+                write_duplicate_results(
+                    &app,
+                    vector,
+                    messages_data,
+                    info,
+                    sd,
+                    stopped_search,
+                    probable_duplicates,
+                    duplicates_number,
+                    groups_number,
+                    lost_space,
+                );
             })
         })
         .expect("Cannot start thread - not much we can do here");
@@ -108,6 +128,7 @@ fn write_duplicate_results(
     info: duplicate::Info,
     sd: ScanData,
     stopped_search: bool,
+    probable_duplicates: bool,
     items_found: usize,
     groups: usize,
     lost_space: u64,
@@ -129,13 +150,25 @@ fn write_duplicate_results(
         }
     }
     app.set_duplicate_files_model(items.into());
+    app.global::<GuiState>().set_probable_duplicates(probable_duplicates);
     if let Some(critical) = messages_data.critical {
         app.invoke_scan_ended(critical.into());
     } else {
         if !stopped_search && sd.basic_settings.play_audio_on_scan_completion {
             sd.audio_player.play_scan_completed();
         }
-        if lost_space > 0 {
+        if probable_duplicates && lost_space > 0 {
+            app.invoke_scan_ended(
+                flk!(
+                    "rust_found_probable_duplicate_files",
+                    items_found = items_found,
+                    groups = groups,
+                    size = format_size(lost_space, BINARY),
+                    time = scanning_time_str
+                )
+                .into(),
+            );
+        } else if lost_space > 0 {
             app.invoke_scan_ended(
                 flk!(
                     "rust_found_duplicate_files",
@@ -167,7 +200,12 @@ fn prepare_data_model_duplicates(fe: DuplicateEntry) -> (ModelRc<SharedString>,
         format_size(fe.size, BINARY).into(),
         file.into(),
         directory.into(),
-        get_dt_timestamp_string(fe.get_modified_date()).into(),
+        // Reference index doesn't store modification date
+        if fe.from_reference_index {
+            flk!("rust_already_archived").into()
+        } else {
+            get_dt_timestamp_string(fe.get_modified_date()).into()
+        },
     ];
     let data_model_str = VecModel::from_slice(&data_model_str_arr);
     let modification_split = split_u64_into_i32s(fe.get_modified_date());
@@ -4,7 +4,7 @@ use std::thread;
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::model::FileEntry;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path, split_path_compare};
 use czkawka_core::tools::empty_files;
 use czkawka_core::tools::empty_files::EmptyFiles;
@@ -12,7 +12,9 @@ use rayon::prelude::*;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_EMPTY_FILES, MAX_STR_DATA_EMPTY_FILES, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_empty_files(a: Weak<MainWindow>, sd: ScanData) {
@@ -21,7 +23,7 @@ pub(crate) fn scan_empty_files(a: Weak<MainWindow>, sd: ScanData) {
         .spawn(move || {
             let mut tool = EmptyFiles::new();
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let mut vector = tool.get_empty_files().clone();
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
@@ -3,16 +3,18 @@ use std::thread;
 
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path};
 use czkawka_core::tools::same_music;
-use czkawka_core::tools::same_music::core::format_audio_duration;
+use czkawka_core::tools::same_music::core::{format_audio_duration, format_matched_segment};
 use czkawka_core::tools::same_music::{MusicEntry, MusicSimilarity, SameMusic, SameMusicParameters};
 use humansize::{BINARY, format_size};
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_SIMILAR_MUSIC, MAX_STR_DATA_SIMILAR_MUSIC, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_similar_music(a: Weak<MainWindow>, sd: ScanData) {
@@ -46,11 +48,12 @@ pub(crate) fn scan_similar_music(a: Weak<MainWindow>, sd: ScanData) {
                 sd.custom_settings.similar_music_sub_minimal_fragment_duration_value,
                 sd.custom_settings.similar_music_sub_maximum_difference_value as f64,
                 sd.custom_settings.similar_music_compare_fingerprints_only_with_similar_titles,
+                0.0,
             );
             let mut tool = SameMusic::new(params);
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
 
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
 
@@ -134,11 +137,22 @@ fn prepare_data_model_similar_music(fe: MusicEntry) -> (ModelRc<SharedString>, M
         fe.genre.clone().into(),
         directory.into(),
         get_dt_timestamp_string(fe.get_modified_date()).into(),
+        fe.matched_segment.as_ref().map(format_matched_segment).unwrap_or_default().into(),
     ];
     let data_model_str = VecModel::from_slice(&data_model_str_arr);
     let modification_split = split_u64_into_i32s(fe.get_modified_date());
     let size_split = split_u64_into_i32s(fe.size);
-    let data_model_int_arr: [i32; MAX_INT_DATA_SIMILAR_MUSIC] = [modification_split.0, modification_split.1, size_split.0, size_split.1, fe.bitrate as i32, fe.length as i32];
+    // Coverage is sorted with 0.01% precision
+    let coverage = fe.matched_segment.map_or(-1, |matched_segment| (matched_segment.coverage * 100.0) as i32);
+    let data_model_int_arr: [i32; MAX_INT_DATA_SIMILAR_MUSIC] = [
+        modification_split.0,
+        modification_split.1,
+        size_split.0,
+        size_split.1,
+        fe.bitrate as i32,
+        fe.length as i32,
+        coverage,
+    ];
     let data_model_int = VecModel::from_slice(&data_model_int_arr);
     (data_model_str, data_model_int)
 }
@@ -3,17 +3,19 @@ use std::thread;
 
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path};
 use czkawka_core::tools::similar_images;
 use czkawka_core::tools::similar_images::core::get_string_from_similarity;
-use czkawka_core::tools::similar_images::{ImagesEntry, SimilarImages, SimilarImagesParameters};
+use czkawka_core::tools::similar_images::{DEFAULT_MATCH_CROPS_MAX_IMAGES, ImagesEntry, SimilarImages, SimilarImagesParameters};
 use humansize::{BINARY, format_size};
 use rayon::prelude::*;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_SIMILAR_IMAGES, MAX_STR_DATA_SIMILAR_IMAGES, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_similar_images(a: Weak<MainWindow>, sd: ScanData) {
@@ -28,18 +30,27 @@ pub(crate) fn scan_similar_images(a: Weak<MainWindow>, sd: ScanData) {
                 .parse()
                 .unwrap_or_else(|_| panic!("Cannot parse hash size {}", sd.custom_settings.similar_images_sub_hash_size));
 
-            let params = SimilarImagesParameters::new(
-                sd.custom_settings.similar_images_sub_similarity as u32,
-                hash_size,
-                hash_alg,
-                resize_algorithm,
-                sd.custom_settings.similar_images_sub_ignore_same_size,
-            );
+            let params = SimilarImagesParameters {
+                calculate_quality: sd.custom_settings.similar_images_sub_calculate_quality,
+                ..SimilarImagesParameters::new(
+                    sd.custom_settings.similar_images_sub_similarity as u32,
+                    hash_size,
+                    hash_alg,
+                    resize_algorithm,
+                    sd.custom_settings.similar_images_sub_ignore_same_size,
+                    sd.custom_settings.similar_images_sub_check_transforms,
+                    // Query images are compared only by hash
+                    sd.custom_settings.similar_images_sub_match_crops && sd.query_images.is_empty(),
+                    DEFAULT_MATCH_CROPS_MAX_IMAGES,
+                    sd.custom_settings.similar_images_sub_series_interval,
+                )
+            };
             let mut tool = SimilarImages::new(params);
+            tool.set_query_images(sd.query_images.clone());
 
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
 
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
 
@@ -53,8 +64,11 @@ pub(crate) fn scan_similar_images(a: Weak<MainWindow>, sd: ScanData) {
                 tool.get_similar_images().iter().cloned().map(|items| (None, items)).collect()
             };
 
-            for (_first_entry, vec_fe) in &mut vector {
-                vec_fe.par_sort_unstable_by_key(|e| (e.difference, u64::MAX - e.size));
+            // Photo series are already sorted from the sharpest photo
+            if sd.custom_settings.similar_images_sub_series_interval == 0 {
+                for (_first_entry, vec_fe) in &mut vector {
+                    vec_fe.par_sort_unstable_by_key(|e| (e.difference, u64::MAX - e.size));
+                }
             }
             vector.sort_by_key(|(_header, vc)| u64::MAX - vc.iter().map(|e| e.size).sum::<u64>()); // Also sorts by size, to show the biggest groups first
 
@@ -120,6 +134,9 @@ fn prepare_data_model_similar_images(fe: ImagesEntry, hash_size: u8) -> (ModelRc
         file.into(),
         directory.into(),
         get_dt_timestamp_string(fe.get_modified_date()).into(),
+        fe.sharpness.map_or_else(String::new, |sharpness| format!("{sharpness:.1}")).into(),
+        fe.jpeg_quality.map_or_else(String::new, |jpeg_quality| jpeg_quality.to_string()).into(),
+        if fe.upscaled { flk!("rust_image_upscaled") } else { String::new() }.into(),
     ];
     let data_model_str = VecModel::from_slice(&data_model_str_arr);
     let modification_split = split_u64_into_i32s(fe.get_modified_date());
@@ -132,6 +149,9 @@ fn prepare_data_model_similar_images(fe: ImagesEntry, hash_size: u8) -> (ModelRc
         fe.width as i32,
         fe.height as i32,
         (fe.width as u64 * fe.height as u64) as i32, // Limited to 2000MP, but using u64, because in cache it can exceed i32
+        fe.sharpness.unwrap_or_default().round() as i32,
+        fe.jpeg_quality.map_or(0, i32::from),
+        fe.get_quality_score().map_or(-1, |quality_score| quality_score.round() as i32), // -1 when metrics were not calculated
     ];
     let data_model_int = VecModel::from_slice(&data_model_int_arr);
     (data_model_str, data_model_int)
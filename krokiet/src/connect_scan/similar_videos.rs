@@ -3,7 +3,7 @@ use std::thread;
 
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::{ResultEntry, Search};
+use czkawka_core::common::traits::ResultEntry;
 use czkawka_core::common::{format_time, split_path, split_path_compare};
 use czkawka_core::tools::similar_videos;
 use czkawka_core::tools::similar_videos::core::{format_bitrate_opt, format_duration_opt};
@@ -13,7 +13,9 @@ use rayon::prelude::*;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_SIMILAR_VIDEOS, MAX_STR_DATA_SIMILAR_VIDEOS, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_similar_videos(a: Weak<MainWindow>, sd: ScanData) {
@@ -34,7 +36,7 @@ pub(crate) fn scan_similar_videos(a: Weak<MainWindow>, sd: ScanData) {
             let mut tool = SimilarVideos::new(params);
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
 
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
 
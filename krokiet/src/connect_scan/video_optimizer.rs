@@ -3,7 +3,6 @@ use std::thread;
 
 use czkawka_core::common::consts::DEFAULT_THREAD_SIZE;
 use czkawka_core::common::tool_data::CommonData;
-use czkawka_core::common::traits::Search;
 use czkawka_core::common::{format_time, split_path};
 use czkawka_core::tools::video_optimizer;
 use czkawka_core::tools::video_optimizer::{
@@ -14,7 +13,9 @@ use log::error;
 use slint::{ComponentHandle, ModelRc, SharedString, VecModel, Weak};
 
 use crate::common::{MAX_INT_DATA_VIDEO_OPTIMIZER, MAX_STR_DATA_VIDEO_OPTIMIZER, split_u64_into_i32s};
-use crate::connect_scan::{MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, set_common_settings};
+use crate::connect_scan::{
+    MessagesData, ScanData, get_dt_timestamp_string, get_text_messages, insert_data_to_model, reset_selection_at_end, search_or_import, set_common_settings,
+};
 use crate::{ActiveTab, GuiState, MainWindow, flk};
 
 pub(crate) fn scan_video_optimizer(a: Weak<MainWindow>, sd: ScanData) {
@@ -59,7 +60,7 @@ pub(crate) fn scan_video_optimizer(a: Weak<MainWindow>, sd: ScanData) {
             let mut tool = VideoOptimizer::new(params);
             set_common_settings(&mut tool, &sd.custom_settings, &sd.stop_flag);
 
-            tool.search(&sd.stop_flag, Some(&sd.progress_sender));
+            search_or_import(&mut tool, &sd);
 
             let (critical, messages) = get_text_messages(&tool, &sd.basic_settings);
 
@@ -1,9 +1,13 @@
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+
+use czkawka_core::common::keep_rules::{KeepRule, KeepRules};
+use czkawka_core::common::traits::ResultEntry;
 use slint::{ComponentHandle, Model, ModelRc, VecModel};
 
 use crate::common::connect_i32_into_u64;
 use crate::connect_row_selection::checker::change_number_of_enabled_items;
 use crate::connect_translation::translate_select_mode;
-use crate::{ActiveTab, Callabler, GuiState, MainWindow, SelectMode, SelectModel, SingleMainListModel};
+use crate::{ActiveTab, Callabler, GuiState, MainWindow, SelectMode, SelectModel, Settings, SingleMainListModel, flk};
 
 type SelectionResult = (u64, u64, ModelRc<SingleMainListModel>);
 
@@ -30,6 +34,36 @@ pub(crate) fn connect_select(app: &MainWindow) {
             SelectMode::SelectOldest => select_by_property(&current_model, active_tab, Property::Date, false),
             SelectMode::SelectShortestPath => select_by_property(&current_model, active_tab, Property::PathLength, false),
             SelectMode::SelectLongestPath => select_by_property(&current_model, active_tab, Property::PathLength, true),
+            SelectMode::SelectAllExceptKeptByRules => {
+                let keep_rules = match app.global::<Settings>().get_keep_rules().parse::<KeepRules>() {
+                    Ok(keep_rules) if !keep_rules.is_empty() => keep_rules,
+                    Ok(_) => {
+                        app.global::<GuiState>().set_info_text(flk!("rust_keep_rules_empty").into());
+                        return;
+                    }
+                    Err(e) => {
+                        app.global::<GuiState>().set_info_text(flk!("rust_keep_rules_invalid", reason = e).into());
+                        return;
+                    }
+                };
+                if let Some(rule) = keep_rules.get_rules().iter().find(|rule| !is_keep_rule_supported(rule, active_tab)) {
+                    app.global::<GuiState>()
+                        .set_info_text(flk!("rust_keep_rule_not_supported", rule = format!("{rule:?}")).into());
+                    return;
+                }
+                if keep_rules.get_rules().iter().any(KeepRule::works_only_with_similar_images) && !has_quality_metrics(&current_model, active_tab) {
+                    app.global::<GuiState>().set_info_text(flk!("rust_image_quality_not_calculated").into());
+                    return;
+                }
+                select_all_except_kept_by_rules(&current_model, active_tab, &keep_rules)
+            }
+            SelectMode::SelectAllExceptHighestQuality => {
+                if !has_quality_metrics(&current_model, active_tab) {
+                    app.global::<GuiState>().set_info_text(flk!("rust_image_quality_not_calculated").into());
+                    return;
+                }
+                select_all_except_kept_by_rules(&current_model, active_tab, &KeepRules::new(vec![KeepRule::HighestQuality]))
+            }
         };
         active_tab.set_tool_model(&app, new_model);
         change_number_of_enabled_items(&app, active_tab, checked_items as i64 - unchecked_items as i64);
@@ -56,6 +90,7 @@ pub(crate) fn set_select_buttons(app: &MainWindow) {
             SelectMode::SelectTheBiggestSize,
             SelectMode::SelectShortestPath,
             SelectMode::SelectLongestPath,
+            SelectMode::SelectAllExceptKeptByRules,
         ],
         ActiveTab::SimilarImages => vec![
             SelectMode::SelectOldest,
@@ -66,6 +101,8 @@ pub(crate) fn set_select_buttons(app: &MainWindow) {
             SelectMode::SelectTheBiggestResolution,
             SelectMode::SelectShortestPath,
             SelectMode::SelectLongestPath,
+            SelectMode::SelectAllExceptHighestQuality,
+            SelectMode::SelectAllExceptKeptByRules,
         ],
         ActiveTab::EmptyFolders
         | ActiveTab::BigFiles
@@ -161,6 +198,118 @@ fn select_by_property(model: &ModelRc<SingleMainListModel>, active_tab: ActiveTa
     (checked_items, 0, ModelRc::new(VecModel::from(old_data)))
 }
 
+// Only properties visible in model are available, rules which need other ones are rejected by is_keep_rule_supported
+struct RowEntry {
+    row_idx: usize,
+    path: PathBuf,
+    size: u64,
+    modified_date: u64,
+    bitrate: Option<u64>,
+    sharpness: Option<f32>,
+    quality_score: Option<f64>,
+}
+
+impl ResultEntry for RowEntry {
+    fn get_path(&self) -> &Path {
+        &self.path
+    }
+    fn get_modified_date(&self) -> u64 {
+        self.modified_date
+    }
+    fn get_size(&self) -> u64 {
+        self.size
+    }
+    fn get_bitrate(&self) -> Option<u64> {
+        self.bitrate
+    }
+    fn get_sharpness(&self) -> Option<f32> {
+        self.sharpness
+    }
+    fn get_quality_score(&self) -> Option<f64> {
+        self.quality_score
+    }
+}
+
+// Rules using values missing in rows of given tab would treat all items as equal and keep arbitrary one
+fn is_keep_rule_supported(rule: &KeepRule, active_tab: ActiveTab) -> bool {
+    match rule {
+        KeepRule::HighestBitrate => active_tab.get_int_bitrate_opt_idx().is_some(),
+        KeepRule::Sharpest | KeepRule::HighestQuality => active_tab.get_int_quality_score_opt_idx().is_some(),
+        _ => true,
+    }
+}
+
+fn bitrate_of_row(row: &SingleMainListModel, active_tab: ActiveTab) -> Option<u64> {
+    let idx = active_tab.get_int_bitrate_opt_idx()?;
+    let high = row.val_int.row_data(idx)?;
+    if active_tab == ActiveTab::SimilarVideos {
+        let low = row.val_int.row_data(idx + 1)?;
+        Some(connect_i32_into_u64(high, low))
+    } else {
+        Some(high as u64)
+    }
+}
+
+// Negative score is set when quality metrics were not calculated during scan
+fn quality_score_of_row(row: &SingleMainListModel, active_tab: ActiveTab) -> Option<f64> {
+    active_tab
+        .get_int_quality_score_opt_idx()
+        .and_then(|idx| row.val_int.row_data(idx))
+        .filter(|quality_score| *quality_score >= 0)
+        .map(f64::from)
+}
+
+fn has_quality_metrics(model: &ModelRc<SingleMainListModel>, active_tab: ActiveTab) -> bool {
+    model.iter().any(|row| !row.header_row && quality_score_of_row(&row, active_tab).is_some())
+}
+
+fn select_all_except_kept_by_rules(model: &ModelRc<SingleMainListModel>, active_tab: ActiveTab, keep_rules: &KeepRules) -> SelectionResult {
+    let mut checked_items = 0;
+
+    let is_header_mode = active_tab.get_is_header_mode();
+    assert!(is_header_mode); // non header modes not really have reason to use this function
+
+    let mut old_data = model.iter().collect::<Vec<_>>();
+    let headers_idx = find_header_idx_and_deselect_all(&mut old_data);
+    let path_idx = active_tab.get_str_path_idx();
+    let name_idx = active_tab.get_str_name_idx();
+
+    for group_idx in headers_idx.windows(2) {
+        let (start, end) = (group_idx[0] + 1, group_idx[1]);
+        let row_entries = (start..end)
+            .map(|row_idx| {
+                let row = &old_data[row_idx];
+                let path = row.val_str.row_data(path_idx).expect("can find file path property");
+                let name = row.val_str.row_data(name_idx).expect("can find file name property");
+                RowEntry {
+                    row_idx,
+                    path: PathBuf::from(format!("{path}{MAIN_SEPARATOR}{name}")),
+                    size: extract_comparable_field(row, Property::Size, active_tab),
+                    modified_date: extract_comparable_field(row, Property::Date, active_tab),
+                    bitrate: bitrate_of_row(row, active_tab),
+                    sharpness: quality_score_of_row(row, active_tab).and(
+                        active_tab
+                            .get_int_sharpness_opt_idx()
+                            .and_then(|idx| row.val_int.row_data(idx))
+                            .map(|sharpness| sharpness as f32),
+                    ),
+                    quality_score: quality_score_of_row(row, active_tab),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // First item is kept, so all others are selected
+        for row_entry in keep_rules.rank(row_entries).into_iter().skip(1) {
+            if !old_data[row_entry.row_idx].checked {
+                checked_items += 1;
+            }
+            old_data[row_entry.row_idx].checked = true;
+        }
+    }
+
+    (checked_items, 0, ModelRc::new(VecModel::from(old_data)))
+}
+
 fn select_all(model: &ModelRc<SingleMainListModel>) -> SelectionResult {
     let mut checked_items = 0;
     let mut old_data = model.iter().collect::<Vec<_>>();
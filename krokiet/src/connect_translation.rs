@@ -192,6 +192,7 @@ fn translate_items(app: &MainWindow) {
     translation.set_main_window_title_text(flk!("main_window_title").into());
     translation.set_scan_button_text(flk!("scan_button").into());
     translation.set_stop_button_text(flk!("stop_button").into());
+    translation.set_open_results_button_text(flk!("open_results_button").into());
     translation.set_select_button_text(flk!("select_button").into());
     translation.set_move_button_text(flk!("move_button").into());
     translation.set_delete_button_text(flk!("delete_button").into());
@@ -236,10 +237,18 @@ fn translate_items(app: &MainWindow) {
     translation.set_subsettings_images_hash_size_text(flk!("subsettings_images_hash_size").into());
     translation.set_subsettings_images_resize_algorithm_text(flk!("subsettings_images_resize_algorithm").into());
     translation.set_subsettings_images_ignore_same_size_text(flk!("subsettings_images_ignore_same_size").into());
+    translation.set_subsettings_images_check_transforms_text(flk!("subsettings_images_check_transforms").into());
+    translation.set_subsettings_images_calculate_quality_text(flk!("subsettings_images_calculate_quality").into());
+    translation.set_subsettings_images_match_crops_text(flk!("subsettings_images_match_crops").into());
+    translation.set_subsettings_images_query_images_text(flk!("subsettings_images_query_images").into());
+    translation.set_subsettings_images_query_images_hint_text(flk!("subsettings_images_query_images_hint").into());
     translation.set_subsettings_images_max_difference_text(flk!("subsettings_images_max_difference").into());
+    translation.set_subsettings_images_series_interval_text(flk!("subsettings_images_series_interval").into());
+    translation.set_subsettings_images_series_interval_hint_text(flk!("subsettings_images_series_interval_hint").into());
     translation.set_subsettings_images_duplicates_hash_type_text(flk!("subsettings_images_duplicates_hash_type").into());
     translation.set_subsettings_duplicates_check_method_text(flk!("subsettings_duplicates_check_method").into());
     translation.set_subsettings_duplicates_name_case_sensitive_text(flk!("subsettings_duplicates_name_case_sensitive").into());
+    translation.set_subsettings_duplicates_verify_byte_by_byte_text(flk!("subsettings_duplicates_verify_byte_by_byte").into());
     translation.set_subsettings_biggest_files_sub_method_text(flk!("subsettings_biggest_files_sub_method").into());
     translation.set_subsettings_biggest_files_sub_number_of_files_text(flk!("subsettings_biggest_files_sub_number_of_files").into());
     translation.set_subsettings_videos_max_difference_text(flk!("subsettings_videos_max_difference").into());
@@ -316,6 +325,7 @@ fn translate_items(app: &MainWindow) {
     translation.set_move_confirmation_text(flk!("move_confirmation_text").into());
     translation.set_rename_confirmation_text(flk!("rename_confirmation_text").into());
     translation.set_settings_excluded_items_text(flk!("settings_excluded_items").into());
+    translation.set_settings_keep_rules_text(flk!("settings_keep_rules").into());
     translation.set_settings_allowed_extensions_text(flk!("settings_allowed_extensions").into());
     translation.set_settings_excluded_extensions_text(flk!("settings_excluded_extensions").into());
     translation.set_settings_file_size_text(flk!("settings_file_size").into());
@@ -351,6 +361,8 @@ fn translate_items(app: &MainWindow) {
     translation.set_settings_save_text(flk!("settings_save").into());
     translation.set_settings_load_text(flk!("settings_load").into());
     translation.set_settings_reset_text(flk!("settings_reset").into());
+    translation.set_settings_export_text(flk!("settings_export").into());
+    translation.set_settings_import_text(flk!("settings_import").into());
     translation.set_settings_similar_videos_tool_text(flk!("settings_similar_videos_tool").into());
     translation.set_settings_video_thumbnails_clear_unused_thumbnails_text(flk!("settings_video_thumbnails_clear_unused_thumbnails").into());
     translation.set_settings_video_thumbnails_header_text(flk!("settings_video_thumbnails_header").into());
@@ -392,6 +404,10 @@ fn translate_items(app: &MainWindow) {
     translation.set_stop_text(flk!("stop_text").into());
     translation.set_settings_cache_header_text(flk!("settings_cache_header_text").into());
     translation.set_settings_clean_cache_button_text(flk!("settings_clean_cache_button_text").into());
+    translation.set_settings_journal_header_text(flk!("settings_journal_header_text").into());
+    translation.set_settings_journal_refresh_button_text(flk!("settings_journal_refresh_button_text").into());
+    translation.set_settings_journal_revert_button_text(flk!("settings_journal_revert_button_text").into());
+    translation.set_settings_journal_hint_text(flk!("settings_journal_hint_text").into());
     translation.set_popup_clean_cache_title_text(flk!("popup_clean_cache_title_text").into());
     translation.set_popup_clean_cache_confirmation_text(flk!("popup_clean_cache_confirmation_text").into());
     translation.set_popup_clean_cache_progress_text(flk!("popup_clean_cache_progress_text").into());
@@ -464,6 +480,10 @@ fn translate_items(app: &MainWindow) {
     let exif_tags = flk!("column_exif_tags");
     let new_dimensions = flk!("column_new_dimensions");
     let new_name = flk!("column_new_name");
+    let matched_segment = flk!("column_matched_segment");
+    let sharpness = flk!("column_sharpness");
+    let jpeg_quality = flk!("column_jpeg_quality");
+    let upscaled = flk!("column_upscaled");
 
     let fnm = |model: &[&str]| {
         let shared_string = model.iter().map(|s| (*s).into()).collect::<Vec<SharedString>>();
@@ -475,9 +495,33 @@ fn translate_items(app: &MainWindow) {
     settings.set_empty_files_column_name(fnm(&[&selection, &file_name, &path, &mod_date]));
     settings.set_temporary_files_column_name(fnm(&[&selection, &file_name, &path, &mod_date]));
     settings.set_big_files_column_name(fnm(&[&selection, &size, &file_name, &path, &mod_date]));
-    settings.set_similar_images_column_name(fnm(&[&selection, &similarity, &size, &dimensions, &file_name, &path, &mod_date]));
+    settings.set_similar_images_column_name(fnm(&[
+        &selection,
+        &similarity,
+        &size,
+        &dimensions,
+        &file_name,
+        &path,
+        &mod_date,
+        &sharpness,
+        &jpeg_quality,
+        &upscaled,
+    ]));
     settings.set_similar_videos_column_name(fnm(&[&selection, &size, &file_name, &path, &dimensions, &duration, &bitrate, &fps, &codec, &mod_date]));
-    settings.set_similar_music_column_name(fnm(&[&selection, &size, &file_name, &title, &artist, &year, &bitrate, &length, &genre, &path, &mod_date]));
+    settings.set_similar_music_column_name(fnm(&[
+        &selection,
+        &size,
+        &file_name,
+        &title,
+        &artist,
+        &year,
+        &bitrate,
+        &length,
+        &genre,
+        &path,
+        &mod_date,
+        &matched_segment,
+    ]));
     settings.set_invalid_symlink_column_name(fnm(&[&selection, &symlink_name, &symlink_folder, &destination_path, &mod_date]));
     settings.set_broken_files_column_name(fnm(&[&selection, &file_name, &path, &type_of_error, &size, &mod_date]));
     settings.set_bad_extensions_column_name(fnm(&[&selection, &file_name, &path, &current_extension, &proper_extension]));
@@ -499,6 +543,8 @@ pub(crate) fn translate_select_mode(select_mode: SelectMode) -> SharedString {
         SelectMode::SelectOldest => flk!("selection_oldest").into(),
         SelectMode::SelectShortestPath => flk!("selection_shortest_path").into(),
         SelectMode::SelectLongestPath => flk!("selection_longest_path").into(),
+        SelectMode::SelectAllExceptKeptByRules => flk!("selection_all_except_kept_by_rules").into(),
+        SelectMode::SelectAllExceptHighestQuality => flk!("selection_all_except_highest_quality").into(),
     }
 }
 
@@ -1,9 +1,10 @@
-use std::path::MAIN_SEPARATOR;
+use std::path::{MAIN_SEPARATOR, Path};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::journal::{Journal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
 use slint::{ComponentHandle, Weak};
 
@@ -37,14 +38,23 @@ impl ModelProcessor {
             let name_idx = self.active_tab.get_str_name_idx();
             let tag_groups_idx = self.active_tab.get_exif_tag_groups_idx();
             let tag_u16_idx = self.active_tab.get_exif_tag_u16_idx();
+            // Without overriding, cleaned file is saved next to original one, so nothing is lost
+            let journal = if override_file {
+                Journal::new(&format!("{:?}", self.active_tab))
+            } else {
+                Journal::default()
+            };
 
             let clean_fnc = move |data: &SimplerSingleMainListModel| {
-                clean_exif_single_file(
-                    &format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]),
-                    &data.val_str[tag_groups_idx].split(',').map(|s| s.to_string()).collect::<Vec<_>>(),
-                    &data.val_str[tag_u16_idx].split(',').map(|s| s.to_string()).collect::<Vec<_>>(),
-                    override_file,
-                )
+                let full_path = format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]);
+                journal.record_operation(JournalAction::CleanExif, Path::new(&full_path), None, || {
+                    clean_exif_single_file(
+                        &full_path,
+                        &data.val_str[tag_groups_idx].split(',').map(|s| s.to_string()).collect::<Vec<_>>(),
+                        &data.val_str[tag_u16_idx].split(',').map(|s| s.to_string()).collect::<Vec<_>>(),
+                        override_file,
+                    )
+                })
             };
 
             self.process_and_update_gui_state(
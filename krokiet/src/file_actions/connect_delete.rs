@@ -1,12 +1,14 @@
-use std::path::MAIN_SEPARATOR;
+use std::path::{MAIN_SEPARATOR, Path};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::journal::{Journal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
 use slint::{ComponentHandle, Weak};
 
+use crate::file_actions::refuse_changing_probable_duplicates;
 use crate::model_operations::model_processor::{MessageType, ModelProcessor, ProcessFunction};
 use crate::simpler_model::{SimplerSingleMainListModel, ToSimplerVec};
 use crate::{ActiveTab, Callabler, GuiState, MainWindow, Settings};
@@ -20,6 +22,9 @@ pub(crate) fn connect_delete_button(app: &MainWindow, progress_sender: Sender<Pr
         stop_flag.store(false, Ordering::Relaxed);
         let app = a.upgrade().expect("Failed to upgrade app :(");
         let active_tab = app.global::<GuiState>().get_active_tab();
+        if refuse_changing_probable_duplicates(&app, active_tab) {
+            return;
+        }
         let settings = app.global::<Settings>();
 
         let processor = ModelProcessor::new(active_tab);
@@ -35,13 +40,14 @@ impl ModelProcessor {
         thread::spawn(move || {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
+            let journal = Journal::new(&format!("{:?}", self.active_tab));
+            let journal_action = if remove_to_trash { JournalAction::MoveToTrash } else { JournalAction::Delete };
 
             let dlt_fnc = move |data: &SimplerSingleMainListModel| {
-                remove_single_item(
-                    &format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]),
-                    is_empty_folder_tab,
-                    remove_to_trash,
-                )
+                let full_path = format!("{}{MAIN_SEPARATOR}{}", data.val_str[path_idx], data.val_str[name_idx]);
+                journal.record_operation(journal_action, Path::new(&full_path), None, || {
+                    remove_single_item(&full_path, is_empty_folder_tab, remove_to_trash)
+                })
             };
 
             self.process_and_update_gui_state(
@@ -4,6 +4,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::{fs, path, thread};
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::journal::{Journal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
 use slint::{ComponentHandle, Weak};
 
@@ -53,7 +54,9 @@ impl ModelProcessor {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
 
-            let mlt_fnc = move |data: &SimplerSingleMainListModel| move_single_item(data, path_idx, name_idx, &output_folder, preserve_structure, copy_mode);
+            let journal = Journal::new(&format!("{:?}", self.active_tab));
+
+            let mlt_fnc = move |data: &SimplerSingleMainListModel| move_single_item(data, path_idx, name_idx, &output_folder, preserve_structure, copy_mode, &journal);
 
             self.process_and_update_gui_state(
                 &weak_app,
@@ -68,7 +71,15 @@ impl ModelProcessor {
     }
 }
 
-fn move_single_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx: usize, output_folder: &str, preserve_structure: bool, copy_mode: bool) -> Result<(), String> {
+fn move_single_item(
+    data: &SimplerSingleMainListModel,
+    path_idx: usize,
+    name_idx: usize,
+    output_folder: &str,
+    preserve_structure: bool,
+    copy_mode: bool,
+    journal: &Journal,
+) -> Result<(), String> {
     let path = &data.val_str[path_idx];
     let name = &data.val_str[name_idx];
 
@@ -78,8 +89,10 @@ fn move_single_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx
     }
 
     if copy_mode {
-        try_to_copy_item(&input_file, &output_file)
-    } else {
+        return try_to_copy_item(&input_file, &output_file);
+    }
+
+    journal.record_operation(JournalAction::Move, &input_file, Some(&output_file), || {
         // Try to rename file, may fail due various reasons
         // It is the easiest way to move file, but only on same partition
         if fs::rename(&input_file, &output_file).is_ok() {
@@ -98,7 +111,7 @@ fn move_single_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx
             ));
         }
         Ok(())
-    }
+    })
 }
 
 // Tries to copy file/folder, and returns error if it fails
@@ -3,6 +3,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::journal::Journal;
 use czkawka_core::common::progress_data::ProgressData;
 use slint::{ComponentHandle, Weak};
 
@@ -42,8 +43,9 @@ impl ModelProcessor {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
             let ext_idx = self.active_tab.get_str_proper_extension();
+            let journal = Journal::new(&format!("{:?}", self.active_tab));
 
-            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_extension_item(data, path_idx, name_idx, ext_idx);
+            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_extension_item(data, path_idx, name_idx, ext_idx, &journal);
 
             self.process_and_update_gui_state(
                 &weak_app,
@@ -63,8 +65,9 @@ impl ModelProcessor {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
             let new_name_idx = StrDataBadNames::NewName as usize;
+            let journal = Journal::new(&format!("{:?}", self.active_tab));
 
-            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_file_name_item(data, path_idx, name_idx, new_name_idx);
+            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_file_name_item(data, path_idx, name_idx, new_name_idx, &journal);
 
             self.process_and_update_gui_state(
                 &weak_app,
@@ -80,7 +83,7 @@ impl ModelProcessor {
 }
 
 #[cfg(not(test))]
-fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx: usize, new_file_name_idx: usize) -> Result<(), String> {
+fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx: usize, new_file_name_idx: usize, journal: &Journal) -> Result<(), String> {
     use std::path::MAIN_SEPARATOR;
     let folder = &data.val_str[path_idx];
     let file_name = &data.val_str[name_idx];
@@ -89,7 +92,12 @@ fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usi
     let new_full_path = format!("{folder}{MAIN_SEPARATOR}{new_file_name}");
     let old_full_path = format!("{folder}{MAIN_SEPARATOR}{file_name}");
 
-    if let Err(e) = std::fs::rename(&old_full_path, &new_full_path) {
+    if let Err(e) = journal.record_operation(
+        czkawka_core::common::journal::JournalAction::Rename,
+        std::path::Path::new(&old_full_path),
+        Some(std::path::Path::new(&new_full_path)),
+        || std::fs::rename(&old_full_path, &new_full_path),
+    ) {
         Err(crate::flk!(
             "rust_failed_to_rename_file",
             old_path = old_full_path,
@@ -102,7 +110,7 @@ fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usi
 }
 
 #[cfg(not(test))]
-fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx: usize, ext_idx: usize) -> Result<(), String> {
+fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usize, name_idx: usize, ext_idx: usize, journal: &Journal) -> Result<(), String> {
     use std::path::MAIN_SEPARATOR;
     let folder = &data.val_str[path_idx];
     let file_name = &data.val_str[name_idx];
@@ -112,7 +120,12 @@ fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usi
     let new_full_path = format!("{folder}{MAIN_SEPARATOR}{file_stem}.{new_extension}");
     let old_full_path = format!("{folder}{MAIN_SEPARATOR}{file_name}");
 
-    if let Err(e) = std::fs::rename(&old_full_path, &new_full_path) {
+    if let Err(e) = journal.record_operation(
+        czkawka_core::common::journal::JournalAction::Rename,
+        std::path::Path::new(&old_full_path),
+        Some(std::path::Path::new(&new_full_path)),
+        || std::fs::rename(&old_full_path, &new_full_path),
+    ) {
         Err(crate::flk!(
             "rust_failed_to_rename_file",
             old_path = old_full_path,
@@ -125,7 +138,7 @@ fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usi
 }
 
 #[cfg(test)]
-fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usize, _name_idx: usize, _ext_idx: usize) -> Result<(), String> {
+fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usize, _name_idx: usize, _ext_idx: usize, _journal: &Journal) -> Result<(), String> {
     let full_path = &data.val_str[path_idx];
     if full_path.contains("test_error") {
         return Err(format!("Test error for item: {full_path}"));
@@ -134,7 +147,7 @@ fn rename_single_extension_item(data: &SimplerSingleMainListModel, path_idx: usi
 }
 
 #[cfg(test)]
-fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usize, _name_idx: usize, _file_name: usize) -> Result<(), String> {
+fn rename_single_file_name_item(data: &SimplerSingleMainListModel, path_idx: usize, _name_idx: usize, _file_name: usize, _journal: &Journal) -> Result<(), String> {
     let full_path = &data.val_str[path_idx];
     if full_path.contains("test_error") {
         return Err(format!("Test error for item: {full_path}"));
@@ -169,7 +182,7 @@ mod tests {
             let name_idx = 0;
             let ext_idx = 0;
 
-            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_extension_item(data, path_idx, name_idx, ext_idx);
+            let rm_fnc = move |data: &SimplerSingleMainListModel| rename_single_extension_item(data, path_idx, name_idx, ext_idx, &Journal::default());
 
             let output = Self::process_items(
                 simplified_model,
@@ -1,12 +1,14 @@
-use std::path::MAIN_SEPARATOR;
+use std::path::{MAIN_SEPARATOR, Path};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use crossbeam_channel::Sender;
+use czkawka_core::common::journal::{Journal, JournalAction};
 use czkawka_core::common::progress_data::ProgressData;
 use slint::{ComponentHandle, Weak};
 
+use crate::file_actions::refuse_changing_probable_duplicates;
 use crate::model_operations::model_processor::{MessageType, ModelProcessor, ProcessFunction};
 use crate::simpler_model::{SimplerSingleMainListModel, ToSimplerVec};
 use crate::{Callabler, GuiState, MainWindow};
@@ -20,6 +22,9 @@ pub(crate) fn connect_symlink(app: &MainWindow, progress_sender: Sender<Progress
         stop_flag.store(false, Ordering::Relaxed);
         let app = a.upgrade().expect("Failed to upgrade app :(");
         let active_tab = app.global::<GuiState>().get_active_tab();
+        if refuse_changing_probable_duplicates(&app, active_tab) {
+            return;
+        }
 
         let processor = ModelProcessor::new(active_tab);
         processor.symlink_selected_items(progress_sender, weak_app, stop_flag);
@@ -33,12 +38,14 @@ impl ModelProcessor {
         thread::spawn(move || {
             let path_idx = self.active_tab.get_str_path_idx();
             let name_idx = self.active_tab.get_str_name_idx();
+            let journal = Journal::new(&format!("{:?}", self.active_tab));
 
             let symlink_fnc = move |original: &SimplerSingleMainListModel, derived: &SimplerSingleMainListModel| {
-                symlink_single_item(
-                    &format!("{}{MAIN_SEPARATOR}{}", original.val_str[path_idx], original.val_str[name_idx]),
-                    &format!("{}{MAIN_SEPARATOR}{}", derived.val_str[path_idx], derived.val_str[name_idx]),
-                )
+                let original_path = format!("{}{MAIN_SEPARATOR}{}", original.val_str[path_idx], original.val_str[name_idx]);
+                let derived_path = format!("{}{MAIN_SEPARATOR}{}", derived.val_str[path_idx], derived.val_str[name_idx]);
+                journal.record_operation(JournalAction::Symlink, Path::new(&derived_path), Some(Path::new(&original_path)), || {
+                    symlink_single_item(&original_path, &derived_path)
+                })
             };
 
             self.process_and_update_gui_state(
@@ -5,3 +5,16 @@ pub mod connect_move;
 pub mod connect_optimize_video;
 pub mod connect_rename;
 pub mod connect_symlink;
+
+use slint::ComponentHandle;
+
+use crate::{ActiveTab, GuiState, MainWindow, flk};
+
+// Duplicates found by partial hash may differ in parts that were not hashed, so removing or linking them could lose data
+pub(crate) fn refuse_changing_probable_duplicates(app: &MainWindow, active_tab: ActiveTab) -> bool {
+    if active_tab == ActiveTab::DuplicateFiles && app.global::<GuiState>().get_probable_duplicates() {
+        app.global::<GuiState>().set_info_text(flk!("rust_probable_duplicates_cannot_be_changed").into());
+        return true;
+    }
+    false
+}
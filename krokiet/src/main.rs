@@ -27,6 +27,7 @@ use slint::VecModel;
 use crate::clear_outdated_video_thumbnails::clear_outdated_video_thumbnails;
 use crate::connect_clean_cache::connect_clean_cache;
 use crate::connect_directories_changes::connect_add_remove_directories;
+use crate::connect_journal::connect_journal;
 use crate::connect_open::connect_open_items;
 use crate::connect_progress_receiver::connect_progress_gathering;
 use crate::connect_row_selection::connect_row_selections;
@@ -53,6 +54,7 @@ mod clear_outdated_video_thumbnails;
 mod common;
 mod connect_clean_cache;
 mod connect_directories_changes;
+mod connect_journal;
 mod connect_open;
 mod connect_progress_receiver;
 mod connect_row_selection;
@@ -154,6 +156,7 @@ fn main() {
     connect_tab_changed(&app, task_sender.clone());
     create_calculate_task_size(task_receiver);
     connect_clean_cache(&app, task_sender);
+    connect_journal(&app);
     connect_show_confirmation(&app, Arc::clone(&shared_models));
 
     clear_outdated_video_thumbnails(&app);
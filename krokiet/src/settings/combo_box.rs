@@ -97,6 +97,7 @@ impl StringComboBoxItems {
 
         let duplicates_check_method = Self::convert_to_combobox_items(&[
             ("hash", "Hash", CheckingMethod::Hash),
+            ("partial_hash", "Partial Hash (probable duplicates)", CheckingMethod::PartialHash),
             ("size", "Size", CheckingMethod::Size),
             ("name", "Name", CheckingMethod::Name),
             ("size_and_name", "Size and Name", CheckingMethod::SizeName),
@@ -3,14 +3,17 @@ pub(crate) mod model;
 
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use czkawka_core::TOOLS_NUMBER;
 use czkawka_core::common::basic_gui_cli::CliResult;
 use czkawka_core::common::config_cache_path::get_config_cache_path;
+use czkawka_core::common::profile::{PROFILE_EXTENSION, get_profiles_folder};
 use czkawka_core::common::{get_all_available_threads, set_number_of_threads};
+use czkawka_core::tools::similar_images::MAX_SERIES_INTERVAL;
 use czkawka_core::tools::similar_videos::{ALLOWED_SKIP_FORWARD_AMOUNT, ALLOWED_VID_HASH_DURATION};
 use log::{debug, error, info};
+use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
 use slint::{ComponentHandle, Model, ModelRc, PhysicalSize, VecModel, WindowSize};
 
@@ -88,6 +91,70 @@ pub(crate) fn connect_changing_settings_preset(app: &MainWindow) {
             }
         }
     });
+    let a = app.as_weak();
+    app.global::<Callabler>().on_export_current_preset(move || {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+        let settings = app.global::<Settings>();
+        let current_item = settings.get_settings_preset_idx();
+        let preset_name = settings.get_settings_presets().row_data(current_item as usize).unwrap_or_default();
+
+        let mut file_dialog = FileDialog::new()
+            .add_filter("TOML", &[PROFILE_EXTENSION])
+            .set_file_name(format!("{preset_name}.{PROFILE_EXTENSION}"));
+        if let Some(profiles_folder) = get_profiles_folder() {
+            let _ = std::fs::create_dir_all(&profiles_folder);
+            file_dialog = file_dialog.set_directory(profiles_folder);
+        }
+        let Some(file) = file_dialog.save_file() else {
+            return;
+        };
+        match export_preset_to_file(&file, &collect_settings(&app)) {
+            Ok(()) => {
+                app.set_text_summary_text(flk!("rust_exported_preset", preset_idx = (current_item + 1), file = file.to_string_lossy().to_string()).into());
+            }
+            Err(e) => {
+                app.set_text_summary_text(flk!("rust_cannot_export_preset", preset_idx = (current_item + 1), reason = (&e)).into());
+                error!("Failed to export preset - {e}");
+            }
+        }
+    });
+    let a = app.as_weak();
+    app.global::<Callabler>().on_import_current_preset(move || {
+        let app = a.upgrade().expect("Failed to upgrade app :(");
+        let settings = app.global::<Settings>();
+        let current_item = settings.get_settings_preset_idx();
+
+        let mut file_dialog = FileDialog::new().add_filter("TOML", &[PROFILE_EXTENSION]);
+        if let Some(profiles_folder) = get_profiles_folder() {
+            file_dialog = file_dialog.set_directory(profiles_folder);
+        }
+        let Some(file) = file_dialog.pick_file() else {
+            return;
+        };
+        let base_settings = load_data_from_file::<BasicSettings>(get_base_config_file()).unwrap_or_default();
+        match import_preset_from_file(&file) {
+            Ok(imported_data) => {
+                set_settings_to_gui(&app, &imported_data, &base_settings, None);
+                app.set_text_summary_text(flk!("rust_imported_preset", preset_idx = (current_item + 1), file = file.to_string_lossy().to_string()).into());
+            }
+            Err(e) => {
+                app.set_text_summary_text(flk!("rust_cannot_import_preset", preset_idx = (current_item + 1), reason = (&e)).into());
+                error!("Failed to import preset - {e}");
+            }
+        }
+    });
+}
+
+// Exported presets use same format as CLI profiles, so they can be used with `--profile`
+fn export_preset_to_file(file: &Path, custom_settings: &SettingsCustom) -> Result<(), String> {
+    let serialized = toml::to_string_pretty(custom_settings).map_err(|e| format!("Cannot serialize settings: {e}"))?;
+    std::fs::write(file, serialized).map_err(|e| format!("Cannot save preset file \"{}\": {e}", file.to_string_lossy()))
+}
+
+// Missing fields get default values, so profiles written by hand for CLI can be imported too
+fn import_preset_from_file(file: &Path) -> Result<SettingsCustom, String> {
+    let content = std::fs::read_to_string(file).map_err(|e| format!("Cannot read preset file \"{}\": {e}", file.to_string_lossy()))?;
+    toml::from_str(&content).map_err(|e| format!("Cannot parse preset file \"{}\": {e}", file.to_string_lossy()))
 }
 
 pub(crate) fn create_default_settings_files() {
@@ -377,6 +444,7 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_excluded_paths_model(excluded_paths);
 
     settings.set_excluded_items(custom_settings.excluded_items.clone().into());
+    settings.set_keep_rules(custom_settings.keep_rules.clone().into());
     settings.set_allowed_extensions(custom_settings.allowed_extensions.clone().into());
     settings.set_excluded_extensions(custom_settings.excluded_extensions.clone().into());
     settings.set_minimum_file_size(custom_settings.minimum_file_size.to_string().into());
@@ -395,6 +463,7 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     settings.set_delete_outdated_cache_entries(custom_settings.delete_outdated_cache_entries);
     settings.set_hide_hard_links(custom_settings.hide_hard_links);
     settings.set_duplicates_sub_name_case_sensitive(custom_settings.duplicates_sub_name_case_sensitive);
+    settings.set_duplicates_sub_verify_byte_by_byte(custom_settings.duplicates_sub_verify_byte_by_byte);
     settings.set_similar_images_show_image_preview(custom_settings.similar_images_show_image_preview);
     settings.set_video_thumbnails_preview(custom_settings.video_thumbnails_preview);
     settings.set_video_thumbnails_unused_thumbnails(custom_settings.video_thumbnails_unused_thumbnails);
@@ -403,6 +472,11 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
     set_combobox_custom_settings_items(&settings, custom_settings);
 
     settings.set_similar_images_sub_ignore_same_size(custom_settings.similar_images_sub_ignore_same_size);
+    settings.set_similar_images_sub_check_transforms(custom_settings.similar_images_sub_check_transforms);
+    settings.set_similar_images_sub_match_crops(custom_settings.similar_images_sub_match_crops);
+    settings.set_similar_images_sub_calculate_quality(custom_settings.similar_images_sub_calculate_quality);
+    settings.set_similar_images_sub_series_interval(custom_settings.similar_images_sub_series_interval.min(MAX_SERIES_INTERVAL) as f32);
+    settings.set_similar_images_sub_series_interval_max(MAX_SERIES_INTERVAL as f32);
     settings.set_similar_images_sub_max_similarity(MAX_HASH_SIZE);
     settings.set_similar_images_sub_current_similarity(custom_settings.similar_images_sub_similarity as f32);
 
@@ -515,9 +589,12 @@ pub(crate) fn set_settings_to_gui(app: &MainWindow, custom_settings: &SettingsCu
         settings.set_empty_files_column_size(fnm(&[sel_px, name_px, path_px, mod_px], "empty_files"));
         settings.set_temporary_files_column_size(fnm(&[sel_px, name_px, path_px, mod_px], "temporary_files"));
         settings.set_big_files_column_size(fnm(&[sel_px, size_px, name_px, path_px, mod_px], "big_files"));
-        settings.set_similar_images_column_size(fnm(&[sel_px, 80.0, 80.0, 80.0, name_px, path_px, mod_px], "similar_images"));
+        settings.set_similar_images_column_size(fnm(&[sel_px, 80.0, 80.0, 80.0, name_px, path_px, mod_px, 80.0, 80.0, 80.0], "similar_images"));
         settings.set_similar_videos_column_size(fnm(&[sel_px, size_px, name_px, path_px, 80.0, 80.0, 80.0, 80.0, 80.0, mod_px], "similar_videos"));
-        settings.set_similar_music_column_size(fnm(&[sel_px, size_px, name_px, 80.0, 80.0, 80.0, 80.0, 80.0, 80.0, path_px, mod_px], "similar_music"));
+        settings.set_similar_music_column_size(fnm(
+            &[sel_px, size_px, name_px, 80.0, 80.0, 80.0, 80.0, 80.0, 80.0, path_px, mod_px, 150.0],
+            "similar_music",
+        ));
         settings.set_invalid_symlink_column_size(fnm(&[sel_px, name_px, path_px, path_px, mod_px], "invalid_symlink"));
         settings.set_broken_files_column_size(fnm(&[sel_px, name_px, path_px, 200.0, size_px, mod_px], "broken_files"));
         settings.set_bad_extensions_column_size(fnm(&[sel_px, name_px, path_px, 40.0, 200.0], "bad_extensions"));
@@ -547,6 +624,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let excluded_paths = excluded_paths_model.iter().map(|model| PathBuf::from(model.path.as_str())).collect::<Vec<_>>();
 
     let excluded_items = settings.get_excluded_items().to_string();
+    let keep_rules = settings.get_keep_rules().to_string();
     let allowed_extensions = settings.get_allowed_extensions().to_string();
     let excluded_extensions = settings.get_excluded_extensions().to_string();
     let minimum_file_size = settings.get_minimum_file_size().parse::<i32>().unwrap_or(DEFAULT_MINIMUM_SIZE_KB);
@@ -569,6 +647,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let delete_outdated_cache_entries = settings.get_delete_outdated_cache_entries();
     let hide_hard_links = settings.get_hide_hard_links();
     let duplicates_sub_name_case_sensitive = settings.get_duplicates_sub_name_case_sensitive();
+    let duplicates_sub_verify_byte_by_byte = settings.get_duplicates_sub_verify_byte_by_byte();
 
     let similar_images_show_image_preview = settings.get_similar_images_show_image_preview();
 
@@ -581,6 +660,10 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     let similar_images_sub_hash_alg = combo_box_items.image_hash_alg.config_name.clone();
     let similar_images_sub_resize_algorithm = combo_box_items.resize_algorithm.config_name.clone();
     let similar_images_sub_ignore_same_size = settings.get_similar_images_sub_ignore_same_size();
+    let similar_images_sub_check_transforms = settings.get_similar_images_sub_check_transforms();
+    let similar_images_sub_match_crops = settings.get_similar_images_sub_match_crops();
+    let similar_images_sub_calculate_quality = settings.get_similar_images_sub_calculate_quality();
+    let similar_images_sub_series_interval = settings.get_similar_images_sub_series_interval().round() as u32;
     let similar_images_sub_similarity = settings.get_similar_images_sub_current_similarity().round() as i32;
 
     let duplicates_sub_check_method = combo_box_items.duplicates_check_method.config_name.clone();
@@ -681,6 +764,7 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         included_paths_referenced,
         excluded_paths,
         excluded_items,
+        keep_rules,
         allowed_extensions,
         excluded_extensions,
         minimum_file_size,
@@ -704,10 +788,15 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
         similar_images_sub_hash_alg,
         similar_images_sub_resize_algorithm,
         similar_images_sub_ignore_same_size,
+        similar_images_sub_check_transforms,
+        similar_images_sub_match_crops,
+        similar_images_sub_calculate_quality,
+        similar_images_sub_series_interval,
         similar_images_sub_similarity,
         duplicates_sub_check_method,
         duplicates_sub_available_hash_type,
         duplicates_sub_name_case_sensitive,
+        duplicates_sub_verify_byte_by_byte,
         biggest_files_sub_method,
         biggest_files_sub_number_of_files,
         similar_videos_sub_ignore_same_size,
@@ -774,6 +863,11 @@ pub(crate) fn collect_settings(app: &MainWindow) -> SettingsCustom {
     }
 }
 
+pub(crate) fn collect_query_images(app: &MainWindow) -> Vec<PathBuf> {
+    let query_images_model = app.global::<Settings>().get_similar_images_sub_query_images_model();
+    query_images_model.iter().map(|model| PathBuf::from(model.path.as_str())).collect()
+}
+
 pub(crate) fn collect_combo_box_settings(app: &MainWindow) -> ComboBoxItems {
     let collected_combo_boxes = StringComboBoxItems::regenerate_items();
     let settings = app.global::<Settings>();
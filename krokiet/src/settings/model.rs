@@ -46,6 +46,8 @@ pub struct SettingsCustom {
     #[serde(default = "default_excluded_items")]
     pub excluded_items: String,
     #[serde(default)]
+    pub keep_rules: String,
+    #[serde(default)]
     pub allowed_extensions: String,
     #[serde(default)]
     pub excluded_extensions: String,
@@ -91,6 +93,14 @@ pub struct SettingsCustom {
     pub similar_images_sub_resize_algorithm: String,
     #[serde(default)]
     pub similar_images_sub_ignore_same_size: bool,
+    #[serde(default)]
+    pub similar_images_sub_check_transforms: bool,
+    #[serde(default)]
+    pub similar_images_sub_match_crops: bool,
+    #[serde(default)]
+    pub similar_images_sub_calculate_quality: bool,
+    #[serde(default)]
+    pub similar_images_sub_series_interval: u32,
     #[serde(default = "default_image_similarity")]
     pub similar_images_sub_similarity: i32,
     #[serde(default = "default_duplicates_check_method")]
@@ -99,6 +109,8 @@ pub struct SettingsCustom {
     pub duplicates_sub_available_hash_type: String,
     #[serde(default)]
     pub duplicates_sub_name_case_sensitive: bool,
+    #[serde(default)]
+    pub duplicates_sub_verify_byte_by_byte: bool,
     #[serde(default = "default_biggest_method")]
     pub biggest_files_sub_method: String,
     #[serde(default = "default_biggest_files")]
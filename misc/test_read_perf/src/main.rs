@@ -1,3 +1,4 @@
+use czkawka_core::common::file_system::LocalFileSystem;
 use czkawka_core::tools::duplicate::{hash_calculation, DuplicateEntry};
 use czkawka_core::common::model::HashType;
 use humansize::{format_size, BINARY};
@@ -97,38 +98,38 @@ fn main() {
 fn array16(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         let mut buffer = [0u8; 16 * 1024];
-        let _ = hash_calculation(&mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
+        let _ = hash_calculation(&LocalFileSystem, &mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
     });
 }
 fn array256(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         let mut buffer = [0u8; 256 * 1024];
-        let _ = hash_calculation(&mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
+        let _ = hash_calculation(&LocalFileSystem, &mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
     });
 }
 fn vec16(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         let mut buffer = vec![0u8; 16 * 1024];
-        let _ = hash_calculation(&mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
+        let _ = hash_calculation(&LocalFileSystem, &mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
     });
 }
 fn vec1024(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         let mut buffer = vec![0u8; 1024 * 1024];
-        let _ = hash_calculation(&mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
+        let _ = hash_calculation(&LocalFileSystem, &mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
     });
 }
 fn vec1024_locking(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         let _lock = GLOBAL_HDD_LOCK.lock().unwrap();
         let mut buffer = vec![0u8; 1024 * 1024];
-        let _ = hash_calculation(&mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
+        let _ = hash_calculation(&LocalFileSystem, &mut buffer, &f, HashType::Blake3, &Arc::default(), &Arc::default());
     });
 }
 fn vec1024_thread(files: &Vec<DuplicateEntry>) {
     files.into_par_iter().for_each(|f| {
         BUFFER.with(|buffer| {
-            let _ = hash_calculation(&mut buffer.borrow_mut(), &f, HashType::Blake3, &Arc::default(), &Arc::default());
+            let _ = hash_calculation(&LocalFileSystem, &mut buffer.borrow_mut(), &f, HashType::Blake3, &Arc::default(), &Arc::default());
         });
     });
 }